@@ -14,7 +14,10 @@ use std::sync::Arc;
 // Official SDK imports for proper order signing
 use polymarket_client_sdk::clob::{Client as ClobClient, Config as ClobConfig};
 use polymarket_client_sdk::clob::types::{Side, OrderType, SignatureType, Amount};
+use polymarket_client_sdk::clob::types::request::{OrdersRequest, TradesRequest};
+use polymarket_client_sdk::clob::types::response::{OpenOrderResponse, TradeResponse};
 use polymarket_client_sdk::{POLYGON, contract_config};
+use polymarket_client_sdk::ContractConfig;
 use alloy::signers::local::LocalSigner;
 use alloy::signers::Signer as _;
 use alloy::primitives::Address as AlloyAddress;
@@ -40,10 +43,96 @@ sol! {
         function setApprovalForAll(address operator, bool approved) external;
         function isApprovedForAll(address account, address operator) external view returns (bool);
     }
+
+    #[sol(rpc)]
+    interface IConditionalTokens {
+        function redeemPositions(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] indexSets) external;
+    }
 }
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// CLOB user channel (authenticated order/trade updates) on Polygon mainnet
+const USER_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
+/// CLOB market channel (public book updates) on Polygon mainnet
+const MARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+/// Builder relayer (gasless redemption and approvals) on Polygon mainnet
+const RELAYER_URL: &str = "https://relayer-v2.polymarket.com";
+/// Number of user events buffered while the consumer is busy
+const USER_EVENT_BUFFER: usize = 1024;
+/// Number of market channel quotes buffered while the consumer is busy
+const MARKET_QUOTE_BUFFER: usize = 1024;
+/// Polygon JSON-RPC endpoint when network.rpc_url is not set
+const DEFAULT_RPC_URL: &str = "https://polygon-rpc.com";
+/// Per-request timeout when network.request_timeout_secs is not set
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 10;
+/// Factor applied to estimated fees when chain.gas.multiplier is not set
+const DEFAULT_GAS_MULTIPLIER: f64 = 1.5;
+/// Blocks a redemption must be confirmed by when chain.gas.confirmations is not set
+const DEFAULT_GAS_CONFIRMATIONS: u64 = 1;
+/// Seconds before a pending redemption is re-submitted when chain.gas.stuck_timeout_secs is not set
+const DEFAULT_GAS_STUCK_TIMEOUT_SECS: u64 = 90;
+/// Fee increase per re-submission when chain.gas.bump_percent is not set
+const DEFAULT_GAS_BUMP_PERCENT: u64 = 25;
+/// Re-submissions of a stuck redemption when chain.gas.max_resubmits is not set
+const DEFAULT_GAS_MAX_RESUBMITS: u32 = 3;
+/// Interval between receipt checks while waiting for confirmations
+const RECEIPT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Result of probing one API base URL (see `PolymarketApi::health_check`)
+#[derive(Debug, Clone)]
+pub struct EndpointHealth {
+    pub name: &'static str,
+    /// Probed URL (base URL + lightweight endpoint)
+    pub url: String,
+    /// HTTP status, if a response was received
+    pub status: Option<u16>,
+    /// Transport error, if no response was received
+    pub error: Option<String>,
+}
+
+impl EndpointHealth {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self.status, Some(code) if (200..300).contains(&code))
+    }
+}
+
+/// Startup health of the gamma and CLOB base URLs
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+impl HealthReport {
+    pub fn is_healthy(&self) -> bool {
+        self.endpoints.iter().all(EndpointHealth::is_healthy)
+    }
+
+    /// Error naming every failing endpoint and the config field to fix, if any endpoint is unhealthy
+    pub fn ensure_healthy(&self) -> Result<()> {
+        let failures: Vec<String> = self.endpoints.iter()
+            .filter(|e| !e.is_healthy())
+            .map(|e| match (&e.status, &e.error) {
+                (Some(code), _) => format!("{} returned HTTP {} for {}", e.name, code, e.url),
+                (None, Some(err)) => format!("{} unreachable at {}: {}", e.name, e.url, err),
+                (None, None) => format!("{} did not respond at {}", e.name, e.url),
+            })
+            .collect();
+        if failures.is_empty() {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "API health check failed - check polymarket.gamma_api_url / polymarket.clob_api_url in config.json:\n  {}",
+            failures.join("\n  ")
+        )
+    }
+}
+
+/// Base URL without trailing slashes, so `https://host/` and `https://host` build the same request URLs
+fn normalize_base_url(url: &str) -> String {
+    url.trim_end_matches('/').to_string()
+}
+
 pub struct PolymarketApi {
     client: Client,
     gamma_url: String,
@@ -57,6 +146,18 @@ pub struct PolymarketApi {
     signature_type: Option<u8>, // 0 = EOA, 1 = Proxy, 2 = GnosisSafe
     // Track if authentication was successful at startup
     authenticated: Arc<tokio::sync::Mutex<bool>>,
+    // Network settings (see with_network)
+    rpc_url: String,
+    http_proxy: Option<String>,
+    ws_proxy: Option<String>,
+    request_timeout: std::time::Duration,
+    retry: Arc<crate::retry::Retrier>, // Backoff and per-endpoint circuit breakers for ApiClient calls
+    // Chain settings (see with_chain)
+    chain_id: u64,
+    user_ws_url: Option<String>, // None = no user channel on this chain
+    market_ws_url: Option<String>, // None = no market channel on this chain
+    relayer_url: Option<String>, // None = no relayer on this chain
+    gas: crate::config::GasConfig,
 }
 
 impl PolymarketApi {
@@ -71,14 +172,14 @@ impl PolymarketApi {
         signature_type: Option<u8>,
     ) -> Self {
         let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
+            .timeout(std::time::Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS))
             .build()
             .expect("Failed to create HTTP client");
         
         Self {
             client,
-            gamma_url,
-            clob_url,
+            gamma_url: normalize_base_url(&gamma_url),
+            clob_url: normalize_base_url(&clob_url),
             api_key,
             api_secret,
             api_passphrase,
@@ -86,9 +187,142 @@ impl PolymarketApi {
             proxy_wallet_address,
             signature_type,
             authenticated: Arc::new(tokio::sync::Mutex::new(false)),
+            rpc_url: DEFAULT_RPC_URL.to_string(),
+            http_proxy: None,
+            ws_proxy: None,
+            request_timeout: std::time::Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            retry: Arc::new(crate::retry::Retrier::default()),
+            chain_id: POLYGON,
+            user_ws_url: Some(USER_WS_URL.to_string()),
+            market_ws_url: Some(MARKET_WS_URL.to_string()),
+            relayer_url: Some(RELAYER_URL.to_string()),
+            gas: crate::config::GasConfig::default(),
         }
     }
-    
+
+    /// Apply `[chain]` settings: chain id for order signing and contract addresses, user/market channel and relayer URLs,
+    /// gas strategy and confirmations for redemptions
+    /// Off Polygon mainnet the user channel and relayer are only available if their URLs are configured;
+    /// features that need them fail with an explicit error instead
+    pub fn with_chain(mut self, chain: &crate::config::ChainConfig) -> Result<Self> {
+        self.chain_id = chain.chain_id();
+        if contract_config(self.chain_id, false).is_none() {
+            anyhow::bail!("chain.chain_id {} is not supported by the Polymarket SDK (supported: 137 Polygon, 80002 Amoy)", self.chain_id);
+        }
+        let mainnet = self.chain_id == POLYGON;
+        self.user_ws_url = chain.user_ws_url.clone().or_else(|| mainnet.then(|| USER_WS_URL.to_string()));
+        self.market_ws_url = chain.market_ws_url.clone().or_else(|| mainnet.then(|| MARKET_WS_URL.to_string()));
+        self.relayer_url = chain.relayer_url.as_deref().map(normalize_base_url).or_else(|| mainnet.then(|| RELAYER_URL.to_string()));
+        self.gas = chain.gas.clone();
+        Ok(self)
+    }
+
+    /// Exchange / collateral / CTF contract addresses of the configured chain
+    fn contracts(&self, neg_risk: bool) -> Result<&'static ContractConfig> {
+        contract_config(self.chain_id, neg_risk)
+            .ok_or_else(|| anyhow::anyhow!("No {}contract config for chain {} in the Polymarket SDK",
+                if neg_risk { "neg-risk " } else { "" }, self.chain_id))
+    }
+
+    /// Relayer base URL, or an explicit error on chains without one
+    fn relayer_url(&self, action: &str) -> Result<&str> {
+        self.relayer_url.as_deref().ok_or_else(|| anyhow::anyhow!(
+            "{} needs the Polymarket relayer, which is not available on chain {} - set chain.relayer_url to use one",
+            action, self.chain_id))
+    }
+
+    /// Apply `[network]` settings: proxy and timeout for every HTTP request, WebSocket proxy, Polygon RPC URL and
+    /// the retry / circuit breaker policy of `ApiClient` calls
+    /// The order-signing SDK builds its own HTTP client and only takes a proxy from the environment, so with
+    /// http_proxy set HTTPS_PROXY must be exported as well - Err otherwise, rather than letting orders go direct
+    pub fn with_network(mut self, network: &crate::config::NetworkConfig) -> Result<Self> {
+        self.request_timeout = std::time::Duration::from_secs(network.request_timeout_secs.unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS));
+        let mut builder = Client::builder().timeout(self.request_timeout);
+        if let Some(proxy) = &network.http_proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy.as_str())
+                .context(format!("Invalid network.http_proxy: {}", proxy))?);
+            if ["HTTPS_PROXY", "https_proxy"].iter().all(|var| std::env::var_os(var).is_none()) {
+                anyhow::bail!("network.http_proxy is set but HTTPS_PROXY is not exported - the Polymarket SDK (order \
+                    signing, balances) reads its proxy only from the environment; export HTTPS_PROXY with the same proxy");
+            }
+        }
+        self.client = builder.build().context("Failed to create HTTP client")?;
+        self.http_proxy = network.http_proxy.clone();
+        self.ws_proxy = network.ws_proxy.clone().or_else(|| network.http_proxy.clone());
+        if let Some(rpc_url) = &network.rpc_url {
+            self.rpc_url = rpc_url.clone();
+        }
+        self.retry = Arc::new(crate::retry::Retrier::new(crate::retry::RetryPolicy::from_config(&network.retry)));
+        Ok(self)
+    }
+
+    /// Retries and circuit breakers the `ApiClient` impl runs calls through
+    pub fn retrier(&self) -> &crate::retry::Retrier {
+        &self.retry
+    }
+
+    /// JSON-RPC client for the configured Polygon RPC, with the shared proxy and timeout
+    fn rpc_client(&self) -> Result<alloy::rpc::client::RpcClient> {
+        use alloy::transports::http::{reqwest as alloy_reqwest, Http};
+        let mut builder = alloy_reqwest::Client::builder().timeout(self.request_timeout);
+        if let Some(proxy) = &self.http_proxy {
+            builder = builder.proxy(alloy_reqwest::Proxy::all(proxy.as_str())
+                .context(format!("Invalid network.http_proxy: {}", proxy))?);
+        }
+        let client = builder.build().context("Failed to create RPC HTTP client")?;
+        let url = self.rpc_url.parse()
+            .context(format!("Invalid network.rpc_url: {}", self.rpc_url))?;
+        Ok(alloy::rpc::client::RpcClient::new(Http::with_client(client, url), false))
+    }
+
+    /// Startup connectivity check: reach the gamma and CLOB base URLs (through the proxy, if any) and
+    /// the Polygon RPC. Any HTTP response counts as reachable. Logs one line per endpoint and returns
+    /// the number of unreachable endpoints
+    pub async fn check_connectivity(&self) -> usize {
+        let mut failures = 0;
+        let via = self.http_proxy.as_deref().map(|p| format!(" via proxy {}", p)).unwrap_or_default();
+        for (name, url) in [("Gamma API", &self.gamma_url), ("CLOB API", &self.clob_url)] {
+            match self.client.get(url.as_str()).send().await {
+                Ok(response) => eprintln!("🌐 {} reachable{} ({} -> {})", name, via, url, response.status()),
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("❌ {} unreachable{} ({}): {}", name, via, url, e);
+                }
+            }
+        }
+        let block = match self.rpc_client() {
+            Ok(client) => ProviderBuilder::new().connect_client(client).get_block_number().await
+                .map_err(anyhow::Error::from),
+            Err(e) => Err(e),
+        };
+        match block {
+            Ok(block) => eprintln!("🌐 Polygon RPC reachable{} ({} -> block {})", via, self.rpc_url, block),
+            Err(e) => {
+                failures += 1;
+                eprintln!("❌ Polygon RPC unreachable{} ({}): {}", via, self.rpc_url, e);
+            }
+        }
+        failures
+    }
+
+    /// Probe a lightweight endpoint on each base URL (gamma `/markets?limit=1`, CLOB `/time`)
+    /// Unlike `check_connectivity`, only a 2xx response counts - a wrong path or host shows up here
+    /// instead of as 404s during market discovery
+    pub async fn health_check(&self) -> HealthReport {
+        let probes = [
+            ("Gamma API", format!("{}/markets?limit=1", self.gamma_url)),
+            ("CLOB API", format!("{}/time", self.clob_url)),
+        ];
+        let mut endpoints = Vec::with_capacity(probes.len());
+        for (name, url) in probes {
+            let (status, error) = match self.client.get(&url).send().await {
+                Ok(response) => (Some(response.status().as_u16()), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            endpoints.push(EndpointHealth { name, url, status, error });
+        }
+        HealthReport { endpoints }
+    }
 
     /// Authenticate with Polymarket CLOB API at startup
     /// This verifies credentials (private_key + API credentials)
@@ -101,7 +335,7 @@ impl PolymarketApi {
         // Create signer from private key (equivalent to: new Wallet(PRIVATE_KEY))
         let signer = LocalSigner::from_str(private_key)
             .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
-            .with_chain_id(Some(POLYGON));
+            .with_chain_id(Some(self.chain_id));
         
         // Build authentication builder with proxy wallet support
         let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
@@ -154,6 +388,7 @@ impl PolymarketApi {
         let _client = auth_builder
             .authenticate()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to authenticate with CLOB API. Check your API credentials (api_key, api_secret, api_passphrase) and private_key.")?;
         
         // Mark as authenticated
@@ -259,6 +494,7 @@ impl PolymarketApi {
             .query(&params)
             .send()
             .await
+            .map_err(ApiError::from_reqwest)
             .context("Failed to fetch all active markets")?;
 
         let status = response.status();
@@ -266,7 +502,7 @@ impl PolymarketApi {
         
         if !status.is_success() {
             log::warn!("Get all active markets API returned error status {}: {}", status, serde_json::to_string(&json).unwrap_or_default());
-            anyhow::bail!("API returned error status {}: {}", status, serde_json::to_string(&json).unwrap_or_default());
+            return Err(ApiError::from_status(status, format!("API returned error status {}: {}", status, serde_json::to_string(&json).unwrap_or_default())).into());
         }
         
         // Extract markets from events - events contain markets
@@ -306,11 +542,12 @@ impl PolymarketApi {
         let url = format!("{}/events/slug/{}", self.gamma_url, slug);
         
         let response = self.client.get(&url).send().await
+            .map_err(ApiError::from_reqwest)
             .context(format!("Failed to fetch market by slug: {}", slug))?;
         
         let status = response.status();
         if !status.is_success() {
-            anyhow::bail!("Failed to fetch market by slug: {} (status: {})", slug, status);
+            return Err(ApiError::from_status(status, format!("Failed to fetch market by slug: {} (status: {})", slug, status)).into());
         }
         
         let json: Value = response.json().await
@@ -330,6 +567,32 @@ impl PolymarketApi {
         anyhow::bail!("Invalid market response format: no markets array found")
     }
 
+    /// UMA resolution status of a market from the gamma API (e.g. "proposed", "disputed", "resolved")
+    /// Posted as soon as the oracle settles - usually before the CLOB market flips to closed.
+    /// Returns None if gamma doesn't report a status (yet)
+    pub async fn get_uma_resolution_status(&self, condition_id: &str) -> Result<Option<String>> {
+        let url = format!("{}/markets", self.gamma_url);
+        let params = [("condition_ids", condition_id)];
+
+        let response = self.client.get(&url).query(&params).send().await
+            .map_err(ApiError::from_reqwest)
+            .context(format!("Failed to fetch gamma market for condition_id: {}", condition_id))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ApiError::from_status(status, format!("Failed to fetch gamma market (status: {})", status)).into());
+        }
+
+        let json: Value = response.json().await
+            .context("Failed to parse gamma market response")?;
+
+        Ok(json.as_array()
+            .and_then(|markets| markets.first())
+            .and_then(|market| market.get("umaResolutionStatus"))
+            .and_then(|status| status.as_str())
+            .map(|status| status.to_string()))
+    }
+
     /// Get order book for a specific token
     pub async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook> {
         let url = format!("{}/book", self.clob_url);
@@ -341,8 +604,14 @@ impl PolymarketApi {
             .query(&params)
             .send()
             .await
+            .map_err(ApiError::from_reqwest)
             .context("Failed to fetch orderbook")?;
 
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ApiError::from_status(status, format!("Failed to fetch orderbook for token {}", token_id)).into());
+        }
+
         let orderbook: OrderBook = response
             .json()
             .await
@@ -351,6 +620,38 @@ impl PolymarketApi {
         Ok(orderbook)
     }
 
+    /// Price series of a token between `start_ts` and `end_ts` (unix seconds), one point per `fidelity_minutes`
+    pub async fn get_prices_history(&self, token_id: &str, start_ts: u64, end_ts: u64, fidelity_minutes: u32) -> Result<Vec<PricePoint>> {
+        let url = format!("{}/prices-history", self.clob_url);
+        let params = [
+            ("market", token_id.to_string()),
+            ("startTs", start_ts.to_string()),
+            ("endTs", end_ts.to_string()),
+            ("fidelity", fidelity_minutes.to_string()),
+        ];
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&params)
+            .send()
+            .await
+            .map_err(ApiError::from_reqwest)
+            .context("Failed to fetch price history")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ApiError::from_status(status, format!("Failed to fetch price history for token {}", token_id)).into());
+        }
+
+        let history: PricesHistory = response
+            .json()
+            .await
+            .context("Failed to parse price history")?;
+
+        Ok(history.history)
+    }
+
     /// Get market details by condition ID
     pub async fn get_market(&self, condition_id: &str) -> Result<MarketDetails> {
         let url = format!("{}/markets/{}", self.clob_url, condition_id);
@@ -360,12 +661,13 @@ impl PolymarketApi {
             .get(&url)
             .send()
             .await
+            .map_err(ApiError::from_reqwest)
             .context(format!("Failed to fetch market for condition_id: {}", condition_id))?;
 
         let status = response.status();
         
         if !status.is_success() {
-            anyhow::bail!("Failed to fetch market (status: {})", status);
+            return Err(ApiError::from_status(status, format!("Failed to fetch market (status: {})", status)).into());
         }
 
         let json_text = response.text().await
@@ -397,11 +699,12 @@ impl PolymarketApi {
             .query(&params)
             .send()
             .await
+            .map_err(ApiError::from_reqwest)
             .context("Failed to fetch price")?;
 
         let status = response.status();
         if !status.is_success() {
-            anyhow::bail!("Failed to fetch price (status: {})", status);
+            return Err(ApiError::from_status(status, format!("Failed to fetch price (status: {})", status)).into());
         }
 
         let json: serde_json::Value = response
@@ -422,23 +725,75 @@ impl PolymarketApi {
     }
 
     /// Get best bid/ask prices for a token (from orderbook)
+    /// Fails with a `CrossedBook` error if the best bid is at or above the best ask
     pub async fn get_best_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
         let orderbook = self.get_orderbook(token_id).await?;
+        orderbook.check_crossed(token_id)?;
         
-        let best_bid = orderbook.bids.first().map(|b| b.price);
-        let best_ask = orderbook.asks.first().map(|a| a.price);
+        let best_bid = orderbook.best_bid();
+        let best_ask = orderbook.best_ask();
 
         if best_ask.is_some() {
             Ok(Some(TokenPrice {
                 token_id: token_id.to_string(),
-                bid: best_bid,
-                ask: best_ask,
+                bid: best_bid.map(|b| b.price),
+                ask: best_ask.map(|a| a.price),
+                signal: None,
+                bid_size: best_bid.map(|b| b.size),
+                ask_size: best_ask.map(|a| a.size),
+                crossed: false,
+                fetched_at: std::time::Instant::now(),
             }))
         } else {
             Ok(None)
         }
     }
 
+    /// Get the gamma API midpoint (displayed outcome price) for a token
+    /// Looks up the market by CLOB token id and returns the outcome price matching the token
+    pub async fn get_gamma_midpoint(&self, token_id: &str) -> Result<rust_decimal::Decimal> {
+        let url = format!("{}/markets", self.gamma_url);
+        let params = [("clob_token_ids", token_id)];
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&params)
+            .send()
+            .await
+            .map_err(ApiError::from_reqwest)
+            .context("Failed to fetch gamma market")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ApiError::from_status(status, format!("Failed to fetch gamma market (status: {})", status)).into());
+        }
+
+        let json: Value = response.json().await
+            .context("Failed to parse gamma market response")?;
+        let market = json.as_array()
+            .and_then(|markets| markets.first())
+            .ok_or_else(|| anyhow::anyhow!("No gamma market found for token {}", token_id))?;
+
+        // clobTokenIds and outcomePrices are JSON-encoded string arrays in the same outcome order
+        let parse_list = |field: &str| -> Vec<String> {
+            market.get(field)
+                .and_then(|v| v.as_str())
+                .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())
+                .unwrap_or_default()
+        };
+        let token_ids = parse_list("clobTokenIds");
+        let outcome_prices = parse_list("outcomePrices");
+
+        let index = token_ids.iter().position(|id| id == token_id)
+            .ok_or_else(|| anyhow::anyhow!("Token {} not listed in gamma market", token_id))?;
+        let price_str = outcome_prices.get(index)
+            .ok_or_else(|| anyhow::anyhow!("No gamma outcome price for token {}", token_id))?;
+
+        rust_decimal::Decimal::from_str(price_str)
+            .context(format!("Failed to parse gamma outcome price: {}", price_str))
+    }
+
     /// Place an order using the official SDK with proper private key signing
     /// 
     /// This method uses the official polymarket-client-sdk to:
@@ -456,7 +811,7 @@ impl PolymarketApi {
         // Create signer from private key (equivalent to: new Wallet(PRIVATE_KEY))
         let signer = LocalSigner::from_str(private_key)
             .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
-            .with_chain_id(Some(POLYGON));
+            .with_chain_id(Some(self.chain_id));
         
         // Build authentication builder with proxy wallet support
         let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
@@ -493,6 +848,7 @@ impl PolymarketApi {
         let client = auth_builder
             .authenticate()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to authenticate with CLOB API. Check your API credentials.")?;
         
         // Convert order side string to SDK Side enum
@@ -530,7 +886,7 @@ impl PolymarketApi {
             Err(e) => {
                 // Log the full error details for debugging
                 error!("❌ Failed to post order. Error details: {:?}", e);
-                anyhow::bail!("Failed to post order: {}", e);
+                return Err(ApiError::classify(&format!("{:?}", e))).context(format!("Failed to post order: {}", e));
             }
         };
         
@@ -538,11 +894,11 @@ impl PolymarketApi {
         if !response.success {
             let error_msg = response.error_msg.as_deref().unwrap_or("Unknown error");
             error!("❌ Order rejected by API: {}", error_msg);
-            anyhow::bail!(
+            return Err(ApiError::classify(error_msg)).context(format!(
                 "Order was rejected: {}\n\
                 Order details: Token ID={}, Side={}, Size={}, Price={}",
                 error_msg, order.token_id, order.side, order.size, order.price
-            );
+            ));
         }
         
         // Convert SDK response to our OrderResponse format
@@ -565,7 +921,7 @@ impl PolymarketApi {
 
         let signer = LocalSigner::from_str(private_key)
             .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
-            .with_chain_id(Some(POLYGON));
+            .with_chain_id(Some(self.chain_id));
 
         let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
             .context("Failed to create CLOB client")?
@@ -595,15 +951,288 @@ impl PolymarketApi {
         let client = auth_builder
             .authenticate()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to authenticate with CLOB API. Check your API credentials.")?;
 
         eprintln!("🛑 Cancelling order: {}", order_id);
         client.cancel_order(order_id).await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context(format!("Failed to cancel order {}", order_id))?;
         eprintln!("✅ Cancel request submitted for order: {}", order_id);
         Ok(())
     }
 
+    /// Our live orders for a token (CLOB /data/orders, all pages)
+    pub async fn get_open_orders(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        let private_key = self.private_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Private key is required for listing orders. Please set private_key in config.json"))?;
+
+        let signer = LocalSigner::from_str(private_key)
+            .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
+            .with_chain_id(Some(self.chain_id));
+
+        let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
+            .context("Failed to create CLOB client")?
+            .authentication_builder(&signer);
+
+        if let Some(proxy_addr) = &self.proxy_wallet_address {
+            let funder_address = AlloyAddress::parse_checksummed(proxy_addr, None)
+                .context(format!("Failed to parse proxy_wallet_address: {}. Ensure it's a valid Ethereum address.", proxy_addr))?;
+            auth_builder = auth_builder.funder(funder_address);
+
+            let sig_type = match self.signature_type {
+                Some(1) => SignatureType::Proxy,
+                Some(2) => SignatureType::GnosisSafe,
+                Some(0) | None => SignatureType::Proxy,
+                Some(n) => anyhow::bail!("Invalid signature_type: {}. Must be 0 (EOA), 1 (Proxy), or 2 (GnosisSafe)", n),
+            };
+            auth_builder = auth_builder.signature_type(sig_type);
+        } else if let Some(sig_type_num) = self.signature_type {
+            let sig_type = match sig_type_num {
+                0 => SignatureType::Eoa,
+                1 | 2 => anyhow::bail!("signature_type {} requires proxy_wallet_address to be set", sig_type_num),
+                n => anyhow::bail!("Invalid signature_type: {}. Must be 0 (EOA), 1 (Proxy), or 2 (GnosisSafe)", n),
+            };
+            auth_builder = auth_builder.signature_type(sig_type);
+        }
+
+        let client = auth_builder
+            .authenticate()
+            .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
+            .context("Failed to authenticate with CLOB API. Check your API credentials.")?;
+
+        let request = OrdersRequest::builder().asset_id(token_id).build();
+        let mut orders = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = client.orders(&request, cursor.clone()).await
+                .map_err(|e| ApiError::classify(&format!("{:?}", e)))
+                .context(format!("Failed to list open orders for token {}", token_id))?;
+            orders.extend(page.data.into_iter().map(open_order_from));
+            // "LTE=" marks the last page
+            if page.next_cursor.is_empty() || page.next_cursor == "LTE=" || cursor.as_deref() == Some(page.next_cursor.as_str()) {
+                break;
+            }
+            cursor = Some(page.next_cursor);
+        }
+        Ok(orders)
+    }
+
+    /// Current status and matched size of one of our orders (CLOB /data/order/{id})
+    pub async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+        let private_key = self.private_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Private key is required for querying orders. Please set private_key in config.json"))?;
+
+        let signer = LocalSigner::from_str(private_key)
+            .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
+            .with_chain_id(Some(self.chain_id));
+
+        let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
+            .context("Failed to create CLOB client")?
+            .authentication_builder(&signer);
+
+        if let Some(proxy_addr) = &self.proxy_wallet_address {
+            let funder_address = AlloyAddress::parse_checksummed(proxy_addr, None)
+                .context(format!("Failed to parse proxy_wallet_address: {}. Ensure it's a valid Ethereum address.", proxy_addr))?;
+            auth_builder = auth_builder.funder(funder_address);
+
+            let sig_type = match self.signature_type {
+                Some(1) => SignatureType::Proxy,
+                Some(2) => SignatureType::GnosisSafe,
+                Some(0) | None => SignatureType::Proxy,
+                Some(n) => anyhow::bail!("Invalid signature_type: {}. Must be 0 (EOA), 1 (Proxy), or 2 (GnosisSafe)", n),
+            };
+            auth_builder = auth_builder.signature_type(sig_type);
+        } else if let Some(sig_type_num) = self.signature_type {
+            let sig_type = match sig_type_num {
+                0 => SignatureType::Eoa,
+                1 | 2 => anyhow::bail!("signature_type {} requires proxy_wallet_address to be set", sig_type_num),
+                n => anyhow::bail!("Invalid signature_type: {}. Must be 0 (EOA), 1 (Proxy), or 2 (GnosisSafe)", n),
+            };
+            auth_builder = auth_builder.signature_type(sig_type);
+        }
+
+        let client = auth_builder
+            .authenticate()
+            .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
+            .context("Failed to authenticate with CLOB API. Check your API credentials.")?;
+
+        let order = client.order(order_id).await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
+            .context(format!("Failed to fetch order {}", order_id))?;
+        Ok(open_order_from(order))
+    }
+
+    /// Our trades in a token matched at or after `after` (Unix seconds), all pages (CLOB /data/trades)
+    /// Trades show up here as soon as they match - before the token balance settles
+    pub async fn get_trades(&self, token_id: &str, after: i64) -> Result<Vec<OwnTrade>> {
+        let private_key = self.private_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Private key is required for listing trades. Please set private_key in config.json"))?;
+
+        let signer = LocalSigner::from_str(private_key)
+            .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
+            .with_chain_id(Some(self.chain_id));
+
+        let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
+            .context("Failed to create CLOB client")?
+            .authentication_builder(&signer);
+
+        if let Some(proxy_addr) = &self.proxy_wallet_address {
+            let funder_address = AlloyAddress::parse_checksummed(proxy_addr, None)
+                .context(format!("Failed to parse proxy_wallet_address: {}. Ensure it's a valid Ethereum address.", proxy_addr))?;
+            auth_builder = auth_builder.funder(funder_address);
+
+            let sig_type = match self.signature_type {
+                Some(1) => SignatureType::Proxy,
+                Some(2) => SignatureType::GnosisSafe,
+                Some(0) | None => SignatureType::Proxy,
+                Some(n) => anyhow::bail!("Invalid signature_type: {}. Must be 0 (EOA), 1 (Proxy), or 2 (GnosisSafe)", n),
+            };
+            auth_builder = auth_builder.signature_type(sig_type);
+        } else if let Some(sig_type_num) = self.signature_type {
+            let sig_type = match sig_type_num {
+                0 => SignatureType::Eoa,
+                1 | 2 => anyhow::bail!("signature_type {} requires proxy_wallet_address to be set", sig_type_num),
+                n => anyhow::bail!("Invalid signature_type: {}. Must be 0 (EOA), 1 (Proxy), or 2 (GnosisSafe)", n),
+            };
+            auth_builder = auth_builder.signature_type(sig_type);
+        }
+
+        let client = auth_builder
+            .authenticate()
+            .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
+            .context("Failed to authenticate with CLOB API. Check your API credentials.")?;
+
+        let request = TradesRequest::builder().asset_id(token_id).after(after).build();
+        let mut trades = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = client.trades(&request, cursor.clone()).await
+                .map_err(|e| ApiError::classify(&format!("{:?}", e)))
+                .context(format!("Failed to list trades for token {}", token_id))?;
+            trades.extend(page.data.into_iter().map(own_trade_from));
+            // "LTE=" marks the last page
+            if page.next_cursor.is_empty() || page.next_cursor == "LTE=" || cursor.as_deref() == Some(page.next_cursor.as_str()) {
+                break;
+            }
+            cursor = Some(page.next_cursor);
+        }
+        Ok(trades)
+    }
+
+    /// Subscribe to the CLOB user WebSocket channel (order and trade updates for our API key)
+    /// Returns a buffered receiver; a background task keeps the socket alive and reconnects
+    /// with exponential backoff until the receiver is dropped
+    pub async fn subscribe_user_events(&self) -> Result<tokio::sync::mpsc::Receiver<UserEvent>> {
+        let (api_key, api_secret, api_passphrase) = match (&self.api_key, &self.api_secret, &self.api_passphrase) {
+            (Some(key), Some(secret), Some(pass)) => (key.clone(), secret.clone(), pass.clone()),
+            _ => anyhow::bail!("API credentials (api_key, api_secret, api_passphrase) are required for the user channel"),
+        };
+        let subscribe_msg = serde_json::json!({
+            "auth": {
+                "apiKey": api_key,
+                "secret": api_secret,
+                "passphrase": api_passphrase,
+            },
+            "markets": [],
+            "type": "user",
+        }).to_string();
+
+        let ws_url = self.user_ws_url.clone().ok_or_else(|| anyhow::anyhow!(
+            "The CLOB user channel is not available on chain {} - set chain.user_ws_url to use one", self.chain_id))?;
+        let (tx, rx) = tokio::sync::mpsc::channel(USER_EVENT_BUFFER);
+        let ws_proxy = self.ws_proxy.clone();
+        tokio::spawn(async move {
+            let mut backoff_secs = 1u64;
+            while !tx.is_closed() {
+                match Self::run_socket("user", &subscribe_msg, &ws_url, ws_proxy.as_deref(), &tx, parse_user_events).await {
+                    Ok(()) => backoff_secs = 1,
+                    Err(e) => warn!("User channel disconnected: {}", e),
+                }
+                if tx.is_closed() {
+                    break;
+                }
+                eprintln!("🔌 Reconnecting to user channel in {}s...", backoff_secs);
+                tokio::time::sleep(std::time::Duration::from_secs(backoff_secs)).await;
+                backoff_secs = (backoff_secs * 2).min(30);
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Subscribe to the CLOB market WebSocket channel for `token_ids` (public, no credentials needed)
+    /// Returns a buffered receiver of top-of-book quotes; a background task keeps the socket alive and reconnects
+    /// with exponential backoff until the receiver is dropped
+    pub async fn subscribe_market_quotes(&self, token_ids: Vec<String>) -> Result<tokio::sync::mpsc::Receiver<MarketQuote>> {
+        let subscribe_msg = serde_json::json!({
+            "assets_ids": token_ids,
+            "type": "market",
+        }).to_string();
+
+        let ws_url = self.market_ws_url.clone().ok_or_else(|| anyhow::anyhow!(
+            "The CLOB market channel is not available on chain {} - set chain.market_ws_url to use one", self.chain_id))?;
+        let (tx, rx) = tokio::sync::mpsc::channel(MARKET_QUOTE_BUFFER);
+        let ws_proxy = self.ws_proxy.clone();
+        tokio::spawn(async move {
+            let mut backoff_secs = 1u64;
+            while !tx.is_closed() {
+                match Self::run_socket("market", &subscribe_msg, &ws_url, ws_proxy.as_deref(), &tx, parse_market_quotes).await {
+                    Ok(()) => backoff_secs = 1,
+                    Err(e) => warn!("Market channel disconnected: {}", e),
+                }
+                if tx.is_closed() {
+                    break;
+                }
+                eprintln!("🔌 Reconnecting to market channel in {}s...", backoff_secs);
+                tokio::time::sleep(std::time::Duration::from_secs(backoff_secs)).await;
+                backoff_secs = (backoff_secs * 2).min(30);
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Run one `channel` connection until it closes or errors, forwarding what `parse` extracts from each message
+    async fn run_socket<T>(channel: &str, subscribe_msg: &str, ws_url: &str, ws_proxy: Option<&str>,
+        tx: &tokio::sync::mpsc::Sender<T>, parse: fn(&str) -> Vec<T>) -> Result<()> {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let ws = connect_websocket(ws_url, ws_proxy).await
+            .context(format!("Failed to connect to {} channel", channel))?;
+        let (mut write, mut read) = ws.split();
+        write.send(Message::Text(subscribe_msg.to_string())).await
+            .context(format!("Failed to send {} channel subscription", channel))?;
+        eprintln!("📡 Subscribed to CLOB {} channel", channel);
+
+        let mut ping = tokio::time::interval(std::time::Duration::from_secs(10));
+        loop {
+            tokio::select! {
+                _ = ping.tick() => {
+                    write.send(Message::Text("PING".to_string())).await
+                        .context(format!("Failed to ping {} channel", channel))?;
+                }
+                msg = read.next() => {
+                    let text = match msg {
+                        Some(Ok(Message::Text(text))) => text,
+                        Some(Ok(Message::Close(_))) | None => return Ok(()),
+                        Some(Ok(_)) => continue,
+                        Some(Err(e)) => return Err(e.into()),
+                    };
+                    for event in parse(&text) {
+                        if tx.send(event).await.is_err() {
+                            return Ok(()); // Receiver dropped
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Discover current BTC or ETH 15-minute market
     /// Similar to main bot's discover_market function
     pub async fn discover_current_market(&self, asset: &str) -> Result<Option<String>> {
@@ -613,12 +1242,12 @@ impl PolymarketApi {
             .as_secs();
         
         // Calculate current 15-minute period
-        let current_period = (current_time / 900) * 900;
+        let current_period = crate::period::start(current_time);
         
         // Try to find market for current period and a few previous periods (in case market is slightly delayed)
         for offset in 0..=2 {
-            let period_to_check = current_period - (offset * 900);
-            let slug = format!("{}-updown-15m-{}", asset.to_lowercase(), period_to_check);
+            let period_to_check = current_period - (offset * crate::period::seconds());
+            let slug = crate::period::slug(&asset.to_lowercase(), period_to_check);
             
             // Try to get market by slug
             if let Ok(market) = self.get_market_by_slug(&slug).await {
@@ -631,7 +1260,7 @@ impl PolymarketApi {
             let asset_upper = asset.to_uppercase();
             for market in markets {
                 // Check if this is a BTC/ETH 15-minute market
-                if market.slug.contains(&format!("{}-updown-15m", asset.to_lowercase())) 
+                if crate::period::is_market_slug(&asset.to_lowercase(), &market.slug)
                     || market.question.to_uppercase().contains(&format!("{} 15", asset_upper)) {
                     return Ok(Some(market.condition_id));
                 }
@@ -654,8 +1283,8 @@ impl PolymarketApi {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            let period_to_check = (current_time / 900) * 900 - (offset * 900);
-            let slug = format!("btc-updown-15m-{}", period_to_check);
+            let period_to_check = crate::period::start(current_time) - (offset * crate::period::seconds());
+            let slug = crate::period::slug("btc", period_to_check);
             
             if let Ok(market) = self.get_market_by_slug(&slug).await {
                 let condition_id = market.condition_id.clone();
@@ -687,8 +1316,8 @@ impl PolymarketApi {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            let period_to_check = (current_time / 900) * 900 - (offset * 900);
-            let slug = format!("eth-updown-15m-{}", period_to_check);
+            let period_to_check = crate::period::start(current_time) - (offset * crate::period::seconds());
+            let slug = crate::period::slug("eth", period_to_check);
             
             if let Ok(market) = self.get_market_by_slug(&slug).await {
                 let condition_id = market.condition_id.clone();
@@ -720,12 +1349,12 @@ impl PolymarketApi {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            let period_to_check = (current_time / 900) * 900 - (offset * 900);
+            let period_to_check = crate::period::start(current_time) - (offset * crate::period::seconds());
             
             // Try both slug formats
             let slugs = vec![
-                format!("solana-updown-15m-{}", period_to_check),
-                format!("sol-updown-15m-{}", period_to_check),
+                crate::period::slug("solana", period_to_check),
+                crate::period::slug("sol", period_to_check),
             ];
             
             for slug in slugs {
@@ -874,7 +1503,7 @@ impl PolymarketApi {
         
         let signer = LocalSigner::from_str(private_key)
             .context("Failed to create signer from private key")?
-            .with_chain_id(Some(POLYGON));
+            .with_chain_id(Some(self.chain_id));
         
         let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
             .context("Failed to create CLOB client")?
@@ -898,10 +1527,11 @@ impl PolymarketApi {
         let client = auth_builder
             .authenticate()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to authenticate for USDC balance check")?;
         
-        // Get USDC token address (collateral token)
-        const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+        // USDC (collateral token) address of the configured chain
+        let usdc_address = format!("{:#x}", self.contracts(false)?.collateral);
         
         // The SDK requires a BalanceAllowanceRequest built with builder pattern
         use polymarket_client_sdk::clob::types::request::BalanceAllowanceRequest;
@@ -910,19 +1540,19 @@ impl PolymarketApi {
         // For USDC (collateral token), use AssetType::Collateral
         use polymarket_client_sdk::clob::types::AssetType;
         let request = BalanceAllowanceRequest::builder()
-            .token_id(USDC_ADDRESS)
+            .token_id(usdc_address)
             .asset_type(AssetType::Collateral)
             .build();
         
         let balance_allowance = client
             .balance_allowance(request)
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to fetch USDC balance and allowance")?;
         
         let balance = balance_allowance.balance;
         // Get allowance for the Exchange contract
-        let config = contract_config(POLYGON, false)
-            .ok_or_else(|| anyhow::anyhow!("Failed to get contract config"))?;
+        let config = self.contracts(false)?;
         let exchange_address = config.exchange;
         
         // Allowances is a HashMap<Address, String> - get the allowance for the Exchange contract
@@ -934,6 +1564,12 @@ impl PolymarketApi {
         Ok((balance, allowance))
     }
 
+    /// USDC collateral balance in dollars (the CLOB reports it in 6-decimal base units)
+    pub async fn get_usdc_balance(&self) -> Result<f64> {
+        let (balance, _) = self.check_usdc_balance_allowance().await?;
+        Ok(f64::try_from(balance / rust_decimal::Decimal::from(1_000_000u64)).unwrap_or(0.0))
+    }
+
     /// Check token balance only (for redemption/portfolio scanning)
     /// Returns balance as Decimal value
     /// This is faster than check_balance_allowance since it doesn't check allowances
@@ -945,7 +1581,7 @@ impl PolymarketApi {
         // Create signer from private key
         let signer = LocalSigner::from_str(private_key)
             .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
-            .with_chain_id(Some(POLYGON));
+            .with_chain_id(Some(self.chain_id));
         
         // Build authentication builder with proxy wallet support
         let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
@@ -989,6 +1625,7 @@ impl PolymarketApi {
         let client = auth_builder
             .authenticate()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to authenticate with CLOB API for balance check")?;
         
         // Get balance using SDK (only balance, not allowance)
@@ -1003,6 +1640,7 @@ impl PolymarketApi {
         let balance_allowance = client
             .balance_allowance(request)
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to fetch balance")?;
         
         Ok(balance_allowance.balance)
@@ -1018,7 +1656,7 @@ impl PolymarketApi {
         // Create signer from private key
         let signer = LocalSigner::from_str(private_key)
             .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
-            .with_chain_id(Some(POLYGON));
+            .with_chain_id(Some(self.chain_id));
         
         // Build authentication builder with proxy wallet support
         let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
@@ -1063,6 +1701,7 @@ impl PolymarketApi {
         let client = auth_builder
             .authenticate()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to authenticate with CLOB API for balance check")?;
         
         // Get balance and allowance using SDK
@@ -1080,13 +1719,13 @@ impl PolymarketApi {
         let balance_allowance = client
             .balance_allowance(request)
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to fetch balance and allowance")?;
         
         let balance = balance_allowance.balance;
         
         // Get contract config to check which contract address we should be checking allowance for
-        let config = contract_config(POLYGON, false)
-            .ok_or_else(|| anyhow::anyhow!("Failed to get contract config"))?;
+        let config = self.contracts(false)?;
         let exchange_address = config.exchange;
         
         // Allowances is a HashMap<Address, String> - check all allowances and find the Exchange contract
@@ -1159,7 +1798,7 @@ impl PolymarketApi {
         
         let signer = LocalSigner::from_str(private_key)
             .context("Failed to create signer from private key")?
-            .with_chain_id(Some(POLYGON));
+            .with_chain_id(Some(self.chain_id));
         
         let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
             .context("Failed to create CLOB client")?
@@ -1189,6 +1828,7 @@ impl PolymarketApi {
         let client = auth_builder
             .authenticate()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to authenticate for update_balance_allowance")?;
         
         use polymarket_client_sdk::clob::types::request::UpdateBalanceAllowanceRequest;
@@ -1203,6 +1843,7 @@ impl PolymarketApi {
         client
             .update_balance_allowance(request)
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to update balance/allowance cache for token")?;
         
         Ok(())
@@ -1212,8 +1853,7 @@ impl PolymarketApi {
     /// This is the Exchange contract address that needs to be approved via setApprovalForAll
     fn get_clob_contract_address(&self) -> Result<String> {
         // Use SDK's contract_config to get the correct Exchange contract address
-        let config = contract_config(POLYGON, false)
-            .ok_or_else(|| anyhow::anyhow!("Failed to get contract config from SDK"))?;
+        let config = self.contracts(false)?;
         Ok(format!("{:#x}", config.exchange))
     }
 
@@ -1221,16 +1861,14 @@ impl PolymarketApi {
     /// This is where we call setApprovalForAll()
     fn get_ctf_contract_address(&self) -> Result<String> {
         // Use SDK's contract_config to get the correct CTF contract address
-        let config = contract_config(POLYGON, false)
-            .ok_or_else(|| anyhow::anyhow!("Failed to get contract config from SDK"))?;
+        let config = self.contracts(false)?;
         Ok(format!("{:#x}", config.conditional_tokens))
     }
 
     /// Check if setApprovalForAll was already set for the Exchange contract
     /// Returns true if the Exchange is already approved to manage all tokens
     pub async fn check_is_approved_for_all(&self) -> Result<bool> {
-        let config = contract_config(POLYGON, false)
-            .ok_or_else(|| anyhow::anyhow!("Failed to get contract config from SDK"))?;
+        let config = self.contracts(false)?;
         
         let ctf_contract_address = config.conditional_tokens;
         let exchange_address = config.exchange;
@@ -1244,15 +1882,12 @@ impl PolymarketApi {
                 .ok_or_else(|| anyhow::anyhow!("Private key required to check approval"))?;
             let signer = LocalSigner::from_str(private_key)
                 .context("Failed to create signer from private key")?
-                .with_chain_id(Some(POLYGON));
+                .with_chain_id(Some(self.chain_id));
             signer.address()
         };
         
-        const RPC_URL: &str = "https://polygon-rpc.com";
         let provider = ProviderBuilder::new()
-            .connect(RPC_URL)
-            .await
-            .context("Failed to connect to Polygon RPC")?;
+            .connect_client(self.rpc_client()?);
         
         let ctf = IERC1155::new(ctf_contract_address, provider);
         
@@ -1260,6 +1895,7 @@ impl PolymarketApi {
             .isApprovedForAll(account_to_check, exchange_address)
             .call()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to check isApprovedForAll")?;
         
         Ok(approved)
@@ -1268,15 +1904,9 @@ impl PolymarketApi {
     /// Check all approvals for all contracts (like SDK's check_approvals example)
     /// Returns a vector of (contract_name, usdc_approved, ctf_approved) tuples
     pub async fn check_all_approvals(&self) -> Result<Vec<(String, bool, bool)>> {
-        use polymarket_client_sdk::types::address;
-        
-        const RPC_URL: &str = "https://polygon-rpc.com";
-        const USDC_ADDRESS: Address = address!("0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174");
-        
-        let config = contract_config(POLYGON, false)
-            .ok_or_else(|| anyhow::anyhow!("Failed to get contract config from SDK"))?;
-        let neg_risk_config = contract_config(POLYGON, true)
-            .ok_or_else(|| anyhow::anyhow!("Failed to get neg risk contract config from SDK"))?;
+        let config = self.contracts(false)?;
+        let usdc_address = config.collateral;
+        let neg_risk_config = self.contracts(true)?;
         
         // Determine which address to check (proxy wallet or EOA)
         let account_to_check = if let Some(proxy_addr) = &self.proxy_wallet_address {
@@ -1287,16 +1917,14 @@ impl PolymarketApi {
                 .ok_or_else(|| anyhow::anyhow!("Private key required to check approval"))?;
             let signer = LocalSigner::from_str(private_key)
                 .context("Failed to create signer from private key")?
-                .with_chain_id(Some(POLYGON));
+                .with_chain_id(Some(self.chain_id));
             signer.address()
         };
         
         let provider = ProviderBuilder::new()
-            .connect(RPC_URL)
-            .await
-            .context("Failed to connect to Polygon RPC")?;
+            .connect_client(self.rpc_client()?);
         
-        let usdc = IERC20::new(USDC_ADDRESS, provider.clone());
+        let usdc = IERC20::new(usdc_address, provider.clone());
         let ctf = IERC1155::new(config.conditional_tokens, provider.clone());
         
         // Collect all contracts that need approval
@@ -1346,8 +1974,7 @@ impl PolymarketApi {
         // Based on SDK example: https://github.com/Polymarket/rs-clob-client/blob/main/examples/approvals.rs
         // - config.conditional_tokens = CTF contract (where we call setApprovalForAll)
         // - config.exchange = CTF Exchange (the operator we approve)
-        let config = contract_config(POLYGON, false)
-            .ok_or_else(|| anyhow::anyhow!("Failed to get contract config from SDK"))?;
+        let config = self.contracts(false)?;
         
         let ctf_contract_address = config.conditional_tokens;
         let exchange_address = config.exchange;
@@ -1376,20 +2003,16 @@ impl PolymarketApi {
             // Create signer from private key
             let signer = LocalSigner::from_str(private_key)
                 .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
-                .with_chain_id(Some(POLYGON));
+                .with_chain_id(Some(self.chain_id));
             
             let signer_address = signer.address();
             eprintln!("   💰 Wallet that needs MATIC for gas: {:#x}", signer_address);
             
             // Use direct RPC call like SDK example (instead of relayer)
             // Based on: https://github.com/Polymarket/rs-clob-client/blob/main/examples/approvals.rs
-            const RPC_URL: &str = "https://polygon-rpc.com";
-            
             let provider = ProviderBuilder::new()
                 .wallet(signer.clone())
-                .connect(RPC_URL)
-                .await
-                .context("Failed to connect to Polygon RPC")?;
+                .connect_client(self.rpc_client()?);
             
             // Create IERC1155 contract instance
             let ctf = IERC1155::new(ctf_contract_address, provider.clone());
@@ -1401,6 +2024,7 @@ impl PolymarketApi {
                 .setApprovalForAll(exchange_address, true)
                 .send()
                 .await
+                .map_err(|e| ApiError::classify(&format!("{:?}", e)))
                 .context("Failed to send setApprovalForAll transaction")?
                 .watch()
                 .await
@@ -1458,7 +2082,7 @@ impl PolymarketApi {
         
         // Use relayer for gasless transaction. The /execute path returns 404; the
         // builder-relayer-client uses POST /submit. See: Polymarket/builder-relayer-client
-        const RELAYER_SUBMIT: &str = "https://relayer-v2.polymarket.com/submit";
+        let relayer_submit = format!("{}/submit", self.relayer_url("setApprovalForAll via relayer")?);
         
         eprintln!("   📤 Sending setApprovalForAll transaction via relayer (POST /submit)...");
         
@@ -1527,7 +2151,7 @@ impl PolymarketApi {
         
         // Send request to relayer
         let response = self.client
-            .post(RELAYER_SUBMIT)
+            .post(&relayer_submit)
             .header("User-Agent", "polymarket-trading-bot/1.0")
             .header("POLY_BUILDER_API_KEY", api_key)
             .header("POLY_BUILDER_TIMESTAMP", &timestamp)
@@ -1538,6 +2162,7 @@ impl PolymarketApi {
             .json(&relayer_request)
             .send()
             .await
+            .map_err(ApiError::from_reqwest)
             .context("Failed to send setApprovalForAll request to relayer")?;
         
         let status = response.status();
@@ -1558,7 +2183,7 @@ impl PolymarketApi {
                 ""
             };
             
-            anyhow::bail!(
+            return Err(ApiError::from_status(status, format!(
                 "Relayer rejected setApprovalForAll request (status: {}): {}\n\
                 \n\
                 CTF Contract Address: {:#x}\n\
@@ -1568,7 +2193,7 @@ impl PolymarketApi {
                 This may be a relayer endpoint issue, authentication problem, or request format mismatch.\n\
                 Please verify your Builder API credentials are correct.{}",
                 status, response_text, ctf_contract_address, exchange_address, self.signature_type, sig_type_hint
-            );
+            )).into());
         }
         
         // Parse relayer response
@@ -1595,7 +2220,7 @@ impl PolymarketApi {
     async fn wait_for_relayer_transaction(&self, transaction_id: &str) -> Result<String> {
         // Based on TypeScript SDK pattern: response.wait() returns transactionHash
         // Relayer states: STATE_NEW, STATE_EXECUTED, STATE_MINE, STATE_CONFIRMED, STATE_FAILED, STATE_INVALID
-        let status_url = format!("https://relayer-v2.polymarket.com/transaction/{}", transaction_id);
+        let status_url = format!("{}/transaction/{}", self.relayer_url("Relayer transaction status")?, transaction_id);
         
         // Poll for transaction confirmation (with timeout)
         let max_wait_seconds = 120;
@@ -1784,7 +2409,7 @@ impl PolymarketApi {
         // Create signer from private key
         let signer = LocalSigner::from_str(private_key)
             .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
-            .with_chain_id(Some(POLYGON));
+            .with_chain_id(Some(self.chain_id));
         
         // Build authentication builder with proxy wallet support
         let mut auth_builder = ClobClient::new(&self.clob_url, ClobConfig::default())
@@ -1833,6 +2458,7 @@ impl PolymarketApi {
         let client = auth_builder
             .authenticate()
             .await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
             .context("Failed to authenticate with CLOB API. Check your API credentials.")?;
         
         // Convert order side string to SDK Side enum
@@ -1881,13 +2507,13 @@ impl PolymarketApi {
                     eprintln!("   Order Amount: ${:.2}", amount_decimal);
                     
                     if usdc_balance_f64 < f64::try_from(amount_decimal).unwrap_or(0.0) {
-                        anyhow::bail!(
-                            "Insufficient USDC balance for BUY order.\n\
+                        return Err(ApiError::InsufficientBalance(format!(
+                            "USDC for BUY order.\n\
                             Required: ${:.2}, Available: ${:.2}\n\
                             Please deposit USDC to your proxy wallet: {}",
                             amount_decimal, usdc_balance_f64,
                             self.proxy_wallet_address.as_deref().unwrap_or("your wallet")
-                        );
+                        )).into());
                     }
                     
                     if usdc_allowance_f64 < f64::try_from(amount_decimal).unwrap_or(0.0) {
@@ -1988,9 +2614,9 @@ impl PolymarketApi {
                     // Separate balance errors from allowance errors
                     // Balance error: You don't own enough tokens (shouldn't retry)
                     // Allowance error: You own tokens but haven't approved contract (should retry - SDK may auto-approve)
-                    let is_allowance_error = error_str.contains("allowance") || 
-                                           (error_str.contains("not enough") && error_str.contains("allowance"));
-                    let is_balance_error = error_str.contains("balance") && !error_str.contains("allowance");
+                    let api_error = ApiError::classify(&error_str);
+                    let is_allowance_error = matches!(api_error, ApiError::InsufficientAllowance(_));
+                    let is_balance_error = matches!(api_error, ApiError::InsufficientBalance(_));
                     
                     retry_count += 1;
                     
@@ -2026,14 +2652,14 @@ impl PolymarketApi {
                     
                     // For balance errors, don't retry - return error immediately
                     if is_balance_error {
-                        anyhow::bail!(
-                            "Insufficient token balance: {}\n\
+                        return Err(ApiError::InsufficientBalance(format!(
+                            "{}\n\
                             Order details: Side={}, Amount={}, Token ID={}\n\
                             \n\
                             This is a portfolio balance issue - you don't own enough tokens.\n\
                             Retrying won't help. Please check your Polymarket portfolio.",
                             error_str, side, amount_decimal, token_id
-                        );
+                        )).into());
                     }
                     
                     // DISABLED: If we've exhausted retries, try setApprovalForAll before giving up
@@ -2062,8 +2688,8 @@ impl PolymarketApi {
                     if is_allowance_error {
                         if matches!(side_enum, Side::Buy) {
                             // For BUY orders, this is USDC allowance issue
-                            anyhow::bail!(
-                                "Insufficient USDC allowance for BUY order: {}\n\
+                            return Err(ApiError::InsufficientAllowance(format!(
+                                "USDC for BUY order: {}\n\
                                 Order details: Side=BUY, Amount=${}, Token ID={}\n\
                                 \n\
                                 USDC allowance issue - SDK may need more time to auto-approve USDC.\n\
@@ -2075,25 +2701,25 @@ impl PolymarketApi {
                                 \n\
                                 This order will be retried on the next check cycle.",
                                 error_str, amount_decimal, token_id
-                            );
+                            )).into());
                         } else {
                             // For SELL orders, this is conditional token allowance issue
-                            anyhow::bail!(
-                                "Insufficient allowance: {}\n\
+                            return Err(ApiError::InsufficientAllowance(format!(
+                                "{}\n\
                                 Order details: Side=SELL, Amount={}, Token ID={}\n\
                                 \n\
                                 Token allowance issue - SDK may need more time to auto-approve.\n\
                                 This order will be retried on the next check cycle.",
                                 error_str, amount_decimal, token_id
-                            );
+                            )).into());
                         }
                     }
                     
-                    anyhow::bail!(
+                    return Err(api_error).context(format!(
                         "Failed to post market order: {}\n\
                         Order details: Side={}, Amount={}, Token ID={}",
                         e, side, amount_decimal, token_id
-                    );
+                    ));
                 }
             }
         };
@@ -2107,11 +2733,11 @@ impl PolymarketApi {
             eprintln!("      Side: {}", side);
             eprintln!("      Amount: ${}", amount_decimal);
             eprintln!("      Type: Market order (price determined by market)");
-                anyhow::bail!(
+                return Err(ApiError::classify(error_msg)).context(format!(
                     "Order was rejected: {}\n\
                     Order details: Token ID={}, Side={}, Amount=${}",
                     error_msg, token_id, side, amount_decimal
-                );
+                ));
         }
         
         // Convert SDK response to our OrderResponse format
@@ -2155,6 +2781,7 @@ impl PolymarketApi {
         let response = request
             .send()
             .await
+            .map_err(ApiError::from_reqwest)
             .context("Failed to place order")?;
 
         let status = response.status();
@@ -2163,13 +2790,10 @@ impl PolymarketApi {
             
             // Provide helpful error messages
             if status == 401 || status == 403 {
-                anyhow::bail!(
-                    "Authentication failed (status: {}): {}",
-                    status, error_text
-                );
+                return Err(ApiError::Auth(format!("Authentication failed (status: {}): {}", status, error_text)).into());
             }
             
-            anyhow::bail!("Failed to place order (status: {}): {}", status, error_text);
+            return Err(ApiError::from_status(status, format!("Failed to place order (status: {}): {}", status, error_text)).into());
         }
 
         let order_response: OrderResponse = response
@@ -2206,11 +2830,8 @@ impl PolymarketApi {
         // No need for private key signing - relayer handles it
         // Based on docs: https://docs.polymarket.com/developers/builders/relayer-client#redeem-positions
         
-        // USDC collateral token address on Polygon
-        let collateral_token = AlloyAddress::parse_checksummed(
-            "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174",
-            None
-        ).context("Failed to parse USDC address")?;
+        // USDC collateral token address of the configured chain
+        let collateral_token = self.contracts(false)?.collateral;
         
         // Parse condition_id to B256 (remove 0x prefix if present)
         let condition_id_clean = condition_id.strip_prefix("0x").unwrap_or(condition_id);
@@ -2226,35 +2847,20 @@ impl PolymarketApi {
         eprintln!("🔄 Redeeming tokens for condition {} (outcome: {})", condition_id, outcome);
         eprintln!("   📋 Passing index_sets: [1, 2] (contract will only redeem winning tokens)");
         
+        // EOA wallets hold the tokens themselves, so they redeem with their own transaction
+        if self.proxy_wallet_address.is_none() && self.private_key.is_some() {
+            return self.redeem_onchain(condition_id_b256, index_sets, outcome).await;
+        }
+        
         // Use Relayer Client for gasless transactions. The /execute path returns 404;
         // builder-relayer-client uses POST /submit. See: Polymarket/builder-relayer-client
         // CTF contract: 0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E
         // Function: redeemPositions(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] indexSets)
         
-        const CTF_CONTRACT: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
-        const RELAYER_SUBMIT: &str = "https://relayer-v2.polymarket.com/submit";
-        
-        let relayer_url = RELAYER_SUBMIT;
+        // Same address as the SDK's exchange contract on Polygon mainnet; taken from the configured chain
+        let ctf_address = self.contracts(false)?.exchange;
+        let relayer_url = format!("{}/submit", self.relayer_url("Redemption")?);
         
-        // Parse CTF contract address using AlloyAddress
-        // Use parse instead of parse_checksummed to avoid checksum validation issues
-        eprintln!("   🔍 Parsing CTF contract address: {}", CTF_CONTRACT);
-        let ctf_address = CTF_CONTRACT.strip_prefix("0x")
-            .and_then(|s| {
-                eprintln!("   🔍 Decoding hex: {}", s);
-                hex::decode(s).ok()
-            })
-            .and_then(|bytes| {
-                eprintln!("   🔍 Decoded bytes length: {}", bytes.len());
-                if bytes.len() == 20 {
-                    Some(AlloyAddress::from_slice(&bytes))
-                } else {
-                    eprintln!("   ⚠️  Invalid address length: {} (expected 20)", bytes.len());
-                    None
-                }
-            })
-            .ok_or_else(|| anyhow::anyhow!("Invalid CTF contract address format: {}", CTF_CONTRACT))
-            .context("Failed to parse CTF contract address")?;
         eprintln!("   ✅ Successfully parsed CTF address: {:#x}", ctf_address);
         
         let parent_collection_id = B256::ZERO;
@@ -2385,7 +2991,7 @@ impl PolymarketApi {
         // CRITICAL: Use .body() with the exact same body_string used for HMAC
         // This ensures the request body matches exactly what was signed
         let response = self.client
-            .post(relayer_url)
+            .post(&relayer_url)
             .header("User-Agent", "polymarket-trading-bot/1.0")
             .header("POLY_BUILDER_API_KEY", api_key)
             .header("POLY_BUILDER_TIMESTAMP", &timestamp)
@@ -2396,6 +3002,7 @@ impl PolymarketApi {
             .body(body_string)  // Use the exact same body string used for HMAC
             .send()
             .await
+            .map_err(ApiError::from_reqwest)
             .context("Failed to send redemption request to relayer")?;
         
         let status = response.status();
@@ -2408,7 +3015,7 @@ impl PolymarketApi {
         if !status.is_success() {
             // Provide detailed error message for 401 Unauthorized
             if status == 401 {
-                anyhow::bail!(
+                return Err(ApiError::Auth(format!(
                     "Relayer redemption failed: 401 Unauthorized - Invalid Builder API credentials\n\
                     \n\
                     This error means your Builder API credentials are incorrect or missing.\n\
@@ -2426,13 +3033,13 @@ impl PolymarketApi {
                     Response: {}",
                     self.signature_type.unwrap_or(0),
                     &response_text[..500.min(response_text.len())]
-                );
+                )).into());
             }
             
-            anyhow::bail!(
+            return Err(ApiError::from_status(status, format!(
                 "Relayer redemption failed (status {}): {}",
                 status, &response_text[..200.min(response_text.len())]
-            );
+            )).into());
         }
         
         // Parse relayer response
@@ -2449,7 +3056,7 @@ impl PolymarketApi {
         
         // Poll for transaction status
         // Relayer states: STATE_NEW, STATE_EXECUTED, STATE_MINE, STATE_CONFIRMED, STATE_FAILED, STATE_INVALID
-        let status_url = format!("https://relayer-v2.polymarket.com/transaction/{}", transaction_id);
+        let status_url = format!("{}/transaction/{}", self.relayer_url("Relayer transaction status")?, transaction_id);
         
         // Poll for transaction confirmation (with timeout)
         let max_wait_seconds = 120;
@@ -2525,6 +3132,17 @@ impl PolymarketApi {
                                 
                                 match state {
                                     "STATE_CONFIRMED" => {
+                                        if let Some(hash) = tx_hash {
+                                            eprintln!("   Transaction hash: {}", hash);
+                                        }
+                                        if let Some(pending) = self.relayer_confirmations_pending(tx_hash).await {
+                                            return Ok(RedeemResponse {
+                                                success: false,
+                                                message: Some(pending),
+                                                transaction_hash: tx_hash.map(|s| s.to_string()),
+                                                amount_redeemed: None,
+                                            });
+                                        }
                                         let redeem_response = RedeemResponse {
                                             success: true,
                                             message: Some(format!("Successfully redeemed tokens via relayer. Transaction ID: {}", transaction_id)),
@@ -2534,9 +3152,6 @@ impl PolymarketApi {
                                         
                                         eprintln!("✅ Successfully redeemed tokens via Relayer Client!");
                                         eprintln!("   Transaction ID: {}", transaction_id);
-                                        if let Some(hash) = tx_hash {
-                                            eprintln!("   Transaction hash: {}", hash);
-                                        }
                                         
                                         return Ok(redeem_response);
                                     }
@@ -2574,6 +3189,163 @@ impl PolymarketApi {
         }
     }
 
+    /// Redeem with a transaction sent from the EOA wallet, priced by `[chain.gas]`
+    /// Succeeds once the transaction is `confirmations` blocks deep; one still pending after `stuck_timeout_secs`
+    /// is replaced (same nonce) with fees raised by `bump_percent`, up to `max_resubmits` times
+    async fn redeem_onchain(&self, condition_id: B256, index_sets: Vec<U256>, outcome: &str) -> Result<RedeemResponse> {
+        let private_key = self.private_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Private key is required for redemption. Please set private_key in config.json"))?;
+        let signer = LocalSigner::from_str(private_key)
+            .context("Failed to create signer from private key. Ensure private_key is a valid hex string.")?
+            .with_chain_id(Some(self.chain_id));
+        let wallet = signer.address();
+        let provider = ProviderBuilder::new()
+            .wallet(signer)
+            .connect_client(self.rpc_client()?);
+        
+        // Unlike the relayer path, the call goes to the conditional tokens contract that holds the positions
+        let contracts = self.contracts(false)?;
+        let ctf = IConditionalTokens::new(contracts.conditional_tokens, provider.clone());
+        let confirmations = self.gas.confirmations.unwrap_or(DEFAULT_GAS_CONFIRMATIONS);
+        let stuck_timeout = std::time::Duration::from_secs(self.gas.stuck_timeout_secs.unwrap_or(DEFAULT_GAS_STUCK_TIMEOUT_SECS));
+        let max_resubmits = self.gas.max_resubmits.unwrap_or(DEFAULT_GAS_MAX_RESUBMITS);
+        
+        eprintln!("   🔄 Using direct RPC call for EOA wallet {:#x} (gas strategy: {:?})", wallet, self.gas.strategy);
+        eprintln!("   - CTF Contract (conditional_tokens): {:#x}", contracts.conditional_tokens);
+        
+        // Every attempt reuses the nonce, so whichever of them is mined is the redemption
+        let nonce = provider.get_transaction_count(wallet).pending().await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
+            .context("Failed to fetch wallet nonce")?;
+        let (mut max_fee, mut priority_fee) = self.redemption_fees(&provider).await?;
+        let mut tx_hashes: Vec<B256> = Vec::new();
+        
+        for attempt in 0..=max_resubmits {
+            let mut send = true;
+            if attempt > 0 {
+                match self.bumped_fees(max_fee, priority_fee) {
+                    Some((bumped_max, bumped_priority)) => {
+                        max_fee = bumped_max;
+                        priority_fee = bumped_priority;
+                    }
+                    None => {
+                        warn!("Redemption fees are at the chain.gas caps - waiting on the pending transaction instead of re-submitting");
+                        send = false;
+                    }
+                }
+            }
+            
+            if send {
+                let sent = ctf
+                    .redeemPositions(contracts.collateral, B256::ZERO, condition_id, index_sets.clone())
+                    .nonce(nonce)
+                    .max_fee_per_gas(max_fee)
+                    .max_priority_fee_per_gas(priority_fee)
+                    .send()
+                    .await;
+                match sent {
+                    Ok(pending) => {
+                        let tx_hash = *pending.tx_hash();
+                        eprintln!("   📤 Redemption attempt {} for {}: tx {:#x} (nonce {}, max fee {:.2} gwei, priority fee {:.2} gwei)",
+                            attempt + 1, outcome, tx_hash, nonce, max_fee as f64 / 1e9, priority_fee as f64 / 1e9);
+                        tx_hashes.push(tx_hash);
+                    }
+                    // A replacement is rejected (e.g. "nonce too low") once an earlier attempt has been mined
+                    Err(e) if !tx_hashes.is_empty() => {
+                        warn!("Redemption re-submission {} was rejected: {} - checking earlier attempts", attempt, e);
+                    }
+                    Err(e) => return Err(ApiError::classify(&format!("{:?}", e))).context("Failed to send redemption transaction"),
+                }
+            }
+            
+            if let Some(receipt) = wait_for_confirmations(&provider, &tx_hashes, confirmations, stuck_timeout).await {
+                if !receipt.status() {
+                    anyhow::bail!("Redemption transaction {:#x} reverted", receipt.transaction_hash);
+                }
+                eprintln!("✅ Successfully redeemed tokens on-chain!");
+                eprintln!("   Transaction hash: {:#x} ({} confirmation(s))", receipt.transaction_hash, confirmations);
+                return Ok(RedeemResponse {
+                    success: true,
+                    message: Some(format!("Successfully redeemed tokens on-chain. Transaction hash: {:#x}", receipt.transaction_hash)),
+                    transaction_hash: Some(format!("{:#x}", receipt.transaction_hash)),
+                    amount_redeemed: None,
+                });
+            }
+            eprintln!("   ⏱️  Redemption not confirmed within {}s", stuck_timeout.as_secs());
+        }
+        
+        eprintln!("   ⏱️  Redemption still unconfirmed after {} re-submission(s) - will retry on next check", max_resubmits);
+        Ok(RedeemResponse {
+            success: false,
+            message: Some(format!("Redemption not confirmed after {} re-submission(s). Will retry.", max_resubmits)),
+            transaction_hash: tx_hashes.last().map(|hash| format!("{:#x}", hash)),
+            amount_redeemed: None,
+        })
+    }
+    
+    /// Starting (max fee, priority fee) per gas in wei for the `[chain.gas]` strategy
+    async fn redemption_fees<P: Provider>(&self, provider: &P) -> Result<(u128, u128)> {
+        use crate::config::GasStrategy;
+        let gas = &self.gas;
+        if gas.strategy == GasStrategy::Fixed {
+            let max_fee = gas.max_fee_gwei.context("chain.gas.max_fee_gwei is required with strategy \"fixed\"")?;
+            let priority_fee = gas.priority_fee_gwei.context("chain.gas.priority_fee_gwei is required with strategy \"fixed\"")?;
+            return Ok((gwei_to_wei(max_fee), gwei_to_wei(priority_fee)));
+        }
+        
+        let estimate = provider.estimate_eip1559_fees().await
+            .map_err(|e| ApiError::classify(&format!("{:?}", e)))
+            .context("Failed to estimate gas fees")?;
+        let factor = match gas.strategy {
+            GasStrategy::Multiplier => gas.multiplier.unwrap_or(DEFAULT_GAS_MULTIPLIER),
+            _ => 1.0,
+        };
+        let max_fee = cap_fee((estimate.max_fee_per_gas as f64 * factor) as u128, gas.max_fee_gwei);
+        let priority_fee = cap_fee((estimate.max_priority_fee_per_gas as f64 * factor) as u128, gas.priority_fee_gwei);
+        Ok((max_fee, priority_fee.min(max_fee)))
+    }
+    
+    /// Fees for re-submitting a stuck redemption, or None if the caps leave no room for a valid replacement
+    fn bumped_fees(&self, max_fee: u128, priority_fee: u128) -> Option<(u128, u128)> {
+        let bump_percent = self.gas.bump_percent.unwrap_or(DEFAULT_GAS_BUMP_PERCENT) as u128;
+        let bump = |fee: u128| fee + fee * bump_percent / 100;
+        let bumped_max = cap_fee(bump(max_fee), self.gas.max_fee_gwei);
+        let bumped_priority = cap_fee(bump(priority_fee), self.gas.priority_fee_gwei).min(bumped_max);
+        // Nodes only replace a pending transaction when both fees rise by at least 10%
+        (bumped_max >= max_fee + max_fee / 10 && bumped_priority >= priority_fee + priority_fee / 10)
+            .then_some((bumped_max, bumped_priority))
+    }
+    
+    /// After the relayer reports a redemption confirmed, wait for `chain.gas.confirmations` blocks on top of it
+    /// Returns a "will retry" message if the depth isn't reached within `stuck_timeout_secs`, None once it is
+    async fn relayer_confirmations_pending(&self, tx_hash: Option<&str>) -> Option<String> {
+        let confirmations = self.gas.confirmations.unwrap_or(DEFAULT_GAS_CONFIRMATIONS);
+        if confirmations <= 1 {
+            return None;
+        }
+        let Some(hash) = tx_hash.and_then(|hash| B256::from_str(hash).ok()) else {
+            warn!("Relayer did not return a parseable transaction hash - cannot wait for {} confirmations", confirmations);
+            return None;
+        };
+        let provider = match self.rpc_client() {
+            Ok(client) => ProviderBuilder::new().connect_client(client),
+            Err(e) => {
+                warn!("Cannot check redemption confirmations: {}", e);
+                return None;
+            }
+        };
+        
+        eprintln!("   Waiting for {} confirmations of {:#x}...", confirmations, hash);
+        let timeout = std::time::Duration::from_secs(self.gas.stuck_timeout_secs.unwrap_or(DEFAULT_GAS_STUCK_TIMEOUT_SECS));
+        match wait_for_confirmations(&provider, &[hash], confirmations, timeout).await {
+            Some(_) => None,
+            None => {
+                eprintln!("   ⏱️  {:#x} not {} blocks deep within {}s - will retry on next check", hash, confirmations, timeout.as_secs());
+                Some(format!("Redemption {:#x} not yet {} blocks deep. Will retry.", hash, confirmations))
+            }
+        }
+    }
+
     /// Merge complete sets of Up and Down tokens for a condition into USDC.
     /// Burns min(Up_balance, Down_balance) pairs and returns that much USDC via the CTF relayer.
     /// Uses the same redeemPositions(conditionId, [1,2]) flow as redeem_tokens.
@@ -2582,3 +3354,221 @@ impl PolymarketApi {
     }
 }
 
+
+/// Wei per gas for a fee given in gwei
+fn gwei_to_wei(gwei: f64) -> u128 {
+    (gwei * 1e9) as u128
+}
+
+/// Fee in wei, limited to an optional cap in gwei
+fn cap_fee(fee: u128, cap_gwei: Option<f64>) -> u128 {
+    cap_gwei.map_or(fee, |cap| fee.min(gwei_to_wei(cap)))
+}
+
+/// Poll until one of `tx_hashes` (replacements sharing a nonce) is mined and `confirmations` blocks deep
+/// Returns None if none of them is mined within `timeout`; once one is mined, waits for its depth without a limit
+async fn wait_for_confirmations<P: Provider>(
+    provider: &P,
+    tx_hashes: &[B256],
+    confirmations: u64,
+    timeout: std::time::Duration,
+) -> Option<alloy::rpc::types::eth::TransactionReceipt> {
+    let mut deadline = std::time::Instant::now() + timeout;
+    loop {
+        for hash in tx_hashes {
+            match provider.get_transaction_receipt(*hash).await {
+                Ok(Some(receipt)) => {
+                    let mined_in = receipt.block_number.unwrap_or_default();
+                    match provider.get_block_number().await {
+                        Ok(head) if head + 1 >= mined_in + confirmations => return Some(receipt),
+                        Ok(_) => {}
+                        Err(e) => warn!("Failed to fetch block number: {} - will retry", e),
+                    }
+                    deadline = std::time::Instant::now() + timeout;
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to fetch receipt for {:#x}: {} - will retry", hash, e),
+            }
+        }
+        if std::time::Instant::now() >= deadline {
+            return None;
+        }
+        tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+    }
+}
+
+/// Map an SDK order response onto our OpenOrder (status uppercased: LIVE, MATCHED, CANCELED, ...)
+fn open_order_from(o: OpenOrderResponse) -> OpenOrder {
+    OpenOrder {
+        order_id: o.id,
+        token_id: o.asset_id,
+        side: match o.side {
+            Side::Buy => "BUY".to_string(),
+            _ => "SELL".to_string(),
+        },
+        price: f64::try_from(o.price).unwrap_or(0.0),
+        original_size: f64::try_from(o.original_size).unwrap_or(0.0),
+        size_matched: f64::try_from(o.size_matched).unwrap_or(0.0),
+        status: format!("{:?}", o.status).to_uppercase(),
+        created_at: o.created_at.timestamp(),
+    }
+}
+
+fn own_trade_from(t: TradeResponse) -> OwnTrade {
+    OwnTrade {
+        trade_id: t.id,
+        token_id: t.asset_id,
+        side: match t.side {
+            Side::Buy => "BUY".to_string(),
+            _ => "SELL".to_string(),
+        },
+        size: f64::try_from(t.size).unwrap_or(0.0),
+        price: f64::try_from(t.price).unwrap_or(0.0),
+        status: format!("{:?}", t.status).to_uppercase(),
+        match_time: t.match_time.timestamp(),
+    }
+}
+
+/// Parse a user-channel message into events
+/// The server sends either a single JSON object or an array of them; "PONG" and unknown types are skipped
+fn parse_user_events(text: &str) -> Vec<UserEvent> {
+    let Ok(json) = serde_json::from_str::<Value>(text) else {
+        return Vec::new();
+    };
+    let items = match json {
+        Value::Array(items) => items,
+        other => vec![other],
+    };
+
+    items.into_iter()
+        .filter_map(|item| {
+            match item.get("event_type").and_then(|t| t.as_str()) {
+                Some("trade") => serde_json::from_value::<UserTrade>(item).ok().map(UserEvent::Trade),
+                Some("order") => serde_json::from_value::<UserOrderUpdate>(item).ok().map(UserEvent::OrderUpdate),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Parse a market-channel message into top-of-book quotes
+/// "book" snapshots carry the full book (best bid = highest bid, best ask = lowest ask); "price_change" and
+/// "best_bid_ask" updates carry best_bid / best_ask per asset. "PONG", trades and unknown types are skipped
+fn parse_market_quotes(text: &str) -> Vec<MarketQuote> {
+    let Ok(json) = serde_json::from_str::<Value>(text) else {
+        return Vec::new();
+    };
+    let items = match json {
+        Value::Array(items) => items,
+        other => vec![other],
+    };
+    // Prices arrive as strings; "0" means that side of the book is empty
+    let price = |value: Option<&Value>| value
+        .and_then(|v| v.as_str())
+        .and_then(|s| rust_decimal::Decimal::from_str(s).ok())
+        .filter(|p| !p.is_zero());
+    let asset = |item: &Value| item.get("asset_id").and_then(|a| a.as_str()).map(str::to_string);
+    let quote = |item: &Value| asset(item).map(|token_id| MarketQuote {
+        token_id,
+        best_bid: price(item.get("best_bid")),
+        best_ask: price(item.get("best_ask")),
+    });
+
+    let mut quotes = Vec::new();
+    for item in &items {
+        match item.get("event_type").and_then(|t| t.as_str()) {
+            Some("book") => {
+                let Some(token_id) = asset(item) else { continue };
+                // Older messages name the sides buys/sells
+                let levels = |side: &str, legacy: &str| item.get(side).or_else(|| item.get(legacy))
+                    .and_then(|l| l.as_array())
+                    .map(|levels| levels.iter().filter_map(|level| price(level.get("price"))).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                quotes.push(MarketQuote {
+                    token_id,
+                    best_bid: levels("bids", "buys").into_iter().max(),
+                    best_ask: levels("asks", "sells").into_iter().min(),
+                });
+            }
+            Some("price_change") => {
+                let changes = item.get("price_changes").and_then(|c| c.as_array());
+                quotes.extend(changes.into_iter().flatten()
+                    .filter(|change| change.get("best_bid").is_some() || change.get("best_ask").is_some())
+                    .filter_map(quote));
+            }
+            Some("best_bid_ask") => quotes.extend(quote(item)),
+            _ => {}
+        }
+    }
+    quotes
+}
+
+type WebSocket = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Open a WebSocket, tunnelling through an HTTP CONNECT proxy when `proxy` is set
+async fn connect_websocket(url: &str, proxy: Option<&str>) -> Result<WebSocket> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let Some(proxy) = proxy else {
+        let (ws, _) = tokio_tungstenite::connect_async(url).await?;
+        return Ok(ws);
+    };
+
+    let target = reqwest::Url::parse(url).context(format!("Invalid WebSocket URL: {}", url))?;
+    let host = target.host_str().ok_or_else(|| anyhow::anyhow!("WebSocket URL has no host: {}", url))?;
+    let port = target.port_or_known_default().unwrap_or(443);
+    let proxy_url = reqwest::Url::parse(proxy).context(format!("Invalid network.ws_proxy: {}", proxy))?;
+    let proxy_host = proxy_url.host_str().ok_or_else(|| anyhow::anyhow!("Proxy URL has no host: {}", proxy))?;
+    let proxy_port = proxy_url.port_or_known_default().unwrap_or(8080);
+
+    let mut stream = tokio::net::TcpStream::connect((proxy_host, proxy_port)).await
+        .context(format!("Failed to connect to WebSocket proxy {}", proxy))?;
+    let connect_request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n\r\n");
+    stream.write_all(connect_request.as_bytes()).await?;
+
+    // Read the proxy's response headers (up to the blank line)
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if stream.read(&mut byte).await? == 0 || response.len() > 8192 {
+            anyhow::bail!("WebSocket proxy {} closed the connection during CONNECT", proxy);
+        }
+        response.push(byte[0]);
+    }
+    let status_line = String::from_utf8_lossy(&response);
+    let status_line = status_line.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        anyhow::bail!("WebSocket proxy {} refused CONNECT: {}", proxy, status_line);
+    }
+
+    let (ws, _) = tokio_tungstenite::client_async_tls(url, stream).await?;
+    Ok(ws)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn market_channel_messages_become_top_of_book_quotes() {
+        let decimal = |s: &str| Some(rust_decimal::Decimal::from_str(s).unwrap());
+        let book = r#"{"event_type":"book","asset_id":"dummy_up","market":"0x01",
+            "bids":[{"price":"0.48","size":"30"},{"price":"0.50","size":"10"}],
+            "asks":[{"price":"0.55","size":"5"},{"price":"0.52","size":"8"}]}"#;
+        assert_eq!(parse_market_quotes(book), vec![
+            MarketQuote { token_id: "dummy_up".to_string(), best_bid: decimal("0.50"), best_ask: decimal("0.52") },
+        ]);
+
+        // Batched updates; an empty side comes through as "0"
+        let updates = r#"[{"event_type":"price_change","market":"0x01","price_changes":[
+                {"asset_id":"dummy_up","price":"0.51","size":"20","side":"BUY","best_bid":"0.51","best_ask":"0.52"},
+                {"asset_id":"dummy_down","price":"0.49","size":"0","side":"SELL","best_bid":"0","best_ask":"0.49"}]},
+            {"event_type":"last_trade_price","asset_id":"dummy_up","price":"0.52"}]"#;
+        assert_eq!(parse_market_quotes(updates), vec![
+            MarketQuote { token_id: "dummy_up".to_string(), best_bid: decimal("0.51"), best_ask: decimal("0.52") },
+            MarketQuote { token_id: "dummy_down".to_string(), best_bid: None, best_ask: decimal("0.49") },
+        ]);
+
+        assert!(parse_market_quotes("PONG").is_empty());
+    }
+}
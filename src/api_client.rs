@@ -0,0 +1,394 @@
+use crate::api::PolymarketApi;
+use crate::models::*;
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+/// The subset of `PolymarketApi` the trader uses: prices, market data, balances, orders and redemption
+/// `Trader` holds an `Arc<dyn ApiClient>`, so its state machine can be driven in tests by `MockApi`
+/// The `PolymarketApi` impl counts every call, its failures and order latency in `metrics`, and runs it through
+/// the client's `Retrier` (network.retry): reads, balance checks and cancels are retried on transient errors,
+/// order placement and redemption only go through the circuit breakers
+#[async_trait]
+pub trait ApiClient: Send + Sync {
+    async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook>;
+
+    /// Top of book (bid = best bid, ask = best ask). Err(CrossedBook) if the book is crossed/locked
+    async fn get_best_price(&self, token_id: &str) -> Result<Option<TokenPrice>>;
+
+    /// Price for `side`: "BUY" = what a buyer pays (best ask), "SELL" = what a seller receives (best bid)
+    async fn get_price(&self, token_id: &str, side: &str) -> Result<Decimal>;
+
+    async fn get_market(&self, condition_id: &str) -> Result<MarketDetails>;
+
+    async fn get_uma_resolution_status(&self, condition_id: &str) -> Result<Option<String>>;
+
+    /// Conditional token balance in base units (1e6 per share)
+    async fn check_balance_only(&self, token_id: &str) -> Result<Decimal>;
+
+    /// (balance, allowance) in base units (1e6 per share)
+    async fn check_balance_allowance(&self, token_id: &str) -> Result<(Decimal, Decimal)>;
+
+    /// USDC collateral balance in dollars
+    async fn get_usdc_balance(&self) -> Result<f64>;
+
+    /// USDC in dollars a buy of `token_id` can spend. Default: the USDC balance
+    async fn buy_usdc_balance(&self, _token_id: &str) -> Result<f64> {
+        self.get_usdc_balance().await
+    }
+
+    /// USDC collateral balance in dollars of each trading wallet as (label, balance), primary first
+    async fn wallet_usdc_balances(&self) -> Result<Vec<(String, f64)>> {
+        Ok(vec![(crate::wallets::PRIMARY_WALLET.to_string(), self.get_usdc_balance().await?)])
+    }
+
+    async fn check_is_approved_for_all(&self) -> Result<bool>;
+
+    async fn update_balance_allowance_for_sell(&self, token_id: &str) -> Result<()>;
+
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResponse>;
+
+    /// BUY `amount` is in USD, SELL `amount` is in shares. `order_type` is "FOK" or "FAK"
+    async fn place_market_order(&self, token_id: &str, amount: f64, side: &str, order_type: Option<&str>) -> Result<OrderResponse>;
+
+    /// Our live orders for a token
+    async fn get_open_orders(&self, token_id: &str) -> Result<Vec<OpenOrder>>;
+
+    /// Status and matched size of one of our orders, by order id
+    async fn get_order(&self, order_id: &str) -> Result<OpenOrder>;
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()>;
+
+    /// Our trades in a token matched at or after `after` (Unix seconds)
+    async fn get_trades(&self, token_id: &str, after: i64) -> Result<Vec<OwnTrade>>;
+
+    async fn redeem_tokens(&self, condition_id: &str, token_id: &str, outcome: &str) -> Result<RedeemResponse>;
+
+    /// Positions with the same key are redeemed by one redeem_tokens call: redeemPositions pays out both outcomes
+    /// of a market at once. Default: the condition id
+    async fn redemption_batch_key(&self, condition_id: &str, _token_id: &str) -> String {
+        condition_id.to_string()
+    }
+}
+
+#[async_trait]
+impl ApiClient for PolymarketApi {
+    async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook> {
+        crate::metrics::observe_api("get_orderbook", self.retrier().call("get_orderbook", true, || PolymarketApi::get_orderbook(self, token_id))).await
+    }
+
+    async fn get_best_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
+        crate::metrics::observe_api("get_best_price", self.retrier().call("get_best_price", true, || PolymarketApi::get_best_price(self, token_id))).await
+    }
+
+    async fn get_price(&self, token_id: &str, side: &str) -> Result<Decimal> {
+        crate::metrics::observe_api("get_price", self.retrier().call("get_price", true, || PolymarketApi::get_price(self, token_id, side))).await
+    }
+
+    async fn get_market(&self, condition_id: &str) -> Result<MarketDetails> {
+        crate::metrics::observe_api("get_market", self.retrier().call("get_market", true, || PolymarketApi::get_market(self, condition_id))).await
+    }
+
+    async fn get_uma_resolution_status(&self, condition_id: &str) -> Result<Option<String>> {
+        crate::metrics::observe_api("get_uma_resolution_status", self.retrier().call("get_uma_resolution_status", true, || PolymarketApi::get_uma_resolution_status(self, condition_id))).await
+    }
+
+    async fn check_balance_only(&self, token_id: &str) -> Result<Decimal> {
+        crate::metrics::observe_api("check_balance_only", self.retrier().call("check_balance_only", true, || PolymarketApi::check_balance_only(self, token_id))).await
+    }
+
+    async fn check_balance_allowance(&self, token_id: &str) -> Result<(Decimal, Decimal)> {
+        crate::metrics::observe_api("check_balance_allowance", self.retrier().call("check_balance_allowance", true, || PolymarketApi::check_balance_allowance(self, token_id))).await
+    }
+
+    async fn get_usdc_balance(&self) -> Result<f64> {
+        crate::metrics::observe_api("get_usdc_balance", self.retrier().call("get_usdc_balance", true, || PolymarketApi::get_usdc_balance(self))).await
+    }
+
+    async fn check_is_approved_for_all(&self) -> Result<bool> {
+        crate::metrics::observe_api("check_is_approved_for_all", self.retrier().call("check_is_approved_for_all", true, || PolymarketApi::check_is_approved_for_all(self))).await
+    }
+
+    async fn update_balance_allowance_for_sell(&self, token_id: &str) -> Result<()> {
+        crate::metrics::observe_api("update_balance_allowance_for_sell", self.retrier().call("update_balance_allowance_for_sell", true, || PolymarketApi::update_balance_allowance_for_sell(self, token_id))).await
+    }
+
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResponse> {
+        crate::metrics::observe_order("place_order", &order.side, self.retrier().call("place_order", false, || PolymarketApi::place_order(self, order))).await
+    }
+
+    async fn place_market_order(&self, token_id: &str, amount: f64, side: &str, order_type: Option<&str>) -> Result<OrderResponse> {
+        crate::metrics::observe_order("place_market_order", side, self.retrier().call("place_market_order", false, || PolymarketApi::place_market_order(self, token_id, amount, side, order_type))).await
+    }
+
+    async fn get_open_orders(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        crate::metrics::observe_api("get_open_orders", self.retrier().call("get_open_orders", true, || PolymarketApi::get_open_orders(self, token_id))).await
+    }
+
+    async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+        crate::metrics::observe_api("get_order", self.retrier().call("get_order", true, || PolymarketApi::get_order(self, order_id))).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        crate::metrics::observe_api("cancel_order", self.retrier().call("cancel_order", true, || PolymarketApi::cancel_order(self, order_id))).await
+    }
+
+    async fn get_trades(&self, token_id: &str, after: i64) -> Result<Vec<OwnTrade>> {
+        crate::metrics::observe_api("get_trades", self.retrier().call("get_trades", true, || PolymarketApi::get_trades(self, token_id, after))).await
+    }
+
+    async fn redeem_tokens(&self, condition_id: &str, token_id: &str, outcome: &str) -> Result<RedeemResponse> {
+        crate::metrics::observe_api("redeem_tokens", self.retrier().call("redeem_tokens", false, || PolymarketApi::redeem_tokens(self, condition_id, token_id, outcome))).await
+    }
+}
+
+/// Scripted in-memory exchange for driving `Trader` in tests
+/// Balances and prices are whatever the test last set; every order is recorded and accepted
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub struct MockApi {
+        balances: Mutex<HashMap<String, f64>>,      // Key: token_id, value: shares
+        prices: Mutex<HashMap<String, (f64, f64)>>, // Key: token_id, value: (best bid, best ask)
+        markets: Mutex<HashMap<String, MarketDetails>>,
+        orders: Mutex<Vec<OrderRequest>>,
+        market_orders: Mutex<Vec<(String, f64, String)>>, // (token_id, amount, side)
+        open_orders: Mutex<Vec<OpenOrder>>,
+        order_statuses: Mutex<HashMap<String, OpenOrder>>, // Key: order_id, value: what get_order reports
+        redemptions: Mutex<Vec<String>>, // Token ids passed to redeem_tokens
+        cancels: Mutex<Vec<String>>, // Order ids passed to cancel_order
+        trades: Mutex<Vec<OwnTrade>>, // What get_trades reports
+        fail_orders: Mutex<Option<String>>, // Error message every place_order returns (after recording the order)
+        usdc_balance: Mutex<f64>, // Dollars
+    }
+
+    impl MockApi {
+        pub fn set_balance(&self, token_id: &str, shares: f64) {
+            self.balances.lock().unwrap().insert(token_id.to_string(), shares);
+        }
+
+        pub fn set_usdc_balance(&self, dollars: f64) {
+            *self.usdc_balance.lock().unwrap() = dollars;
+        }
+
+        /// Top of book: best bid (what a seller receives) and best ask (what a buyer pays)
+        pub fn set_price(&self, token_id: &str, best_bid: f64, best_ask: f64) {
+            self.prices.lock().unwrap().insert(token_id.to_string(), (best_bid, best_ask));
+        }
+
+        /// Register an Up/Down market so the opposite token can be resolved
+        pub fn add_market(&self, condition_id: &str, up_token_id: &str, down_token_id: &str) {
+            let market = serde_json::from_value(serde_json::json!({
+                "accepting_order_timestamp": null,
+                "accepting_orders": true,
+                "active": true,
+                "archived": false,
+                "closed": false,
+                "condition_id": condition_id,
+                "description": "",
+                "enable_order_book": true,
+                "end_date_iso": "",
+                "fpmm": "",
+                "game_start_time": null,
+                "icon": "",
+                "image": "",
+                "is_50_50_outcome": true,
+                "maker_base_fee": "0",
+                "market_slug": "dummy-updown-15m",
+                "minimum_order_size": "5",
+                "minimum_tick_size": "0.01",
+                "neg_risk": false,
+                "neg_risk_market_id": "",
+                "neg_risk_request_id": "",
+                "notifications_enabled": false,
+                "question": "Dummy Up or Down",
+                "question_id": "",
+                "rewards": { "max_spread": "0", "min_size": "0", "rates": null },
+                "seconds_delay": 0,
+                "tags": [],
+                "taker_base_fee": "0",
+                "tokens": [
+                    { "outcome": "Up", "price": "0.5", "token_id": up_token_id, "winner": false },
+                    { "outcome": "Down", "price": "0.5", "token_id": down_token_id, "winner": false }
+                ]
+            })).expect("mock market details");
+            self.markets.lock().unwrap().insert(condition_id.to_string(), market);
+        }
+
+        /// Close a market registered with add_market and set its winner
+        pub fn resolve_market(&self, condition_id: &str, up_wins: bool) {
+            let mut markets = self.markets.lock().unwrap();
+            let market = markets.get_mut(condition_id).expect("market registered with add_market");
+            market.closed = true;
+            for token in market.tokens.iter_mut() {
+                token.winner = (token.outcome == "Up") == up_wins;
+            }
+        }
+
+        /// Live orders reported by get_open_orders
+        pub fn set_open_orders(&self, orders: Vec<OpenOrder>) {
+            *self.open_orders.lock().unwrap() = orders;
+        }
+
+        /// What get_order reports for `order.order_id` (orders without one fall back to set_open_orders)
+        pub fn set_order_status(&self, order: OpenOrder) {
+            self.order_statuses.lock().unwrap().insert(order.order_id.clone(), order);
+        }
+
+        /// Our fills reported by get_trades
+        pub fn set_trades(&self, trades: Vec<OwnTrade>) {
+            *self.trades.lock().unwrap() = trades;
+        }
+
+        /// Make place_order fail with `message` even though the order is recorded (a lost response)
+        pub fn fail_orders_with(&self, message: &str) {
+            *self.fail_orders.lock().unwrap() = Some(message.to_string());
+        }
+
+        /// Limit orders placed so far, in order
+        pub fn orders(&self) -> Vec<OrderRequest> {
+            self.orders.lock().unwrap().clone()
+        }
+
+        /// Market orders placed so far, in order: (token_id, amount, side)
+        pub fn market_orders(&self) -> Vec<(String, f64, String)> {
+            self.market_orders.lock().unwrap().clone()
+        }
+
+        /// Token ids redeemed so far
+        pub fn redemptions(&self) -> Vec<String> {
+            self.redemptions.lock().unwrap().clone()
+        }
+
+        /// Order ids passed to cancel_order, in order
+        pub fn cancels(&self) -> Vec<String> {
+            self.cancels.lock().unwrap().clone()
+        }
+
+        fn balance_units(&self, token_id: &str) -> Decimal {
+            let shares = self.balances.lock().unwrap().get(token_id).copied().unwrap_or(0.0);
+            Decimal::from_f64_retain(shares * 1_000_000.0).unwrap_or(Decimal::ZERO).round()
+        }
+
+        fn top_of_book(&self, token_id: &str) -> Result<(Decimal, Decimal)> {
+            let (bid, ask) = self.prices.lock().unwrap().get(token_id).copied()
+                .ok_or_else(|| anyhow::anyhow!("No mock price for token {}", token_id))?;
+            let to_decimal = |p: f64| Decimal::from_f64_retain(p).unwrap_or(Decimal::ZERO).round_dp(4);
+            Ok((to_decimal(bid), to_decimal(ask)))
+        }
+
+        fn accepted(&self) -> OrderResponse {
+            let count = self.orders.lock().unwrap().len() + self.market_orders.lock().unwrap().len();
+            OrderResponse {
+                order_id: Some(format!("dummy_order_{}", count)),
+                status: "LIVE".to_string(),
+                message: None,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ApiClient for MockApi {
+        async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook> {
+            let (bid, ask) = self.top_of_book(token_id)?;
+            let size = Decimal::from(1000);
+            Ok(OrderBook {
+                bids: vec![OrderBookEntry { price: bid, size }],
+                asks: vec![OrderBookEntry { price: ask, size }],
+            })
+        }
+
+        async fn get_best_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
+            let (bid, ask) = self.top_of_book(token_id)?;
+            Ok(Some(TokenPrice {
+                token_id: token_id.to_string(),
+                bid: Some(bid),
+                ask: Some(ask),
+                signal: None,
+                bid_size: None,
+                ask_size: None,
+                crossed: bid >= ask,
+                fetched_at: std::time::Instant::now(),
+            }))
+        }
+
+        async fn get_price(&self, token_id: &str, side: &str) -> Result<Decimal> {
+            let (bid, ask) = self.top_of_book(token_id)?;
+            Ok(if side == "BUY" { ask } else { bid })
+        }
+
+        async fn get_market(&self, condition_id: &str) -> Result<MarketDetails> {
+            self.markets.lock().unwrap().get(condition_id).cloned()
+                .ok_or_else(|| anyhow::anyhow!("No mock market {}", condition_id))
+        }
+
+        async fn get_uma_resolution_status(&self, _condition_id: &str) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn check_balance_only(&self, token_id: &str) -> Result<Decimal> {
+            Ok(self.balance_units(token_id))
+        }
+
+        async fn check_balance_allowance(&self, token_id: &str) -> Result<(Decimal, Decimal)> {
+            Ok((self.balance_units(token_id), Decimal::from(u64::MAX)))
+        }
+
+        async fn get_usdc_balance(&self) -> Result<f64> {
+            Ok(*self.usdc_balance.lock().unwrap())
+        }
+
+        async fn check_is_approved_for_all(&self) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn update_balance_allowance_for_sell(&self, _token_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn place_order(&self, order: &OrderRequest) -> Result<OrderResponse> {
+            let response = self.accepted();
+            self.orders.lock().unwrap().push(order.clone());
+            if let Some(message) = self.fail_orders.lock().unwrap().clone() {
+                return Err(ApiError::classify(&message).into());
+            }
+            Ok(response)
+        }
+
+        async fn place_market_order(&self, token_id: &str, amount: f64, side: &str, _order_type: Option<&str>) -> Result<OrderResponse> {
+            let response = self.accepted();
+            self.market_orders.lock().unwrap().push((token_id.to_string(), amount, side.to_string()));
+            Ok(response)
+        }
+
+        async fn get_open_orders(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+            Ok(self.open_orders.lock().unwrap().iter().filter(|o| o.token_id == token_id).cloned().collect())
+        }
+
+        async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+            if let Some(order) = self.order_statuses.lock().unwrap().get(order_id) {
+                return Ok(order.clone());
+            }
+            self.open_orders.lock().unwrap().iter().find(|o| o.order_id == order_id).cloned()
+                .ok_or_else(|| anyhow::anyhow!("No mock order {}", order_id))
+        }
+
+        async fn cancel_order(&self, order_id: &str) -> Result<()> {
+            self.cancels.lock().unwrap().push(order_id.to_string());
+            Ok(())
+        }
+
+        async fn get_trades(&self, token_id: &str, after: i64) -> Result<Vec<OwnTrade>> {
+            Ok(self.trades.lock().unwrap().iter().filter(|t| t.token_id == token_id && t.match_time >= after).cloned().collect())
+        }
+
+        async fn redeem_tokens(&self, _condition_id: &str, token_id: &str, _outcome: &str) -> Result<RedeemResponse> {
+            self.redemptions.lock().unwrap().push(token_id.to_string());
+            Ok(RedeemResponse { success: true, message: None, transaction_hash: None, amount_redeemed: None })
+        }
+    }
+}
@@ -1,11 +1,21 @@
 // Backtest module: simulate trading strategies using historical price data
 
+use crate::api_client::ApiClient;
 use crate::config::Config;
+use crate::detector::PriceDetector;
+use crate::models::*;
+use crate::monitor::{RecordedQuote, RecordedSnapshot};
+use crate::price_feed::PriceFeed;
+use crate::trader::Trader;
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use chrono::DateTime;
 use regex::Regex;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
 
 /// Represents a price snapshot from history
 #[derive(Debug, Clone)]
@@ -357,7 +367,7 @@ pub fn backtest_period(
 
     // Process snapshots chronologically
     for snapshot in snapshots {
-        let time_elapsed_seconds = 900 - snapshot.time_remaining_seconds;
+        let time_elapsed_seconds = crate::period::seconds().saturating_sub(snapshot.time_remaining_seconds);
         let time_elapsed_minutes = time_elapsed_seconds / 60;
 
         // Get current prices for this asset
@@ -539,14 +549,8 @@ pub fn backtest_period(
     })
 }
 
-/// Run backtest on all history files
-pub fn run_backtest(config: &Config) -> Result<BacktestResults> {
-    let history_dir = Path::new("history");
-    if !history_dir.exists() {
-        anyhow::bail!("History directory does not exist");
-    }
-
-    // Find all price history files
+/// Price history files (market_<period>_prices.toml) in `history_dir`, sorted by period
+fn find_history_files(history_dir: &Path) -> Result<Vec<(u64, std::path::PathBuf)>> {
     let mut history_files: Vec<_> = fs::read_dir(history_dir)?
         .filter_map(|entry| {
             let entry = entry.ok()?;
@@ -563,48 +567,81 @@ pub fn run_backtest(config: &Config) -> Result<BacktestResults> {
     // Sort by filename (which contains timestamp)
     history_files.sort();
 
+    history_files.into_iter()
+        .map(|file_path| {
+            // Extract period timestamp from filename: market_1769549400_prices.toml
+            let filename = file_path.file_name().unwrap().to_string_lossy().to_string();
+            let period_str = filename
+                .strip_prefix("market_")
+                .and_then(|s| s.strip_suffix("_prices.toml"))
+                .ok_or_else(|| anyhow::anyhow!("Invalid filename format: {}", filename))?;
+            let period_timestamp: u64 = period_str.parse()
+                .with_context(|| format!("Failed to parse period timestamp: {}", period_str))?;
+            Ok((period_timestamp, file_path))
+        })
+        .collect()
+}
+
+/// Assets to backtest and whether each is enabled (BTC always)
+pub(crate) fn backtest_assets(config: &Config) -> Vec<(&'static str, bool)> {
+    vec![
+        ("BTC", true), // Always enabled
+        ("ETH", config.trading.enable_eth_trading),
+        ("SOL", config.trading.enable_solana_trading),
+        ("XRP", config.trading.enable_xrp_trading),
+    ]
+}
+
+/// Snapshots of every price history file in history/, as (period, snapshots) sorted by period
+/// Files without a single price line are left out
+pub fn load_history_dir() -> Result<Vec<(u64, Vec<PriceSnapshot>)>> {
+    let history_dir = Path::new("history");
+    if !history_dir.exists() {
+        anyhow::bail!("History directory does not exist");
+    }
+
+    let history_files = find_history_files(history_dir)?;
+
     eprintln!("📊 Found {} history files", history_files.len());
 
+    let mut history = Vec::new();
+    for (period_timestamp, file_path) in history_files {
+        let snapshots = load_price_history(&file_path)?;
+        if !snapshots.is_empty() {
+            history.push((period_timestamp, snapshots));
+        }
+    }
+    Ok(history)
+}
+
+/// Run backtest on all history files
+pub fn run_backtest(config: &Config) -> Result<BacktestResults> {
+    Ok(backtest_history(config, &load_history_dir()?))
+}
+
+/// Run backtest on `history` - (period, snapshots) from the history/ files or `price_history::fetch_periods`
+pub fn backtest_history(config: &Config, history: &[(u64, Vec<PriceSnapshot>)]) -> BacktestResults {
     let mut period_results = Vec::new();
     let mut processed_periods = std::collections::HashSet::new();
 
     // Determine which assets to backtest
-    let assets = vec![
-        ("BTC", true), // Always enabled
-        ("ETH", config.trading.enable_eth_trading),
-        ("SOL", config.trading.enable_solana_trading),
-        ("XRP", config.trading.enable_xrp_trading),
-    ];
-
-    for file_path in &history_files {
-        // Extract period timestamp from filename: market_1769549400_prices.toml
-        let filename = file_path.file_name().unwrap().to_string_lossy();
-        let period_str = filename
-            .strip_prefix("market_")
-            .and_then(|s| s.strip_suffix("_prices.toml"))
-            .ok_or_else(|| anyhow::anyhow!("Invalid filename format: {}", filename))?;
-        
-        let period_timestamp: u64 = period_str.parse()
-            .with_context(|| format!("Failed to parse period timestamp: {}", period_str))?;
+    let assets = backtest_assets(config);
+
+    for (period_timestamp, snapshots) in history {
+        let period_timestamp = *period_timestamp;
 
         // Skip if we've already processed this period for any asset
         if processed_periods.contains(&period_timestamp) {
             continue;
         }
 
-        // Load price history
-        let snapshots = load_price_history(file_path)?;
-        if snapshots.is_empty() {
-            continue;
-        }
-
         // Backtest each enabled asset
         for (asset, enabled) in &assets {
             if !enabled {
                 continue;
             }
 
-            match backtest_period(&snapshots, period_timestamp, config, asset) {
+            match backtest_period(snapshots, period_timestamp, config, asset) {
                 Ok(result) => {
                     period_results.push(result);
                     processed_periods.insert(period_timestamp);
@@ -624,7 +661,7 @@ pub fn run_backtest(config: &Config) -> Result<BacktestResults> {
     let winning_periods = period_results.iter().filter(|r| r.pnl > 0.0).count();
     let losing_periods = period_results.iter().filter(|r| r.pnl < 0.0).count();
 
-    Ok(BacktestResults {
+    BacktestResults {
         period_results,
         total_periods,
         total_cost,
@@ -632,5 +669,584 @@ pub fn run_backtest(config: &Config) -> Result<BacktestResults> {
         total_pnl,
         winning_periods,
         losing_periods,
-    })
+    }
+}
+
+/// Candidate exit rule for `exit_analysis`. Every rule falls back to holding to resolution if it never triggers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExitRule {
+    /// Hold to resolution ($1.00 if the token won, $0.00 otherwise)
+    HoldToClose,
+    /// Sell at the bid as soon as it reaches this price
+    SellAt(f64),
+    /// Sell at the bid as soon as it drops to this price
+    StopLoss(f64),
+    /// Sell at the bid once it falls this far below the highest bid seen since entry
+    Trailing(f64),
+}
+
+impl std::fmt::Display for ExitRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExitRule::HoldToClose => write!(f, "hold-to-close"),
+            ExitRule::SellAt(price) => write!(f, "sell-at-{:.2}", price),
+            ExitRule::StopLoss(price) => write!(f, "stop-{:.2}", price),
+            ExitRule::Trailing(distance) => write!(f, "trailing-{:.2}", distance),
+        }
+    }
+}
+
+/// Default rule set: hold, the configured sell_price / fixed stop-loss, and a few alternatives around them
+pub fn default_exit_rules(config: &Config) -> Vec<ExitRule> {
+    let mut rules = vec![ExitRule::HoldToClose];
+    for price in [config.trading.sell_price, 0.99, 0.95, 0.90] {
+        if !rules.contains(&ExitRule::SellAt(price)) {
+            rules.push(ExitRule::SellAt(price));
+        }
+    }
+    if let Some(crate::config::StopLoss::Fixed { price }) = config.trading.stop_loss() {
+        rules.push(ExitRule::StopLoss(price));
+    }
+    rules.push(ExitRule::Trailing(0.05));
+    rules.push(ExitRule::Trailing(0.10));
+    rules
+}
+
+/// Hindsight analysis of one simulated entry
+#[derive(Debug, Clone)]
+pub struct EntryExitAnalysis {
+    pub token_type: String,
+    pub shares: f64,
+    pub purchase_price: f64,
+    pub max_favorable_excursion: f64, // Highest bid after entry minus purchase price (per share)
+    pub max_adverse_excursion: f64,   // Lowest bid after entry minus purchase price (per share, <= 0 when it dipped)
+    pub rule_pnl: Vec<f64>,           // PnL per rule, same order as the analysed rules
+}
+
+/// Exit-rule comparison for one period/asset
+#[derive(Debug, Clone)]
+pub struct PeriodExitAnalysis {
+    pub period_timestamp: u64,
+    pub entries: Vec<EntryExitAnalysis>,
+    pub rule_pnl: Vec<f64>, // Summed over entries
+}
+
+impl PeriodExitAnalysis {
+    /// Index of the rule with the highest PnL (first one on ties), None without entries
+    pub fn best_rule(&self) -> Option<usize> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        best_index(&self.rule_pnl)
+    }
+}
+
+/// Exit-rule comparison across all analysed periods
+#[derive(Debug)]
+pub struct ExitAnalysis {
+    pub rules: Vec<ExitRule>,
+    pub periods: Vec<PeriodExitAnalysis>,
+}
+
+impl ExitAnalysis {
+    /// Average PnL per period (periods with entries only) for each rule
+    pub fn average_rule_pnl(&self) -> Vec<f64> {
+        let traded: Vec<&PeriodExitAnalysis> = self.periods.iter().filter(|p| !p.entries.is_empty()).collect();
+        (0..self.rules.len())
+            .map(|i| {
+                if traded.is_empty() {
+                    0.0
+                } else {
+                    traded.iter().map(|p| p.rule_pnl[i]).sum::<f64>() / traded.len() as f64
+                }
+            })
+            .collect()
+    }
+
+    /// Table of per-period PnL by rule, the best rule per period, and the per-rule average
+    pub fn format_table(&self) -> String {
+        let mut out = String::new();
+        let mut header = format!("{:<12} {:<10}", "Period", "Entries");
+        for rule in &self.rules {
+            header.push_str(&format!(" {:>14}", rule.to_string()));
+        }
+        header.push_str(&format!(" {:>14}", "Best"));
+        out.push_str(&header);
+        out.push('\n');
+        out.push_str(&"-".repeat(header.chars().count()));
+        out.push('\n');
+
+        for period in self.periods.iter().filter(|p| !p.entries.is_empty()) {
+            let mut row = format!("{:<12} {:<10}", period.period_timestamp, period.entries.len());
+            for pnl in &period.rule_pnl {
+                row.push_str(&format!(" {:>14.4}", pnl));
+            }
+            let best = period.best_rule().map(|i| self.rules[i].to_string()).unwrap_or_default();
+            row.push_str(&format!(" {:>14}", best));
+            out.push_str(&row);
+            out.push('\n');
+        }
+
+        let averages = self.average_rule_pnl();
+        let mut row = format!("{:<12} {:<10}", "Average", "");
+        for pnl in &averages {
+            row.push_str(&format!(" {:>14.4}", pnl));
+        }
+        let best = best_index(&averages).map(|i| self.rules[i].to_string()).unwrap_or_default();
+        row.push_str(&format!(" {:>14}", best));
+        out.push_str(&row);
+        out.push('\n');
+        out
+    }
+}
+
+fn best_index(values: &[f64]) -> Option<usize> {
+    values.iter()
+        .enumerate()
+        .fold(None, |best: Option<(usize, f64)>, (i, v)| match best {
+            Some((_, best_value)) if best_value >= *v => best,
+            _ => Some((i, *v)),
+        })
+        .map(|(i, _)| i)
+}
+
+/// Bid (what a sell receives) for a backtest token type such as "BTC_UP"
+fn token_bid(snapshot: &PriceSnapshot, token_type: &str) -> Option<f64> {
+    match token_type {
+        "BTC_UP" => snapshot.btc_up_bid,
+        "BTC_DOWN" => snapshot.btc_down_bid,
+        "ETH_UP" => snapshot.eth_up_bid,
+        "ETH_DOWN" => snapshot.eth_down_bid,
+        "SOL_UP" => snapshot.solana_up_bid,
+        "SOL_DOWN" => snapshot.solana_down_bid,
+        "XRP_UP" => snapshot.xrp_up_bid,
+        "XRP_DOWN" => snapshot.xrp_down_bid,
+        _ => None,
+    }
+}
+
+/// Sale price per share a rule would have achieved, given the bids after entry (oldest first)
+fn rule_exit_price(rule: ExitRule, bids: &[f64], resolution_value: f64) -> f64 {
+    let mut peak = f64::MIN;
+    for &bid in bids {
+        peak = peak.max(bid);
+        let triggered = match rule {
+            ExitRule::HoldToClose => false,
+            ExitRule::SellAt(price) => bid >= price,
+            ExitRule::StopLoss(price) => bid <= price,
+            ExitRule::Trailing(distance) => bid <= peak - distance,
+        };
+        if triggered {
+            return bid;
+        }
+    }
+    resolution_value
+}
+
+/// Hindsight exit analysis for the entries of one backtested period
+/// For each entry, walks the recorded bids after the fill to compute the maximum favorable / adverse
+/// excursion and the PnL each rule would have realized (a rule that never triggers holds to resolution)
+pub fn exit_analysis(recording: &[PriceSnapshot], entries: &PeriodResult, rules: &[ExitRule]) -> PeriodExitAnalysis {
+    let mut analysed = Vec::new();
+    for position in &entries.positions {
+        let position_won = match position.token_type.as_str() {
+            s if s.ends_with("_UP") => entries.up_won,
+            s if s.ends_with("_DOWN") => !entries.up_won,
+            _ => false,
+        };
+        let resolution_value = if position_won { 1.0 } else { 0.0 };
+
+        // Snapshots strictly after the fill (time remaining counts down)
+        let bids: Vec<f64> = recording.iter()
+            .filter(|s| s.time_remaining_seconds < position.purchase_time_remaining)
+            .filter_map(|s| token_bid(s, &position.token_type))
+            .collect();
+
+        let max_bid = bids.iter().cloned().fold(resolution_value, f64::max);
+        let min_bid = bids.iter().cloned().fold(resolution_value, f64::min);
+        let rule_pnl = rules.iter()
+            .map(|rule| (rule_exit_price(*rule, &bids, resolution_value) - position.purchase_price) * position.shares)
+            .collect();
+
+        analysed.push(EntryExitAnalysis {
+            token_type: position.token_type.clone(),
+            shares: position.shares,
+            purchase_price: position.purchase_price,
+            max_favorable_excursion: max_bid - position.purchase_price,
+            max_adverse_excursion: min_bid - position.purchase_price,
+            rule_pnl,
+        });
+    }
+
+    let rule_pnl = (0..rules.len())
+        .map(|i| analysed.iter().map(|e| e.rule_pnl[i]).sum())
+        .collect();
+
+    PeriodExitAnalysis {
+        period_timestamp: entries.period_timestamp,
+        entries: analysed,
+        rule_pnl,
+    }
+}
+
+/// Backtest all history files, then run `exit_analysis` with `default_exit_rules` on every period's entries
+pub fn run_exit_analysis(config: &Config) -> Result<ExitAnalysis> {
+    Ok(exit_analysis_history(config, &load_history_dir()?))
+}
+
+/// `run_exit_analysis` on `history` - (period, snapshots) from the history/ files or `price_history::fetch_periods`
+pub fn exit_analysis_history(config: &Config, history: &[(u64, Vec<PriceSnapshot>)]) -> ExitAnalysis {
+    let rules = default_exit_rules(config);
+    let mut periods = Vec::new();
+    for (period_timestamp, snapshots) in history {
+        for (asset, enabled) in backtest_assets(config) {
+            if !enabled {
+                continue;
+            }
+            match backtest_period(snapshots, *period_timestamp, config, asset) {
+                Ok(result) => periods.push(exit_analysis(snapshots, &result, &rules)),
+                Err(e) => eprintln!("⚠️  Failed to analyse {} period {}: {}", asset, period_timestamp, e),
+            }
+        }
+    }
+
+    ExitAnalysis { rules, periods }
+}
+
+/// Outcome of replaying a snapshot recording (`run_replay`)
+#[derive(Debug, Clone, Default)]
+pub struct ReplayResults {
+    pub snapshots: usize,
+    pub skipped_lines: usize, // Lines of the recording that weren't valid snapshots
+    pub periods: usize,
+    pub opportunities: usize, // Opportunities the strategy reported
+    pub entries: usize, // Opportunities handed to the trader (one position per token type per period)
+    pub unresolved_markets: usize, // Markets whose winner couldn't be told from the last snapshot (left open)
+    pub total_invested: f64,
+    pub realized_pnl: f64,
+}
+
+/// Load a recording written by `monitor::SnapshotRecorder`, in file order
+/// Returns the snapshots and the number of lines that couldn't be parsed (e.g. a line cut off by a crash)
+pub fn load_recording(file_path: &Path) -> Result<(Vec<RecordedSnapshot>, usize)> {
+    let content = fs::read_to_string(file_path)
+        .with_context(|| format!("Failed to read snapshot recording {}", file_path.display()))?;
+    let mut snapshots = Vec::new();
+    let mut skipped = 0;
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<RecordedSnapshot>(line) {
+            Ok(snapshot) => snapshots.push(snapshot),
+            Err(_) => skipped += 1,
+        }
+    }
+    Ok((snapshots, skipped))
+}
+
+struct ReplayMarket {
+    period_timestamp: u64,
+    up_token_id: Option<String>,
+    down_token_id: Option<String>,
+    up_won: Option<bool>, // Set when the market's period has been replayed to the end
+}
+
+/// Market data of the snapshot being replayed, served to the simulated trader both as its `PriceFeed` (simulated
+/// fills and market results) and as its `ApiClient` (prices for sell checks). Nothing is sent anywhere: balance,
+/// order and redemption calls fail, as they would for any simulated position
+#[derive(Default)]
+pub struct ReplayFeed {
+    quotes: std::sync::Mutex<HashMap<String, RecordedQuote>>, // Key: token_id, latest quote
+    markets: std::sync::Mutex<HashMap<String, ReplayMarket>>, // Key: condition_id
+}
+
+impl ReplayFeed {
+    /// Make `snapshot` the current market state
+    pub fn apply(&self, snapshot: &RecordedSnapshot) {
+        let mut quotes = self.quotes.lock().unwrap();
+        let mut markets = self.markets.lock().unwrap();
+        for (_, market) in snapshot.markets() {
+            if market.condition_id.is_empty() {
+                continue;
+            }
+            let entry = markets.entry(market.condition_id.clone()).or_insert(ReplayMarket {
+                period_timestamp: snapshot.period_timestamp,
+                up_token_id: None,
+                down_token_id: None,
+                up_won: None,
+            });
+            for (quote, token_id) in [(&market.up_token, &mut entry.up_token_id), (&market.down_token, &mut entry.down_token_id)] {
+                if let Some(quote) = quote {
+                    *token_id = Some(quote.token_id.clone());
+                    quotes.insert(quote.token_id.clone(), quote.clone());
+                }
+            }
+        }
+    }
+
+    /// Resolve the markets of `period_timestamp` from their last quotes: the side with the higher ask won
+    /// (same rule as the history backtest). Returns how many markets couldn't be resolved that way
+    pub fn close_period(&self, period_timestamp: u64) -> usize {
+        let quotes = self.quotes.lock().unwrap();
+        let mut markets = self.markets.lock().unwrap();
+        let ask = |token_id: &Option<String>| token_id.as_ref()
+            .and_then(|id| quotes.get(id))
+            .and_then(|q| q.ask)
+            .and_then(|a| f64::try_from(a).ok());
+        let mut unresolved = 0;
+        for market in markets.values_mut().filter(|m| m.period_timestamp == period_timestamp && m.up_won.is_none()) {
+            match (ask(&market.up_token_id), ask(&market.down_token_id)) {
+                (Some(up), Some(down)) if up != down => market.up_won = Some(up > down),
+                _ => unresolved += 1,
+            }
+        }
+        unresolved
+    }
+
+    fn quote(&self, token_id: &str) -> Option<TokenPrice> {
+        self.quotes.lock().unwrap().get(token_id).map(|q| q.to_token_price(std::time::Instant::now()))
+    }
+}
+
+#[async_trait]
+impl PriceFeed for ReplayFeed {
+    async fn get_token_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
+        Ok(self.quote(token_id))
+    }
+
+    async fn get_market_tokens(&self, condition_id: &str) -> Result<Vec<MarketToken>> {
+        let markets = self.markets.lock().unwrap();
+        let market = markets.get(condition_id)
+            .ok_or_else(|| anyhow::anyhow!("Market {} is not in the recording", condition_id))?;
+        let price = |token_id: &Option<String>| token_id.as_ref()
+            .and_then(|id| self.quote(id))
+            .and_then(|q| q.bid)
+            .unwrap_or(Decimal::ZERO);
+        let mut tokens = Vec::new();
+        for (outcome, token_id, won) in [("Up", &market.up_token_id, market.up_won == Some(true)), ("Down", &market.down_token_id, market.up_won == Some(false))] {
+            if let Some(id) = token_id {
+                tokens.push(MarketToken { outcome: outcome.to_string(), price: price(token_id), token_id: id.clone(), winner: won });
+            }
+        }
+        Ok(tokens)
+    }
+
+    async fn get_market_result(&self, condition_id: &str, token_id: &str) -> Result<(bool, bool)> {
+        let markets = self.markets.lock().unwrap();
+        let market = markets.get(condition_id)
+            .ok_or_else(|| anyhow::anyhow!("Market {} is not in the recording", condition_id))?;
+        Ok(match market.up_won {
+            Some(up_won) => (true, (market.up_token_id.as_deref() == Some(token_id)) == up_won),
+            None => (false, false),
+        })
+    }
+}
+
+#[async_trait]
+impl ApiClient for ReplayFeed {
+    async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook> {
+        let quote = self.quote(token_id)
+            .ok_or_else(|| anyhow::anyhow!("No recorded quote for token {}", token_id))?;
+        let level = |price: Option<Decimal>, size: Option<Decimal>| price
+            .map(|price| OrderBookEntry { price, size: size.unwrap_or(Decimal::ZERO) })
+            .into_iter()
+            .collect();
+        Ok(OrderBook { bids: level(quote.bid, quote.bid_size), asks: level(quote.ask, quote.ask_size) })
+    }
+
+    async fn get_best_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
+        match self.quote(token_id) {
+            Some(TokenPrice { bid: Some(bid), ask: Some(ask), .. }) if bid >= ask => {
+                Err(CrossedBook { token_id: token_id.to_string(), best_bid: bid, best_ask: ask }.into())
+            }
+            quote => Ok(quote),
+        }
+    }
+
+    async fn get_price(&self, token_id: &str, side: &str) -> Result<Decimal> {
+        let quote = self.quote(token_id)
+            .ok_or_else(|| anyhow::anyhow!("No recorded quote for token {}", token_id))?;
+        let price = if side == "BUY" { quote.ask } else { quote.bid };
+        price.ok_or_else(|| anyhow::anyhow!("No recorded {} price for token {}", side, token_id))
+    }
+
+    async fn get_market(&self, condition_id: &str) -> Result<MarketDetails> {
+        anyhow::bail!("Replay has no market details for {}", condition_id)
+    }
+
+    async fn get_uma_resolution_status(&self, _condition_id: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn check_balance_only(&self, _token_id: &str) -> Result<Decimal> {
+        anyhow::bail!("Replay has no wallet")
+    }
+
+    async fn check_balance_allowance(&self, _token_id: &str) -> Result<(Decimal, Decimal)> {
+        anyhow::bail!("Replay has no wallet")
+    }
+
+    async fn get_usdc_balance(&self) -> Result<f64> {
+        anyhow::bail!("Replay has no wallet")
+    }
+
+    async fn check_is_approved_for_all(&self) -> Result<bool> {
+        anyhow::bail!("Replay has no wallet")
+    }
+
+    async fn update_balance_allowance_for_sell(&self, _token_id: &str) -> Result<()> {
+        anyhow::bail!("Replay has no wallet")
+    }
+
+    async fn place_order(&self, _order: &OrderRequest) -> Result<OrderResponse> {
+        anyhow::bail!("Replay never places orders")
+    }
+
+    async fn place_market_order(&self, _token_id: &str, _amount: f64, _side: &str, _order_type: Option<&str>) -> Result<OrderResponse> {
+        anyhow::bail!("Replay never places orders")
+    }
+
+    async fn get_open_orders(&self, _token_id: &str) -> Result<Vec<OpenOrder>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+        anyhow::bail!("Replay never places orders ({})", order_id)
+    }
+
+    async fn get_trades(&self, _token_id: &str, _after: i64) -> Result<Vec<OwnTrade>> {
+        Ok(Vec::new())
+    }
+
+    async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn redeem_tokens(&self, _condition_id: &str, _token_id: &str, _outcome: &str) -> Result<RedeemResponse> {
+        anyhow::bail!("Replay never redeems")
+    }
+}
+
+/// Replay a snapshot recording through the same pipeline as a live simulation run: the configured strategy
+/// (with `PriceDetector::from_config`) evaluates every snapshot and a simulation-mode `Trader` enters, manages
+/// and settles positions at the recorded prices. A period's markets resolve when the recording moves past it
+/// trading.warmup_seconds is ignored; other wall-clock limits (schedule, min_seconds_between_buys, cooldowns)
+/// run on real time, which passes much faster than the recorded time
+pub async fn run_replay(config: &Config, file_path: &Path) -> Result<ReplayResults> {
+    let (recording, skipped_lines) = load_recording(file_path)?;
+    if recording.is_empty() {
+        anyhow::bail!("{} contains no snapshots", file_path.display());
+    }
+
+    let mut trading = config.trading.clone();
+    trading.warmup_seconds = None;
+    let feed = Arc::new(ReplayFeed::default());
+    let detector = Arc::new(PriceDetector::from_config(&trading));
+    let strategy = crate::strategy::from_config(config, detector.clone())?;
+    let trader = Trader::new_with_price_feed(feed.clone(), trading, true, Some(detector), feed.clone())?
+        .with_sim(&config.sim)
+        .with_strategy(strategy.clone());
+
+    let mut results = ReplayResults { skipped_lines, ..ReplayResults::default() };
+    let mut current_period: Option<u64> = None;
+    for recorded in &recording {
+        if current_period.is_some_and(|period| period != recorded.period_timestamp) {
+            let period = current_period.unwrap_or_default();
+            results.unresolved_markets += feed.close_period(period);
+            trader.check_market_closure().await?;
+            trader.reset_period(period).await;
+            strategy.reset_period().await;
+        }
+        if current_period != Some(recorded.period_timestamp) {
+            current_period = Some(recorded.period_timestamp);
+            results.periods += 1;
+        }
+
+        feed.apply(recorded);
+        let snapshot = recorded.to_snapshot();
+        let state = trader.strategy_state(snapshot.period_timestamp).await;
+        let opportunities = strategy.evaluate(&snapshot, &state).await;
+        results.opportunities += opportunities.len();
+        for opportunity in opportunities {
+            if trader.has_active_position(opportunity.period_timestamp, opportunity.token_type.clone()).await {
+                continue;
+            }
+            results.entries += 1;
+            if let Err(e) = trader.enter(&opportunity).await {
+                eprintln!("⚠️  Replay buy failed: {}", e);
+            }
+        }
+        trader.check_pending_trades().await?;
+        results.snapshots += 1;
+    }
+    if let Some(period) = current_period {
+        results.unresolved_markets += feed.close_period(period);
+        trader.check_market_closure().await?;
+    }
+
+    if let Some(tracker) = trader.get_simulation_tracker() {
+        let (invested, _, realized) = tracker.get_total_spending_and_earnings().await;
+        results.total_invested = invested;
+        results.realized_pnl = realized;
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::MarketSnapshot;
+
+    const PERIOD: u64 = 1_700_000_100;
+
+    fn quote(token_id: &str, bid: &str, ask: &str) -> TokenPrice {
+        TokenPrice {
+            token_id: token_id.to_string(),
+            bid: Some(bid.parse().unwrap()),
+            ask: Some(ask.parse().unwrap()),
+            signal: None,
+            bid_size: Some(Decimal::from(120)),
+            ask_size: None,
+            crossed: false,
+            fetched_at: std::time::Instant::now(),
+        }
+    }
+
+    fn market(condition_id: &str, up: Option<TokenPrice>, down: Option<TokenPrice>) -> MarketData {
+        MarketData { condition_id: condition_id.to_string(), market_name: "BTC".to_string(), up_token: up, down_token: down }
+    }
+
+    #[tokio::test]
+    async fn recorded_snapshots_replay_with_their_prices_and_resolve_on_the_last_quote() {
+        let snapshot = MarketSnapshot {
+            eth_market: market("", None, None),
+            btc_market: market("dummy_btc_condition_id",
+                Some(quote("dummy_btc_up_token_id", "0.60", "0.62")),
+                Some(quote("dummy_btc_down_token_id", "0.37", "0.39"))),
+            solana_market: market("", None, None),
+            xrp_market: market("", None, None),
+            timestamp: std::time::Instant::now(),
+            time_remaining_seconds: 240,
+            period_timestamp: PERIOD,
+        };
+        let path = std::env::temp_dir().join("dummy_replay_recording.jsonl");
+        let line = serde_json::to_string(&RecordedSnapshot::from_snapshot(&snapshot, 1_700_000_760_000)).unwrap();
+        fs::write(&path, format!("{}\n{{\"truncated\n", line)).unwrap();
+
+        let (recording, skipped) = load_recording(&path).unwrap();
+        assert_eq!((recording.len(), skipped), (1, 1));
+        let replayed = recording[0].to_snapshot();
+        assert_eq!((replayed.period_timestamp, replayed.time_remaining_seconds), (PERIOD, 240));
+        let up = replayed.btc_market.up_token.unwrap();
+        assert_eq!((up.bid, up.ask, up.bid_size), (Some("0.60".parse().unwrap()), Some("0.62".parse().unwrap()), Some(Decimal::from(120))));
+        assert!(replayed.eth_market.up_token.is_none());
+
+        // The feed serves the replayed quotes; the market stays open until its period is closed
+        let feed = ReplayFeed::default();
+        feed.apply(&recording[0]);
+        assert_eq!(ApiClient::get_price(&feed, "dummy_btc_down_token_id", "BUY").await.unwrap(), "0.39".parse::<Decimal>().unwrap());
+        assert_eq!(feed.get_market_result("dummy_btc_condition_id", "dummy_btc_up_token_id").await.unwrap(), (false, false));
+        assert_eq!(feed.close_period(PERIOD), 0);
+        assert_eq!(feed.get_market_result("dummy_btc_condition_id", "dummy_btc_up_token_id").await.unwrap(), (true, true));
+        assert_eq!(feed.get_market_result("dummy_btc_condition_id", "dummy_btc_down_token_id").await.unwrap(), (true, false));
+        let _ = fs::remove_file(&path);
+    }
 }
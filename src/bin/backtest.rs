@@ -1,9 +1,12 @@
 // Backtest binary: simulate trading strategies using historical price data
 
-use polymarket_arbitrage_bot::backtest::run_backtest;
+use polymarket_arbitrage_bot::api::PolymarketApi;
+use polymarket_arbitrage_bot::backtest::{backtest_history, exit_analysis_history, load_history_dir, run_replay};
 use polymarket_arbitrage_bot::config::{Args, Config};
+use polymarket_arbitrage_bot::{period, price_history};
 use clap::Parser;
 use anyhow::Result;
+use std::path::Path;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -16,6 +19,40 @@ async fn main() -> Result<()> {
     }
 
     let config = Config::load(&args.config)?;
+    config.validate()?;
+    period::configure(config.trading.period_seconds(), config.trading.slug_template.as_deref());
+
+    if let Some(recording) = &args.replay {
+        eprintln!("🚀 Starting Replay Mode");
+        eprintln!("═══════════════════════════════════════════════════════════");
+        eprintln!("📼 Recording: {}", recording.display());
+        eprintln!("🧠 Strategy: {}", config.trading.strategy_name.as_deref().unwrap_or(polymarket_arbitrage_bot::strategy::DEFAULT_STRATEGY));
+        eprintln!("═══════════════════════════════════════════════════════════");
+        eprintln!();
+
+        let results = run_replay(&config, recording).await?;
+
+        eprintln!();
+        eprintln!("═══════════════════════════════════════════════════════════");
+        eprintln!("📊 REPLAY RESULTS SUMMARY");
+        eprintln!("═══════════════════════════════════════════════════════════");
+        eprintln!("   Snapshots Replayed: {}", results.snapshots);
+        if results.skipped_lines > 0 {
+            eprintln!("   ⚠️  Unreadable Lines Skipped: {}", results.skipped_lines);
+        }
+        eprintln!("   Periods: {}", results.periods);
+        eprintln!("   Opportunities: {}", results.opportunities);
+        eprintln!("   Entries: {}", results.entries);
+        if results.unresolved_markets > 0 {
+            eprintln!("   ⚠️  Markets Left Unresolved: {} (no clear winner in their last snapshot)", results.unresolved_markets);
+        }
+        eprintln!();
+        eprintln!("💰 FINANCIAL SUMMARY:");
+        eprintln!("   Total Invested: ${:.2}", results.total_invested);
+        eprintln!("   Realized PnL: ${:.2}", results.realized_pnl);
+        eprintln!("═══════════════════════════════════════════════════════════");
+        return Ok(());
+    }
 
     eprintln!("🚀 Starting Backtest Mode");
     eprintln!("═══════════════════════════════════════════════════════════");
@@ -43,7 +80,26 @@ async fn main() -> Result<()> {
     eprintln!("═══════════════════════════════════════════════════════════");
     eprintln!("");
 
-    let results = run_backtest(&config)?;
+    let history = match args.fetch_history {
+        Some(count) => {
+            let api = PolymarketApi::new(
+                config.polymarket.gamma_api_url.clone(),
+                config.polymarket.clob_api_url.clone(),
+                config.polymarket.api_key.clone(),
+                config.polymarket.api_secret.clone(),
+                config.polymarket.api_passphrase.clone(),
+                config.polymarket.private_key.clone(),
+                config.polymarket.proxy_wallet_address.clone(),
+                config.polymarket.signature_type,
+            ).with_network(&config.network)?;
+            eprintln!("📥 Fetching the last {} periods from prices-history (cache: {}/)", count, price_history::HISTORY_CACHE_DIR);
+            let history = price_history::fetch_periods(&api, &config, count, Path::new(price_history::HISTORY_CACHE_DIR)).await;
+            eprintln!("📊 Fetched {} periods", history.len());
+            history
+        }
+        None => load_history_dir()?,
+    };
+    let results = backtest_history(&config, &history);
 
     // Print results
     eprintln!("");
@@ -114,5 +170,24 @@ async fn main() -> Result<()> {
         eprintln!("");
     }
 
+    if args.exit_analysis {
+        let analysis = exit_analysis_history(&config, &history);
+        eprintln!("═══════════════════════════════════════════════════════════");
+        eprintln!("🔎 EXIT ANALYSIS (PnL by exit rule, in hindsight)");
+        eprintln!("═══════════════════════════════════════════════════════════");
+        eprintln!();
+        eprint!("{}", analysis.format_table());
+        eprintln!();
+        let entries: Vec<_> = analysis.periods.iter().flat_map(|p| p.entries.iter()).collect();
+        if !entries.is_empty() {
+            let avg_mfe = entries.iter().map(|e| e.max_favorable_excursion).sum::<f64>() / entries.len() as f64;
+            let avg_mae = entries.iter().map(|e| e.max_adverse_excursion).sum::<f64>() / entries.len() as f64;
+            eprintln!("   Entries analysed: {}", entries.len());
+            eprintln!("   Average max favorable excursion: ${:.4}/share", avg_mfe);
+            eprintln!("   Average max adverse excursion: ${:.4}/share", avg_mae);
+            eprintln!();
+        }
+    }
+
     Ok(())
 }
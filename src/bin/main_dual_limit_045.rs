@@ -17,7 +17,6 @@ use polymarket_arbitrage_bot::detector::BuyOpportunity;
 use polymarket_arbitrage_bot::trader::Trader;
 
 const LIMIT_PRICE: f64 = 0.45;
-const PERIOD_DURATION: u64 = 900;
 const DEFAULT_HEDGE_AFTER_MINUTES: u64 = 10;
 const DEFAULT_HEDGE_PRICE: f64 = 0.85;
 
@@ -83,11 +82,19 @@ macro_rules! log_println {
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = Config::load(&args.config)?;
+    config.validate()?;
+    if args.print_config {
+        println!("{}", serde_json::to_string_pretty(&config.redacted())?);
+        return Ok(());
+    }
+
     let log_file = OpenOptions::new()
         .create(true)
         .append(true)
-        .open("history.toml")
-        .context("Failed to open history.toml for logging")?;
+        .open(config.logging.log_file())
+        .with_context(|| format!("Failed to open {} for logging", config.logging.log_file()))?;
 
     init_history_file(log_file.try_clone().context("Failed to clone history file")?);
     polymarket_arbitrage_bot::init_history_file(log_file.try_clone().context("Failed to clone history file for lib.rs")?);
@@ -97,16 +104,18 @@ async fn main() -> Result<()> {
         file: Mutex::new(log_file),
     };
 
-    env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Info)
+    config.logging.logger_builder()?
         .target(env_logger::Target::Pipe(Box::new(dual_writer)))
         .init();
-
-    let args = Args::parse();
-    let config = Config::load(&args.config)?;
+    polymarket_arbitrage_bot::set_monitor_sample_every(config.logging.sample_monitor_every_n.unwrap_or(1));
+    period::configure(config.trading.period_seconds(), config.trading.slug_template.as_deref());
 
     eprintln!("🚀 Starting Polymarket Dual Limit-Start Bot");
-    eprintln!("📝 Logs are being saved to: history.toml");
+    eprintln!("📝 Logs are being saved to: {}", config.logging.log_file());
+    if let Some(addr) = &config.metrics.listen_addr {
+        let bound = metrics::serve(addr).await?;
+        eprintln!("📈 Prometheus metrics at http://{}/metrics", bound);
+    }
     let is_simulation = args.is_simulation();
     eprintln!("Mode: {}", if is_simulation { "SIMULATION" } else { "PRODUCTION" });
     let limit_price = config.trading.dual_limit_price.unwrap_or(LIMIT_PRICE);
@@ -157,7 +166,14 @@ async fn main() -> Result<()> {
         config.polymarket.private_key.clone(),
         config.polymarket.proxy_wallet_address.clone(),
         config.polymarket.signature_type,
-    ));
+    ).with_network(&config.network)?.with_chain(&config.chain)?);
+
+    // Telegram/Discord trade notifications
+    notifier::init(&config.notifications, is_simulation, config.network.http_proxy.as_deref())?;
+
+    // A mistyped gamma/CLOB URL fails here rather than as 404s during market discovery
+    api.health_check().await.ensure_healthy()?;
+    eprintln!("✅ Gamma and CLOB APIs healthy");
 
     if !is_simulation {
         eprintln!("\n═══════════════════════════════════════════════════════════");
@@ -205,9 +221,18 @@ async fn main() -> Result<()> {
         config.trading.clone(),
         is_simulation,
         None,
-    )?;
+    )?
+    .with_balance_confirm(config.balance_confirm.clone())
+    .with_sell_config(config.sell.clone())
+    .with_resolution(config.resolution.clone())
+    .with_redemption(config.redemption.clone())
+    .with_sim(&config.sim);
     let trader_arc = Arc::new(trader);
     let trader_clone = trader_arc.clone();
+    if let Some(addr) = &config.dashboard.listen_addr {
+        let bound = dashboard::serve(addr, trader_arc.clone()).await?;
+        eprintln!("📊 PnL dashboard at http://{}/", bound);
+    }
 
     crate::log_println!("🔄 Syncing pending trades with portfolio balance...");
     if let Err(e) = trader_clone.sync_trades_with_portfolio().await {
@@ -252,14 +277,14 @@ async fn main() -> Result<()> {
                 .unwrap()
                 .as_secs();
 
-            let current_period = (current_time / 900) * 900;
+            let current_period = period::start(current_time);
             let current_market_timestamp = monitor_for_period_check.get_current_market_timestamp().await;
 
             if current_market_timestamp != current_period && current_market_timestamp != 0 {
                 eprintln!("🔄 Market period mismatch detected! Current market: {}, Current period: {}",
                     current_market_timestamp, current_period);
             } else {
-                let next_period_timestamp = current_period + 900;
+                let next_period_timestamp = current_period + period::seconds();
                 let sleep_duration = if next_period_timestamp > current_time {
                     next_period_timestamp - current_time
                 } else {
@@ -283,7 +308,7 @@ async fn main() -> Result<()> {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            let current_period = (current_time / 900) * 900;
+            let current_period = period::start(current_time);
 
             eprintln!("🔄 New 15-minute period detected! (Period: {}) Discovering new markets...", current_period);
 
@@ -316,7 +341,7 @@ async fn main() -> Result<()> {
                     } else {
                         // Log market start in simulation mode
                         if let Some(tracker) = &simulation_tracker_for_market_start {
-                            let period = (current_time / 900) * 900;
+                            let period = period::start(current_time);
                             tracker.log_market_start(
                                 period,
                                 &eth_market.condition_id,
@@ -384,7 +409,7 @@ async fn main() -> Result<()> {
                 }
             }
 
-            let time_elapsed_seconds = PERIOD_DURATION - snapshot.time_remaining_seconds;
+            let time_elapsed_seconds = period::seconds().saturating_sub(snapshot.time_remaining_seconds);
             // Market-start placement (first ~2 seconds)
             let mut opportunities: Vec<BuyOpportunity> = Vec::new();
             if time_elapsed_seconds <= 2 {
@@ -406,6 +431,7 @@ async fn main() -> Result<()> {
                     time_elapsed_seconds,
                     use_market_order: false,
                                 investment_amount_override: None,
+                                sell_price_override: None,
                                 is_individual_hedge: false,
                                 is_standard_hedge: false,
                                 dual_limit_shares: None,
@@ -422,6 +448,7 @@ async fn main() -> Result<()> {
                     time_elapsed_seconds,
                     use_market_order: false,
                                 investment_amount_override: None,
+                                sell_price_override: None,
                                 is_individual_hedge: false,
                                 is_standard_hedge: false,
                                 dual_limit_shares: None,
@@ -440,6 +467,7 @@ async fn main() -> Result<()> {
                         time_elapsed_seconds,
                         use_market_order: false,
                                     investment_amount_override: None,
+                                    sell_price_override: None,
                                     is_individual_hedge: false,
                                     is_standard_hedge: false,
                                     dual_limit_shares: None,
@@ -456,6 +484,7 @@ async fn main() -> Result<()> {
                         time_elapsed_seconds,
                         use_market_order: false,
                                     investment_amount_override: None,
+                                    sell_price_override: None,
                                     is_individual_hedge: false,
                                     is_standard_hedge: false,
                                     dual_limit_shares: None,
@@ -474,6 +503,7 @@ async fn main() -> Result<()> {
                         time_elapsed_seconds,
                         use_market_order: false,
                                     investment_amount_override: None,
+                                    sell_price_override: None,
                                     is_individual_hedge: false,
                                     is_standard_hedge: false,
                                     dual_limit_shares: None,
@@ -490,6 +520,7 @@ async fn main() -> Result<()> {
                         time_elapsed_seconds,
                         use_market_order: false,
                                     investment_amount_override: None,
+                                    sell_price_override: None,
                                     is_individual_hedge: false,
                                     is_standard_hedge: false,
                                     dual_limit_shares: None,
@@ -509,6 +540,7 @@ async fn main() -> Result<()> {
                         time_elapsed_seconds,
                         use_market_order: false,
                                     investment_amount_override: None,
+                                    sell_price_override: None,
                                     is_individual_hedge: false,
                                     is_standard_hedge: false,
                                     dual_limit_shares: None,
@@ -525,6 +557,7 @@ async fn main() -> Result<()> {
                         time_elapsed_seconds,
                         use_market_order: false,
                                     investment_amount_override: None,
+                                    sell_price_override: None,
                                     is_individual_hedge: false,
                                     is_standard_hedge: false,
                                     dual_limit_shares: None,
@@ -1193,6 +1226,7 @@ async fn main() -> Result<()> {
                                         time_elapsed_seconds,
                                         use_market_order: true, // MARKET ORDER
                                         investment_amount_override: Some(investment_amount), // Always 2x for individual hedges
+                                        sell_price_override: None,
                                         is_individual_hedge: true, // Mark as individual hedge to place limit sell order
                                         is_standard_hedge: false,
                                         dual_limit_shares: limit_shares, // Pass dual_limit_shares for sell orders
@@ -1204,7 +1238,7 @@ async fn main() -> Result<()> {
                                     } else {
                                         crate::log_println!("✅ Market buy executed for {} {}: ~{:.6} shares (double amount) at ~${:.4}", 
                                             market_name, token_side, expected_shares, current_price);
-                                        crate::log_println!("   📤 Two limit sell orders will be placed at $0.93 and $0.98 after 7 seconds");
+                                        crate::log_println!("   📤 Hedge limit sell orders (trading.hedge_sell_ladder) will be placed after 7 seconds");
                                         any_hedge_executed = true;
                                     }
                                 } else {
@@ -1362,6 +1396,7 @@ async fn main() -> Result<()> {
                             time_elapsed_seconds,
                             use_market_order: true, // Use market order for standard hedge
                             investment_amount_override: Some(double_investment_amount),
+                            sell_price_override: None,
                             is_individual_hedge: false,
                             is_standard_hedge: true, // This is a standard hedge (after dual_limit_hedge_after_minutes)
                             dual_limit_shares: limit_shares, // Pass dual_limit_shares for sell orders
@@ -1636,13 +1671,13 @@ async fn discover_market(
     seen_ids: &mut std::collections::HashSet<String>,
     include_previous: bool,
 ) -> Result<crate::models::Market> {
-    let rounded_time = (current_time / 900) * 900;
+    let rounded_time = period::start(current_time);
 
     for (i, prefix) in slug_prefixes.iter().enumerate() {
         if i > 0 {
             eprintln!("🔍 Trying {} market with slug prefix '{}'...", market_name, prefix);
         }
-        let slug = format!("{}-updown-15m-{}", prefix, rounded_time);
+        let slug = period::slug(prefix, rounded_time);
         if let Ok(market) = api.get_market_by_slug(&slug).await {
             if !seen_ids.contains(&market.condition_id) && market.active && !market.closed {
                 eprintln!("Found {} market by slug: {} | Condition ID: {}", market_name, market.slug, market.condition_id);
@@ -1652,8 +1687,8 @@ async fn discover_market(
 
         if include_previous {
             for offset in 1..=3 {
-                let try_time = rounded_time - (offset * 900);
-                let try_slug = format!("{}-updown-15m-{}", prefix, try_time);
+                let try_time = rounded_time - (offset * period::seconds());
+                let try_slug = period::slug(prefix, try_time);
                 eprintln!("Trying previous {} market by slug: {}", market_name, try_slug);
                 if let Ok(market) = api.get_market_by_slug(&try_slug).await {
                     if !seen_ids.contains(&market.condition_id) && market.active && !market.closed {
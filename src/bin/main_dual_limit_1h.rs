@@ -103,6 +103,7 @@ async fn main() -> Result<()> {
 
     let args = Args::parse();
     let config = Config::load(&args.config)?;
+    config.validate()?;
 
     eprintln!("🚀 Starting Polymarket Dual Limit-Start Bot (1-hour)");
     eprintln!("📝 Logs are being saved to: history_1h.toml");
@@ -125,7 +126,7 @@ async fn main() -> Result<()> {
         config.polymarket.private_key.clone(),
         config.polymarket.proxy_wallet_address.clone(),
         config.polymarket.signature_type,
-    ));
+    ).with_network(&config.network)?.with_chain(&config.chain)?);
 
     if !is_simulation {
         match api.authenticate().await {
@@ -220,6 +221,11 @@ async fn main() -> Result<()> {
                         time_remaining_seconds: snapshot.time_remaining_seconds,
                         time_elapsed_seconds,
                         use_market_order: false,
+                        investment_amount_override: None,
+                        sell_price_override: None,
+                        is_individual_hedge: false,
+                        is_standard_hedge: false,
+                        dual_limit_shares: None,
                     });
                 }
                 if let Some(btc_down) = snapshot.btc_market.down_token.as_ref() {
@@ -232,6 +238,11 @@ async fn main() -> Result<()> {
                         time_remaining_seconds: snapshot.time_remaining_seconds,
                         time_elapsed_seconds,
                         use_market_order: false,
+                        investment_amount_override: None,
+                        sell_price_override: None,
+                        is_individual_hedge: false,
+                        is_standard_hedge: false,
+                        dual_limit_shares: None,
                     });
                 }
 
@@ -246,6 +257,11 @@ async fn main() -> Result<()> {
                             time_remaining_seconds: snapshot.time_remaining_seconds,
                             time_elapsed_seconds,
                             use_market_order: false,
+                            investment_amount_override: None,
+                            sell_price_override: None,
+                            is_individual_hedge: false,
+                            is_standard_hedge: false,
+                            dual_limit_shares: None,
                         });
                     }
                     if let Some(eth_down) = snapshot.eth_market.down_token.as_ref() {
@@ -258,6 +274,11 @@ async fn main() -> Result<()> {
                             time_remaining_seconds: snapshot.time_remaining_seconds,
                             time_elapsed_seconds,
                             use_market_order: false,
+                            investment_amount_override: None,
+                            sell_price_override: None,
+                            is_individual_hedge: false,
+                            is_standard_hedge: false,
+                            dual_limit_shares: None,
                         });
                     }
                 }
@@ -273,6 +294,11 @@ async fn main() -> Result<()> {
                             time_remaining_seconds: snapshot.time_remaining_seconds,
                             time_elapsed_seconds,
                             use_market_order: false,
+                            investment_amount_override: None,
+                            sell_price_override: None,
+                            is_individual_hedge: false,
+                            is_standard_hedge: false,
+                            dual_limit_shares: None,
                         });
                     }
                     if let Some(solana_down) = snapshot.solana_market.down_token.as_ref() {
@@ -285,6 +311,11 @@ async fn main() -> Result<()> {
                             time_remaining_seconds: snapshot.time_remaining_seconds,
                             time_elapsed_seconds,
                             use_market_order: false,
+                            investment_amount_override: None,
+                            sell_price_override: None,
+                            is_individual_hedge: false,
+                            is_standard_hedge: false,
+                            dual_limit_shares: None,
                         });
                     }
                 }
@@ -300,6 +331,11 @@ async fn main() -> Result<()> {
                             time_remaining_seconds: snapshot.time_remaining_seconds,
                             time_elapsed_seconds,
                             use_market_order: false,
+                            investment_amount_override: None,
+                            sell_price_override: None,
+                            is_individual_hedge: false,
+                            is_standard_hedge: false,
+                            dual_limit_shares: None,
                         });
                     }
                     if let Some(xrp_down) = snapshot.xrp_market.down_token.as_ref() {
@@ -312,6 +348,11 @@ async fn main() -> Result<()> {
                             time_remaining_seconds: snapshot.time_remaining_seconds,
                             time_elapsed_seconds,
                             use_market_order: false,
+                            investment_amount_override: None,
+                            sell_price_override: None,
+                            is_individual_hedge: false,
+                            is_standard_hedge: false,
+                            dual_limit_shares: None,
                         });
                     }
                 }
@@ -80,11 +80,19 @@ macro_rules! log_println {
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = Config::load(&args.config)?;
+    config.validate()?;
+    if args.print_config {
+        println!("{}", serde_json::to_string_pretty(&config.redacted())?);
+        return Ok(());
+    }
+
     let log_file = OpenOptions::new()
         .create(true)
         .append(true)
-        .open("history.toml")
-        .context("Failed to open history.toml for logging")?;
+        .open(config.logging.log_file())
+        .with_context(|| format!("Failed to open {} for logging", config.logging.log_file()))?;
     
     init_history_file(log_file.try_clone().context("Failed to clone history file")?);
     polymarket_arbitrage_bot::init_history_file(log_file.try_clone().context("Failed to clone history file for lib.rs")?);
@@ -94,16 +102,18 @@ async fn main() -> Result<()> {
         file: Mutex::new(log_file),
     };
     
-    env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Info)
+    config.logging.logger_builder()?
         .target(env_logger::Target::Pipe(Box::new(dual_writer)))
         .init();
-
-    let args = Args::parse();
-    let config = Config::load(&args.config)?;
+    polymarket_arbitrage_bot::set_monitor_sample_every(config.logging.sample_monitor_every_n.unwrap_or(1));
+    period::configure(config.trading.period_seconds(), config.trading.slug_template.as_deref());
 
     eprintln!("🚀 Starting Polymarket Limit Order Trading Bot");
-    eprintln!("📝 Logs are being saved to: history.toml");
+    eprintln!("📝 Logs are being saved to: {}", config.logging.log_file());
+    if let Some(addr) = &config.metrics.listen_addr {
+        let bound = metrics::serve(addr).await?;
+        eprintln!("📈 Prometheus metrics at http://{}/metrics", bound);
+    }
     let is_simulation = args.is_simulation();
     eprintln!("Mode: {}", if is_simulation { "SIMULATION" } else { "PRODUCTION" });
     eprintln!("Strategy: Limit orders - Buy both Up/Down at min_elapsed_minutes, sell when filled");
@@ -122,7 +132,14 @@ async fn main() -> Result<()> {
         config.polymarket.private_key.clone(),
         config.polymarket.proxy_wallet_address.clone(),
         config.polymarket.signature_type,
-    ));
+    ).with_network(&config.network)?.with_chain(&config.chain)?);
+
+    // Telegram/Discord trade notifications
+    notifier::init(&config.notifications, is_simulation, config.network.http_proxy.as_deref())?;
+
+    // A mistyped gamma/CLOB URL fails here rather than as 404s during market discovery
+    api.health_check().await.ensure_healthy()?;
+    eprintln!("✅ Gamma and CLOB APIs healthy");
 
     if !is_simulation {
         eprintln!("\n═══════════════════════════════════════════════════════════");
@@ -168,7 +185,11 @@ async fn main() -> Result<()> {
         0, // min_time_remaining_seconds - ignored for limit orders
         config.trading.enable_eth_trading,
         config.trading.enable_solana_trading,
-    );
+    )
+    .with_directions(config.trading.directions.clone())
+    .with_max_spread(config.trading.max_spread)
+    .with_settlement_cooldowns(config.trading.post_win_cooldown_secs, config.trading.post_loss_cooldown_secs)
+    .with_trigger_confirmations(config.trading.trigger_confirmations);
 
     let detector_arc = Arc::new(detector);
     
@@ -177,9 +198,17 @@ async fn main() -> Result<()> {
         config.trading.clone(),
         is_simulation,
         Some(detector_arc.clone()),
-    )?;
+    )?
+    .with_balance_confirm(config.balance_confirm.clone())
+    .with_sell_config(config.sell.clone())
+    .with_resolution(config.resolution.clone())
+    .with_sim(&config.sim);
     let trader_arc = Arc::new(trader);
     let trader_clone = trader_arc.clone();
+    if let Some(addr) = &config.dashboard.listen_addr {
+        let bound = dashboard::serve(addr, trader_arc.clone()).await?;
+        eprintln!("📊 PnL dashboard at http://{}/", bound);
+    }
     
     crate::log_println!("🔄 Syncing pending trades with portfolio balance...");
     if let Err(e) = trader_clone.sync_trades_with_portfolio().await {
@@ -231,14 +260,14 @@ async fn main() -> Result<()> {
                 .unwrap()
                 .as_secs();
             
-            let current_period = (current_time / 900) * 900;
+            let current_period = period::start(current_time);
             let current_market_timestamp = monitor_for_period_check.get_current_market_timestamp().await;
             
             if current_market_timestamp != current_period && current_market_timestamp != 0 {
                 eprintln!("🔄 Market period mismatch detected! Current market: {}, Current period: {}", 
                     current_market_timestamp, current_period);
             } else {
-                let next_period_timestamp = current_period + 900;
+                let next_period_timestamp = current_period + period::seconds();
                 let sleep_duration = if next_period_timestamp > current_time {
                     next_period_timestamp - current_time
                 } else {
@@ -262,7 +291,7 @@ async fn main() -> Result<()> {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            let current_period = (current_time / 900) * 900;
+            let current_period = period::start(current_time);
             
             eprintln!("🔄 New 15-minute period detected! (Period: {}) Discovering new markets...", current_period);
             
@@ -325,7 +354,7 @@ async fn main() -> Result<()> {
                     continue;
                 }
                 
-                if let Err(e) = trader.execute_buy(&opportunity).await {
+                if let Err(e) = trader.enter(&opportunity).await {
                     warn!("Error executing buy: {}", e);
                 }
             }
@@ -463,14 +492,14 @@ async fn discover_market(
     current_time: u64,
     seen_ids: &mut std::collections::HashSet<String>,
 ) -> Result<crate::models::Market> {
-    let rounded_time = (current_time / 900) * 900;
+    let rounded_time = period::start(current_time);
 
     for (i, prefix) in slug_prefixes.iter().enumerate() {
         if i > 0 {
             eprintln!("🔍 Trying {} market with slug prefix '{}'...", market_name, prefix);
         }
 
-        let slug = format!("{}-updown-15m-{}", prefix, rounded_time);
+        let slug = period::slug(prefix, rounded_time);
         if let Ok(market) = api.get_market_by_slug(&slug).await {
             if !seen_ids.contains(&market.condition_id) && market.active && !market.closed {
                 eprintln!("Found {} market by slug: {} | Condition ID: {}", market_name, market.slug, market.condition_id);
@@ -479,8 +508,8 @@ async fn discover_market(
         }
     
         for offset in 1..=3 {
-            let try_time = rounded_time - (offset * 900);
-            let try_slug = format!("{}-updown-15m-{}", prefix, try_time);
+            let try_time = rounded_time - (offset * period::seconds());
+            let try_slug = period::slug(prefix, try_time);
             eprintln!("Trying previous {} market by slug: {}", market_name, try_slug);
             if let Ok(market) = api.get_market_by_slug(&try_slug).await {
                 if !seen_ids.contains(&market.condition_id) && market.active && !market.closed {
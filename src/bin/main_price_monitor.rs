@@ -20,6 +20,7 @@ async fn main() -> Result<()> {
 
     let args = Args::parse();
     let config = Config::load(&args.config)?;
+    period::configure(config.trading.period_seconds(), config.trading.slug_template.as_deref());
 
     eprintln!("═══════════════════════════════════════════════════════════");
     eprintln!("📊 PRICE MONITORING MODE");
@@ -39,7 +40,11 @@ async fn main() -> Result<()> {
         config.polymarket.private_key.clone(),
         config.polymarket.proxy_wallet_address.clone(),
         config.polymarket.signature_type,
-    ));
+    ).with_network(&config.network)?.with_chain(&config.chain)?);
+
+    // A mistyped gamma/CLOB URL fails here rather than as 404s during market discovery
+    api.health_check().await.ensure_healthy()?;
+    eprintln!("✅ Gamma and CLOB APIs healthy");
 
     // Get market data for BTC, ETH, Solana, and XRP markets
     eprintln!("🔍 Discovering BTC, ETH, Solana, and XRP markets...");
@@ -79,7 +84,7 @@ async fn main() -> Result<()> {
                 .unwrap()
                 .as_secs();
             
-            let current_period = (current_time / 900) * 900;
+            let current_period = period::start(current_time);
             let current_market_timestamp = monitor_for_period_check.get_current_market_timestamp().await;
             
             // Check if we need to discover a new market (current market is from a different period)
@@ -111,7 +116,7 @@ async fn main() -> Result<()> {
                 }
             } else {
                 // Calculate when next period starts
-                let next_period_timestamp = current_period + 900;
+                let next_period_timestamp = current_period + period::seconds();
                 let sleep_duration = if next_period_timestamp > current_time {
                     next_period_timestamp - current_time
                 } else {
@@ -262,7 +267,7 @@ async fn discover_market(
     current_time: u64,
     seen_ids: &mut std::collections::HashSet<String>,
 ) -> Result<crate::models::Market> {
-    let rounded_time = (current_time / 900) * 900; // Round to nearest 15 minutes
+    let rounded_time = period::start(current_time);
 
     for (i, prefix) in slug_prefixes.iter().enumerate() {
         if i > 0 {
@@ -270,7 +275,7 @@ async fn discover_market(
         }
 
         // Try current period with this prefix
-        let slug = format!("{}-updown-15m-{}", prefix, rounded_time);
+        let slug = period::slug(prefix, rounded_time);
         if let Ok(market) = api.get_market_by_slug(&slug).await {
             if !seen_ids.contains(&market.condition_id) && market.active && !market.closed {
                 eprintln!("Found {} market by slug: {} | Condition ID: {}", market_name, market.slug, market.condition_id);
@@ -280,8 +285,8 @@ async fn discover_market(
     
         // Try previous periods with this prefix
         for offset in 1..=3 {
-            let try_time = rounded_time - (offset * 900);
-            let try_slug = format!("{}-updown-15m-{}", prefix, try_time);
+            let try_time = rounded_time - (offset * period::seconds());
+            let try_slug = period::slug(prefix, try_time);
             eprintln!("Trying previous {} market by slug: {}", market_name, try_slug);
             if let Ok(market) = api.get_market_by_slug(&try_slug).await {
                 if !seen_ids.contains(&market.condition_id) && market.active && !market.closed {
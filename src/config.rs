@@ -2,6 +2,39 @@ use clap::Parser;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+const MAX_BUY_STAGGER_MS: u64 = 2000; // Longest buy.stagger_ms that still fits the entry window
+
+/// trading.* settings that are only read at startup (market discovery, timers, the price pipeline, the strategy);
+/// a config reload keeps the running value and reports the change as needing a restart
+const RESTART_ONLY_TRADING_SETTINGS: &[&str] = &[
+    "eth_condition_id", "btc_condition_id", "solana_condition_id", "xrp_condition_id",
+    "check_interval_ms", "period_seconds", "slug_template", "market_closure_check_interval_seconds", "resolution_poll_interval_ms",
+    "enable_eth_trading", "enable_solana_trading", "enable_xrp_trading",
+    "price_source", "price_source_max_spread", "price_transport", "price_transport_stale_ms", "min_book_imbalance", "opposite_price_lookup_timeout_ms",
+    "synthetic_volatility", "synthetic_half_spread", "synthetic_paths", "strategy_name", "starting_balance",
+];
+
+/// One setting that differs between two configs (values as JSON, secrets shown as "***")
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    pub path: String, // Dotted path, e.g. "trading.trigger_price"
+    pub old: String,
+    pub new: String,
+}
+
+impl ConfigChange {
+    /// Whether a config reload applies this change to the running bot (see RESTART_ONLY_TRADING_SETTINGS)
+    pub fn is_hot_swappable(&self) -> bool {
+        match self.path.strip_prefix("trading.") {
+            Some(setting) => {
+                let name = setting.split('.').next().unwrap_or(setting);
+                !RESTART_ONLY_TRADING_SETTINGS.contains(&name)
+            }
+            None => false,
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
@@ -20,6 +53,55 @@ pub struct Args {
     #[arg(long)]
     pub backtest: bool,
 
+    /// Backtest only: also compare exit rules (hold-to-close, sell-at-X, stop, trailing-Y) in hindsight
+    /// Prints per-period PnL by rule and the best rule per period and on average
+    #[arg(long)]
+    pub exit_analysis: bool,
+
+    /// Backtest only: replay a snapshot recording (see --record-snapshots) through the configured strategy and
+    /// a simulated trader instead of backtesting the history/ price logs
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// Backtest only: download the last N ended periods from the CLOB prices-history endpoint (cached in
+    /// history_cache/) and backtest those instead of the history/ price logs
+    #[arg(long, value_name = "N")]
+    pub fetch_history: Option<u64>,
+
+    /// Append every market snapshot the monitor takes to FILE (JSON lines), for backtest --replay
+    #[arg(long, value_name = "FILE")]
+    pub record_snapshots: Option<PathBuf>,
+
+    /// Retry redemption of trades abandoned in previous runs, then exit
+    /// Reads abandoned_trades.json and clears entries that are redeemed
+    #[arg(long)]
+    pub recover_abandoned: bool,
+
+    /// Simulation only: generate prices offline instead of reading Polymarket
+    /// Markets and price paths come from the synthetic_* trading settings
+    #[arg(long)]
+    pub synthetic: bool,
+
+    /// Seed for --synthetic random-walk prices (same seed = same price paths)
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+
+    /// Load and validate the config, print it as JSON with defaults filled in and secrets redacted, then exit
+    #[arg(long)]
+    pub print_config: bool,
+
+    /// Run market discovery for every enabled asset, print each slug tried with its condition ID, flags,
+    /// token IDs and top of book, then exit (no orders are placed). Exits with an error if an enabled
+    /// asset has no usable market
+    #[arg(long)]
+    pub validate_markets: bool,
+
+    /// Print the open positions as JSON (Polymarket positions API shape: asset, conditionId, outcome, size,
+    /// avgPrice, curPrice, ...) after the startup portfolio sync, including untracked tokens held in the current
+    /// and recent markets, then exit without trading
+    #[arg(long)]
+    pub positions: bool,
+
     /// Configuration file path
     #[arg(short, long, default_value = "config.json")]
     pub config: PathBuf,
@@ -49,11 +131,564 @@ impl Args {
 pub struct Config {
     pub polymarket: PolymarketConfig,
     pub trading: TradingConfig,
+    #[serde(default)]
+    pub debug: DebugConfig,
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub chain: ChainConfig,
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+    #[serde(default)]
+    pub balance_confirm: BalanceConfirmConfig,
+    #[serde(default)]
+    pub buy: BuyConfig,
+    #[serde(default)]
+    pub sell: SellConfig,
+    #[serde(default)]
+    pub strategy: StrategyConfig,
+    #[serde(default)]
+    pub resolution: ResolutionConfig,
+    #[serde(default)]
+    pub redemption: RedemptionConfig,
+    #[serde(default)]
+    pub sim: SimConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub startup: StartupConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub dashboard: DashboardConfig,
+    #[serde(default)]
+    pub control: ControlConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+}
+
+/// Settings for the built-in entry strategies (`trading.strategy_name` picks which one runs)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StrategyConfig {
+    #[serde(default)]
+    pub mean_reversion: MeanReversionConfig,
+    #[serde(default)]
+    pub two_sided_entry: TwoSidedEntryConfig,
+}
+
+/// "mean_reversion": buy the cheaper token of a market after it overshoots down late in the period, sell the bounce
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MeanReversionConfig {
+    /// Enter once the cheaper token's BUY price is at or below this
+    /// Default: None (0.20)
+    pub oversold_price: Option<f64>,
+    /// Skip tokens priced below this - near-zero tokens are usually losing, not overshooting
+    /// Default: None (0.05)
+    pub min_price: Option<f64>,
+    /// Sell target for mean-reversion entries (used instead of trading.sell_price)
+    /// Default: None (0.35)
+    pub sell_price: Option<f64>,
+    /// Minutes into the period before entries are considered
+    /// Default: None (10)
+    pub min_elapsed_minutes: Option<u64>,
+    /// Don't enter with less than this many seconds left in the period
+    /// Default: None (60)
+    pub min_time_remaining_seconds: Option<u64>,
+}
+
+/// "two_sided_entry": buy both Up and Down of a market when together they cost less than the $1 the winner pays,
+/// in equal shares, and hold both to resolution
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TwoSidedEntryConfig {
+    /// Enter once Up BUY price + Down BUY price is at or below this (must be below 1 for the pair to profit)
+    /// Default: None (0.97)
+    pub max_combined_price: Option<f64>,
+    /// Don't enter with less than this many seconds left in the period
+    /// Default: None (60)
+    pub min_time_remaining_seconds: Option<u64>,
+}
+
+/// Pacing of entries when several assets trigger on the same snapshot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BuyConfig {
+    /// Pause before each buy after the first in one snapshot, in milliseconds; each pause is jittered to a random
+    /// 50-100% of this value so simultaneous entries neither burst the API nor line up in time (max 2000)
+    /// Default: None (buys go out back to back)
+    pub stagger_ms: Option<u64>,
+}
+
+/// Polling for the token balance after a confirmed market buy (the conditional token balance can lag the fill)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BalanceConfirmConfig {
+    /// Balance checks before giving up and falling back to the expected units
+    /// Default: None (5)
+    pub max_attempts: Option<u32>,
+    /// Delay between balance checks, in milliseconds
+    /// Default: None (1000)
+    pub delay_ms: Option<u64>,
+}
+
+/// Retries of the profit-target and stop-loss sell loops (each loop stops early once the price recovers)
+/// and pegging of resting limit sells to the book
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SellConfig {
+    /// Sell attempts per loop before giving up; a profit sell that gives up is marked for claim at closure,
+    /// a stop-loss retries on the next check
+    /// Default: None (20)
+    pub max_retries: Option<u32>,
+    /// Delay between sell attempts, in milliseconds
+    /// Default: None (1500)
+    pub retry_delay_ms: Option<u64>,
+    /// Re-price our resting limit sells to one tick below the best ask of other sellers, so they sit at the front
+    /// of the book instead of at a fixed target that may never fill (production only)
+    /// Default: false
+    #[serde(default)]
+    pub peg_to_book: bool,
+    /// peg_to_book: cancel and re-place a resting sell only once it is more than this far from the peg price
+    /// Default: None (0.02)
+    pub repeg_threshold: Option<f64>,
+    /// peg_to_book: never peg below this price
+    /// Default: None (the position's purchase price - a pegged sell never locks in a loss)
+    pub peg_min_price: Option<f64>,
+    /// What to do with a position that has no resting limit sell when the book has no bid to sell into
+    /// Default: wait_for_close
+    #[serde(default)]
+    pub no_bid_policy: NoBidPolicy,
+    /// no_bid_policy lowball_limit: price of the resting limit sell (required with that policy)
+    /// Default: None
+    pub lowball_price: Option<f64>,
+}
+
+/// Exit behaviour when a position's book has no bid (`sell.no_bid_policy`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoBidPolicy {
+    /// Skip the sell and hold until closure (claim or redeem)
+    #[default]
+    WaitForClose,
+    /// FAK market sell accepting whatever fill exists
+    DumpAtAnyPrice,
+    /// Rest a limit sell at sell.lowball_price so the position fills if a buyer shows up (production only -
+    /// simulation waits for close)
+    LowballLimit,
+}
+
+/// When an ended market counts as settled for booking PnL and redeeming
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolutionConfig {
+    /// Settle and redeem as soon as the CLOB reports a winner, without waiting for the market's `closed` flag
+    /// Default: false (wait until the market is closed and has a winner)
+    #[serde(default)]
+    pub early_redeem_on_resolved: bool,
+}
+
+/// Which resolved positions are worth an on-chain redemption
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RedemptionConfig {
+    /// Skip redeeming positions whose resolved value (units x $1 if won, $0 if lost) is below this many dollars -
+    /// the gas would cost more than the payout. The trade is closed as if redeemed and the tokens stay in the wallet
+    /// Default: None (redeem everything, including losing positions)
+    pub min_value_to_redeem: Option<f64>,
+}
+
+/// Safety actions taken once at startup, after the portfolio sync (production only)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartupConfig {
+    /// Deal with positions left over from earlier periods (e.g. after a crash): tracked trades and tokens still
+    /// held in the last `lookback_periods` markets are routed to redemption at market closure
+    /// Default: false
+    #[serde(default)]
+    pub flatten_stale_positions: bool,
+    /// With flatten_stale_positions: market-sell leftovers whose market has ended but isn't resolved yet
+    /// instead of waiting to redeem them
+    /// Default: false
+    #[serde(default)]
+    pub sell_unresolved: bool,
+    /// Tokens held in the current markets without a tracked trade (manual trades, another tool) are always
+    /// logged at startup; with this set they are adopted as managed positions, priced at the best bid
+    /// Default: false (log only)
+    #[serde(default)]
+    pub adopt_untracked: bool,
+    /// How many earlier 15-minute periods per enabled asset are scanned for leftover tokens
+    /// Default: None (8 = the last two hours)
+    pub lookback_periods: Option<u64>,
+}
+
+/// Prometheus metrics endpoint for external health alerting
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    /// Address ("ip:port") to serve GET /metrics on, e.g. "127.0.0.1:9464"
+    /// Default: None (no metrics endpoint)
+    pub listen_addr: Option<String>,
+}
+
+/// Read-only web dashboard of pending trades, fills and PnL (see `dashboard`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DashboardConfig {
+    /// Address ("ip:port") to serve the dashboard on, e.g. "127.0.0.1:8080" - it has no authentication,
+    /// so keep it on localhost or behind a proxy
+    /// Default: None (no dashboard)
+    pub listen_addr: Option<String>,
+}
+
+/// Runtime commands over a Unix socket: pause/resume, set-param, list-positions, cancel-order, force-sell,
+/// force-redeem (see `control`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ControlConfig {
+    /// Path of the Unix socket to accept commands on, e.g. "bot.sock" - created owner-only (0600), since anyone
+    /// who can connect can sell positions
+    /// Default: None (no control socket)
+    pub socket_path: Option<String>,
+}
+
+/// Push notifications of trade events to Telegram and/or Discord (see `notifier`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Telegram bot token (from @BotFather); needs telegram_chat_id
+    /// Default: None (no Telegram messages)
+    pub telegram_bot_token: Option<String>,
+    /// Telegram chat (user, group or channel id) the bot posts to
+    /// Default: None
+    pub telegram_chat_id: Option<String>,
+    /// Discord channel webhook URL (Channel settings > Integrations > Webhooks)
+    /// Default: None (no Discord messages)
+    pub discord_webhook_url: Option<String>,
+    /// Which event types are sent
+    /// Default: all of them
+    #[serde(default)]
+    pub events: NotificationEvents,
+    /// Most messages sent in any 60 seconds; the rest are dropped and counted in the next message that goes out
+    /// Default: None (20)
+    pub max_per_minute: Option<u32>,
+}
+
+impl NotificationsConfig {
+    /// Whether any destination is configured
+    pub fn is_enabled(&self) -> bool {
+        self.telegram_bot_token.is_some() || self.discord_webhook_url.is_some()
+    }
+}
+
+/// Per-event-type switches for `notifications`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationEvents {
+    /// A market buy went through or a limit buy filled
+    pub buy_executed: bool,
+    /// A stop-loss (own or opposite-token) triggered
+    pub stop_loss: bool,
+    /// A redemption was given up after its retries
+    pub redemption_failed: bool,
+    /// A market with a position resolved (won or lost)
+    pub market_resolved: bool,
+}
+
+impl Default for NotificationEvents {
+    fn default() -> Self {
+        Self { buy_executed: true, stop_loss: true, redemption_failed: true, market_resolved: true }
+    }
+}
+
+/// Log verbosity and the history log file. RUST_LOG, when set, replaces `level` and `modules`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    /// Default level: "off", "error", "warn", "info", "debug" or "trace"
+    /// Default: None ("info")
+    pub level: Option<String>,
+    /// Per-module levels, e.g. {"trader": "warn", "api": "debug"}. Names without "::" are modules of this
+    /// crate ("trader" = polymarket_arbitrage_bot::trader); full paths are used as given
+    /// Default: empty (every module at `level`)
+    #[serde(default)]
+    pub modules: std::collections::HashMap<String, String>,
+    /// Where log lines and trading events are appended
+    /// Default: None ("history.toml")
+    pub log_file: Option<String>,
+    /// Write the periodic monitoring lines (no bid / crossed book / waiting for closure) only on every Nth
+    /// market snapshot. Trades, sells, resolutions, errors and trading events are always logged
+    /// Default: None (1 = every snapshot)
+    pub sample_monitor_every_n: Option<u64>,
+}
+
+impl LoggingConfig {
+    pub fn log_file(&self) -> &str {
+        self.log_file.as_deref().unwrap_or("history.toml")
+    }
+
+    /// Logger builder with the configured levels, or straight from RUST_LOG when that is set
+    pub fn logger_builder(&self) -> anyhow::Result<env_logger::Builder> {
+        if std::env::var_os("RUST_LOG").is_some() {
+            return Ok(env_logger::Builder::from_default_env());
+        }
+        let mut builder = env_logger::Builder::new();
+        builder.filter_level(parse_level(self.level.as_deref().unwrap_or("info"))?);
+        for (module, level) in &self.modules {
+            let path = if module.contains("::") {
+                module.clone()
+            } else {
+                format!("polymarket_arbitrage_bot::{}", module)
+            };
+            builder.filter_module(&path, parse_level(level)?);
+        }
+        Ok(builder)
+    }
+}
+
+fn parse_level(level: &str) -> anyhow::Result<log::LevelFilter> {
+    level.parse::<log::LevelFilter>().map_err(|_| anyhow::anyhow!(
+        "invalid log level \"{}\" (expected off, error, warn, info, debug or trace)", level))
+}
+
+/// Simulation-only settings (`--sim`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimConfig {
+    /// Cash the simulated account starts with. Buys are debited, sells and resolutions credited, and a
+    /// simulated buy that costs more than the cash left is skipped
+    /// Default: None (unlimited cash)
+    pub starting_bankroll: Option<f64>,
+    /// How simulated limit orders fill against the book
+    #[serde(default)]
+    pub fill_model: SimFillModel,
+}
+
+/// Simulated fill model. With every field unset a limit order fills in full at the touch as soon as the price
+/// crosses its limit, with no fees
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimFillModel {
+    /// Adverse slippage applied to the touch price, in basis points (a BUY pays ask * (1 + bps / 10000),
+    /// a SELL receives bid * (1 - bps / 10000)). Never fills beyond the order's limit price
+    /// Default: None (fill at the touch)
+    pub slippage_bps: Option<f64>,
+    /// Fraction of the top-of-book size (ask size for a BUY, bid size for a SELL) one check may fill.
+    /// The rest of the order keeps resting and fills over later checks. Books without a size fill in full
+    /// Default: None (fill the whole order at once regardless of depth)
+    pub book_share: Option<f64>,
+    /// Fee on fills of orders that were marketable when placed, in basis points of the fill notional
+    /// Default: None (no fee)
+    pub taker_fee_bps: Option<f64>,
+    /// Fee on fills of orders that rested on the book first, in basis points of the fill notional
+    /// Default: None (no fee)
+    pub maker_fee_bps: Option<f64>,
+}
+
+/// UTC trading hours: new buys are only placed inside one of the windows
+/// Open positions, sells and redemptions are managed around the clock
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    /// Allowed UTC time ranges
+    /// Default: empty (trade at any time)
+    #[serde(default)]
+    pub windows: Vec<ScheduleWindow>,
+}
+
+/// One allowed UTC time range, e.g. {"start": "13:30", "end": "20:00", "days": ["Mon", "Tue"]}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleWindow {
+    /// Start time "HH:MM" (inclusive)
+    pub start: String,
+    /// End time "HH:MM" (exclusive). An end before the start wraps past midnight (e.g. 22:00 - 02:00)
+    pub end: String,
+    /// Days of week the window starts on ("Mon".."Sun"); for a window that wraps midnight the early-morning
+    /// part belongs to the previous day's window
+    /// Default: None (every day)
+    pub days: Option<Vec<chrono::Weekday>>,
+}
+
+fn parse_schedule_time(value: &str) -> Option<chrono::NaiveTime> {
+    chrono::NaiveTime::parse_from_str(value, "%H:%M").ok()
+        .or_else(|| chrono::NaiveTime::parse_from_str(value, "%H:%M:%S").ok())
+}
+
+impl ScheduleWindow {
+    fn contains(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        use chrono::Datelike;
+        let (Some(start), Some(end)) = (parse_schedule_time(&self.start), parse_schedule_time(&self.end)) else {
+            return false;
+        };
+        let time = now.time();
+        let runs_on = |day: chrono::Weekday| self.days.as_ref().is_none_or(|days| days.contains(&day));
+        if start <= end {
+            start <= time && time < end && runs_on(now.weekday())
+        } else if time >= start {
+            runs_on(now.weekday()) // Evening part - window started today
+        } else {
+            time < end && runs_on(now.weekday().pred()) // After midnight - window started yesterday
+        }
+    }
+}
+
+impl ScheduleConfig {
+    /// Whether new buys are allowed at `now` (always, when no windows are configured)
+    pub fn allows(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.windows.is_empty() || self.windows.iter().any(|w| w.contains(now))
+    }
+}
+
+/// Proxy / RPC settings shared by every HTTP, WebSocket and chain request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Proxy for all HTTP(S) traffic (gamma, CLOB, relayer, order signing SDK, Polygon RPC), e.g. "http://proxy.corp:3128"
+    /// The order-signing SDK only reads HTTPS_PROXY from the environment, so that must be exported too when this is set
+    /// Default: None (direct connections; HTTPS_PROXY/HTTP_PROXY env vars still apply)
+    pub http_proxy: Option<String>,
+    /// HTTP CONNECT proxy for WebSocket traffic (CLOB user channel), e.g. "http://proxy.corp:3128"
+    /// Default: None (falls back to http_proxy, then a direct connection)
+    pub ws_proxy: Option<String>,
+    /// Polygon JSON-RPC endpoint used for approvals and on-chain checks
+    /// Default: None ("https://polygon-rpc.com")
+    pub rpc_url: Option<String>,
+    /// Timeout for every HTTP and RPC request, in seconds
+    /// Default: None (10 seconds)
+    pub request_timeout_secs: Option<u64>,
+    /// Retries and circuit breakers for CLOB/gamma API calls
+    #[serde(default)]
+    pub retry: RetryConfig,
+}
+
+/// How API calls are retried and when an endpoint is given a rest (`[network.retry]`)
+/// Reads, balance checks and cancels are retried with exponential backoff and jitter on retryable errors
+/// (network errors, timeouts, 429, 5xx); order placement and redemption are never retried by the client.
+/// After `breaker_failures` retryable failures in a row an endpoint's circuit opens and its calls fail
+/// immediately for `breaker_open_secs`, then one trial call decides whether it closes again
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Attempts per call, including the first
+    /// Default: None (3)
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry, in milliseconds; doubles with every further retry
+    /// Default: None (200)
+    pub base_delay_ms: Option<u64>,
+    /// Longest delay between two attempts, in milliseconds
+    /// Default: None (5000)
+    pub max_delay_ms: Option<u64>,
+    /// Retryable failures in a row that open an endpoint's circuit (0 = never)
+    /// Default: None (5)
+    pub breaker_failures: Option<u32>,
+    /// How long an open circuit rejects calls, in seconds
+    /// Default: None (30)
+    pub breaker_open_secs: Option<u64>,
+}
+
+/// Chain the bot signs orders for and settles on; set it to trial the bot on a testnet without risking funds
+/// Pair it with polymarket.clob_api_url / gamma_api_url and network.rpc_url of the same environment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChainConfig {
+    /// Chain id used in the order-signing domain and for the exchange / USDC / CTF contract addresses
+    /// 137 = Polygon mainnet, 80002 = Polygon Amoy testnet
+    /// Default: None (137)
+    pub chain_id: Option<u64>,
+    /// CLOB user channel WebSocket URL
+    /// Default: None (Polymarket's on mainnet; unavailable on other chains)
+    pub user_ws_url: Option<String>,
+    /// CLOB market channel WebSocket URL (price stream, see trading.price_transport)
+    /// Default: None (Polymarket's on mainnet; unavailable on other chains)
+    pub market_ws_url: Option<String>,
+    /// Builder relayer base URL, used for gasless redemption and approvals
+    /// Default: None (Polymarket's on mainnet; unavailable on other chains)
+    pub relayer_url: Option<String>,
+    /// Gas pricing and confirmation of the redemption transactions the bot sends itself
+    #[serde(default)]
+    pub gas: GasConfig,
+}
+
+/// How fees are set for on-chain transactions sent from the wallet (EOA redemptions)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasStrategy {
+    /// The node's EIP-1559 fee estimate, capped by max_fee_gwei / priority_fee_gwei
+    #[default]
+    Estimate,
+    /// Exactly max_fee_gwei and priority_fee_gwei
+    Fixed,
+    /// The estimate times `multiplier`, capped by max_fee_gwei / priority_fee_gwei
+    Multiplier,
+}
+
+/// Gas and confirmation settings for redemptions (`[chain.gas]`)
+/// EOA wallets redeem with their own transaction and use all of these; proxy-wallet redemptions go through the
+/// gasless relayer, which sets its own gas - only `confirmations` applies to them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GasConfig {
+    /// Default: estimate
+    #[serde(default)]
+    pub strategy: GasStrategy,
+    /// Max fee per gas in gwei: the fee for "fixed", a cap otherwise
+    /// Default: None (no cap)
+    pub max_fee_gwei: Option<f64>,
+    /// Priority fee per gas in gwei: the tip for "fixed", a cap otherwise
+    /// Default: None (no cap)
+    pub priority_fee_gwei: Option<f64>,
+    /// Factor applied to the estimated fees with strategy "multiplier"
+    /// Default: None (1.5)
+    pub multiplier: Option<f64>,
+    /// Blocks a redemption must be confirmed by before it counts as done
+    /// Default: None (1)
+    pub confirmations: Option<u64>,
+    /// A transaction not confirmed within this many seconds is re-submitted (same nonce) with bumped fees
+    /// Default: None (90)
+    pub stuck_timeout_secs: Option<u64>,
+    /// Fee increase per re-submission, in percent (nodes require at least 10 to replace a transaction)
+    /// Default: None (25)
+    pub bump_percent: Option<u64>,
+    /// Re-submissions before the redemption is reported as failed (it is retried on a later check)
+    /// Default: None (3)
+    pub max_resubmits: Option<u32>,
+}
+
+impl ChainConfig {
+    /// Configured chain id (Polygon mainnet unless set)
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id.unwrap_or(polymarket_client_sdk::POLYGON)
+    }
+}
+
+/// Market discovery settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscoveryConfig {
+    /// Fail at startup if any enabled asset's market can't be discovered, instead of substituting a
+    /// dummy fallback market (which silently disables that asset for the run)
+    /// Default: false (run degraded with a fallback)
+    /// Disabled assets (enable_*_trading = false) are ignored either way
+    #[serde(default)]
+    pub strict: bool,
+    /// Check that each discovered market's question/slug names its asset (e.g. "bitcoin"/"btc" for BTC)
+    /// before trading it. A mismatch is fatal with strict = true, otherwise a warning
+    /// Default: None (true)
+    pub verify_asset: Option<bool>,
+    /// When a period rotates and the new period's market isn't listed yet, keep retrying discovery (with
+    /// backoff from 1s up to 10s between attempts) for this many seconds before staying on the old markets
+    /// until the next period. 0 disables the retries
+    /// Default: None (60)
+    pub rotation_retry_secs: Option<u64>,
+    /// Only accept a discovered market once its orderbook has at least one bid or ask, so the bot doesn't
+    /// switch to a market it can't price yet in the first seconds of a period
+    /// Default: false (accept any active, open market)
+    #[serde(default)]
+    pub require_book: bool,
+    /// With require_book: seconds into a market's period after which an empty book no longer blocks it
+    /// (it is accepted with a warning), so a quiet market isn't rejected for the whole period
+    /// Default: None (60)
+    pub empty_book_grace_secs: Option<u64>,
+}
+
+/// Diagnostic settings (all off by default)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DebugConfig {
+    /// Before and after every buy/sell order, log the traded token's top orderbook levels to orderbook_dumps.jsonl
+    /// Default: false (costs two extra orderbook requests per order)
+    #[serde(default)]
+    pub dump_orderbook_on_trade: bool,
+    /// Number of levels per side included in each orderbook dump
+    /// Default: 10
+    pub orderbook_dump_levels: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolymarketConfig {
+    /// Gamma API base URL; a trailing slash is ignored. Probed at startup (see `PolymarketApi::health_check`)
     pub gamma_api_url: String,
+    /// CLOB API base URL; a trailing slash is ignored. Probed at startup (see `PolymarketApi::health_check`)
     pub clob_api_url: String,
     pub api_key: Option<String>,
     pub api_secret: Option<String>,
@@ -71,6 +706,58 @@ pub struct PolymarketConfig {
     /// 2 = GnosisSafe (Gnosis Safe wallet)
     /// If proxy_wallet_address is set, this should be 1 (Proxy)
     pub signature_type: Option<u8>,
+    /// Additional wallets to spread buys over. The credentials above are the "primary" wallet; each new position
+    /// is bought from the wallet `wallet_rotation` picks, and everything later done with that position (sells,
+    /// cancels, balance checks, redemption) uses the same wallet
+    /// Default: empty (trade from the primary wallet only)
+    #[serde(default)]
+    pub wallets: Vec<WalletConfig>,
+    /// How the next buy's wallet is picked when `wallets` is set
+    /// Default: "round_robin"
+    #[serde(default)]
+    pub wallet_rotation: WalletRotation,
+    /// Weight of the primary wallet for `wallet_rotation: "weighted"`
+    /// Default: None (1)
+    pub primary_wallet_weight: Option<u32>,
+}
+
+/// Credentials of one additional trading wallet (`polymarket.wallets`) - same meaning as the primary wallet's
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletConfig {
+    /// Name used in logs and balance summaries
+    pub label: String,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub api_passphrase: Option<String>,
+    pub private_key: Option<String>,
+    pub proxy_wallet_address: Option<String>,
+    pub signature_type: Option<u8>,
+    /// Share of buys for `wallet_rotation: "weighted"`, relative to the other wallets' weights
+    /// Default: None (1)
+    pub weight: Option<u32>,
+}
+
+/// How buys are spread over the trading wallets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletRotation {
+    /// Each wallet in turn, primary first
+    #[default]
+    RoundRobin,
+    /// In proportion to the wallets' weights, interleaved (weights 2 and 1 buy from A, B, A, A, B, A, ...)
+    Weighted,
+}
+
+/// Stop-loss rule for open positions (`trading.stop_loss`), written as e.g. `{"type": "trailing", "percent": 10}`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StopLoss {
+    /// Sell once the price drops to `price` (same as trading.stop_loss_price)
+    Fixed { price: f64 },
+    /// Sell once the price drops `percent` below the highest price seen since entry
+    Trailing { percent: f64 },
+    /// Sell if the price is below `price` once the position has been held `after_minutes` minutes
+    TimeBased { price: f64, after_minutes: u64 },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,9 +767,31 @@ pub struct TradingConfig {
     pub solana_condition_id: Option<String>,
     pub xrp_condition_id: Option<String>,
     pub check_interval_ms: u64,
+    /// Length of one market period in seconds, e.g. 3600 for hourly or 86400 for daily up/down markets
+    /// Periods start on multiples of this length since the Unix epoch; must be a whole number of minutes
+    /// Default: None (900 = 15 minutes)
+    pub period_seconds: Option<u64>,
+    /// Market slug format used for discovery: {prefix} = asset slug prefix ("btc", "eth", "solana", ...),
+    /// {period} = period length in minutes, {timestamp} = period start, e.g. "{prefix}-updown-1h-{timestamp}"
+    /// Default: None ("{prefix}-updown-{period}m-{timestamp}")
+    pub slug_template: Option<String>,
     /// Fixed trade amount in USD for BTC Up token purchase
     /// Default: 1.0 ($1.00)
     pub fixed_trade_amount: f64,
+    /// Growth mode: size each buy as base_trade_amount + realized PnL * compound_fraction (recomputed before every buy)
+    /// so winnings are reinvested and size shrinks after losses
+    /// Default: false (every buy uses fixed_trade_amount)
+    #[serde(default)]
+    pub compound: bool,
+    /// Compounding: trade size before any realized PnL
+    /// Default: None (fixed_trade_amount)
+    pub base_trade_amount: Option<f64>,
+    /// Compounding: share of realized PnL added to the base size (losses shrink it by the same share)
+    /// Default: 1.0 (all realized PnL)
+    pub compound_fraction: Option<f64>,
+    /// Compounding: upper bound on the compounded trade size
+    /// Default: None (no cap)
+    pub max_trade_amount: Option<f64>,
     /// Price threshold to trigger buy (BTC Up token must reach this price)
     /// Default: 0.9 ($0.90)
     pub trigger_price: f64,
@@ -100,6 +809,9 @@ pub struct TradingConfig {
     /// Default: 0.85 ($0.85) - sell if price drops 5% below purchase price
     /// If None, stop-loss is disabled
     pub stop_loss_price: Option<f64>,
+    /// Stop-loss rule: fixed, trailing or time-based (see `StopLoss`); replaces stop_loss_price when set
+    /// Default: None (stop_loss_price as a fixed stop)
+    pub stop_loss: Option<StopLoss>,
     /// Hedge price - limit buy price for opposite token when buying at $0.9+ (hedging strategy)
     /// Default: 0.5 ($0.50) - place limit buy order for opposite token at this price
     /// If None, hedging is disabled
@@ -130,11 +842,17 @@ pub struct TradingConfig {
     /// Dual limit-start bot: fixed number of shares per order
     pub dual_limit_shares: Option<f64>,
     /// Dual limit-start bot: after this many minutes, if only one side filled, begin hedging the unfilled side
+    /// Also the age at which an open position without exit orders moves to the standard hedge (hedge_sell_ladder limit sells)
     /// Default: 10 (minutes)
     pub dual_limit_hedge_after_minutes: Option<u64>,
     /// Dual limit-start bot: hedge trigger/limit price for buying the unfilled side
     /// Default: 0.85 ($0.85)
     pub dual_limit_hedge_price: Option<f64>,
+    /// Limit sells placed once a standard or individual hedge is bought: list of [price, fraction], one sell
+    /// of fraction x the hedged shares at each price
+    /// Default: None (two sells of the full position, at 0.93 and 0.98)
+    /// e.g. [[0.93, 0.5], [0.96, 0.3], [0.99, 0.2]]. Prices must be in (0, 1], fractions in (0, 1] summing to at most 1
+    pub hedge_sell_ladder: Option<Vec<(f64, f64)>>,
     /// Dual limit-start bot: early hedge check time (minutes) - check earlier if multiple markets unfilled or uptrending
     /// Default: 5 (minutes)
     pub dual_limit_early_hedge_minutes: Option<u64>,
@@ -147,6 +865,463 @@ pub struct TradingConfig {
     /// Dual limit-start bot: number of price snapshots to track for trend analysis
     /// Default: 20
     pub dual_limit_trend_history_size: Option<usize>,
+    /// Order type used for entries routed through `Trader::enter`
+    /// Default: "market_fok"
+    /// "market_fok": fill-or-kill market order - fills almost immediately but pays the ask (worse price)
+    /// "limit": limit order at the detected price - better price, but may not fill before the window closes
+    /// "maker": limit order at the best bid (see maker_price_improvement) that never crosses the ask; converted
+    /// to a market buy if it hasn't filled within maker_timeout_secs
+    /// Hedge entries always use market orders regardless of this setting
+    #[serde(default)]
+    pub entry_order_type: EntryOrderType,
+    /// Maker entries: seconds a resting maker buy waits for a fill before it is cancelled and the unfilled
+    /// part is bought at market
+    /// Default: None (10)
+    pub maker_timeout_secs: Option<u64>,
+    /// Maker entries: how far above the best bid the maker buy is placed (still at least one tick below the ask)
+    /// Default: None (join the best bid)
+    pub maker_price_improvement: Option<f64>,
+    /// Price source for the detection signal (orders always use executable CLOB prices)
+    /// Default: "clob_top"
+    /// "clob_top": best CLOB prices only
+    /// "gamma_midpoint": gamma API outcome price (midpoint) - more stable in thin markets
+    /// "blend": CLOB when the spread is tight, gamma midpoint when the book is wide, crossed or empty
+    #[serde(default)]
+    pub price_source: PriceSource,
+    /// Blend price source: maximum CLOB spread to still trust the book
+    /// Default: 0.05 ($0.05)
+    pub price_source_max_spread: Option<f64>,
+    /// How the monitor gets CLOB prices (production; simulation reads its price feed)
+    /// Default: "websocket_with_poll_fallback"
+    /// "websocket": CLOB market channel only
+    /// "poll": REST requests on every check_interval_ms
+    /// "websocket_with_poll_fallback": market channel, polling REST while it is silent for price_transport_stale_ms
+    #[serde(default)]
+    pub price_transport: Transport,
+    /// Market channel silence, in milliseconds, after which websocket_with_poll_fallback polls REST instead
+    /// Default: None (5000)
+    pub price_transport_stale_ms: Option<u64>,
+    /// Stop after this many completed 15-minute periods
+    /// Default: None (run indefinitely)
+    /// Once reached, no new buys are placed; open positions settle/redeem, the final summary prints and the bot exits
+    pub max_periods: Option<u64>,
+    /// Minimum seconds between any two buys across all markets (global throttle)
+    /// Default: None (no throttle)
+    /// Throttled opportunities are not queued - they are re-detected on the next snapshot if still valid
+    pub min_seconds_between_buys: Option<u64>,
+    /// --synthetic: per-step standard move of the random-walk Up price
+    /// Default: 0.01 ($0.01 per monitor check)
+    pub synthetic_volatility: Option<f64>,
+    /// --synthetic: half of the quoted bid/ask spread around the path price
+    /// Default: 0.01 (bid = price - 0.01, ask = price + 0.01)
+    pub synthetic_half_spread: Option<f64>,
+    /// --synthetic: scripted Up price paths by asset ("btc", "eth", "solana", "xrp"), one price per monitor check
+    /// Default: None (all markets use a seeded random walk from 0.50)
+    /// A scripted market closes on its last price; Up wins if that price is >= 0.50
+    pub synthetic_paths: Option<std::collections::HashMap<String, Vec<f64>>>,
+    /// Skip a buy when the opposite token (other side of the same market) is already above this price
+    /// Default: None (filter disabled)
+    pub opposite_max_price: Option<f64>,
+    /// Re-fetch the opposite token price live before applying opposite_max_price, with this timeout in milliseconds
+    /// Default: None (use the opposite price from the same snapshot - no extra latency)
+    /// If the lookup fails or times out, the trade is allowed
+    pub opposite_price_lookup_timeout_ms: Option<u64>,
+    /// Time-in-force for market buy orders
+    /// Default: "FOK" (fill-or-kill - whole entry fills or nothing)
+    /// "FAK": fill-and-kill - accepts partial fills, useful in thin Solana/XRP books
+    #[serde(default)]
+    pub buy_order_tif: MarketOrderTif,
+    /// Time-in-force for market sell orders (stop-loss, profit and closing sells)
+    /// Default: "FAK" (fill-and-kill - sell whatever liquidity is available)
+    /// "FOK": fill-or-kill - sell the full size or nothing
+    #[serde(default = "default_sell_order_tif")]
+    pub sell_order_tif: MarketOrderTif,
+    /// Seconds after startup during which prices are monitored but no buys are placed
+    /// Default: None (trade immediately)
+    /// Applied once per process, not per period - avoids acting on the first, possibly stale, snapshots
+    pub warmup_seconds: Option<u64>,
+    /// Minimum top-of-book imbalance bid_size / (bid_size + ask_size) required to enter
+    /// Default: None (filter disabled)
+    /// e.g. 0.6 = at least 60% of the resting top-of-book size is on the buy side. Enabling it fetches
+    /// the orderbook alongside each price (one extra request per token)
+    pub min_book_imbalance: Option<f64>,
+    /// Minimum expected edge per share to enter: P * (1 - entry) - (1 - P) * entry, where P is the price as
+    /// implied win probability, pulled towards the favoured outcome as the period runs out
+    /// Default: None (gate disabled)
+    /// e.g. 0.02 = the estimated win probability must beat the entry price by 2 cents
+    pub min_expected_edge: Option<f64>,
+    /// Maximum expected slippage of a market buy, as a fraction of the best ask. Before an entry the ask levels
+    /// are walked to estimate the average fill price, and the buy is shrunk until that average is within
+    /// best_ask * (1 + max_entry_slippage); with no depth at all the buy is skipped. Hedge buys are never shrunk
+    /// Default: None (no depth check - buy the full amount)
+    /// e.g. 0.02 = the average fill may be at most 2% above the best ask
+    pub max_entry_slippage: Option<f64>,
+    /// Only enter when the token and its opposite both have a bid and an ask (uncrossed), so the position
+    /// can still be hedged or exited. Suppressed entries are logged with the side that is missing
+    /// Default: false (one-sided books are allowed)
+    #[serde(default)]
+    pub require_two_sided_book: bool,
+    /// Skip an entry when the token's spread (ask - bid in the snapshot) is wider than this, to avoid buying into
+    /// thin books. A token without both a bid and an ask is skipped too
+    /// Default: None (filter disabled)
+    pub max_spread: Option<f64>,
+    /// Outcomes the momentum detector may buy, per asset ("btc", "eth", "solana", "xrp"),
+    /// e.g. {"btc": ["down"]} to only trade BTC Down. Enabling the asset is still up to enable_*_trading
+    /// Default: empty (both directions for every asset)
+    #[serde(default)]
+    pub directions: std::collections::HashMap<String, Vec<Direction>>,
+    /// Maximum age of a quote, in milliseconds, before it is acted on (entries and sell decisions)
+    /// Default: None (no limit)
+    /// A stale entry quote is skipped until the next snapshot; a stale sell-side quote is re-fetched first
+    pub max_price_age_ms: Option<u64>,
+    /// Emergency exit window: in the last N seconds before close, sell losing positions at market (FAK)
+    /// Default: None (hold losing positions to resolution)
+    /// Needs to be above 30 - the last 30 seconds are handled as closed (resolution/redemption)
+    pub emergency_exit_seconds_before_close: Option<u64>,
+    /// Emergency exit only fires while the current bid is above this price (below it, holding costs little)
+    /// Default: None (emergency exit disabled)
+    /// Only positions currently below their purchase price are sold; hedge trades are never emergency-sold
+    pub emergency_exit_min_price: Option<f64>,
+    /// Per-position age limit: sell at the current bid (FAK) once a position has been held this many seconds,
+    /// whether or not the sell target was reached
+    /// Default: None (hold until the target, stop-loss or resolution)
+    /// Hedge/opposite trades and no_sell / claim_on_closure positions are never force-exited
+    pub max_hold_secs: Option<u64>,
+    /// Sell only winners early, hold losers: a position whose SELL price reaches winner_confidence_price is sold
+    /// at market right away, one that is below its purchase price is marked to be claimed at closure instead of
+    /// being stop-lossed, force-exited or scaled out (it is likely going to $0, and a few cents rarely help)
+    /// Default: false
+    /// Hedge/opposite trades and no_sell positions are left alone
+    #[serde(default)]
+    pub asymmetric_exit: bool,
+    /// asymmetric_exit: SELL price at which a position counts as the likely winner and is sold
+    /// Default: None (0.95)
+    pub winner_confidence_price: Option<f64>,
+    /// Hard cap on total notional (units * purchase price) across all open positions, all periods and assets
+    /// Default: None (no cap)
+    /// A buy that would push open exposure above the cap is refused. Caps exposure, not wallet balance
+    pub max_total_notional: Option<f64>,
+    /// USD per buy for specific assets ("btc", "eth", "solana", "xrp"), e.g. {"btc": 5.0, "solana": 1.0}
+    /// Default: None (fixed_trade_amount for every asset)
+    /// Replaces fixed_trade_amount for that asset, and the base size in compounding mode; strategies that size
+    /// their own legs (investment overrides) are unaffected
+    pub trade_amount_by_asset: Option<std::collections::HashMap<String, f64>>,
+    /// Halt new buys once realized PnL since 00:00 UTC is at or below -daily_loss_limit (USD); buys resume at the
+    /// start of the next UTC day
+    /// Default: None (no daily loss limit)
+    /// Counts settlements (sells, resolutions); open positions keep being managed while buys are halted
+    pub daily_loss_limit: Option<f64>,
+    /// Sell target escalation as resolution approaches: list of [seconds_before_close, price]
+    /// Default: None (always sell at sell_price)
+    /// e.g. [[120, 0.97], [60, 0.95], [30, 0.0]] = sell_price until T-120s, 0.97 until T-60s, 0.95 until T-30s,
+    /// then sell at the best bid. Prices must not increase as close approaches
+    pub sell_price_schedule: Option<Vec<(u64, f64)>>,
+    /// Sell into strength: list of [trigger_price, fraction]. The first time the SELL price reaches a trigger,
+    /// that fraction of the shares still held is market-sold; the rest waits for sell_price as usual
+    /// Default: None (one sell at sell_price)
+    /// e.g. [[0.93, 0.25], [0.96, 0.5]] = sell a quarter at 0.93, then half of what is left at 0.96.
+    /// Triggers must be ascending and the fractions must sum to at most 1
+    pub scale_out_schedule: Option<Vec<(f64, f64)>>,
+    /// Halt new buys after this many losing settlements in a row (any win resets the count)
+    /// Default: None (no loss-streak circuit breaker)
+    /// Targets streaks, not drawdown size - a run of small losses trips it just like a run of big ones
+    pub max_consecutive_losses: Option<u32>,
+    /// How long new buys stay halted once max_consecutive_losses is hit
+    /// Default: one period (trading.period_seconds) when unset
+    pub loss_streak_cooldown_secs: Option<u64>,
+    /// After a position on an asset settles with a profit (resolution or exit), skip new entries on that
+    /// asset (both Up and Down) for this many seconds, so the bot doesn't re-buy a market that already moved
+    /// Default: 0 (off)
+    #[serde(default)]
+    pub post_win_cooldown_secs: u64,
+    /// Same as post_win_cooldown_secs, after a losing settlement (e.g. a stop-loss exit)
+    /// Default: 0 (off)
+    #[serde(default)]
+    pub post_loss_cooldown_secs: u64,
+    /// Only buy once the price has been at/above trigger_price on this many consecutive snapshots of the token,
+    /// so a one-off spike or stale quote doesn't trigger an entry. A snapshot below the trigger (or without a
+    /// usable quote) and a new period restart the count
+    /// Default: None (1 - buy on the first snapshot at the trigger)
+    pub trigger_confirmations: Option<u32>,
+    /// Poll the gamma API's umaResolutionStatus this often for ended markets with open positions, and run the
+    /// closure check (redemption) as soon as one reports "resolved" instead of waiting for the next tick
+    /// Default: None (closure checks only every market_closure_check_interval_seconds)
+    /// Redemption is still gated on the CLOB market having a winner set (and being closed, unless
+    /// resolution.early_redeem_on_resolved)
+    pub resolution_poll_interval_ms: Option<u64>,
+    /// Order size precision per asset ("btc", "eth", "solana", "xrp"), e.g. {"xrp": 0}
+    /// Default: None (2 decimals for every asset)
+    /// Used for limit orders and market sells; the CLOB rejects market-order sizes with more than 2 decimals
+    pub size_decimals: Option<std::collections::HashMap<String, u32>>,
+    /// How order sizes are rounded to size_decimals: "floor", "round" or "ceil"
+    /// Default: "floor" (a sell never exceeds the balance held, a buy never exceeds the intended size)
+    #[serde(default)]
+    pub size_rounding: RoundingMode,
+    /// Re-check every confirmed buy against fresh market metadata: the token must belong to the trade's
+    /// condition_id and its outcome must match the intended Up/Down side. "off", "warn" (log TOKEN MISMATCH)
+    /// or "flatten" (also sell the position straight back at market)
+    /// Default: "off"
+    #[serde(default)]
+    pub verify_purchased_token: TokenVerification,
+    /// When placing a limit buy fails with a timeout/transport error, poll our open orders this long for a
+    /// matching order (same token, side, price and size, just created) and track it instead of treating the
+    /// buy as failed. 0 disables the lookup
+    /// Default: None (5)
+    pub lost_order_lookup_secs: Option<u64>,
+    /// Starting collateral (USDC) that account PnL is measured against
+    /// Default: None (USDC balance snapshot taken at startup)
+    /// Set it to keep measuring from an earlier point across restarts
+    pub starting_balance: Option<f64>,
+    /// Entry strategy: "momentum" (buy on trigger_price after min_elapsed_minutes), "mean_reversion"
+    /// (buy the oversold cheaper token late in the period, see strategy.mean_reversion) or "two_sided_entry"
+    /// (buy both tokens when they cost less than $1 together, see strategy.two_sided_entry)
+    /// Default: None ("momentum")
+    pub strategy_name: Option<String>,
+    /// Balance checks run in parallel by the startup portfolio sync
+    /// Default: None (4)
+    /// Keep it small - each check is an authenticated CLOB request
+    pub portfolio_sync_concurrency: Option<usize>,
+    /// Price, orderbook and balance requests run in parallel by each pending-trade check
+    /// (all distinct tokens are fetched up front, then every trade is decided from those results)
+    /// Default: None (8). 1 fetches one token at a time
+    pub price_fetch_concurrency: Option<usize>,
+    /// Before placing an opposite-token hedge order after a stop-loss, check our open orders on that token and
+    /// skip it if one already rests at the same side and price, or on the other side at a crossing price
+    /// Default: None (true)
+    pub self_cross_guard: Option<bool>,
+    /// After placing a limit sell (profit target or opposite-token hedge), check that it is live on the book
+    /// (or already filled) and re-place it if it was rejected or cancelled right after being accepted
+    /// Default: false
+    #[serde(default)]
+    pub verify_resting: bool,
+    /// verify_resting: wait before each check, in milliseconds
+    /// Default: None (1000)
+    pub verify_resting_delay_ms: Option<u64>,
+    /// verify_resting: re-placements before the sell is reported as failed
+    /// Default: None (2)
+    pub verify_resting_retries: Option<u32>,
+}
+
+impl TradingConfig {
+    /// Stop-loss rule in effect: trading.stop_loss, or a fixed stop at stop_loss_price
+    pub fn stop_loss(&self) -> Option<StopLoss> {
+        self.stop_loss.or(self.stop_loss_price.map(|price| StopLoss::Fixed { price }))
+    }
+
+    /// Configured market period length in seconds (15 minutes unless set)
+    pub fn period_seconds(&self) -> u64 {
+        self.period_seconds.unwrap_or(crate::period::DEFAULT_PERIOD_SECONDS)
+    }
+
+    /// Sell target active with `seconds_until_close` left: the price of the closest schedule entry
+    /// whose window has started, or `sell_price` before the first one
+    pub fn scheduled_sell_price(&self, seconds_until_close: u64) -> f64 {
+        self.sell_price_schedule.as_ref()
+            .and_then(|schedule| {
+                schedule.iter()
+                    .filter(|(seconds, _)| seconds_until_close <= *seconds)
+                    .min_by_key(|(seconds, _)| *seconds)
+                    .map(|(_, price)| *price)
+            })
+            .unwrap_or(self.sell_price)
+    }
+
+    /// Scale-out triggers must be valid prices in ascending order, with fractions in (0, 1] summing to at most 1
+    fn check_scale_out_schedule(&self, problems: &mut Vec<String>) {
+        let Some(schedule) = &self.scale_out_schedule else {
+            return;
+        };
+        let mut previous: Option<f64> = None;
+        for (trigger, fraction) in schedule {
+            if !(*trigger > 0.0 && *trigger <= 1.0) {
+                problems.push(format!("trading.scale_out_schedule: trigger {} must be in (0, 1]", trigger));
+            }
+            if !(*fraction > 0.0 && *fraction <= 1.0) {
+                problems.push(format!("trading.scale_out_schedule: fraction {} at {} must be in (0, 1]", fraction, trigger));
+            }
+            if let Some(previous) = previous.filter(|previous| trigger <= previous) {
+                problems.push(format!("trading.scale_out_schedule: trigger {} is not above the previous trigger {} - triggers must be ascending",
+                    trigger, previous));
+            }
+            previous = Some(*trigger);
+        }
+        let total: f64 = schedule.iter().map(|(_, fraction)| fraction).sum();
+        if total > 1.0 + 1e-9 {
+            problems.push(format!("trading.scale_out_schedule: fractions sum to {} - must be at most 1", total));
+        }
+    }
+
+    /// Hedge ladder rungs must be valid prices, with fractions in (0, 1] summing to at most 1
+    fn check_hedge_sell_ladder(&self, problems: &mut Vec<String>) {
+        let Some(ladder) = &self.hedge_sell_ladder else {
+            return;
+        };
+        if ladder.is_empty() {
+            problems.push("trading.hedge_sell_ladder must not be empty (omit it for the default 0.93/0.98 sells)".to_string());
+        }
+        for (price, fraction) in ladder {
+            if !(*price > 0.0 && *price <= 1.0) {
+                problems.push(format!("trading.hedge_sell_ladder: price {} must be in (0, 1]", price));
+            }
+            if !(*fraction > 0.0 && *fraction <= 1.0) {
+                problems.push(format!("trading.hedge_sell_ladder: fraction {} at {} must be in (0, 1]", fraction, price));
+            }
+        }
+        let total: f64 = ladder.iter().map(|(_, fraction)| fraction).sum();
+        if total > 1.0 + 1e-9 {
+            problems.push(format!("trading.hedge_sell_ladder: fractions sum to {} - must be at most 1", total));
+        }
+    }
+
+    /// The schedule must only lower the target as close approaches, starting at or below sell_price
+    fn check_sell_price_schedule(&self, problems: &mut Vec<String>) {
+        let Some(schedule) = &self.sell_price_schedule else {
+            return;
+        };
+        let mut entries = schedule.clone();
+        entries.sort_by_key(|(seconds, _)| std::cmp::Reverse(*seconds));
+        let mut previous = self.sell_price;
+        for (seconds, price) in entries {
+            if seconds > self.period_seconds() {
+                problems.push(format!("trading.sell_price_schedule: T-{}s is longer than a {}s period", seconds, self.period_seconds()));
+            }
+            if !(0.0..=1.0).contains(&price) {
+                problems.push(format!("trading.sell_price_schedule: price {} at T-{}s must be between 0 and 1", price, seconds));
+            } else if price > previous {
+                problems.push(format!("trading.sell_price_schedule: price {} at T-{}s is above the earlier target {} - prices must decrease towards close",
+                    price, seconds, previous));
+            }
+            previous = price;
+        }
+    }
+}
+
+/// Dotted paths of the leaves that differ between two JSON values (objects are compared key by key,
+/// anything else - arrays included - as a whole)
+fn diff_paths(old: &serde_json::Value, new: &serde_json::Value, path: String, out: &mut Vec<String>) {
+    match (old, new) {
+        (serde_json::Value::Object(old), serde_json::Value::Object(new)) => {
+            let keys: std::collections::BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                diff_paths(old.get(key).unwrap_or(&serde_json::Value::Null), new.get(key).unwrap_or(&serde_json::Value::Null), child, out);
+            }
+        }
+        _ if old != new => out.push(path),
+        _ => {}
+    }
+}
+
+fn default_sell_order_tif() -> MarketOrderTif {
+    MarketOrderTif::Fak
+}
+
+/// Time-in-force for market orders (GTC/GTD only apply to resting limit orders)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum MarketOrderTif {
+    /// Fill-or-kill: the full amount fills immediately or the order is rejected
+    #[default]
+    Fok,
+    /// Fill-and-kill: fills what it can immediately, the rest is cancelled
+    Fak,
+}
+
+impl MarketOrderTif {
+    /// Order type string expected by `PolymarketApi::place_market_order`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketOrderTif::Fok => "FOK",
+            MarketOrderTif::Fak => "FAK",
+        }
+    }
+}
+
+/// Where the detection signal price comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    /// Top of the CLOB book
+    #[default]
+    ClobTop,
+    /// Gamma API midpoint
+    GammaMidpoint,
+    /// CLOB when the spread is tight, gamma midpoint otherwise
+    Blend,
+}
+
+/// Price transport of the market monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transport {
+    /// CLOB market WebSocket channel only
+    #[serde(rename = "websocket")]
+    WebSocket,
+    /// REST polling
+    Poll,
+    /// Market channel while it streams, REST polling while it is stale (the channel keeps reconnecting)
+    #[default]
+    #[serde(rename = "websocket_with_poll_fallback")]
+    WebSocketWithPollFallback,
+}
+
+/// Outcome side of an Up/Down market
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[serde(alias = "Up", alias = "UP")]
+    Up,
+    #[serde(alias = "Down", alias = "DOWN")]
+    Down,
+}
+
+/// How order sizes are rounded to the asset's size_decimals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round down - never sells more than held or buys more than intended
+    #[default]
+    Floor,
+    /// Round to nearest, half away from zero
+    Round,
+    /// Round up
+    Ceil,
+}
+
+impl RoundingMode {
+    pub fn strategy(self) -> rust_decimal::RoundingStrategy {
+        match self {
+            RoundingMode::Floor => rust_decimal::RoundingStrategy::ToNegativeInfinity,
+            RoundingMode::Round => rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::Ceil => rust_decimal::RoundingStrategy::ToPositiveInfinity,
+        }
+    }
+}
+
+/// What to do after a confirmed buy when the token doesn't match the intended market/outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenVerification {
+    /// No post-buy check
+    #[default]
+    Off,
+    /// Log a TOKEN MISMATCH error and keep the position
+    Warn,
+    /// Log a TOKEN MISMATCH error and sell the position at market
+    Flatten,
+}
+
+/// How the bot places entry (buy) orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryOrderType {
+    /// Fill-or-kill market order (guaranteed-ish fill at a worse price)
+    #[default]
+    MarketFok,
+    /// Resting limit order at the detected price (better price, may not fill)
+    Limit,
+    /// Resting limit order at the best bid, converted to a market order after trading.maker_timeout_secs
+    Maker,
 }
 
 impl Default for Config {
@@ -161,6 +1336,9 @@ impl Default for Config {
                 private_key: None,
                 proxy_wallet_address: None,
                 signature_type: None,
+                wallets: Vec::new(), // Primary wallet only
+                wallet_rotation: WalletRotation::RoundRobin, // Wallets take turns
+                primary_wallet_weight: None, // Weight 1
             },
             trading: TradingConfig {
                 eth_condition_id: None,
@@ -168,12 +1346,19 @@ impl Default for Config {
                 solana_condition_id: None,
                 xrp_condition_id: None,
                 check_interval_ms: 1000,
+                period_seconds: None, // 15-minute markets
+                slug_template: None, // "{prefix}-updown-{period}m-{timestamp}"
                 fixed_trade_amount: 1.0, // $1.00
+                compound: false, // Fixed size per buy
+                base_trade_amount: None, // fixed_trade_amount
+                compound_fraction: None, // 1.0
+                max_trade_amount: None, // No cap
                 trigger_price: 0.9, // $0.90 trigger price
                 min_elapsed_minutes: 10, // 10 minutes must have elapsed
                 sell_price: 0.99, // Sell at $0.99
                 max_buy_price: Some(0.95), // Maximum price to buy at ($0.95)
                 stop_loss_price: Some(0.85), // Stop-loss at $0.85 (sell if price drops below this)
+                stop_loss: None, // Fixed stop at stop_loss_price
                 hedge_price: Some(0.5), // Hedge price at $0.5 (limit buy for opposite token)
                 market_closure_check_interval_seconds: 10, // 10 seconds - faster redemption retries
                 min_time_remaining_seconds: Some(30), // 30 seconds - don't buy if less time remains
@@ -184,10 +1369,178 @@ impl Default for Config {
                 dual_limit_shares: None,
                 dual_limit_hedge_after_minutes: Some(10),
                 dual_limit_hedge_price: Some(0.85),
+                hedge_sell_ladder: None, // Full position at $0.93 and at $0.98
                 dual_limit_early_hedge_minutes: Some(5),
                 dual_limit_trend_strength_threshold: Some(0.3),
                 dual_limit_trend_price_buffer: Some(0.05),
                 dual_limit_trend_history_size: Some(60),
+                entry_order_type: EntryOrderType::MarketFok, // Market FOK entries by default
+                maker_timeout_secs: None, // Maker buys go to market after 10 seconds
+                maker_price_improvement: None, // Maker buys join the best bid
+                price_source: PriceSource::ClobTop, // Signal from the CLOB book by default
+                price_source_max_spread: Some(0.05), // $0.05 max spread before blending to gamma midpoint
+                price_transport: Transport::WebSocketWithPollFallback, // Market channel, REST while it is stale
+                price_transport_stale_ms: None, // 5 seconds
+                max_periods: None, // Run indefinitely
+                min_seconds_between_buys: None, // No global buy throttle
+                synthetic_volatility: Some(0.01), // $0.01 random-walk step
+                synthetic_half_spread: Some(0.01), // $0.02 synthetic spread
+                synthetic_paths: None, // Random walk for every synthetic market
+                opposite_max_price: None, // Opposite-side filter disabled
+                opposite_price_lookup_timeout_ms: None, // Use snapshot opposite price
+                buy_order_tif: MarketOrderTif::Fok, // Buys fill completely or not at all
+                sell_order_tif: MarketOrderTif::Fak, // Sells accept partial fills
+                warmup_seconds: None, // No startup warmup
+                min_book_imbalance: None, // Book imbalance filter disabled
+                min_expected_edge: None, // Expected edge gate disabled
+                max_entry_slippage: None, // Buy the full amount regardless of depth
+                require_two_sided_book: false, // One-sided books allowed
+                max_spread: None, // Spread filter disabled
+                directions: std::collections::HashMap::new(), // Up and Down for every asset
+                max_price_age_ms: None, // No staleness limit
+                emergency_exit_seconds_before_close: None, // Emergency exit disabled
+                emergency_exit_min_price: None, // Emergency exit disabled
+                max_hold_secs: None, // No age limit
+                asymmetric_exit: false, // Winners and losers exit the same way
+                winner_confidence_price: None, // 0.95 when asymmetric_exit is on
+                max_total_notional: None, // No global exposure cap
+                trade_amount_by_asset: None, // fixed_trade_amount for every asset
+                daily_loss_limit: None, // No daily loss limit
+                sell_price_schedule: None, // Fixed sell_price until close
+                scale_out_schedule: None, // No partial exits
+                max_consecutive_losses: None, // No loss-streak cooldown
+                loss_streak_cooldown_secs: None, // One period when max_consecutive_losses is set
+                post_win_cooldown_secs: 0, // Re-enter right after a win
+                post_loss_cooldown_secs: 0, // Re-enter right after a loss
+                trigger_confirmations: None, // Buy on the first snapshot at the trigger
+                resolution_poll_interval_ms: None, // Closure checks on market_closure_check_interval_seconds only
+                size_decimals: None, // 2 decimals for every asset
+                size_rounding: RoundingMode::Floor, // Never round a size up past what is held
+                verify_purchased_token: TokenVerification::Off, // No post-buy token check
+                lost_order_lookup_secs: None, // Look for a lost limit order for 5 seconds
+                starting_balance: None, // Snapshot the USDC balance at startup
+                strategy_name: None, // Momentum
+                portfolio_sync_concurrency: None, // 4 balance checks at a time
+                price_fetch_concurrency: None, // 8 token fetches at a time
+                self_cross_guard: None, // Guard opposite-token hedge orders
+                verify_resting: false, // Trust the placement response
+                verify_resting_delay_ms: None, // Check 1 second after placing
+                verify_resting_retries: None, // Re-place up to 2 times
+            },
+            debug: DebugConfig {
+                dump_orderbook_on_trade: false, // No orderbook dumps
+                orderbook_dump_levels: Some(10), // Top 10 levels per side when enabled
+            },
+            discovery: DiscoveryConfig {
+                strict: false, // Fall back to dummy markets when discovery fails
+                verify_asset: None, // Check question/slug against the asset
+                rotation_retry_secs: None, // Retry new-period discovery for 60 seconds
+                require_book: false, // Accept markets without checking the book
+                empty_book_grace_secs: None, // Empty books block a market for its first 60 seconds
+            },
+            schedule: ScheduleConfig {
+                windows: Vec::new(), // Trade at any time
+            },
+            network: NetworkConfig {
+                http_proxy: None, // Direct connections
+                ws_proxy: None, // Same as http_proxy
+                rpc_url: None, // https://polygon-rpc.com
+                request_timeout_secs: None, // 10 seconds
+                retry: RetryConfig {
+                    max_attempts: None, // 3 attempts
+                    base_delay_ms: None, // 200ms, doubling
+                    max_delay_ms: None, // 5 seconds
+                    breaker_failures: None, // 5 failures in a row
+                    breaker_open_secs: None, // 30 seconds
+                },
+            },
+            chain: ChainConfig {
+                chain_id: None, // Polygon mainnet
+                user_ws_url: None, // Polymarket's user channel (mainnet only)
+                market_ws_url: None, // Polymarket's market channel (mainnet only)
+                relayer_url: None, // Polymarket's relayer (mainnet only)
+                gas: GasConfig {
+                    strategy: GasStrategy::Estimate, // Node's EIP-1559 estimate
+                    max_fee_gwei: None, // No fee cap
+                    priority_fee_gwei: None, // No tip cap
+                    multiplier: None, // 1.5x (multiplier strategy only)
+                    confirmations: None, // 1 block
+                    stuck_timeout_secs: None, // Re-submit after 90 seconds
+                    bump_percent: None, // +25% per re-submission
+                    max_resubmits: None, // 3 re-submissions
+                },
+            },
+            balance_confirm: BalanceConfirmConfig {
+                max_attempts: None, // 5 balance checks
+                delay_ms: None, // 1 second apart
+            },
+            buy: BuyConfig {
+                stagger_ms: None, // Back-to-back buys
+            },
+            sell: SellConfig {
+                max_retries: None, // 20 sell attempts
+                retry_delay_ms: None, // 1.5 seconds apart
+                peg_to_book: false, // Limit sells rest at their target price
+                repeg_threshold: None, // Re-peg when 2 cents off
+                peg_min_price: None, // Purchase price
+                no_bid_policy: NoBidPolicy::WaitForClose, // Hold until closure when nobody bids
+                lowball_price: None,
+            },
+            resolution: ResolutionConfig {
+                early_redeem_on_resolved: false, // Wait for closed + winner
+            },
+            redemption: RedemptionConfig {
+                min_value_to_redeem: None, // Redeem every resolved position
+            },
+            sim: SimConfig {
+                starting_bankroll: None, // Unlimited simulated cash
+                fill_model: SimFillModel {
+                    slippage_bps: None, // Fill at the touch
+                    book_share: None, // Fill in full regardless of depth
+                    taker_fee_bps: None, // No taker fee
+                    maker_fee_bps: None, // No maker fee
+                },
+            },
+            startup: StartupConfig {
+                flatten_stale_positions: false, // Leave leftovers to the normal closure checks
+                sell_unresolved: false, // Redeem leftovers rather than selling them
+                adopt_untracked: false, // Only log untracked positions
+                lookback_periods: None, // Last 8 periods (2 hours)
+            },
+            metrics: MetricsConfig {
+                listen_addr: None, // No metrics endpoint
+            },
+            dashboard: DashboardConfig {
+                listen_addr: None, // No dashboard
+            },
+            control: ControlConfig {
+                socket_path: None, // No control socket
+            },
+            notifications: NotificationsConfig {
+                telegram_bot_token: None, // No Telegram messages
+                telegram_chat_id: None,
+                discord_webhook_url: None, // No Discord messages
+                events: NotificationEvents::default(), // Every event type
+                max_per_minute: None, // 20 messages a minute
+            },
+            logging: LoggingConfig {
+                level: None, // info
+                modules: std::collections::HashMap::new(), // Every module at `level`
+                log_file: None, // history.toml
+                sample_monitor_every_n: None, // Every snapshot
+            },
+            strategy: StrategyConfig {
+                mean_reversion: MeanReversionConfig {
+                    oversold_price: None, // 0.20
+                    min_price: None, // 0.05
+                    sell_price: None, // 0.35
+                    min_elapsed_minutes: None, // 10 minutes
+                    min_time_remaining_seconds: None, // 60 seconds
+                },
+                two_sided_entry: TwoSidedEntryConfig {
+                    max_combined_price: None, // 0.97
+                    min_time_remaining_seconds: None, // 60 seconds
+                },
             },
         }
     }
@@ -196,8 +1549,7 @@ impl Default for Config {
 impl Config {
     pub fn load(path: &PathBuf) -> anyhow::Result<Self> {
         if path.exists() {
-            let content = std::fs::read_to_string(path)?;
-            Ok(serde_json::from_str(&content)?)
+            Self::read(path)
         } else {
             let config = Config::default();
             let content = serde_json::to_string_pretty(&config)?;
@@ -205,5 +1557,515 @@ impl Config {
             Ok(config)
         }
     }
+
+    /// Parse an existing config file; unlike `load`, a missing file is an error rather than a new default config
+    pub fn read(path: &PathBuf) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("cannot read {}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Settings that differ in `other`, by dotted path. Secrets are compared as they are but shown redacted
+    pub fn diff(&self, other: &Config) -> anyhow::Result<Vec<ConfigChange>> {
+        let mut paths = Vec::new();
+        diff_paths(&serde_json::to_value(self)?, &serde_json::to_value(other)?, String::new(), &mut paths);
+        let (old, new) = (serde_json::to_value(self.redacted())?, serde_json::to_value(other.redacted())?);
+        Ok(paths.into_iter().map(|path| {
+            let pointer = format!("/{}", path.replace('.', "/"));
+            let show = |value: &serde_json::Value| value.pointer(&pointer)
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "(unset)".to_string());
+            ConfigChange { old: show(&old), new: show(&new), path }
+        }).collect())
+    }
+
+    /// Config reload: the config to keep running with - `new`'s hot-swappable trading settings, everything else
+    /// from `self` - plus the changes applied and the changes that need a restart to take effect
+    pub fn reload(&self, new: &Config) -> anyhow::Result<(Config, Vec<ConfigChange>, Vec<ConfigChange>)> {
+        let (applied, restart_required) = self.diff(new)?.into_iter().partition(ConfigChange::is_hot_swappable);
+        let running = serde_json::to_value(&self.trading)?;
+        let mut trading = serde_json::to_value(&new.trading)?;
+        for setting in RESTART_ONLY_TRADING_SETTINGS {
+            trading[*setting] = running.get(*setting).cloned().unwrap_or(serde_json::Value::Null);
+        }
+        let mut config = self.clone();
+        config.trading = serde_json::from_value(trading)?;
+        Ok((config, applied, restart_required))
+    }
+
+    /// Copy of the config that is safe to print or log: API credentials, the private keys (of every wallet) and the
+    /// notification token/webhook are replaced
+    /// with "***" and any user:password in the proxy/RPC URLs is masked
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        let mask = |secret: &mut Option<String>| {
+            if secret.is_some() {
+                *secret = Some("***".to_string());
+            }
+        };
+        mask(&mut config.polymarket.api_key);
+        mask(&mut config.polymarket.api_secret);
+        mask(&mut config.polymarket.api_passphrase);
+        mask(&mut config.polymarket.private_key);
+        for wallet in &mut config.polymarket.wallets {
+            mask(&mut wallet.api_key);
+            mask(&mut wallet.api_secret);
+            mask(&mut wallet.api_passphrase);
+            mask(&mut wallet.private_key);
+        }
+        mask(&mut config.notifications.telegram_bot_token);
+        mask(&mut config.notifications.discord_webhook_url);
+        let mask_userinfo = |url: &mut Option<String>| {
+            if let Some(u) = url.as_mut() {
+                if let (Some(scheme_end), Some(at)) = (u.find("://"), u.rfind('@')) {
+                    if at > scheme_end {
+                        *u = format!("{}***{}", &u[..scheme_end + 3], &u[at..]);
+                    }
+                }
+            }
+        };
+        mask_userinfo(&mut config.network.http_proxy);
+        mask_userinfo(&mut config.network.ws_proxy);
+        mask_userinfo(&mut config.network.rpc_url);
+        config
+    }
+
+    /// Check numeric bounds and cross-field constraints
+    /// Returns one error listing every problem found, so a bad config fails at startup instead of trading oddly
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let t = &self.trading;
+        let mut problems: Vec<String> = Vec::new();
+        let mut check = |ok: bool, message: String| {
+            if !ok {
+                problems.push(message);
+            }
+        };
+        let is_price = |p: f64| p > 0.0 && p <= 1.0;
+
+        check(t.check_interval_ms > 0, "trading.check_interval_ms must be above 0".to_string());
+        check(t.market_closure_check_interval_seconds > 0,
+            "trading.market_closure_check_interval_seconds must be above 0".to_string());
+        check(t.fixed_trade_amount > 0.0,
+            format!("trading.fixed_trade_amount ({}) must be above 0", t.fixed_trade_amount));
+        check(is_price(t.trigger_price),
+            format!("trading.trigger_price ({}) must be in (0, 1]", t.trigger_price));
+        check(is_price(t.sell_price),
+            format!("trading.sell_price ({}) must be in (0, 1]", t.sell_price));
+        check(t.sell_price > t.trigger_price,
+            format!("trading.sell_price ({}) must be above trading.trigger_price ({}) - otherwise every position is sold at or below its entry",
+                t.sell_price, t.trigger_price));
+        let period = t.period_seconds();
+        check(period >= 60 && period.is_multiple_of(60),
+            format!("trading.period_seconds ({}) must be a whole number of minutes", period));
+        if let Some(template) = &t.slug_template {
+            check(template.contains("{prefix}") && template.contains("{timestamp}"),
+                format!("trading.slug_template (\"{}\") must contain {{prefix}} and {{timestamp}}", template));
+        }
+        check(t.min_elapsed_minutes * 60 < period,
+            format!("trading.min_elapsed_minutes ({}) must be shorter than a {}-minute period", t.min_elapsed_minutes, period / 60));
+        if let Some(max_buy_price) = t.max_buy_price {
+            check(is_price(max_buy_price), format!("trading.max_buy_price ({}) must be in (0, 1]", max_buy_price));
+            check(max_buy_price >= t.trigger_price,
+                format!("trading.max_buy_price ({}) must be at least trading.trigger_price ({}) - no price could trigger a buy",
+                    max_buy_price, t.trigger_price));
+        }
+        if let Some(stop_loss_price) = t.stop_loss_price {
+            check(is_price(stop_loss_price), format!("trading.stop_loss_price ({}) must be in (0, 1]", stop_loss_price));
+            check(stop_loss_price < t.trigger_price,
+                format!("trading.stop_loss_price ({}) must be below trading.trigger_price ({}) - positions would be stopped out right after entry",
+                    stop_loss_price, t.trigger_price));
+        }
+        match t.stop_loss {
+            Some(StopLoss::Fixed { price }) | Some(StopLoss::TimeBased { price, .. }) => {
+                check(is_price(price), format!("trading.stop_loss.price ({}) must be in (0, 1]", price));
+                check(price < t.trigger_price,
+                    format!("trading.stop_loss.price ({}) must be below trading.trigger_price ({}) - positions would be stopped out right after entry",
+                        price, t.trigger_price));
+            }
+            Some(StopLoss::Trailing { percent }) => {
+                check(percent > 0.0 && percent < 100.0, format!("trading.stop_loss.percent ({}) must be in (0, 100)", percent));
+            }
+            None => {}
+        }
+        if let Some(StopLoss::TimeBased { after_minutes, .. }) = t.stop_loss {
+            check(after_minutes * 60 < period,
+                format!("trading.stop_loss.after_minutes ({}) must be shorter than the market period ({} minutes)", after_minutes, period / 60));
+        }
+        if let Some(hedge_price) = t.hedge_price {
+            check(is_price(hedge_price), format!("trading.hedge_price ({}) must be in (0, 1]", hedge_price));
+        }
+        if let Some(min_time_remaining) = t.min_time_remaining_seconds {
+            check(min_time_remaining < period,
+                format!("trading.min_time_remaining_seconds ({}) must be shorter than a {}s period - no buy could ever be placed",
+                    min_time_remaining, period));
+        }
+
+        // Dual limit bots
+        if let Some(price) = t.dual_limit_price {
+            check(is_price(price), format!("trading.dual_limit_price ({}) must be in (0, 1]", price));
+        }
+        if let Some(shares) = t.dual_limit_shares {
+            check(shares > 0.0, format!("trading.dual_limit_shares ({}) must be above 0", shares));
+        }
+        if let Some(price) = t.dual_limit_hedge_price {
+            check(is_price(price), format!("trading.dual_limit_hedge_price ({}) must be in (0, 1]", price));
+        }
+        if let Some(minutes) = t.dual_limit_hedge_after_minutes {
+            check(minutes * 60 < period,
+                format!("trading.dual_limit_hedge_after_minutes ({}) must be shorter than a {}-minute period", minutes, period / 60));
+        }
+        if let Some(minutes) = t.dual_limit_early_hedge_minutes {
+            check(minutes * 60 < period,
+                format!("trading.dual_limit_early_hedge_minutes ({}) must be shorter than a {}-minute period", minutes, period / 60));
+        }
+        if let Some(size) = t.dual_limit_trend_history_size {
+            check(size > 0, "trading.dual_limit_trend_history_size must be above 0".to_string());
+        }
+
+        // Optional filters and limits
+        if let Some(spread) = t.price_source_max_spread {
+            check(spread >= 0.0, format!("trading.price_source_max_spread ({}) must not be negative", spread));
+        }
+        if let Some(max_periods) = t.max_periods {
+            check(max_periods > 0, "trading.max_periods must be above 0 (omit it for no limit)".to_string());
+        }
+        if let Some(price) = t.opposite_max_price {
+            check(is_price(price), format!("trading.opposite_max_price ({}) must be in (0, 1]", price));
+        }
+        if let Some(spread) = t.max_spread {
+            check(spread > 0.0 && spread < 1.0, format!("trading.max_spread ({}) must be in (0, 1)", spread));
+        }
+        if let Some(improvement) = t.maker_price_improvement {
+            check((0.0..0.1).contains(&improvement), format!("trading.maker_price_improvement ({}) must be in [0, 0.1)", improvement));
+        }
+        if let Some(slippage) = t.max_entry_slippage {
+            check(slippage > 0.0 && slippage < 1.0, format!("trading.max_entry_slippage ({}) must be in (0, 1)", slippage));
+        }
+        if let Some(imbalance) = t.min_book_imbalance {
+            check((0.0..=1.0).contains(&imbalance), format!("trading.min_book_imbalance ({}) must be in [0, 1]", imbalance));
+        }
+        if let Some(edge) = t.min_expected_edge {
+            check(edge > -1.0 && edge < 1.0, format!("trading.min_expected_edge ({}) must be between -1 and 1", edge));
+        }
+        if let Some(seconds) = t.emergency_exit_seconds_before_close {
+            check(seconds > 30 && seconds <= period,
+                format!("trading.emergency_exit_seconds_before_close ({}) must be in (30, {}] - the last 30 seconds are handled as closed",
+                    seconds, period));
+        }
+        if let Some(price) = t.emergency_exit_min_price {
+            check((0.0..1.0).contains(&price), format!("trading.emergency_exit_min_price ({}) must be in [0, 1)", price));
+        }
+        if let Some(age) = t.max_price_age_ms {
+            check(age > 0, "trading.max_price_age_ms must be above 0".to_string());
+        }
+        if let Some(stale_ms) = t.price_transport_stale_ms {
+            check(stale_ms > 0, "trading.price_transport_stale_ms must be above 0".to_string());
+        }
+        if let Some(n) = self.logging.sample_monitor_every_n {
+            check(n > 0, "logging.sample_monitor_every_n must be above 0".to_string());
+        }
+        if let Some(Err(e)) = self.logging.level.as_deref().map(parse_level) {
+            check(false, format!("logging.level: {}", e));
+        }
+        for (module, level) in &self.logging.modules {
+            if let Err(e) = parse_level(level) {
+                check(false, format!("logging.modules.{}: {}", module, e));
+            }
+        }
+        if let Some(bankroll) = self.sim.starting_bankroll {
+            check(bankroll > 0.0, "sim.starting_bankroll must be above 0".to_string());
+        }
+        let fill_model = &self.sim.fill_model;
+        for (name, bps) in [
+            ("slippage_bps", fill_model.slippage_bps),
+            ("taker_fee_bps", fill_model.taker_fee_bps),
+            ("maker_fee_bps", fill_model.maker_fee_bps),
+        ] {
+            if let Some(bps) = bps {
+                check((0.0..10000.0).contains(&bps), format!("sim.fill_model.{} ({}) must be in [0, 10000)", name, bps));
+            }
+        }
+        if let Some(share) = fill_model.book_share {
+            check(share > 0.0 && share <= 1.0, format!("sim.fill_model.book_share ({}) must be in (0, 1]", share));
+        }
+        if let Some(seconds) = t.max_hold_secs {
+            check(seconds > 0, "trading.max_hold_secs must be above 0".to_string());
+        }
+        if let Some(price) = t.winner_confidence_price {
+            check(is_price(price), format!("trading.winner_confidence_price ({}) must be between 0 and 1", price));
+        }
+        if let Some(base) = t.base_trade_amount {
+            check(base > 0.0, format!("trading.base_trade_amount ({}) must be above 0", base));
+        }
+        if let Some(fraction) = t.compound_fraction {
+            check((0.0..=1.0).contains(&fraction), format!("trading.compound_fraction ({}) must be in [0, 1]", fraction));
+        }
+        if let Some(max) = t.max_trade_amount {
+            let base = t.base_trade_amount.unwrap_or(t.fixed_trade_amount);
+            check(max >= base,
+                format!("trading.max_trade_amount ({}) must be at least the base trade amount ({})", max, base));
+        }
+        if let Some(notional) = t.max_total_notional {
+            check(notional >= t.fixed_trade_amount,
+                format!("trading.max_total_notional ({}) must be at least trading.fixed_trade_amount ({}) - no buy could ever be placed",
+                    notional, t.fixed_trade_amount));
+        }
+        if let Some(amounts) = &t.trade_amount_by_asset {
+            for (asset, amount) in amounts {
+                check(["btc", "eth", "solana", "xrp"].contains(&asset.as_str()),
+                    format!("trading.trade_amount_by_asset: unknown asset \"{}\" (expected btc, eth, solana or xrp)", asset));
+                check(*amount > 0.0, format!("trading.trade_amount_by_asset.{} ({}) must be above 0", asset, amount));
+                if let Some(notional) = t.max_total_notional {
+                    check(notional >= *amount,
+                        format!("trading.max_total_notional ({}) must be at least trading.trade_amount_by_asset.{} ({}) - no {} buy could ever be placed",
+                            notional, asset, amount, asset));
+                }
+            }
+        }
+        if let Some(limit) = t.daily_loss_limit {
+            check(limit > 0.0, format!("trading.daily_loss_limit ({}) must be above 0 (omit it to disable the limit)", limit));
+        }
+
+        if let Some(max_losses) = t.max_consecutive_losses {
+            check(max_losses > 0, "trading.max_consecutive_losses must be above 0 (omit it to disable the cooldown)".to_string());
+        }
+        if let Some(cooldown) = t.loss_streak_cooldown_secs {
+            check(cooldown > 0, "trading.loss_streak_cooldown_secs must be above 0".to_string());
+        }
+        if let Some(confirmations) = t.trigger_confirmations {
+            check(confirmations > 0, "trading.trigger_confirmations must be above 0".to_string());
+        }
+
+        if let Some(interval) = t.resolution_poll_interval_ms {
+            check(interval > 0, "trading.resolution_poll_interval_ms must be above 0 (omit it to disable)".to_string());
+        }
+
+        if let Some(decimals) = &t.size_decimals {
+            for (asset, places) in decimals {
+                check(["btc", "eth", "solana", "xrp"].contains(&asset.as_str()),
+                    format!("trading.size_decimals: unknown asset \"{}\" (expected btc, eth, solana or xrp)", asset));
+                check(*places <= 6, format!("trading.size_decimals.{} ({}) must be at most 6", asset, places));
+            }
+        }
+
+        if let Some(balance) = t.starting_balance {
+            check(balance >= 0.0, format!("trading.starting_balance ({}) must not be negative", balance));
+        }
+
+        if let Some(name) = &t.strategy_name {
+            check(crate::strategy::STRATEGY_NAMES.contains(&name.as_str()),
+                format!("trading.strategy_name (\"{}\") must be one of: {}", name, crate::strategy::STRATEGY_NAMES.join(", ")));
+        }
+
+        if let Some(concurrency) = t.portfolio_sync_concurrency {
+            check(concurrency > 0, "trading.portfolio_sync_concurrency must be above 0".to_string());
+        }
+        for (asset, directions) in &t.directions {
+            check(["btc", "eth", "solana", "xrp"].contains(&asset.as_str()),
+                format!("trading.directions: unknown asset \"{}\" (expected btc, eth, solana or xrp)", asset));
+            check(!directions.is_empty(),
+                format!("trading.directions.{} must list at least one of \"up\" / \"down\" (disable the asset with enable_*_trading instead)", asset));
+        }
+        if let Some(concurrency) = t.price_fetch_concurrency {
+            check(concurrency > 0, "trading.price_fetch_concurrency must be above 0".to_string());
+        }
+
+        // Synthetic price feed
+        if let Some(volatility) = t.synthetic_volatility {
+            check(volatility >= 0.0, format!("trading.synthetic_volatility ({}) must not be negative", volatility));
+        }
+        if let Some(half_spread) = t.synthetic_half_spread {
+            check((0.0..0.5).contains(&half_spread), format!("trading.synthetic_half_spread ({}) must be in [0, 0.5)", half_spread));
+        }
+        if let Some(paths) = &t.synthetic_paths {
+            for (asset, path) in paths {
+                check(!path.is_empty(), format!("trading.synthetic_paths.{} must not be empty", asset));
+                check(path.iter().all(|p| (0.0..=1.0).contains(p)),
+                    format!("trading.synthetic_paths.{} prices must be in [0, 1]", asset));
+            }
+        }
+
+        for (name, url) in [("polymarket.gamma_api_url", &self.polymarket.gamma_api_url), ("polymarket.clob_api_url", &self.polymarket.clob_api_url)] {
+            let valid = reqwest::Url::parse(url)
+                .map(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some())
+                .unwrap_or(false);
+            check(valid, format!("{} (\"{}\") must be an http(s) URL with a host, e.g. \"https://clob.polymarket.com\"", name, url));
+        }
+        let mut labels = std::collections::HashSet::from(["primary"]);
+        for (i, wallet) in self.polymarket.wallets.iter().enumerate() {
+            check(!wallet.label.is_empty(), format!("polymarket.wallets[{}].label must be set", i));
+            check(labels.insert(wallet.label.as_str()), format!("polymarket.wallets[{}].label (\"{}\") is used twice (\"primary\" is the top-level wallet)", i, wallet.label));
+            check(wallet.private_key.is_some(), format!("polymarket.wallets[{}] (\"{}\") needs a private_key", i, wallet.label));
+            check(wallet.weight != Some(0), format!("polymarket.wallets[{}].weight must be above 0", i));
+        }
+        check(self.polymarket.primary_wallet_weight != Some(0), "polymarket.primary_wallet_weight must be above 0".to_string());
+        for (name, proxy) in [("network.http_proxy", &self.network.http_proxy), ("network.ws_proxy", &self.network.ws_proxy)] {
+            if let Some(proxy) = proxy {
+                check(reqwest::Url::parse(proxy).map(|u| u.host_str().is_some()).unwrap_or(false),
+                    format!("{} (\"{}\") must be a URL with a host, e.g. \"http://proxy.example:3128\"", name, proxy));
+            }
+        }
+        if let Some(rpc_url) = &self.network.rpc_url {
+            check(rpc_url.starts_with("http://") || rpc_url.starts_with("https://"),
+                format!("network.rpc_url (\"{}\") must be an http(s) URL", rpc_url));
+        }
+        if let Some(timeout) = self.network.request_timeout_secs {
+            check(timeout > 0, "network.request_timeout_secs must be above 0".to_string());
+        }
+        let retry = &self.network.retry;
+        check(retry.max_attempts != Some(0), "network.retry.max_attempts must be at least 1".to_string());
+        if let (Some(base), Some(max)) = (retry.base_delay_ms, retry.max_delay_ms) {
+            check(base <= max, format!("network.retry.base_delay_ms ({}) must not exceed max_delay_ms ({})", base, max));
+        }
+        check(retry.breaker_open_secs != Some(0), "network.retry.breaker_open_secs must be above 0".to_string());
+        let chain_id = self.chain.chain_id();
+        check(polymarket_client_sdk::contract_config(chain_id, false).is_some(),
+            format!("chain.chain_id {} is not supported (137 = Polygon mainnet, 80002 = Polygon Amoy)", chain_id));
+        if chain_id != polymarket_client_sdk::POLYGON {
+            // The defaults all point at mainnet - mixing them with another chain would fail in confusing ways
+            check(self.network.rpc_url.is_some(),
+                format!("network.rpc_url must be set for chain.chain_id {} (the default RPC is Polygon mainnet)", chain_id));
+            check(!self.polymarket.clob_api_url.contains("clob.polymarket.com"),
+                format!("polymarket.clob_api_url (\"{}\") is the mainnet CLOB - point it at the CLOB for chain.chain_id {}",
+                    self.polymarket.clob_api_url, chain_id));
+        }
+        let gas = &self.chain.gas;
+        if gas.strategy == GasStrategy::Fixed {
+            check(gas.max_fee_gwei.is_some() && gas.priority_fee_gwei.is_some(),
+                "chain.gas: strategy \"fixed\" needs max_fee_gwei and priority_fee_gwei".to_string());
+        }
+        for (name, fee) in [("max_fee_gwei", gas.max_fee_gwei), ("priority_fee_gwei", gas.priority_fee_gwei)] {
+            if let Some(fee) = fee {
+                check(fee > 0.0, format!("chain.gas.{} must be above 0", name));
+            }
+        }
+        if let (Some(max_fee), Some(priority_fee)) = (gas.max_fee_gwei, gas.priority_fee_gwei) {
+            check(priority_fee <= max_fee, format!("chain.gas.priority_fee_gwei ({}) must not exceed max_fee_gwei ({})", priority_fee, max_fee));
+        }
+        if let Some(multiplier) = gas.multiplier {
+            check(multiplier >= 1.0, format!("chain.gas.multiplier ({}) must be at least 1", multiplier));
+        }
+        if let Some(confirmations) = gas.confirmations {
+            check(confirmations > 0, "chain.gas.confirmations must be above 0".to_string());
+        }
+        if let Some(timeout) = gas.stuck_timeout_secs {
+            check(timeout > 0, "chain.gas.stuck_timeout_secs must be above 0".to_string());
+        }
+        if let Some(bump) = gas.bump_percent {
+            check(bump >= 10, format!("chain.gas.bump_percent ({}) must be at least 10 to replace a stuck transaction", bump));
+        }
+        for (name, url) in [("chain.user_ws_url", &self.chain.user_ws_url), ("chain.market_ws_url", &self.chain.market_ws_url),
+            ("chain.relayer_url", &self.chain.relayer_url)] {
+            if let Some(url) = url {
+                check(reqwest::Url::parse(url).map(|u| u.host_str().is_some()).unwrap_or(false),
+                    format!("{} (\"{}\") must be a URL with a host", name, url));
+            }
+        }
+
+        let mr = crate::strategy::MeanReversionParams::from(&self.strategy.mean_reversion);
+        check(mr.min_price < mr.oversold_price && mr.oversold_price < mr.sell_price && mr.sell_price <= 1.0 && mr.min_price >= 0.0,
+            format!("strategy.mean_reversion: need 0 <= min_price ({}) < oversold_price ({}) < sell_price ({}) <= 1",
+                mr.min_price, mr.oversold_price, mr.sell_price));
+        check(mr.min_elapsed_minutes * 60 < period,
+            format!("strategy.mean_reversion.min_elapsed_minutes ({}) must be shorter than a period ({}s)",
+                mr.min_elapsed_minutes, period));
+        let two_sided = crate::strategy::TwoSidedEntryParams::from(&self.strategy.two_sided_entry);
+        check(two_sided.max_combined_price > 0.0 && two_sided.max_combined_price < 1.0,
+            format!("strategy.two_sided_entry.max_combined_price ({}) must be between 0 and 1 - at 1 or more the pair can't profit",
+                two_sided.max_combined_price));
+
+        if let Some(stagger_ms) = self.buy.stagger_ms {
+            check(stagger_ms <= MAX_BUY_STAGGER_MS,
+                format!("buy.stagger_ms ({}) must be at most {} - a longer pause can miss the entry window", stagger_ms, MAX_BUY_STAGGER_MS));
+        }
+        if let Some(retries) = self.sell.max_retries {
+            check(retries > 0, "sell.max_retries must be above 0".to_string());
+        }
+        if let Some(threshold) = self.sell.repeg_threshold {
+            check(threshold > 0.0 && threshold < 1.0, format!("sell.repeg_threshold ({}) must be between 0 and 1", threshold));
+        }
+        if let Some(min_price) = self.sell.peg_min_price {
+            check(is_price(min_price), format!("sell.peg_min_price ({}) must be between 0 and 1", min_price));
+        }
+        match self.sell.lowball_price {
+            Some(price) => check(is_price(price), format!("sell.lowball_price ({}) must be between 0 and 1", price)),
+            None => check(self.sell.no_bid_policy != NoBidPolicy::LowballLimit,
+                "sell.no_bid_policy lowball_limit requires sell.lowball_price".to_string()),
+        }
+        if let Some(min_value) = self.redemption.min_value_to_redeem {
+            check(min_value >= 0.0, format!("redemption.min_value_to_redeem ({}) must not be negative", min_value));
+        }
+        if let Some(attempts) = self.balance_confirm.max_attempts {
+            check(attempts > 0, "balance_confirm.max_attempts must be above 0".to_string());
+        }
+
+        for (i, window) in self.schedule.windows.iter().enumerate() {
+            for (field, value) in [("start", &window.start), ("end", &window.end)] {
+                check(parse_schedule_time(value).is_some(),
+                    format!("schedule.windows[{}].{} (\"{}\") must be a UTC time \"HH:MM\"", i, field, value));
+            }
+            check(window.start != window.end,
+                format!("schedule.windows[{}]: start and end are both {} - the window would be empty", i, window.start));
+            if let Some(days) = &window.days {
+                check(!days.is_empty(), format!("schedule.windows[{}].days must not be empty (omit it for every day)", i));
+            }
+        }
+
+        if let Some(addr) = &self.metrics.listen_addr {
+            check(addr.parse::<std::net::SocketAddr>().is_ok(),
+                format!("metrics.listen_addr (\"{}\") must be an \"ip:port\" address, e.g. \"127.0.0.1:9464\"", addr));
+        }
+        if let Some(addr) = &self.dashboard.listen_addr {
+            check(addr.parse::<std::net::SocketAddr>().is_ok(),
+                format!("dashboard.listen_addr (\"{}\") must be an \"ip:port\" address, e.g. \"127.0.0.1:8080\"", addr));
+            check(self.metrics.listen_addr.as_deref() != Some(addr.as_str()),
+                format!("dashboard.listen_addr and metrics.listen_addr are both \"{}\" - use two ports", addr));
+        }
+        if let Some(path) = &self.control.socket_path {
+            check(!path.trim().is_empty(), "control.socket_path must not be empty".to_string());
+        }
+
+        let n = &self.notifications;
+        check(n.telegram_bot_token.is_some() == n.telegram_chat_id.is_some(),
+            "notifications.telegram_bot_token and notifications.telegram_chat_id must be set together".to_string());
+        if let Some(url) = &n.discord_webhook_url {
+            check(url.starts_with("https://"), "notifications.discord_webhook_url must be an https:// URL".to_string());
+        }
+        if let Some(max) = n.max_per_minute {
+            check(max > 0, "notifications.max_per_minute must be above 0".to_string());
+        }
+
+        if let Some(levels) = self.debug.orderbook_dump_levels {
+            check(levels > 0, "debug.orderbook_dump_levels must be above 0".to_string());
+        }
+
+        t.check_sell_price_schedule(&mut problems);
+        t.check_scale_out_schedule(&mut problems);
+        t.check_hedge_sell_ladder(&mut problems);
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+        anyhow::bail!("Invalid config ({} problem{}):\n  - {}",
+            problems.len(), if problems.len() == 1 { "" } else { "s" }, problems.join("\n  - "))
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overnight_window_belongs_to_the_day_it_starts() {
+        let window = ScheduleWindow {
+            start: "22:00".to_string(),
+            end: "02:00".to_string(),
+            days: Some(vec![chrono::Weekday::Mon]),
+        };
+        // 2026-10-12 is a Monday
+        let at = |d: u32, h: u32| chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2026, 10, d, h, 0, 0).unwrap();
+        assert!(window.contains(at(12, 23)));
+        assert!(window.contains(at(13, 1)));
+        assert!(!window.contains(at(12, 1)));
+    }
+}
@@ -0,0 +1,243 @@
+//! Runtime control of the running bot (`control.socket_path`).
+//!
+//! `serve` accepts connections on a Unix socket and reads one command per line; every command gets one JSON line
+//! back: `{"ok": true, "message": ..., "data": ...}` or `{"ok": false, "message": <error>}`. Commands:
+//! - `pause` / `resume`: hold or allow new entries (open positions keep being managed)
+//! - `set-param <setting> <value>`: change a hot-swappable `trading` setting, e.g. `set-param trigger_price 0.88`
+//! - `list-positions`: the open positions, as `--positions` prints them
+//! - `cancel-order <order_id>`, `force-sell <token_id>`, `force-redeem <condition_id>`
+//!
+//! `ControlRouter` dispatches the commands to the `Trader` and `PriceDetector`. It also owns the running config,
+//! so a `set-param` and a SIGHUP reload (`apply`) always build on each other's changes.
+
+use crate::config::{Config, ConfigChange};
+use crate::detector::PriceDetector;
+use crate::trader::Trader;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Reply to one command, sent back as a JSON line
+#[derive(Debug, Serialize)]
+pub struct Reply {
+    pub ok: bool,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl Reply {
+    fn ok(message: impl Into<String>) -> Self {
+        Self { ok: true, message: message.into(), data: None }
+    }
+}
+
+pub struct ControlRouter {
+    trader: Arc<Trader>,
+    detector: Arc<PriceDetector>,
+    running: tokio::sync::Mutex<Config>, // Config the bot runs with, including applied reloads and set-params
+}
+
+impl ControlRouter {
+    pub fn new(trader: Arc<Trader>, detector: Arc<PriceDetector>, config: Config) -> Self {
+        Self { trader, detector, running: tokio::sync::Mutex::new(config) }
+    }
+
+    /// Swap `new`'s hot-swappable trading settings into the running trader and detector and log what changed
+    /// (`source` names the trigger, e.g. "SIGHUP"). Changes to anything else are logged as needing a restart and
+    /// keep their running value. Returns the applied and the restart-only changes
+    pub async fn apply(&self, new: &Config, source: &str) -> Result<(Vec<ConfigChange>, Vec<ConfigChange>)> {
+        self.apply_to(&mut *self.running.lock().await, new, source)
+    }
+
+    /// `apply` with the running config already locked by the caller
+    fn apply_to(&self, running: &mut Config, new: &Config, source: &str) -> Result<(Vec<ConfigChange>, Vec<ConfigChange>)> {
+        let (config, applied, restart_required) = running.reload(new)?;
+        for change in &applied {
+            crate::log_println!("   ✏️  {}: {} -> {}", change.path, change.old, change.new);
+        }
+        for change in &restart_required {
+            crate::log_println!("   ⚠️  {}: {} -> {} - needs a restart, still running with {}",
+                change.path, change.old, change.new, change.old);
+        }
+        if applied.is_empty() {
+            crate::log_println!("🔁 {}: no setting that can change while running was changed", source);
+            return Ok((applied, restart_required));
+        }
+        self.trader.reload_config(config.trading.clone());
+        self.detector.retune(self.detector.tuning().reloaded(&config.trading));
+        crate::log_println!("🔁 {}: {} setting(s) applied, {} need a restart", source, applied.len(), restart_required.len());
+        crate::log_trading_event(&format!("CONFIG RELOADED | Source: {} | Applied: {} | Restart Required: {}",
+            source,
+            applied.iter().map(|c| format!("{}={}", c.path, c.new)).collect::<Vec<_>>().join(", "),
+            restart_required.iter().map(|c| c.path.as_str()).collect::<Vec<_>>().join(", ")));
+        *running = config;
+        Ok((applied, restart_required))
+    }
+
+    /// Run one command line and reply to it
+    pub async fn handle(&self, line: &str) -> Reply {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("");
+        let args: Vec<&str> = words.collect();
+        let result = match (command, args.as_slice()) {
+            ("pause", []) => {
+                self.trader.set_paused(true);
+                crate::log_println!("⏸️  CONTROL: new entries paused");
+                Ok(Reply::ok("new entries paused"))
+            }
+            ("resume", []) => {
+                self.trader.set_paused(false);
+                crate::log_println!("▶️  CONTROL: new entries resumed");
+                Ok(Reply::ok("new entries resumed"))
+            }
+            ("set-param", [setting, value @ ..]) if !value.is_empty() => self.set_param(setting, &value.join(" ")).await,
+            ("list-positions", []) => {
+                let positions = self.trader.export_positions().await;
+                serde_json::to_value(&positions).map(|data| Reply {
+                    ok: true,
+                    message: format!("{} position(s){}", positions.len(), if self.trader.is_paused() { ", entries paused" } else { "" }),
+                    data: Some(data),
+                }).map_err(Into::into)
+            }
+            ("cancel-order", [order_id]) => self.trader.cancel_order(order_id).await
+                .map(|_| Reply::ok(format!("order {} cancelled", order_id))),
+            ("force-sell", [token_id]) => self.trader.force_sell(token_id).await
+                .map(|units| Reply::ok(format!("sold {:.6} shares of {}", units, token_id))),
+            ("force-redeem", [condition_id]) => self.trader.force_redeem(condition_id).await
+                .map(|trades| Reply::ok(format!("{} trade(s) in {} routed to redemption", trades, condition_id))),
+            _ => Err(anyhow::anyhow!("unknown command \"{}\" - expected pause, resume, set-param <setting> <value>, \
+                list-positions, cancel-order <order_id>, force-sell <token_id> or force-redeem <condition_id>", line.trim())),
+        };
+        result.unwrap_or_else(|e| Reply { ok: false, message: format!("{:#}", e), data: None })
+    }
+
+    /// Set trading setting `setting` to `value` (JSON, or a plain string) if it can change while running
+    /// The running config stays locked throughout, so a SIGHUP reload can't land between reading and applying it
+    async fn set_param(&self, setting: &str, value: &str) -> Result<Reply> {
+        let setting = setting.strip_prefix("trading.").unwrap_or(setting);
+        let mut running = self.running.lock().await;
+        let mut new = serde_json::to_value(&*running)?;
+        let slot = new["trading"].get_mut(setting)
+            .with_context(|| format!("unknown trading setting \"{}\"", setting))?;
+        *slot = serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+        let new: Config = serde_json::from_value(new)
+            .with_context(|| format!("invalid value for trading.{}", setting))?;
+        new.validate()?;
+        if !running.reload(&new)?.2.is_empty() {
+            anyhow::bail!("trading.{} is only read at startup - change it in the config file and restart", setting);
+        }
+        crate::log_println!("🎛️  CONTROL: set-param trading.{} {}", setting, value);
+        let (applied, _) = self.apply_to(&mut running, &new, "Control")?;
+        Ok(match applied.first() {
+            Some(change) => Reply::ok(format!("{}: {} -> {}", change.path, change.old, change.new)),
+            None => Reply::ok(format!("trading.{} unchanged", setting)),
+        })
+    }
+}
+
+/// Accept commands on the Unix socket `socket_path` (replacing a stale socket file) until the process exits
+#[cfg(unix)]
+pub async fn serve(socket_path: &str, router: Arc<ControlRouter>) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let listener = bind_owner_only(std::path::Path::new(socket_path))?;
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let router = router.clone();
+                    tokio::spawn(async move {
+                        let (read, mut write) = stream.into_split();
+                        let mut lines = BufReader::new(read).lines();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            if line.trim().is_empty() {
+                                continue;
+                            }
+                            let reply = router.handle(&line).await;
+                            let mut json = serde_json::to_string(&reply).unwrap_or_default();
+                            json.push('\n');
+                            if write.write_all(json.as_bytes()).await.is_err() {
+                                break;
+                            }
+                        }
+                    });
+                }
+                Err(e) => log::warn!("Control socket accept failed: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Bind the socket inside a fresh owner-only directory, restrict it to 0600 there and only then move it to
+/// `socket_path` (replacing a stale socket file), so it is never reachable with the umask's permissions
+#[cfg(unix)]
+fn bind_owner_only(socket_path: &std::path::Path) -> Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let name = socket_path.file_name().and_then(|name| name.to_str()).unwrap_or("control.sock");
+    let staging = socket_path.with_file_name(format!(".{}.{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::DirBuilder::new().mode(0o700).create(&staging)
+        .with_context(|| format!("Failed to create {} for the control socket", staging.display()))?;
+    let staged = staging.join(name);
+    let bound = tokio::net::UnixListener::bind(&staged)
+        .with_context(|| format!("Failed to bind control socket {}", socket_path.display()))
+        .and_then(|listener| {
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))
+                .with_context(|| format!("Failed to restrict control socket {}", socket_path.display()))?;
+            let _ = std::fs::remove_file(socket_path);
+            std::fs::rename(&staged, socket_path)
+                .with_context(|| format!("Failed to move control socket to {}", socket_path.display()))?;
+            Ok(listener)
+        });
+    let _ = std::fs::remove_dir_all(&staging);
+    bound
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::api_client::mock::MockApi;
+    use crate::price_feed::SyntheticPriceFeed;
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    async fn ask(stream: &mut BufReader<tokio::net::UnixStream>, command: &str) -> serde_json::Value {
+        stream.get_mut().write_all(format!("{}\n", command).as_bytes()).await.unwrap();
+        let mut reply = String::new();
+        stream.read_line(&mut reply).await.unwrap();
+        serde_json::from_str(&reply).unwrap()
+    }
+
+    #[tokio::test]
+    async fn commands_pause_entries_and_retune_the_detector() {
+        let config = Config::default();
+        let detector = Arc::new(PriceDetector::new(config.trading.trigger_price, 0.95, 10, 30, false, false));
+        let trader = Arc::new(Trader::new_with_price_feed(Arc::new(MockApi::default()), config.trading.clone(),
+            false, Some(detector.clone()), Arc::new(SyntheticPriceFeed::new(1, 0.01))).unwrap());
+        let router = Arc::new(ControlRouter::new(trader.clone(), detector.clone(), config));
+
+        let path = std::env::temp_dir().join(format!("control_test_{}.sock", std::process::id()));
+        serve(path.to_str().unwrap(), router.clone()).await.unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        let mut stream = BufReader::new(tokio::net::UnixStream::connect(&path).await.unwrap());
+        assert_eq!(ask(&mut stream, "pause").await["ok"], true);
+        assert!(trader.is_paused());
+        let reply = ask(&mut stream, "set-param trigger_price 0.88").await;
+        assert_eq!(reply["ok"], true, "{}", reply);
+        assert_eq!(detector.tuning().trigger_price, 0.88);
+        let _ = std::fs::remove_file(&path);
+
+        // Invalid values, restart-only settings and unknown commands are rejected without changing anything
+        assert!(!router.handle("set-param trigger_price 1.5").await.ok);
+        assert!(!router.handle("set-param check_interval_ms 50").await.ok);
+        assert!(!router.handle("set-param no_such_setting 1").await.ok);
+        assert!(!router.handle("sell-everything").await.ok);
+        assert_eq!(detector.tuning().trigger_price, 0.88);
+        assert!(router.handle("list-positions").await.ok);
+        router.handle("resume").await;
+        assert!(!trader.is_paused());
+    }
+}
@@ -0,0 +1,305 @@
+//! Live PnL dashboard (`dashboard.listen_addr`).
+//!
+//! `serve` answers `GET /` with an HTML page (refreshing itself every few seconds) and `GET /state.json` with
+//! the same data as JSON: pending trades, open positions marked at the current SELL price, recent fills, and
+//! realized / unrealized PnL per asset and per period. Positions and trades are read from the `Trader` (the
+//! `SimulationTracker` in simulation) on every request; fills and realized PnL since start are collected in a
+//! process-wide ledger that the trader feeds as buys fill and positions settle. Read-only - nothing on the
+//! dashboard places or cancels orders.
+
+use crate::models::PendingTrade;
+use crate::trader::{PositionRecord, Trader};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+/// Fills kept for the "recent fills" table
+const RECENT_FILLS: usize = 50;
+/// Seconds between automatic reloads of the HTML page
+const REFRESH_SECS: u64 = 5;
+
+static LEDGER: Mutex<Ledger> = Mutex::new(Ledger::new());
+
+/// One buy fill or settlement (sell before resolution, or the resolution itself)
+#[derive(Debug, Clone, Serialize)]
+pub struct FillRecord {
+    pub at: u64,            // Unix seconds
+    pub kind: &'static str, // "BUY", "SELL" or "RESOLVED"
+    pub asset: String,      // "btc", "eth", "solana" or "xrp"
+    pub period: u64,
+    pub units: f64,
+    pub price: f64,         // Per share; resolution value ($1 / $0) for RESOLVED
+    pub pnl: Option<f64>,   // Settlements only: proceeds minus cost
+}
+
+struct Ledger {
+    fills: VecDeque<FillRecord>,          // Newest last, at most RECENT_FILLS
+    realized: BTreeMap<(u64, String), f64>, // Key: (period, asset)
+}
+
+impl Ledger {
+    const fn new() -> Self {
+        Self { fills: VecDeque::new(), realized: BTreeMap::new() }
+    }
+
+    fn push(&mut self, fill: FillRecord) {
+        if self.fills.len() == RECENT_FILLS {
+            self.fills.pop_front();
+        }
+        self.fills.push_back(fill);
+    }
+}
+
+fn now_unix() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Record a buy fill of `units` shares at `price`
+pub fn record_buy(asset: &str, period: u64, units: f64, price: f64) {
+    LEDGER.lock().unwrap().push(FillRecord {
+        at: now_unix(),
+        kind: "BUY",
+        asset: asset.to_string(),
+        period,
+        units,
+        price,
+        pnl: None,
+    });
+}
+
+/// Record a settled part of a position: sold before resolution (`exited`) or resolved
+pub fn record_settlement(asset: &str, period: u64, units: f64, cost: f64, proceeds: f64, exited: bool) {
+    let mut ledger = LEDGER.lock().unwrap();
+    *ledger.realized.entry((period, asset.to_string())).or_default() += proceeds - cost;
+    ledger.push(FillRecord {
+        at: now_unix(),
+        kind: if exited { "SELL" } else { "RESOLVED" },
+        asset: asset.to_string(),
+        period,
+        units,
+        price: if units > 0.0 { proceeds / units } else { 0.0 },
+        pnl: Some(proceeds - cost),
+    });
+}
+
+/// Realized and unrealized PnL of one asset, or of one asset in one period
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PnlRow {
+    pub asset: String,
+    pub period: Option<u64>, // None in the per-asset rollup
+    pub realized: f64,
+    pub unrealized: f64,     // Open positions at the current SELL price minus their cost
+}
+
+/// Everything the dashboard shows, as served on `/state.json`
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardState {
+    pub generated_at: u64,
+    pub simulation: bool,
+    pub simulation_cash: Option<f64>, // sim.starting_bankroll minus what's invested (None = unlimited)
+    pub realized_pnl: f64,            // Since start
+    pub unrealized_pnl: f64,
+    pub pnl_by_asset: Vec<PnlRow>,
+    pub pnl_by_period: Vec<PnlRow>,
+    pub positions: Vec<PositionRecord>,
+    pub pending_trades: Vec<PendingTrade>,
+    pub recent_fills: Vec<FillRecord>, // Newest first
+}
+
+impl DashboardState {
+    /// Combine the trader's current positions and trades with the ledger
+    pub fn new(simulation: bool, simulation_cash: Option<f64>, positions: Vec<PositionRecord>, pending_trades: Vec<PendingTrade>) -> Self {
+        let ledger = LEDGER.lock().unwrap();
+        let mut by_period: BTreeMap<(u64, String), PnlRow> = ledger.realized.iter()
+            .map(|((period, asset), realized)| ((*period, asset.clone()), PnlRow {
+                asset: asset.clone(),
+                period: Some(*period),
+                realized: *realized,
+                unrealized: 0.0,
+            }))
+            .collect();
+        for position in &positions {
+            by_period.entry((position.period, position.asset.clone()))
+                .or_insert_with(|| PnlRow { asset: position.asset.clone(), period: Some(position.period), ..Default::default() })
+                .unrealized += position.cash_pnl.unwrap_or(0.0);
+        }
+        let mut by_asset: BTreeMap<String, PnlRow> = BTreeMap::new();
+        for row in by_period.values() {
+            let total = by_asset.entry(row.asset.clone())
+                .or_insert_with(|| PnlRow { asset: row.asset.clone(), ..Default::default() });
+            total.realized += row.realized;
+            total.unrealized += row.unrealized;
+        }
+        Self {
+            generated_at: now_unix(),
+            simulation,
+            simulation_cash,
+            realized_pnl: by_asset.values().map(|row| row.realized).sum(),
+            unrealized_pnl: by_asset.values().map(|row| row.unrealized).sum(),
+            pnl_by_asset: by_asset.into_values().collect(),
+            pnl_by_period: by_period.into_values().rev().collect(), // Latest period first
+            positions,
+            pending_trades,
+            recent_fills: ledger.fills.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn money(value: f64) -> String {
+    format!("<td class=\"{}\">${:.2}</td>", if value < 0.0 { "neg" } else { "pos" }, value)
+}
+
+fn optional(value: Option<f64>, decimals: usize) -> String {
+    value.map_or("-".to_string(), |v| format!("{:.*}", decimals, v))
+}
+
+fn table(out: &mut String, title: &str, headers: &[&str], rows: Vec<String>) {
+    out.push_str(&format!("<h2>{}</h2>", title));
+    if rows.is_empty() {
+        out.push_str("<p class=\"empty\">none</p>");
+        return;
+    }
+    out.push_str("<table><tr>");
+    for header in headers {
+        out.push_str(&format!("<th>{}</th>", header));
+    }
+    out.push_str("</tr>");
+    for row in rows {
+        out.push_str(&format!("<tr>{}</tr>", row));
+    }
+    out.push_str("</table>");
+}
+
+/// The dashboard page for `state`
+pub fn render_html(state: &DashboardState) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><meta http-equiv=\"refresh\" content=\"{}\">\
+         <title>Polymarket bot</title><style>\
+         body{{font-family:monospace;margin:1.5em}}table{{border-collapse:collapse;margin-bottom:1em}}\
+         th,td{{border:1px solid #ccc;padding:2px 8px;text-align:right}}th{{background:#eee}}\
+         .pos{{color:#070}}.neg{{color:#b00}}.empty{{color:#888}}</style></head><body>",
+        REFRESH_SECS
+    );
+    out.push_str(&format!(
+        "<h1>Polymarket bot - {}</h1><p>Realized ${:.2} | Unrealized ${:.2} | Total ${:.2}{} | <a href=\"/state.json\">JSON</a></p>",
+        if state.simulation { "SIMULATION" } else { "PRODUCTION" },
+        state.realized_pnl,
+        state.unrealized_pnl,
+        state.realized_pnl + state.unrealized_pnl,
+        state.simulation_cash.map_or(String::new(), |cash| format!(" | Simulated cash ${:.2}", cash))
+    ));
+
+    let pnl_row = |row: &PnlRow| format!("<td>{}</td>{}{}{}", escape(&row.asset),
+        money(row.realized), money(row.unrealized), money(row.realized + row.unrealized));
+    table(&mut out, "PnL by asset", &["Asset", "Realized", "Unrealized", "Total"],
+        state.pnl_by_asset.iter().map(pnl_row).collect());
+    table(&mut out, "PnL by period", &["Period", "Asset", "Realized", "Unrealized", "Total"],
+        state.pnl_by_period.iter().map(|row| format!("<td>{}</td>{}", row.period.unwrap_or_default(), pnl_row(row))).collect());
+    table(&mut out, "Open positions", &["Period", "Asset", "Outcome", "Shares", "Avg price", "Mark", "Unrealized"],
+        state.positions.iter().map(|p| format!("<td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td><td>{}</td><td>{}</td>{}",
+            p.period, escape(&p.asset), escape(&p.outcome), p.size, optional(p.avg_price, 4), optional(p.cur_price, 4),
+            money(p.cash_pnl.unwrap_or(0.0)))).collect());
+    table(&mut out, "Pending trades", &["Period", "Market", "Shares", "Buy price", "Sell price", "Status"],
+        state.pending_trades.iter().map(|t| format!("<td>{}</td><td>{}</td><td>{:.2}</td><td>{:.4}</td><td>{:.4}</td><td>{}</td>",
+            t.market_timestamp, escape(t.token_type.display_name()), t.units, t.purchase_price, t.sell_price,
+            if t.sold { "sold" } else if t.redemption_abandoned { "abandoned" } else if t.buy_order_confirmed { "open" } else { "buy pending" }))
+            .collect());
+    table(&mut out, "Recent fills", &["Time (UTC)", "Kind", "Period", "Asset", "Shares", "Price", "PnL"],
+        state.recent_fills.iter().map(|f| format!("<td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td><td>{:.4}</td>{}",
+            chrono::DateTime::from_timestamp(f.at as i64, 0).map_or(f.at.to_string(), |t| t.format("%H:%M:%S").to_string()),
+            f.kind, f.period, escape(&f.asset), f.units, f.price, f.pnl.map_or("<td>-</td>".to_string(), money)))
+            .collect());
+    out.push_str("</body></html>");
+    out
+}
+
+/// Bind `listen_addr` (e.g. "127.0.0.1:8080") and serve `trader`'s dashboard in the background
+/// Returns the bound address; paths other than `/` and `/state.json` get 404
+pub async fn serve(listen_addr: &str, trader: Arc<Trader>) -> Result<SocketAddr> {
+    let listener = tokio::net::TcpListener::bind(listen_addr).await
+        .with_context(|| format!("Failed to bind dashboard on {}", listen_addr))?;
+    let local_addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let trader = trader.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, &trader).await {
+                            log::debug!("Dashboard request failed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => log::warn!("Dashboard accept failed: {}", e),
+            }
+        }
+    });
+    Ok(local_addr)
+}
+
+async fn handle_connection(mut stream: tokio::net::TcpStream, trader: &Trader) -> Result<()> {
+    let (method, path) = crate::metrics::read_request(&mut stream).await?;
+    match (method.as_str(), path.as_str()) {
+        ("GET", "/") => {
+            let page = render_html(&trader.dashboard_state().await);
+            crate::metrics::respond(&mut stream, "200 OK", "text/html; charset=utf-8", &page).await
+        }
+        ("GET", "/state.json") => {
+            let json = serde_json::to_string(&trader.dashboard_state().await)?;
+            crate::metrics::respond(&mut stream, "200 OK", "application/json", &json).await
+        }
+        _ => crate::metrics::respond(&mut stream, "404 Not Found", "text/plain", "not found\n").await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_client::mock::MockApi;
+    use crate::price_feed::SyntheticPriceFeed;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn dashboard_serves_fills_and_pnl_per_asset_and_period() {
+        record_buy("dummy_dashboard_asset", 1_700_000_100, 10.0, 0.60);
+        record_settlement("dummy_dashboard_asset", 1_700_000_100, 4.0, 2.40, 3.60, true);
+        record_settlement("dummy_dashboard_asset", 1_700_001_000, 6.0, 3.60, 0.0, false);
+        let trader = Trader::new_with_price_feed(Arc::new(MockApi::default()), crate::config::Config::default().trading,
+            false, None, Arc::new(SyntheticPriceFeed::new(1, 0.01))).unwrap();
+
+        let addr = serve("127.0.0.1:0", Arc::new(trader)).await.unwrap();
+        let response = get(addr, "/state.json").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        let state: serde_json::Value = serde_json::from_str(response.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        let asset = state["pnl_by_asset"].as_array().unwrap().iter()
+            .find(|row| row["asset"] == "dummy_dashboard_asset").unwrap();
+        assert!((asset["realized"].as_f64().unwrap() - (1.20 - 3.60)).abs() < 1e-9);
+        let periods: Vec<_> = state["pnl_by_period"].as_array().unwrap().iter()
+            .filter(|row| row["asset"] == "dummy_dashboard_asset")
+            .map(|row| row["period"].as_u64().unwrap())
+            .collect();
+        assert_eq!(periods, vec![1_700_001_000, 1_700_000_100]);
+        assert!(state["recent_fills"].as_array().unwrap().iter().any(|f| f["kind"] == "RESOLVED"));
+
+        let page = get(addr, "/").await;
+        assert!(page.contains("text/html") && page.contains("dummy_dashboard_asset"));
+        assert!(get(addr, "/metrics").await.starts_with("HTTP/1.1 404"));
+    }
+}
@@ -1,10 +1,19 @@
+use crate::api::PolymarketApi;
 use crate::monitor::MarketSnapshot;
+use crate::config::{Direction, TradingConfig};
 use rust_decimal::Decimal;
 use std::sync::Arc;
 use std::collections::HashMap;
 use tokio::sync::Mutex;
 use log::debug;
 
+/// How far towards certainty the win estimate moves by the end of the period (0 = trust the price as is)
+const TIME_CONFIDENCE: f64 = 0.5;
+/// Highest price the detector buys at when trading.max_buy_price is not set
+pub const DEFAULT_MAX_BUY_PRICE: f64 = 0.95;
+/// Seconds that must remain in the period to buy when trading.min_time_remaining_seconds is not set
+pub const DEFAULT_MIN_TIME_REMAINING_SECS: u64 = 30;
+
 /// Reset state for a token type after a buy-sell cycle
 #[derive(Debug, Clone, PartialEq)]
 enum ResetState {
@@ -21,10 +30,8 @@ enum ResetState {
 /// Reset mechanism: After a successful buy-sell cycle, require price to drop below trigger_price
 /// before allowing another buy. This prevents buying immediately after selling when price only dips slightly.
 pub struct PriceDetector {
-    trigger_price: f64, // Minimum price threshold to trigger buy (e.g., 0.9)
-    max_buy_price: f64, // Maximum price to buy at (e.g., 0.95) - don't buy if price > this
-    min_elapsed_minutes: u64, // Minimum minutes that must have elapsed (e.g., 10 minutes)
-    min_time_remaining_seconds: u64, // Minimum seconds that must remain (e.g., 30 seconds) - don't buy if less time remains
+    // Entry thresholds and filters - swapped as a whole on a config reload (retune)
+    tuning: std::sync::RwLock<Arc<DetectorTuning>>,
     enable_eth_trading: bool, // Whether ETH trading is enabled
     enable_solana_trading: bool, // Whether Solana trading is enabled
     // Track which tokens we've bought in this period (key: token_id)
@@ -34,6 +41,63 @@ pub struct PriceDetector {
     // Track reset state per token type after successful buy-sell cycles
     // Key: TokenType, Value: ResetState
     reset_states: Arc<Mutex<HashMap<TokenType, ResetState>>>,
+    // Optional live re-fetch of the opposite price: (API, timeout). None = use the snapshot price
+    opposite_price_lookup: Option<(Arc<PolymarketApi>, std::time::Duration)>,
+    // Last settlement per token type: (when, whether it was a win)
+    last_settlements: Arc<Mutex<HashMap<TokenType, (std::time::Instant, bool)>>>,
+    // Consecutive trigger hits per token_id: (period, count)
+    trigger_hits: Arc<Mutex<HashMap<String, (u64, u32)>>>,
+}
+
+/// The detector's entry thresholds and filters, i.e. everything a config reload can change while running
+#[derive(Debug, Clone)]
+pub struct DetectorTuning {
+    pub trigger_price: f64, // Minimum price threshold to trigger buy (e.g., 0.9)
+    pub max_buy_price: f64, // Maximum price to buy at (e.g., 0.95) - don't buy if price > this
+    pub min_elapsed_minutes: u64, // Minimum minutes that must have elapsed (e.g., 10 minutes)
+    pub min_time_remaining_seconds: u64, // Minimum seconds that must remain (e.g., 30 seconds) - don't buy if less time remains
+    // Skip entries when the opposite token is already above this price (None = filter disabled)
+    pub opposite_max_price: Option<f64>,
+    // Minimum top-of-book imbalance bid_size / (bid_size + ask_size) to enter (None = filter disabled)
+    pub min_book_imbalance: Option<f64>,
+    // Only enter when both this token and its opposite have a bid and an ask (uncrossed)
+    pub require_two_sided_book: bool,
+    // Widest ask - bid to enter on (None = filter disabled)
+    pub max_spread: Option<f64>,
+    // Quotes older than this are not acted on (None = no limit)
+    pub max_price_age: Option<std::time::Duration>,
+    // Allowed outcomes per asset key (missing asset = both directions)
+    pub directions: HashMap<String, Vec<Direction>>,
+    // Entry pause per asset after a winning / losing settlement (zero = off)
+    pub post_win_cooldown: std::time::Duration,
+    pub post_loss_cooldown: std::time::Duration,
+    // Minimum expected edge per share to enter (None = gate disabled)
+    pub min_expected_edge: Option<f64>,
+    // Consecutive snapshots at/above the trigger required before buying (1 = no debounce)
+    pub trigger_confirmations: u32,
+}
+
+impl DetectorTuning {
+    /// Thresholds for reloaded trading settings; filters only wired at startup (min_book_imbalance needs book
+    /// sizes the monitor may not fetch) keep their running value
+    pub fn reloaded(&self, trading: &TradingConfig) -> Self {
+        Self {
+            trigger_price: trading.trigger_price,
+            max_buy_price: trading.max_buy_price.unwrap_or(DEFAULT_MAX_BUY_PRICE),
+            min_elapsed_minutes: trading.min_elapsed_minutes,
+            min_time_remaining_seconds: trading.min_time_remaining_seconds.unwrap_or(DEFAULT_MIN_TIME_REMAINING_SECS),
+            opposite_max_price: trading.opposite_max_price,
+            min_book_imbalance: self.min_book_imbalance,
+            require_two_sided_book: trading.require_two_sided_book,
+            max_spread: trading.max_spread,
+            max_price_age: trading.max_price_age_ms.map(std::time::Duration::from_millis),
+            directions: trading.directions.clone(),
+            post_win_cooldown: std::time::Duration::from_secs(trading.post_win_cooldown_secs),
+            post_loss_cooldown: std::time::Duration::from_secs(trading.post_loss_cooldown_secs),
+            min_expected_edge: trading.min_expected_edge,
+            trigger_confirmations: trading.trigger_confirmations.unwrap_or(1).max(1),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -47,12 +111,13 @@ pub struct BuyOpportunity {
     pub time_elapsed_seconds: u64, // How many seconds have elapsed in this period
     pub use_market_order: bool, // If true, use market order; if false, use limit order
     pub investment_amount_override: Option<f64>, // Optional override for investment amount (e.g., for individual hedges that need double amount)
+    pub sell_price_override: Option<f64>, // Optional sell target instead of trading.sell_price (e.g., mean-reversion exits)
     pub is_individual_hedge: bool, // If true, this is an individual hedge that should place a limit sell order after buy
-    pub is_standard_hedge: bool, // If true, this is a standard hedge (after dual_limit_hedge_after_minutes) that should place a limit sell order at $0.98
+    pub is_standard_hedge: bool, // If true, this is a standard hedge (after dual_limit_hedge_after_minutes) that should place its hedge_sell_ladder limit sells
     pub dual_limit_shares: Option<f64>, // Optional dual_limit_shares value for placing sell orders
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum TokenType {
     BtcUp,
     BtcDown,
@@ -78,6 +143,36 @@ impl TokenType {
         }
     }
     
+    /// Lowercase asset key used by per-asset config maps ("btc", "eth", "solana", "xrp")
+    pub fn asset_key(&self) -> &'static str {
+        match self {
+            TokenType::BtcUp | TokenType::BtcDown => "btc",
+            TokenType::EthUp | TokenType::EthDown => "eth",
+            TokenType::SolanaUp | TokenType::SolanaDown => "solana",
+            TokenType::XrpUp | TokenType::XrpDown => "xrp",
+        }
+    }
+    
+    /// Token type for an asset key ("btc", "eth", "solana", "xrp") and outcome
+    pub fn from_asset(asset_key: &str, is_up: bool) -> Option<TokenType> {
+        Some(match (asset_key, is_up) {
+            ("btc", true) => TokenType::BtcUp,
+            ("btc", false) => TokenType::BtcDown,
+            ("eth", true) => TokenType::EthUp,
+            ("eth", false) => TokenType::EthDown,
+            ("solana", true) => TokenType::SolanaUp,
+            ("solana", false) => TokenType::SolanaDown,
+            ("xrp", true) => TokenType::XrpUp,
+            ("xrp", false) => TokenType::XrpDown,
+            _ => return None,
+        })
+    }
+
+    /// Whether this is the Up outcome of its market
+    pub fn is_up(&self) -> bool {
+        matches!(self, TokenType::BtcUp | TokenType::EthUp | TokenType::SolanaUp | TokenType::XrpUp)
+    }
+
     /// Get the opposite token type (Up <-> Down)
     pub fn opposite(&self) -> TokenType {
         match self {
@@ -96,16 +191,209 @@ impl TokenType {
 impl PriceDetector {
     pub fn new(trigger_price: f64, max_buy_price: f64, min_elapsed_minutes: u64, min_time_remaining_seconds: u64, enable_eth_trading: bool, enable_solana_trading: bool) -> Self {
         Self {
-            trigger_price,
-            max_buy_price,
-            min_elapsed_minutes,
-            min_time_remaining_seconds,
+            tuning: std::sync::RwLock::new(Arc::new(DetectorTuning {
+                trigger_price,
+                max_buy_price,
+                min_elapsed_minutes,
+                min_time_remaining_seconds,
+                opposite_max_price: None,
+                min_book_imbalance: None,
+                require_two_sided_book: false,
+                max_spread: None,
+                max_price_age: None,
+                directions: HashMap::new(),
+                post_win_cooldown: std::time::Duration::ZERO,
+                post_loss_cooldown: std::time::Duration::ZERO,
+                min_expected_edge: None,
+                trigger_confirmations: 1,
+            })),
             enable_eth_trading,
             enable_solana_trading,
             current_period_bought: Arc::new(Mutex::new(std::collections::HashSet::new())),
             last_logged_period: Arc::new(tokio::sync::Mutex::new(None)),
             reset_states: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            opposite_price_lookup: None,
+            last_settlements: Arc::new(Mutex::new(HashMap::new())),
+            trigger_hits: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Entry thresholds in effect right now; one snapshot per evaluation keeps a check consistent across a reload
+    pub fn tuning(&self) -> Arc<DetectorTuning> {
+        self.tuning.read().unwrap().clone()
+    }
+
+    /// Replace the entry thresholds of the running detector (config reload)
+    pub fn retune(&self, tuning: DetectorTuning) {
+        *self.tuning.write().unwrap() = Arc::new(tuning);
+    }
+
+    fn tuning_mut(&mut self) -> &mut DetectorTuning {
+        Arc::make_mut(self.tuning.get_mut().unwrap())
+    }
+
+    /// Only enter when `expected_edge` of the estimated win probability over the entry price is at least
+    /// `min_edge` (trading.min_expected_edge)
+    pub fn with_min_expected_edge(mut self, min_edge: Option<f64>) -> Self {
+        self.tuning_mut().min_expected_edge = min_edge;
+        self
+    }
+
+    /// Require the price at/above trigger_price on `confirmations` consecutive snapshots of a token before
+    /// buying it (trading.trigger_confirmations); None or 1 buys on the first one
+    pub fn with_trigger_confirmations(mut self, confirmations: Option<u32>) -> Self {
+        self.tuning_mut().trigger_confirmations = confirmations.unwrap_or(1).max(1);
+        self
+    }
+
+    /// Count this snapshot of `token_id` towards trigger_confirmations: a hit extends the streak (a streak from
+    /// an earlier period starts over), a miss resets it. Returns the streak length
+    async fn record_trigger_hit(&self, token_id: &str, period_timestamp: u64, hit: bool) -> u32 {
+        let tuning = self.tuning();
+        if tuning.trigger_confirmations <= 1 {
+            return u32::from(hit);
+        }
+        let mut hits = self.trigger_hits.lock().await;
+        if !hit {
+            hits.remove(token_id);
+            return 0;
+        }
+        let streak = hits.entry(token_id.to_string()).or_insert((period_timestamp, 0));
+        if streak.0 != period_timestamp {
+            *streak = (period_timestamp, 0);
+        }
+        streak.1 += 1;
+        streak.1
+    }
+
+    /// Pause entries on an asset for `win_secs` / `loss_secs` after one of its positions settles with a
+    /// profit / loss (trading.post_win_cooldown_secs / post_loss_cooldown_secs); 0 disables either
+    pub fn with_settlement_cooldowns(mut self, win_secs: u64, loss_secs: u64) -> Self {
+        self.tuning_mut().post_win_cooldown = std::time::Duration::from_secs(win_secs);
+        self.tuning_mut().post_loss_cooldown = std::time::Duration::from_secs(loss_secs);
+        self
+    }
+
+    /// Record a realized settlement (resolution or exit) of a `token_type` position for the settlement cooldowns
+    /// Break-even settlements start neither cooldown
+    pub async fn record_settlement(&self, token_type: TokenType, pnl: f64) {
+        if pnl == 0.0 {
+            return;
         }
+        self.last_settlements.lock().await.insert(token_type, (std::time::Instant::now(), pnl > 0.0));
+    }
+
+    /// Active settlement cooldown on `token_type`'s asset: ("win" / "loss", settled token type, time left)
+    /// Either side of the market settling pauses both, since they trade the same move
+    async fn settlement_cooldown(&self, token_type: &TokenType) -> Option<(&'static str, TokenType, std::time::Duration)> {
+        let tuning = self.tuning();
+        if tuning.post_win_cooldown.is_zero() && tuning.post_loss_cooldown.is_zero() {
+            return None;
+        }
+        let settlements = self.last_settlements.lock().await;
+        [token_type.clone(), token_type.opposite()].into_iter()
+            .filter_map(|settled| {
+                let (at, won) = *settlements.get(&settled)?;
+                let (kind, cooldown) = if won { ("win", tuning.post_win_cooldown) } else { ("loss", tuning.post_loss_cooldown) };
+                let left = cooldown.checked_sub(at.elapsed()).filter(|left| !left.is_zero())?;
+                Some((kind, settled, left))
+            })
+            .max_by_key(|(_, _, left)| *left)
+    }
+
+    /// Restrict entries per asset to the listed outcomes (trading.directions); assets not listed trade both sides
+    pub fn with_directions(mut self, directions: HashMap<String, Vec<Direction>>) -> Self {
+        self.tuning_mut().directions = directions;
+        self
+    }
+
+    /// Whether trading.directions allows buying this outcome
+    fn direction_allowed(&self, token_type: &TokenType) -> bool {
+        let tuning = self.tuning();
+        let direction = if token_type.is_up() { Direction::Up } else { Direction::Down };
+        tuning.directions.get(token_type.asset_key())
+            .map(|allowed| allowed.contains(&direction))
+            .unwrap_or(true)
+    }
+
+    /// Only enter when the token and its opposite both have two-sided (bid and ask, uncrossed) quotes,
+    /// so the position can still be hedged or exited. Near expiry one side of a book often empties
+    pub fn with_two_sided_book(mut self, require: bool) -> Self {
+        self.tuning_mut().require_two_sided_book = require;
+        self
+    }
+
+    /// Skip entries while the token's book is wider than `max_spread` (ask - bid), so thin books aren't bought into
+    /// A token without both a bid and an ask counts as too wide
+    pub fn with_max_spread(mut self, max_spread: Option<f64>) -> Self {
+        self.tuning_mut().max_spread = max_spread;
+        self
+    }
+
+    /// Refuse entries on quotes fetched more than `max_age_ms` ago; the token is re-evaluated on the next,
+    /// freshly fetched snapshot
+    pub fn with_max_price_age(mut self, max_age_ms: Option<u64>) -> Self {
+        self.tuning_mut().max_price_age = max_age_ms.map(std::time::Duration::from_millis);
+        self
+    }
+
+    /// Only enter when top-of-book buy pressure supports the move: bid_size / (bid_size + ask_size) >= min
+    /// Requires top-of-book sizes in the snapshot (see `MarketMonitor::with_book_sizes`)
+    pub fn with_min_book_imbalance(mut self, min_book_imbalance: Option<f64>) -> Self {
+        self.tuning_mut().min_book_imbalance = min_book_imbalance;
+        self
+    }
+
+    /// Detector with every filter from the trading settings. The opposite-price filter uses the snapshot's price;
+    /// call `with_opposite_filter` again to look it up live
+    pub fn from_config(trading: &crate::config::TradingConfig) -> Self {
+        Self::new(
+            trading.trigger_price,
+            trading.max_buy_price.unwrap_or(DEFAULT_MAX_BUY_PRICE),
+            trading.min_elapsed_minutes,
+            trading.min_time_remaining_seconds.unwrap_or(DEFAULT_MIN_TIME_REMAINING_SECS),
+            trading.enable_eth_trading,
+            trading.enable_solana_trading,
+        )
+        .with_opposite_filter(trading.opposite_max_price, None)
+        .with_min_book_imbalance(trading.min_book_imbalance)
+        .with_two_sided_book(trading.require_two_sided_book)
+        .with_max_spread(trading.max_spread)
+        .with_max_price_age(trading.max_price_age_ms)
+        .with_directions(trading.directions.clone())
+        .with_settlement_cooldowns(trading.post_win_cooldown_secs, trading.post_loss_cooldown_secs)
+        .with_trigger_confirmations(trading.trigger_confirmations)
+        .with_min_expected_edge(trading.min_expected_edge)
+    }
+
+    /// Skip entries when the opposite token has already rallied above `max_price`
+    /// With `lookup` set, the opposite price is re-fetched live (bounded by the timeout) instead of taken
+    /// from the snapshot; if that lookup fails or times out the trade is allowed
+    pub fn with_opposite_filter(mut self, max_price: Option<f64>, lookup: Option<(Arc<PolymarketApi>, std::time::Duration)>) -> Self {
+        self.tuning_mut().opposite_max_price = max_price;
+        self.opposite_price_lookup = lookup;
+        self
+    }
+
+    /// Current price of the opposite token (the other side of the same market)
+    async fn opposite_price(&self, token_type: &TokenType, snapshot: &MarketSnapshot) -> Option<f64> {
+        let opposite = opposite_token(token_type, snapshot)?;
+
+        if let Some((api, timeout)) = &self.opposite_price_lookup {
+            return match tokio::time::timeout(*timeout, api.get_price(&opposite.token_id, "BUY")).await {
+                Ok(Ok(price)) => Some(decimal_to_f64(price)),
+                Ok(Err(e)) => {
+                    debug!("Opposite price lookup for {} failed: {}", token_type.opposite().display_name(), e);
+                    None
+                }
+                Err(_) => {
+                    debug!("Opposite price lookup for {} timed out", token_type.opposite().display_name());
+                    None
+                }
+            };
+        }
+
+        opposite.signal_price().map(decimal_to_f64)
     }
 
     /// Check a single token for opportunity
@@ -118,17 +406,46 @@ impl PriceDetector {
         time_elapsed_seconds: u64,
         min_elapsed_seconds: u64,
     ) -> Option<BuyOpportunity> {
+        let tuning = self.tuning();
+        if !self.direction_allowed(&token_type) {
+            return None;
+        }
+
+        if let Some((kind, settled, left)) = self.settlement_cooldown(&token_type).await {
+            if time_elapsed_seconds >= min_elapsed_seconds {
+                crate::log_monitor_println!("⏸️  {}: SKIPPING BUY - post-{} cooldown after {} settled ({}s left, post_{}_cooldown_secs)",
+                    token_type.display_name(), kind, settled.display_name(), left.as_secs(), kind);
+            }
+            return None;
+        }
+        
         // Use BID price (what we pay to buy) - return None if bid price is missing
         let bid_price = match token.bid {
             Some(bid) => decimal_to_f64(bid),
             None => {
+                self.record_trigger_hit(&token.token_id, snapshot.period_timestamp, false).await;
                 if time_elapsed_seconds >= min_elapsed_seconds - 60 {
-                    crate::log_println!("⚠️  {}: No BID price available, skipping", token_type.display_name());
+                    crate::log_monitor_println!("⚠️  {}: No BID price available, skipping", token_type.display_name());
                 }
             return None;
             },
         };
 
+        // Crossed/locked book - the BID isn't a real executable price, wait for the book to clear
+        if token.crossed {
+            self.record_trigger_hit(&token.token_id, snapshot.period_timestamp, false).await;
+            if time_elapsed_seconds >= min_elapsed_seconds.saturating_sub(60) {
+                crate::log_monitor_println!("⚠️  {}: Orderbook is crossed/locked (BUY ${:.4} <= SELL ${:.4}), skipping",
+                    token_type.display_name(), bid_price, token.ask.map(decimal_to_f64).unwrap_or(0.0));
+            }
+            return None;
+        }
+
+        // Trigger/range checks use the signal price (CLOB BID unless a gamma/blend price source is configured)
+        // The opportunity still carries the executable BID so orders are priced off the book
+        let signal_price = token.signal.map(decimal_to_f64).unwrap_or(bid_price);
+        let trigger_streak = self.record_trigger_hit(&token.token_id, snapshot.period_timestamp, signal_price >= tuning.trigger_price).await;
+
         let time_elapsed_minutes = time_elapsed_seconds / 60;
         let time_remaining_minutes = snapshot.time_remaining_seconds / 60;
 
@@ -140,13 +457,13 @@ impl PriceDetector {
         match reset_state {
             ResetState::NeedsReset => {
                 // After a successful sell, we need price to drop below trigger_price to reset
-                if bid_price < self.trigger_price {
+                if signal_price < tuning.trigger_price {
                     // Price dropped below trigger - reset completed, allow buying again
                     reset_states.insert(token_type.clone(), ResetState::Ready);
                     drop(reset_states);
                     if time_elapsed_seconds >= min_elapsed_seconds {
                         eprintln!("✅ {}: Reset completed - BID=${:.6} < trigger=${:.6}, ready for next buy", 
-                            token_type.display_name(), bid_price, self.trigger_price);
+                            token_type.display_name(), signal_price, tuning.trigger_price);
                     }
                     // Still return None here - we need price to go back up >= trigger_price to buy
                     return None;
@@ -155,7 +472,7 @@ impl PriceDetector {
                     drop(reset_states);
                     if time_elapsed_seconds >= min_elapsed_seconds {
                         eprintln!("⏸️  {}: Needs reset - BID=${:.6} >= trigger=${:.6}, waiting for price to drop below trigger first", 
-                            token_type.display_name(), bid_price, self.trigger_price);
+                            token_type.display_name(), signal_price, tuning.trigger_price);
                     }
             return None;
         }
@@ -167,12 +484,12 @@ impl PriceDetector {
         }
 
         // Log when price is close to trigger (within 0.05) or past buy window - helps debug why buys aren't triggering
-        let price_diff = bid_price - self.trigger_price;
+        let price_diff = signal_price - tuning.trigger_price;
         if time_elapsed_seconds >= min_elapsed_seconds.saturating_sub(60) || price_diff.abs() < 0.05 {
             eprintln!("🔍 {}: BID=${:.6} (trigger=${:.2}, diff=${:.3}), range: ${:.2}-${:.2}, elapsed={}m{}s (need {}m), remaining={}m{}s",
-                token_type.display_name(), bid_price, self.trigger_price, price_diff,
-                self.trigger_price, self.max_buy_price,
-                time_elapsed_minutes, time_elapsed_seconds % 60, self.min_elapsed_minutes,
+                token_type.display_name(), signal_price, tuning.trigger_price, price_diff,
+                tuning.trigger_price, tuning.max_buy_price,
+                time_elapsed_minutes, time_elapsed_seconds % 60, tuning.min_elapsed_minutes,
                 time_remaining_minutes, snapshot.time_remaining_seconds % 60);
         }
 
@@ -180,57 +497,159 @@ impl PriceDetector {
         if time_elapsed_seconds < min_elapsed_seconds {
             // Log when close to buy window or when price is near trigger - helps debug why buys aren't triggering
             let time_remaining_until_window = min_elapsed_seconds - time_elapsed_seconds;
-            let price_diff = bid_price - self.trigger_price;
+            let price_diff = signal_price - tuning.trigger_price;
             if time_elapsed_seconds >= min_elapsed_seconds - 60 || price_diff.abs() < 0.05 {
                 eprintln!("⏸️  {}: Time not elapsed yet: {}m{}s elapsed < {}m required (need {}s more) | BID=${:.6}",
                     token_type.display_name(), time_elapsed_minutes, time_elapsed_seconds % 60, 
-                    self.min_elapsed_minutes, time_remaining_until_window, bid_price);
+                    tuning.min_elapsed_minutes, time_remaining_until_window, signal_price);
             }
             return None;
         }
 
         // Buy when price is between trigger_price (min) and max_buy_price (max)
-        // Example: buy when 0.87 <= bid_price <= 0.95
-        if bid_price < self.trigger_price {
+        // Example: buy when 0.87 <= signal_price <= 0.95
+        if signal_price < tuning.trigger_price {
             // Log when close to trigger or past buy window - helps debug why buys aren't triggering
-            let price_diff = self.trigger_price - bid_price;
+            let price_diff = tuning.trigger_price - signal_price;
             if time_elapsed_seconds >= min_elapsed_seconds || price_diff < 0.05 {
                 eprintln!("⏸️  {}: Price too low: BID=${:.6} < ${:.6} (trigger) - need ${:.3} more",
-                    token_type.display_name(), bid_price, self.trigger_price, price_diff);
+                    token_type.display_name(), signal_price, tuning.trigger_price, price_diff);
             }
             return None; // Price too low, wait for it to reach trigger_price (0.87)
         }
         
-        if bid_price > self.max_buy_price {
+        if signal_price > tuning.max_buy_price {
             // Only log when close to buy window (reduce noise)
             if time_elapsed_seconds >= min_elapsed_seconds {
                 debug!("{}: Price too high: ${:.6} > ${:.6} (max)", 
-                    token_type.display_name(), bid_price, self.max_buy_price);
+                    token_type.display_name(), signal_price, tuning.max_buy_price);
             }
             return None; // Price too high (> 0.95), skip buying and wait for price to drop
         }
 
+        // The signal may come from gamma/blend; never pay more than max_buy_price on the book
+        if signal_price != bid_price && bid_price > tuning.max_buy_price {
+            if time_elapsed_seconds >= min_elapsed_seconds {
+                eprintln!("⏸️  {}: Signal ${:.6} in range but executable BID ${:.6} > ${:.6} (max)",
+                    token_type.display_name(), signal_price, bid_price, tuning.max_buy_price);
+            }
+            return None;
+        }
+
         // Check if there's enough time remaining (at least min_time_remaining_seconds)
         // Don't buy if market is closing soon - too risky
-        if snapshot.time_remaining_seconds < self.min_time_remaining_seconds {
+        if snapshot.time_remaining_seconds < tuning.min_time_remaining_seconds {
             // Log prominently when skipping buy due to insufficient time remaining
             if time_elapsed_seconds >= min_elapsed_seconds {
                 eprintln!("⏸️  {}: SKIPPING BUY - insufficient time remaining: {}s < {}s (minimum required)", 
-                    token_type.display_name(), snapshot.time_remaining_seconds, self.min_time_remaining_seconds);
+                    token_type.display_name(), snapshot.time_remaining_seconds, tuning.min_time_remaining_seconds);
                 eprintln!("   💡 Price conditions met, but market closing too soon - too risky to buy");
             }
             return None; // Too little time remaining, skip buying
         }
 
+        // Both sides need a live two-sided book, otherwise the position could be neither hedged nor exited
+        if tuning.require_two_sided_book {
+            let opposite = opposite_token(&token_type, snapshot);
+            let missing = if !is_two_sided(token) {
+                Some(token_type.clone())
+            } else if !opposite.map(is_two_sided).unwrap_or(false) {
+                Some(token_type.opposite())
+            } else {
+                None
+            };
+            if let Some(side) = missing {
+                eprintln!("⏸️  {}: SKIPPING BUY - {} book is not two-sided (missing bid/ask or crossed) (require_two_sided_book)",
+                    token_type.display_name(), side.display_name());
+                return None;
+            }
+        }
+
+        // Thin book: a wide spread means paying well above the bid the trigger fired on
+        if let Some(max_spread) = tuning.max_spread {
+            match spread(token) {
+                Some(spread) if spread <= max_spread + 1e-9 => {}
+                Some(spread) => {
+                    eprintln!("⏸️  {}: SKIPPING BUY - spread ${:.4} > ${:.4} (max_spread)",
+                        token_type.display_name(), spread, max_spread);
+                    return None;
+                }
+                None => {
+                    eprintln!("⏸️  {}: SKIPPING BUY - no bid/ask to measure the spread (max_spread)",
+                        token_type.display_name());
+                    return None;
+                }
+            }
+        }
+
+        // If the other side has already rallied hard, this side is likely to lose at resolution
+        // An unknown opposite price never blocks the trade
+        if let Some(opposite_max_price) = tuning.opposite_max_price {
+            if let Some(opposite_price) = self.opposite_price(&token_type, snapshot).await {
+                if opposite_price > opposite_max_price {
+                    eprintln!("⏸️  {}: SKIPPING BUY - opposite side {} already at ${:.6} > ${:.6} (opposite_max_price)",
+                        token_type.display_name(), token_type.opposite().display_name(), opposite_price, opposite_max_price);
+                    return None;
+                }
+            }
+        }
+
+        // Microstructure filter: resting buy size must outweigh sell size at the top of the book
+        let imbalance = book_imbalance(token);
+        if let Some(min_imbalance) = tuning.min_book_imbalance {
+            match imbalance {
+                Some(ratio) if ratio >= min_imbalance => {}
+                Some(ratio) => {
+                    eprintln!("⏸️  {}: SKIPPING BUY - book imbalance {:.3} < {:.3} (min_book_imbalance)",
+                        token_type.display_name(), ratio, min_imbalance);
+                    return None;
+                }
+                None => {
+                    eprintln!("⏸️  {}: SKIPPING BUY - top-of-book sizes unavailable for min_book_imbalance",
+                        token_type.display_name());
+                    return None;
+                }
+            }
+        }
+
+        // Everything above was decided on this quote - don't buy on it if the market may already have moved
+        if let Some(max_age) = tuning.max_price_age {
+            let age = token.age();
+            if age > max_age {
+                eprintln!("⏸️  {}: STALE PRICE - BID ${:.6} is {}ms old > {}ms (max_price_age_ms), waiting for a fresh quote",
+                    token_type.display_name(), bid_price, age.as_millis(), max_age.as_millis());
+                return None;
+            }
+        }
+
+        // Price and time in one number: what a share is expected to earn at this entry
+        let win_probability = win_probability_estimate(signal_price, snapshot.time_remaining_seconds);
+        let edge = expected_edge(win_probability, bid_price);
+        if let Some(min_edge) = tuning.min_expected_edge {
+            if edge < min_edge {
+                eprintln!("⏸️  {}: SKIPPING BUY - expected edge ${:.4} < ${:.4} (min_expected_edge) | P(win)={:.3} BID=${:.6} remaining={}s",
+                    token_type.display_name(), edge, min_edge, win_probability, bid_price, snapshot.time_remaining_seconds);
+                return None;
+            }
+        }
+
+        // Debounce: one snapshot at the trigger may be a spike or a stale quote
+        if trigger_streak < tuning.trigger_confirmations {
+            eprintln!("⏸️  {}: Waiting for trigger confirmation - BID=${:.6} at/above trigger on {}/{} consecutive snapshots (trigger_confirmations)",
+                token_type.display_name(), signal_price, trigger_streak, tuning.trigger_confirmations);
+            return None;
+        }
+
         // Price is in valid range! (trigger_price <= bid_price <= max_buy_price)
         // And there's enough time remaining (>= 30 seconds)
         // This should trigger a buy
         let expected_profit_at_1_0 = 1.0 - bid_price; // Profit if token reaches $1.0
 
         // Log momentum opportunity in compact one-line format
-        eprintln!("🎯 {} BUY: BID=${:.3} | Elapsed: {}m | Remaining: {}s | Profit@$1.0: ${:.3}", 
+        let imbalance_note = imbalance.map(|ratio| format!(" | Imbalance: {:.3}", ratio)).unwrap_or_default();
+        eprintln!("🎯 {} BUY: BID=${:.3} | Elapsed: {}m | Remaining: {}s | Profit@$1.0: ${:.3} | Edge: ${:.4} (P(win)={:.3}){}", 
             token_type.display_name(), bid_price, 
-            time_elapsed_minutes, snapshot.time_remaining_seconds, expected_profit_at_1_0);
+            time_elapsed_minutes, snapshot.time_remaining_seconds, expected_profit_at_1_0, edge, win_probability, imbalance_note);
 
         Some(BuyOpportunity {
             condition_id: condition_id.to_string(),
@@ -242,6 +661,7 @@ impl PriceDetector {
             time_elapsed_seconds,
             use_market_order: false, // Regular detect_opportunities always uses market orders
             investment_amount_override: None,
+            sell_price_override: None,
             is_individual_hedge: false,
             is_standard_hedge: false,
             dual_limit_shares: None,
@@ -252,6 +672,7 @@ impl PriceDetector {
     /// Strategy: Buy BTC/ETH token when price reaches 0.9 after 10 minutes have elapsed
     /// Returns all matching opportunities so we can buy both ETH Down and BTC Down (and Up) when multiple qualify
     pub async fn detect_opportunities(&self, snapshot: &MarketSnapshot) -> Vec<BuyOpportunity> {
+        let tuning = self.tuning();
         let mut opportunities = Vec::new();
 
         // Reject expired markets (time_remaining_seconds <= 0)
@@ -260,10 +681,9 @@ impl PriceDetector {
             return opportunities;
         }
 
-        // Calculate time elapsed (15 minutes = 900 seconds total)
-        const PERIOD_DURATION: u64 = 900; // 15 minutes in seconds
-        let time_elapsed_seconds = PERIOD_DURATION - snapshot.time_remaining_seconds;
-        let min_elapsed_seconds = self.min_elapsed_minutes * 60;
+        // Calculate time elapsed in the period (trading.period_seconds)
+        let time_elapsed_seconds = crate::period::seconds().saturating_sub(snapshot.time_remaining_seconds);
+        let min_elapsed_seconds = tuning.min_elapsed_minutes * 60;
         
         // Log when we detect a new market period (to show we're monitoring each market)
         let time_elapsed_minutes = time_elapsed_seconds / 60;
@@ -273,19 +693,19 @@ impl PriceDetector {
             let mut last_period = self.last_logged_period.lock().await;
             if last_period.is_none() || last_period.unwrap() != snapshot.period_timestamp {
                 eprintln!("🔄 New market (period: {}): {}m elapsed, {}m remaining (buy window: after {}m)", 
-                    snapshot.period_timestamp, time_elapsed_minutes, time_remaining_minutes, self.min_elapsed_minutes);
+                    snapshot.period_timestamp, time_elapsed_minutes, time_remaining_minutes, tuning.min_elapsed_minutes);
                 *last_period = Some(snapshot.period_timestamp);
             }
         }
         
-        if time_elapsed_seconds < min_elapsed_seconds && time_elapsed_minutes > 0 && time_elapsed_minutes % 2 == 0 && time_elapsed_seconds % 60 < 2 {
+        if time_elapsed_seconds < min_elapsed_seconds && time_elapsed_minutes > 0 && time_elapsed_minutes.is_multiple_of(2) && time_elapsed_seconds % 60 < 2 {
             eprintln!("⏱️  Monitoring (period: {}): {}m elapsed, {}m remaining (buy window: after {}m)", 
-                snapshot.period_timestamp, time_elapsed_minutes, time_remaining_minutes, self.min_elapsed_minutes);
+                snapshot.period_timestamp, time_elapsed_minutes, time_remaining_minutes, tuning.min_elapsed_minutes);
         }
         
         debug!("detect_opportunity: time_elapsed={}s ({}m), remaining={}s, min_required={}s ({}m)", 
             time_elapsed_seconds, time_elapsed_seconds / 60, 
-            snapshot.time_remaining_seconds, min_elapsed_seconds, self.min_elapsed_minutes);
+            snapshot.time_remaining_seconds, min_elapsed_seconds, tuning.min_elapsed_minutes);
 
         // Check BTC Up
         if let Some(btc_up) = snapshot.btc_market.up_token.as_ref() {
@@ -335,6 +755,7 @@ impl PriceDetector {
     /// Strategy: Place limit buy orders for both tokens when min_elapsed_minutes have passed
     /// This ignores price checks and max_buy_price/min_time_remaining_seconds
     pub async fn detect_limit_order_opportunities(&self, snapshot: &MarketSnapshot) -> Vec<BuyOpportunity> {
+        let tuning = self.tuning();
         let mut opportunities = Vec::new();
 
         if snapshot.time_remaining_seconds == 0 {
@@ -342,9 +763,8 @@ impl PriceDetector {
             return opportunities;
         }
 
-        const PERIOD_DURATION: u64 = 900;
-        let time_elapsed_seconds = PERIOD_DURATION - snapshot.time_remaining_seconds;
-        let min_elapsed_seconds = self.min_elapsed_minutes * 60;
+        let time_elapsed_seconds = crate::period::seconds().saturating_sub(snapshot.time_remaining_seconds);
+        let min_elapsed_seconds = tuning.min_elapsed_minutes * 60;
         let time_elapsed_minutes = time_elapsed_seconds / 60;
 
         // Only trigger once when min_elapsed_minutes is reached
@@ -387,6 +807,7 @@ impl PriceDetector {
                 time_elapsed_seconds,
                 use_market_order: true, // Always use market order
                 investment_amount_override: None,
+                sell_price_override: None,
                 is_individual_hedge: false,
                 is_standard_hedge: false,
                 dual_limit_shares: None,
@@ -411,6 +832,7 @@ impl PriceDetector {
                 time_elapsed_seconds,
                 use_market_order: true, // Always use market order
                 investment_amount_override: None,
+                sell_price_override: None,
                 is_individual_hedge: false,
                 is_standard_hedge: false,
                 dual_limit_shares: None,
@@ -436,6 +858,7 @@ impl PriceDetector {
                     time_elapsed_seconds,
                     use_market_order: true, // Always use market order
                     investment_amount_override: None,
+                    sell_price_override: None,
                     is_individual_hedge: false,
                     is_standard_hedge: false,
                     dual_limit_shares: None,
@@ -458,6 +881,7 @@ impl PriceDetector {
                     time_elapsed_seconds,
                     use_market_order: true, // Always use market order
                     investment_amount_override: None,
+                    sell_price_override: None,
                     is_individual_hedge: false,
                     is_standard_hedge: false,
                     dual_limit_shares: None,
@@ -484,6 +908,7 @@ impl PriceDetector {
                     time_elapsed_seconds,
                     use_market_order: true, // Always use market order
                     investment_amount_override: None,
+                    sell_price_override: None,
                     is_individual_hedge: false,
                     is_standard_hedge: false,
                     dual_limit_shares: None,
@@ -506,6 +931,7 @@ impl PriceDetector {
                     time_elapsed_seconds,
                     use_market_order: true, // Always use market order
                     investment_amount_override: None,
+                    sell_price_override: None,
                     is_individual_hedge: false,
                     is_standard_hedge: false,
                     dual_limit_shares: None,
@@ -513,6 +939,7 @@ impl PriceDetector {
             }
         }
 
+        opportunities.retain(|opp| self.direction_allowed(&opp.token_type));
         eprintln!("📋 Found {} limit order opportunities", opportunities.len());
         opportunities
     }
@@ -530,6 +957,7 @@ impl PriceDetector {
         // Also reset reset states for new period
         let mut reset_states = self.reset_states.lock().await;
         reset_states.clear();
+        self.trigger_hits.lock().await.clear();
     }
 
     /// Clear limit order tracking for a specific period to allow re-entry after a sell
@@ -544,14 +972,155 @@ impl PriceDetector {
     /// Mark that a buy-sell cycle completed for a token type
     /// After this, the price must drop below trigger_price before allowing another buy
     pub async fn mark_cycle_completed(&self, token_type: TokenType) {
+        let tuning = self.tuning();
         let mut reset_states = self.reset_states.lock().await;
         reset_states.insert(token_type.clone(), ResetState::NeedsReset);
         crate::log_println!("🔄 {}: Buy-sell cycle completed. Will require price to drop below ${:.6} before next buy", 
-            token_type.display_name(), self.trigger_price);
+            token_type.display_name(), tuning.trigger_price);
     }
 }
 
+/// Win probability of a token trading at `price` with `time_remaining_seconds` left in the period
+/// The price is the market's implied probability; as the period runs out there is less time for a reversal,
+/// so the estimate moves towards the outcome the price favours - by up to TIME_CONFIDENCE of the way at close
+pub fn win_probability_estimate(price: f64, time_remaining_seconds: u64) -> f64 {
+    let price = price.clamp(0.0, 1.0);
+    let elapsed_fraction = 1.0 - (time_remaining_seconds as f64 / crate::period::seconds() as f64).min(1.0);
+    let favoured = if price >= 0.5 { 1.0 } else { 0.0 };
+    price + (favoured - price) * TIME_CONFIDENCE * elapsed_fraction
+}
+
+/// Expected profit per share of buying at `entry_price` a token that wins with `win_probability`:
+/// P * (1 - entry) - (1 - P) * entry
+pub fn expected_edge(win_probability: f64, entry_price: f64) -> f64 {
+    win_probability * (1.0 - entry_price) - (1.0 - win_probability) * entry_price
+}
+
 // Helper function for Decimal to f64 conversion
 fn decimal_to_f64(d: Decimal) -> f64 {
     d.to_string().parse().unwrap_or(0.0)
 }
+
+/// Top-of-book imbalance bid_size / (bid_size + ask_size) - None if either size is unknown or both are zero
+fn book_imbalance(token: &crate::models::TokenPrice) -> Option<f64> {
+    let bid_size = decimal_to_f64(token.bid_size?);
+    let ask_size = decimal_to_f64(token.ask_size?);
+    let total = bid_size + ask_size;
+    if total <= 0.0 {
+        return None;
+    }
+    Some(bid_size / total)
+}
+
+/// The other side (Up <-> Down) of `token_type`'s market in the snapshot
+fn opposite_token<'a>(token_type: &TokenType, snapshot: &'a MarketSnapshot) -> Option<&'a crate::models::TokenPrice> {
+    let market = match token_type {
+        TokenType::BtcUp | TokenType::BtcDown => &snapshot.btc_market,
+        TokenType::EthUp | TokenType::EthDown => &snapshot.eth_market,
+        TokenType::SolanaUp | TokenType::SolanaDown => &snapshot.solana_market,
+        TokenType::XrpUp | TokenType::XrpDown => &snapshot.xrp_market,
+    };
+    match token_type.opposite() {
+        TokenType::BtcUp | TokenType::EthUp | TokenType::SolanaUp | TokenType::XrpUp => market.up_token.as_ref(),
+        TokenType::BtcDown | TokenType::EthDown | TokenType::SolanaDown | TokenType::XrpDown => market.down_token.as_ref(),
+    }
+}
+
+/// Quoted on both sides (a price to buy and a price to sell) and not crossed/locked
+fn is_two_sided(token: &crate::models::TokenPrice) -> bool {
+    token.bid.is_some() && token.ask.is_some() && !token.crossed
+}
+
+/// Ask - bid of the token's book, if it has both
+fn spread(token: &crate::models::TokenPrice) -> Option<f64> {
+    Some(decimal_to_f64(token.ask?) - decimal_to_f64(token.bid?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MarketData, TokenPrice};
+
+    const PERIOD: u64 = 1_767_796_200;
+
+    fn market(up_token: Option<TokenPrice>) -> MarketData {
+        MarketData {
+            condition_id: "dummy_condition".to_string(),
+            market_name: "BTC".to_string(),
+            up_token,
+            down_token: None,
+        }
+    }
+
+    /// Snapshot 10 minutes into the period with BTC Up bid at `bid`
+    fn snapshot(bid: f64) -> MarketSnapshot {
+        let to_decimal = |p: f64| Decimal::from_f64_retain(p).unwrap().round_dp(4);
+        let up = TokenPrice {
+            token_id: "dummy_up_token".to_string(),
+            bid: Some(to_decimal(bid)),
+            ask: Some(to_decimal(bid + 0.01)),
+            signal: None,
+            bid_size: None,
+            ask_size: None,
+            crossed: false,
+            fetched_at: std::time::Instant::now(),
+        };
+        MarketSnapshot {
+            eth_market: market(None),
+            btc_market: market(Some(up)),
+            solana_market: market(None),
+            xrp_market: market(None),
+            timestamp: std::time::Instant::now(),
+            time_remaining_seconds: 300,
+            period_timestamp: PERIOD,
+        }
+    }
+
+    #[test]
+    fn win_estimate_and_edge_grow_as_the_period_runs_out() {
+        // Full period left - the price is the estimate, so buying at it has no edge
+        assert!((win_probability_estimate(0.90, 900) - 0.90).abs() < 1e-9);
+        assert!(expected_edge(0.90, 0.90).abs() < 1e-9);
+
+        // Halfway: a quarter of the way to certainty; at close: half of it
+        assert!((win_probability_estimate(0.90, 450) - 0.925).abs() < 1e-9);
+        assert!((win_probability_estimate(0.90, 0) - 0.95).abs() < 1e-9);
+        assert!((win_probability_estimate(0.20, 0) - 0.10).abs() < 1e-9);
+
+        // Paying above the estimate is a negative edge
+        assert!((expected_edge(0.95, 0.90) - 0.05).abs() < 1e-9);
+        assert!((expected_edge(0.90, 0.95) + 0.05).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn trigger_fires_only_after_the_required_consecutive_snapshots() {
+        let detector = PriceDetector::new(0.85, 0.95, 10, 30, false, false).with_trigger_confirmations(Some(3));
+
+        // 2 of 3 confirmations, then the price dips - the streak starts over
+        assert!(detector.detect_opportunities(&snapshot(0.90)).await.is_empty());
+        assert!(detector.detect_opportunities(&snapshot(0.90)).await.is_empty());
+        assert!(detector.detect_opportunities(&snapshot(0.80)).await.is_empty());
+
+        assert!(detector.detect_opportunities(&snapshot(0.90)).await.is_empty());
+        assert!(detector.detect_opportunities(&snapshot(0.91)).await.is_empty());
+        let opportunities = detector.detect_opportunities(&snapshot(0.90)).await;
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].token_type, TokenType::BtcUp);
+    }
+
+    #[tokio::test]
+    async fn entries_are_skipped_while_the_spread_is_too_wide() {
+        // snapshot() quotes a $0.01 spread
+        let tight = PriceDetector::new(0.85, 0.95, 10, 30, false, false).with_max_spread(Some(0.01));
+        assert_eq!(tight.detect_opportunities(&snapshot(0.90)).await.len(), 1);
+
+        let strict = PriceDetector::new(0.85, 0.95, 10, 30, false, false).with_max_spread(Some(0.005));
+        assert!(strict.detect_opportunities(&snapshot(0.90)).await.is_empty());
+
+        // No ask to measure against - treated as too wide
+        let mut one_sided = snapshot(0.90);
+        one_sided.btc_market.up_token.as_mut().unwrap().ask = None;
+        let detector = PriceDetector::new(0.85, 0.95, 10, 30, false, false).with_max_spread(Some(0.05));
+        assert!(detector.detect_opportunities(&one_sided).await.is_empty());
+    }
+}
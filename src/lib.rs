@@ -1,12 +1,24 @@
 pub mod api;
+pub mod api_client;
 pub mod backtest;
 pub mod detector;
 pub mod config;
+pub mod control;
+pub mod dashboard;
 pub mod merge;
+pub mod metrics;
 pub mod models;
 pub mod monitor;
+pub mod notifier;
+pub mod order_tracker;
+pub mod period;
+pub mod price_feed;
+pub mod price_history;
+pub mod retry;
 pub mod simulation;
+pub mod strategy;
 pub mod trader;
+pub mod wallets;
 
 // Re-export commonly used types
 pub use api::PolymarketApi;
@@ -15,6 +27,7 @@ pub use models::TokenPrice;
 
 // Global file writer for history.toml (initialized by main.rs)
 use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::fs::File;
 use std::io::Write;
 
@@ -48,6 +61,25 @@ pub fn log_trading_event(event: &str) {
     log_to_history(&format!("[{}] {}\n", timestamp, event));
 }
 
+// logging.sample_monitor_every_n: periodic monitoring lines are only written on every Nth market snapshot
+static MONITOR_SAMPLE_EVERY: AtomicU64 = AtomicU64::new(1);
+static MONITOR_SNAPSHOTS: AtomicU64 = AtomicU64::new(0);
+
+/// Log periodic monitoring lines (log_monitor_println!) only on every `n`th market snapshot (1 = every snapshot)
+pub fn set_monitor_sample_every(n: u64) {
+    MONITOR_SAMPLE_EVERY.store(n.max(1), Ordering::Relaxed);
+}
+
+/// Count a market snapshot (called by the monitor once per fetch)
+pub fn record_monitor_snapshot() {
+    MONITOR_SNAPSHOTS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Whether the current snapshot is one whose monitoring lines are logged
+pub fn monitor_sample_due() -> bool {
+    MONITOR_SNAPSHOTS.load(Ordering::Relaxed).is_multiple_of(MONITOR_SAMPLE_EVERY.load(Ordering::Relaxed))
+}
+
 // Macro for logging - modules use crate::log_println!
 #[macro_export]
 macro_rules! log_println {
@@ -58,3 +90,14 @@ macro_rules! log_println {
         }
     };
 }
+
+// Periodic monitoring/price-check lines - decimated by logging.sample_monitor_every_n
+// Never use this for trades, resolutions or errors; those always go through log_println!/log_trading_event
+#[macro_export]
+macro_rules! log_monitor_println {
+    ($($arg:tt)*) => {
+        if $crate::monitor_sample_due() {
+            $crate::log_println!($($arg)*);
+        }
+    };
+}
@@ -4,6 +4,8 @@ use polymarket_arbitrage_bot::*;
 use anyhow::{Context, Result};
 use clap::Parser;
 use polymarket_arbitrage_bot::config::{Args, Config};
+#[cfg(unix)]
+use polymarket_arbitrage_bot::control::ControlRouter;
 use log::warn;
 use std::sync::Arc;
 use std::io::{self, Write};
@@ -14,8 +16,18 @@ use chrono::Utc;
 use polymarket_arbitrage_bot::api::PolymarketApi;
 use polymarket_arbitrage_bot::detector::PriceDetector;
 use polymarket_arbitrage_bot::monitor::MarketMonitor;
+use polymarket_arbitrage_bot::price_feed::{SyntheticPath, SyntheticPriceFeed};
 use polymarket_arbitrage_bot::trader::Trader;
 
+/// How long to keep retrying discovery of a new period's markets (discovery.rotation_retry_secs default)
+const DEFAULT_ROTATION_RETRY_SECS: u64 = 60;
+/// Cap on the doubling delay between rotation discovery retries
+const ROTATION_RETRY_MAX_BACKOFF_SECS: u64 = 10;
+/// Earlier periods scanned for leftover positions when startup.lookback_periods is not set (two hours)
+const DEFAULT_STARTUP_LOOKBACK_PERIODS: u64 = 8;
+/// Seconds into a period after which an empty orderbook no longer blocks discovery (discovery.empty_book_grace_secs default)
+const DEFAULT_EMPTY_BOOK_GRACE_SECS: u64 = 60;
+
 /// A writer that writes to both stderr (terminal) and a file
 /// Wrapped in Arc<Mutex<>> for thread-safe access
 struct DualWriter {
@@ -93,12 +105,20 @@ macro_rules! log_println {
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = Config::load(&args.config)?;
+    config.validate()?;
+    if args.print_config {
+        println!("{}", serde_json::to_string_pretty(&config.redacted())?);
+        return Ok(());
+    }
+
     // Open log file in append mode
     let log_file = OpenOptions::new()
         .create(true)
         .append(true)
-        .open("history.toml")
-        .context("Failed to open history.toml for logging")?;
+        .open(config.logging.log_file())
+        .with_context(|| format!("Failed to open {} for logging", config.logging.log_file()))?;
     
     // Initialize global history file for eprintln! messages
     init_history_file(log_file.try_clone().context("Failed to clone history file")?);
@@ -113,16 +133,18 @@ async fn main() -> Result<()> {
     };
     
     // Initialize logger with dual writer
-    env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Info)
+    config.logging.logger_builder()?
         .target(env_logger::Target::Pipe(Box::new(dual_writer)))
         .init();
-
-    let args = Args::parse();
-    let config = Config::load(&args.config)?;
+    polymarket_arbitrage_bot::set_monitor_sample_every(config.logging.sample_monitor_every_n.unwrap_or(1));
+    period::configure(config.trading.period_seconds(), config.trading.slug_template.as_deref());
 
     eprintln!("🚀 Starting Polymarket Trend Trading Bot");
-    eprintln!("📝 Logs are being saved to: history.toml");
+    eprintln!("📝 Logs are being saved to: {}", config.logging.log_file());
+    if let Some(addr) = &config.metrics.listen_addr {
+        let bound = metrics::serve(addr).await?;
+        eprintln!("📈 Prometheus metrics at http://{}/metrics", bound);
+    }
     let is_simulation = args.is_simulation();
     eprintln!("Mode: {}", if is_simulation { "SIMULATION" } else { "PRODUCTION" });
     if config.trading.enable_eth_trading {
@@ -141,7 +163,29 @@ async fn main() -> Result<()> {
         config.polymarket.private_key.clone(),
         config.polymarket.proxy_wallet_address.clone(),
         config.polymarket.signature_type,
-    ));
+    ).with_network(&config.network)?.with_chain(&config.chain)?);
+
+    // Telegram/Discord trade notifications
+    notifier::init(&config.notifications, is_simulation, config.network.http_proxy.as_deref())?;
+
+    // Catch proxy/RPC misconfiguration early (--synthetic runs offline)
+    if !args.synthetic {
+        let unreachable = api.check_connectivity().await;
+        if unreachable > 0 {
+            warn!("{} endpoint(s) unreachable - check the [network] settings in config.json", unreachable);
+        }
+        // A mistyped gamma/CLOB URL fails here rather than as 404s during market discovery
+        api.health_check().await.ensure_healthy()?;
+        eprintln!("✅ Gamma and CLOB APIs healthy");
+    }
+
+    // One-shot discovery report - read-only, so it runs before authentication
+    if args.validate_markets {
+        if args.synthetic {
+            anyhow::bail!("--validate-markets checks Polymarket's live markets - it can't be combined with --synthetic");
+        }
+        return validate_markets(&api, &config).await;
+    }
 
     // Authenticate with Polymarket CLOB API at startup
     // This verifies credentials and creates an authenticated client
@@ -172,10 +216,67 @@ async fn main() -> Result<()> {
         eprintln!("");
     }
 
+    // polymarket.wallets: buys rotate over the primary and additional wallets (production only)
+    let trader_api: Arc<dyn api_client::ApiClient> = if !is_simulation && !config.polymarket.wallets.is_empty() {
+        let mut wallets = vec![wallets::Wallet {
+            label: wallets::PRIMARY_WALLET.to_string(),
+            api: api.clone(),
+            weight: config.polymarket.primary_wallet_weight.unwrap_or(1),
+        }];
+        for (label, wallet_api, weight) in wallets::wallet_apis(&config)? {
+            if let Err(e) = wallet_api.authenticate().await {
+                warn!("⚠️  Failed to authenticate wallet \"{}\" - its orders may fail: {}", label, e);
+            }
+            wallets.push(wallets::Wallet { label, api: wallet_api, weight });
+        }
+        let pool = wallets::WalletPool::new(wallets, config.polymarket.wallet_rotation);
+        eprintln!("👛 Trading from {} wallets ({:?}): {}", pool.labels().len(), config.polymarket.wallet_rotation, pool.labels().join(", "));
+        Arc::new(pool)
+    } else {
+        api.clone()
+    };
+
+    // One-off sweep of redemptions abandoned in previous runs
+    if args.recover_abandoned {
+        if is_simulation {
+            anyhow::bail!("--recover-abandoned redeems real tokens - run it with --no-simulation");
+        }
+        let report = Trader::recover_abandoned_trades(&api).await?;
+        crate::log_println!("═══════════════════════════════════════════════════════════");
+        crate::log_println!("📋 Abandoned trade recovery: {} recovered, {} already settled, {} still failing",
+            report.recovered, report.already_settled, report.still_failing);
+        crate::log_println!("═══════════════════════════════════════════════════════════");
+        return Ok(());
+    }
+
+    // Offline price generator for --sim --synthetic (no Polymarket requests)
+    let synthetic_feed = if args.synthetic {
+        if !is_simulation {
+            anyhow::bail!("--synthetic generates fake prices - it can only be used in simulation mode");
+        }
+        eprintln!("🧪 Synthetic prices enabled (seed: {}) - running fully offline", args.seed);
+        Some(Arc::new(SyntheticPriceFeed::new(
+            args.seed,
+            config.trading.synthetic_half_spread.unwrap_or(0.01),
+        )))
+    } else {
+        None
+    };
+
     // Get market data for BTC, ETH, and Solana markets
-    eprintln!("🔍 Discovering BTC, ETH, Solana, and XRP markets...");
-    let (eth_market_data, btc_market_data, solana_market_data, xrp_market_data) = 
-        get_or_discover_markets(&api, &config).await?;
+    let (eth_market_data, btc_market_data, solana_market_data, xrp_market_data) = match &synthetic_feed {
+        Some(feed) => {
+            let current_time = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            create_synthetic_markets(feed, &config, period::start(current_time))
+        }
+        None => {
+            eprintln!("🔍 Discovering BTC, ETH, Solana, and XRP markets...");
+            get_or_discover_markets(&api, &config).await?
+        }
+    };
     
     // DISABLED: Pre-approve all conditional tokens at startup using setApprovalForAll
     // Temporarily disabled - approval functions are disabled throughout the codebase
@@ -212,6 +313,23 @@ async fn main() -> Result<()> {
     //     eprintln!("");
     // }
 
+    // Markets of the current period, for the startup check of positions the bot doesn't track
+    let current_period = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let current_period = period::start(current_period);
+    let current_markets: Vec<polymarket_arbitrage_bot::trader::PeriodMarket> = [
+        ("eth", &eth_market_data), ("btc", &btc_market_data), ("solana", &solana_market_data), ("xrp", &xrp_market_data),
+    ].into_iter()
+        .filter(|(_, market)| !market.condition_id.starts_with("dummy_"))
+        .map(|(asset_key, market)| polymarket_arbitrage_bot::trader::PeriodMarket {
+            asset_key: asset_key.to_string(),
+            period_timestamp: current_period,
+            condition_id: market.condition_id.clone(),
+        })
+        .collect();
+
     // Initialize components
     let monitor = MarketMonitor::new(
         api.clone(),
@@ -221,39 +339,164 @@ async fn main() -> Result<()> {
         xrp_market_data,
         config.trading.check_interval_ms,
         is_simulation,
-    )?;
+    )?
+    .with_price_source(
+        config.trading.price_source,
+        config.trading.price_source_max_spread.unwrap_or(0.05),
+    )
+    .with_book_sizes(config.trading.min_book_imbalance.is_some())
+    .with_price_transport(
+        config.trading.price_transport,
+        config.trading.price_transport_stale_ms.unwrap_or(monitor::DEFAULT_PRICE_TRANSPORT_STALE_MS),
+    );
+    let monitor = match &synthetic_feed {
+        Some(feed) => monitor.with_price_feed(feed.clone()),
+        None => monitor,
+    };
+    let monitor = match &args.record_snapshots {
+        Some(path) => {
+            let path = path.to_string_lossy();
+            eprintln!("📼 Recording market snapshots to {}", path);
+            monitor.with_snapshot_recorder(monitor::SnapshotRecorder::open(&path)?)
+        }
+        None => monitor,
+    };
     let monitor_arc = Arc::new(monitor);
 
-    let max_buy_price = config.trading.max_buy_price.unwrap_or(0.95);
-    let min_time_remaining = config.trading.min_time_remaining_seconds.unwrap_or(30);
-    let detector = PriceDetector::new(
-        config.trading.trigger_price,
-        max_buy_price,
-        config.trading.min_elapsed_minutes,
-        min_time_remaining,
-        config.trading.enable_eth_trading,
-        config.trading.enable_solana_trading,
-    );
+    let detector = PriceDetector::from_config(&config.trading)
+        .with_opposite_filter(
+            config.trading.opposite_max_price,
+            // Synthetic runs stay offline - use the snapshot's opposite price
+            config.trading.opposite_price_lookup_timeout_ms
+                .filter(|_| synthetic_feed.is_none())
+                .map(|ms| (api.clone(), std::time::Duration::from_millis(ms))),
+        );
 
     // Start monitoring
     let detector_arc = Arc::new(detector);
-    let detector_clone = detector_arc.clone();
+    let strategy = polymarket_arbitrage_bot::strategy::from_config(&config, detector_arc.clone())?;
+    crate::log_println!("🧠 Entry strategy: {}", strategy.name());
     
-    let trader = Trader::new(
-        api.clone(),
+    let price_feed: Arc<dyn price_feed::PriceFeed> = match &synthetic_feed {
+        Some(feed) => feed.clone(),
+        None => Arc::new(price_feed::LivePriceFeed::new(api.clone())),
+    };
+    let trader = Trader::new_with_price_feed(
+        trader_api.clone(),
         config.trading.clone(),
         is_simulation,
         Some(detector_arc.clone()),
-    )?;
+        price_feed,
+    )?
+    .with_orderbook_dumps(
+        config.debug.dump_orderbook_on_trade
+            .then(|| config.debug.orderbook_dump_levels.unwrap_or(10)),
+    )
+    .with_schedule(config.schedule.clone())
+    .with_balance_confirm(config.balance_confirm.clone())
+    .with_sell_config(config.sell.clone())
+    .with_resolution(config.resolution.clone())
+    .with_redemption(config.redemption.clone())
+    .with_sim(&config.sim)
+    .with_strategy(strategy.clone());
+    // Open trades survive a crash or restart (simulation always starts fresh)
+    let trader = if is_simulation {
+        trader
+    } else {
+        trader.with_trade_persistence(trader::PENDING_TRADES_FILE)?
+    };
     let trader_arc = Arc::new(trader);
     let trader_clone = trader_arc.clone();
+    if let Some(addr) = &config.dashboard.listen_addr {
+        let bound = dashboard::serve(addr, trader_arc.clone()).await?;
+        eprintln!("📊 PnL dashboard at http://{}/", bound);
+    }
     
+    // Baseline for account PnL (production only - simulation has no wallet)
+    if !is_simulation {
+        match trader_clone.snapshot_starting_balance().await {
+            Ok(balance) => crate::log_println!("💰 Starting balance for account PnL: ${:.2}", balance),
+            Err(e) => warn!("Could not snapshot USDC balance - account PnL disabled: {}", e),
+        }
+        if !config.polymarket.wallets.is_empty() {
+            match trader_clone.refresh_wallet_balances().await {
+                Ok(balances) => {
+                    for (label, balance) in balances {
+                        crate::log_println!("   👛 {}: ${:.2}", label, balance);
+                    }
+                }
+                Err(e) => warn!("Could not read wallet balances: {}", e),
+            }
+        }
+    }
+
     // Sync pending trades with portfolio on startup (check if tokens were already redeemed)
     crate::log_println!("🔄 Syncing pending trades with portfolio balance...");
     if let Err(e) = trader_clone.sync_trades_with_portfolio().await {
         warn!("Error syncing trades with portfolio: {}", e);
     }
     
+    // Read-only position export - before the startup flatten/adopt steps, which would change what is held
+    if args.positions {
+        let records = if is_simulation {
+            trader_clone.export_positions().await
+        } else {
+            let mut markets = current_markets.clone();
+            markets.extend(find_stale_markets(&api, &config).await);
+            trader_clone.export_held_positions(&markets).await
+        };
+        println!("{}", serde_json::to_string_pretty(&records)?);
+        return Ok(());
+    }
+    
+    // Leftover positions from earlier periods (e.g. after a crash) - redeem or sell them before trading
+    if !is_simulation && config.startup.flatten_stale_positions {
+        crate::log_println!("🧹 Startup flatten: checking for positions left over from earlier periods...");
+        let markets = find_stale_markets(&api, &config).await;
+        let report = trader_clone.flatten_stale_positions(&markets, config.startup.sell_unresolved).await;
+        crate::log_println!("🧹 Startup flatten: {} routed to redemption, {} sold, {} could not be checked",
+            report.redeeming, report.sold, report.failed);
+    }
+    
+    // Tokens held in the current markets that no trade tracks - log them, or adopt them with startup.adopt_untracked
+    if !is_simulation {
+        let report = trader_clone.check_untracked_positions(&current_markets, config.startup.adopt_untracked).await;
+        if report.found > 0 || report.failed > 0 {
+            crate::log_println!("👀 Untracked positions: {} found, {} adopted, {} could not be checked",
+                report.found, report.adopted, report.failed);
+        }
+    }
+    
+    // Live tuning: `kill -HUP <pid>` re-reads the config file and swaps in the trading settings that can change
+    // while running - positions and all other in-memory state are kept. The control socket's set-param goes
+    // through the same router, so both build on the same running config
+    #[cfg(unix)]
+    {
+        let control = Arc::new(ControlRouter::new(trader_clone.clone(), detector_arc.clone(), config.clone()));
+        if let Some(socket_path) = &config.control.socket_path {
+            control::serve(socket_path, control.clone()).await?;
+            eprintln!("🎛️  Control socket at {}", socket_path);
+        }
+        let path = args.config.clone();
+        tokio::spawn(async move {
+            reload_config_on_sighup(path, control).await;
+        });
+    }
+    
+    // Subscribe to the CLOB user channel for push-based fill detection (production only)
+    // Balance polling in check_pending_trades remains as the reconciliation fallback
+    if !is_simulation {
+        match api.subscribe_user_events().await {
+            Ok(events) => {
+                let trader_events = trader_clone.clone();
+                tokio::spawn(async move {
+                    trader_events.run_user_event_loop(events).await;
+                });
+            }
+            Err(e) => warn!("User channel unavailable, using balance polling only: {}", e),
+        }
+    }
+
     // Start a background task to check pending trades and sell points
     let trader_check = trader_clone.clone();
     tokio::spawn(async move {
@@ -287,29 +530,42 @@ async fn main() -> Result<()> {
         }
     });
 
+    // Trigger redemption as soon as an ended market resolves (production only - simulation resolves via its feed)
+    if let (false, Some(poll_ms)) = (is_simulation, config.trading.resolution_poll_interval_ms) {
+        let trader_resolution = trader_clone.clone();
+        tokio::spawn(async move {
+            trader_resolution.run_resolution_watcher(tokio::time::Duration::from_millis(poll_ms)).await;
+        });
+    }
+
     // Start a background task to detect new 15-minute periods and discover new markets
     let monitor_for_period_check = monitor_arc.clone();
     let api_for_period_check = api.clone();
     let trader_for_period_reset = trader_clone.clone();
-    let detector_for_period_reset = detector_arc.clone();
+    let strategy_for_period_reset = strategy.clone();
+    let feed_for_period_check = synthetic_feed.clone();
+    let config_for_period_check = config.clone();
     tokio::spawn(async move {
+        // Period whose market discovery ran out of retries - stay on the old markets until the next boundary
+        let mut gave_up_period: Option<u64> = None;
         loop {
             let current_time = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
             
-            let current_period = (current_time / 900) * 900;
+            let current_period = period::start(current_time);
             let current_market_timestamp = monitor_for_period_check.get_current_market_timestamp().await;
             
             // Check if we need to discover a new market (current market is from a different period)
-            if current_market_timestamp != current_period && current_market_timestamp != 0 {
+            if current_market_timestamp != current_period && current_market_timestamp != 0
+                && gave_up_period != Some(current_period) {
                 eprintln!("🔄 Market period mismatch detected! Current market: {}, Current period: {}", 
                     current_market_timestamp, current_period);
                 // Fall through to discover new market immediately
             } else {
                 // Calculate when next period starts
-                let next_period_timestamp = current_period + 900;
+                let next_period_timestamp = current_period + period::seconds();
             let sleep_duration = if next_period_timestamp > current_time {
                 next_period_timestamp - current_time
             } else {
@@ -337,44 +593,83 @@ async fn main() -> Result<()> {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            let current_period = (current_time / 900) * 900;
+            let current_period = period::start(current_time);
             
-            eprintln!("🔄 New 15-minute period detected! (Period: {}) Discovering new markets...", current_period);
+            eprintln!("🔄 New period detected! (Period: {}) Discovering new markets...", current_period);
+            
+            // Synthetic runs generate the next period's markets instead of discovering them
+            if let Some(feed) = &feed_for_period_check {
+                let (eth_market, btc_market, solana_market, xrp_market) =
+                    create_synthetic_markets(feed, &config_for_period_check, current_period);
+                if let Err(e) = monitor_for_period_check.update_markets(eth_market, btc_market, solana_market, xrp_market).await {
+                    warn!("Failed to update markets: {}", e);
+                } else {
+                    trader_for_period_reset.reset_period(current_market_timestamp).await;
+                    strategy_for_period_reset.reset_period().await;
+                }
+                continue;
+            }
             
-            let mut seen_ids = std::collections::HashSet::new();
+            // The new period's markets are often listed a few seconds after the boundary - retry with backoff
+            let retry_window = tokio::time::Duration::from_secs(
+                config_for_period_check.discovery.rotation_retry_secs.unwrap_or(DEFAULT_ROTATION_RETRY_SECS));
+            let retry_started = std::time::Instant::now();
+            let mut backoff = tokio::time::Duration::from_secs(1);
             let (eth_id, btc_id) = monitor_for_period_check.get_current_condition_ids().await;
-            seen_ids.insert(eth_id);
-            seen_ids.insert(btc_id);
+            let discovered = loop {
+                let mut seen_ids = std::collections::HashSet::new();
+                seen_ids.insert(eth_id.clone());
+                seen_ids.insert(btc_id.clone());
+                
+                // Discover ETH and BTC for the new period (Solana/XRP below may return fallback)
+                let eth_result = discover_market(&api_for_period_check, "ETH", &["eth"], current_time, &mut seen_ids, &config_for_period_check.discovery).await;
+                let btc_result = discover_market(&api_for_period_check, "BTC", &["btc"], current_time, &mut seen_ids, &config_for_period_check.discovery).await;
+                let error = match (eth_result, btc_result) {
+                    (Ok(eth_market), Ok(btc_market)) => break Some((eth_market, btc_market, seen_ids)),
+                    (Err(e), _) => format!("Failed to discover new ETH market: {}", e),
+                    (_, Err(e)) => format!("Failed to discover new BTC market: {}", e),
+                };
+                if retry_started.elapsed() + backoff > retry_window {
+                    warn!("{} - giving up after {}s, staying on the previous period's markets",
+                        error, retry_started.elapsed().as_secs());
+                    break None;
+                }
+                eprintln!("⏳ {} - retrying in {}s", error, backoff.as_secs());
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(tokio::time::Duration::from_secs(ROTATION_RETRY_MAX_BACKOFF_SECS));
+            };
             
-            // Discover ETH, BTC, Solana, and XRP for the new period (Solana/XRP may return fallback)
-            let eth_result = discover_market(&api_for_period_check, "ETH", &["eth"], current_time, &mut seen_ids).await;
-            let btc_result = discover_market(&api_for_period_check, "BTC", &["btc"], current_time, &mut seen_ids).await;
-            let solana_market = discover_solana_market(&api_for_period_check, current_time, &mut seen_ids).await;
-            let xrp_market = discover_xrp_market(&api_for_period_check, current_time, &mut seen_ids).await;
+            let Some((eth_market, btc_market, mut seen_ids)) = discovered else {
+                gave_up_period = Some(current_period);
+                continue;
+            };
+            let solana_market = discover_solana_market(&api_for_period_check, current_time, &mut seen_ids, &config_for_period_check.discovery).await;
+            let xrp_market = discover_xrp_market(&api_for_period_check, current_time, &mut seen_ids, &config_for_period_check.discovery).await;
             
-            match (eth_result, btc_result) {
-                (Ok(eth_market), Ok(btc_market)) => {
-                    if let Err(e) = monitor_for_period_check.update_markets(eth_market, btc_market, solana_market, xrp_market).await {
-                                warn!("Failed to update markets: {}", e);
-                            } else {
-                                trader_for_period_reset.reset_period(current_market_timestamp).await;
-                                detector_for_period_reset.reset_period().await;
-                            }
-                        }
-                (Err(e), _) => warn!("Failed to discover new ETH market: {}", e),
-                (_, Err(e)) => warn!("Failed to discover new BTC market: {}", e),
+            if let Err(e) = check_market_assets(&config_for_period_check, &[
+                ("ETH", &eth_market), ("BTC", &btc_market), ("Solana", &solana_market), ("XRP", &xrp_market),
+            ]) {
+                warn!("Not switching to the new period's markets: {}", e);
+            } else if let Err(e) = monitor_for_period_check.update_markets(eth_market, btc_market, solana_market, xrp_market).await {
+                warn!("Failed to update markets: {}", e);
+            } else {
+                trader_for_period_reset.reset_period(current_market_timestamp).await;
+                strategy_for_period_reset.reset_period().await;
             }
         }
     });
     
     // Start monitoring with detector (BTC, ETH, and optionally Solana trading enabled)
-    monitor_arc.start_monitoring(move |snapshot| {
-        let detector = detector_clone.clone();
+    let trader_for_stop = trader_arc.clone();
+    let buy_stagger_ms = config.buy.stagger_ms.unwrap_or(0);
+    let monitoring = monitor_arc.start_monitoring(move |snapshot| {
+        let strategy = strategy.clone();
         let trader = trader_clone.clone();
         
         async move {
             // Detect all opportunities (BTC Up/Down, ETH Up/Down) so we can buy both ETH Down and BTC Down when both qualify
-            let opportunities = detector.detect_opportunities(&snapshot).await;
+            let state = trader.strategy_state(snapshot.period_timestamp).await;
+            let opportunities = strategy.evaluate(&snapshot, &state).await;
             if opportunities.is_empty() {
                 return;
             }
@@ -384,6 +679,7 @@ async fn main() -> Result<()> {
                 trader.cleanup_old_abandoned_trades(first.period_timestamp).await;
             }
 
+            let mut entered = 0;
             for opportunity in opportunities {
                 // Only allow one position per token type (BTC or ETH) per market period
                 if trader.has_active_position(opportunity.period_timestamp, opportunity.token_type.clone()).await {
@@ -398,19 +694,103 @@ async fn main() -> Result<()> {
                     continue;
                 }
                 
-                if let Err(e) = trader.execute_buy(&opportunity).await {
+                // buy.stagger_ms: spread simultaneous entries out; the first (or only) one goes out immediately
+                if entered > 0 && buy_stagger_ms > 0 {
+                    tokio::time::sleep(stagger_delay(buy_stagger_ms)).await;
+                }
+                entered += 1;
+                if let Err(e) = trader.enter(&opportunity).await {
                     warn!("Error executing buy: {}", e);
                 }
             }
         }
-    }).await;
+    });
+
+    // Run until monitoring ends, max_periods is reached and all positions have settled, or Ctrl-C
+    tokio::select! {
+        _ = monitoring => {}
+        _ = wait_for_max_periods(&trader_for_stop, config.trading.max_periods) => {
+            trader_for_stop.print_trade_summary().await;
+            crate::log_println!("🏁 Completed {} period(s) (max_periods reached) - exiting", 
+                trader_for_stop.completed_period_count().await);
+        }
+        _ = tokio::signal::ctrl_c() => {
+            // Ctrl-C: take our orders off the book before exiting (monitoring - and with it new entries - stops here)
+            crate::log_println!("🛑 SIGINT received - cancelling open orders and shutting down");
+            let report = trader_for_stop.shutdown().await;
+            crate::log_println!("   Cancelled {} open order(s){}", report.cancelled,
+                if report.failed > 0 { format!(", {} failed - check the book manually", report.failed) } else { String::new() });
+            trader_for_stop.print_trade_summary().await;
+            crate::log_println!("👋 Shutdown complete");
+        }
+    }
+    trader_for_stop.save_pending_trades().await;
 
     Ok(())
 }
 
+/// On every SIGHUP: re-read and validate the config file, then apply it through the control router, which swaps
+/// its hot-swappable trading settings into the running trader and detector and logs what changed. Changes to
+/// anything else are logged as needing a restart (the running value is kept); a file that is missing or doesn't
+/// parse or validate is rejected as a whole
+#[cfg(unix)]
+async fn reload_config_on_sighup(path: std::path::PathBuf, control: Arc<ControlRouter>) {
+    let mut hangups = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(e) => {
+            warn!("SIGHUP config reload unavailable: {}", e);
+            return;
+        }
+    };
+    while hangups.recv().await.is_some() {
+        crate::log_println!("🔁 SIGHUP: reloading {}", path.display());
+        let new = match Config::read(&path).and_then(|new| new.validate().map(|_| new)) {
+            Ok(new) => new,
+            Err(e) => {
+                warn!("Config reload rejected, keeping the running settings: {:#}", e);
+                continue;
+            }
+        };
+        if let Err(e) = control.apply(&new, "Config reload").await {
+            warn!("Config reload rejected, keeping the running settings: {:#}", e);
+        }
+    }
+}
+
+/// Random pause of 50-100% of `stagger_ms` between consecutive buys (buy.stagger_ms)
+fn stagger_delay(stagger_ms: u64) -> std::time::Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .subsec_nanos() as u64;
+    let half = stagger_ms / 2;
+    std::time::Duration::from_millis(stagger_ms - half + nanos % (half + 1))
+}
+
+/// Resolves once max_periods periods have ended and no open positions remain (never resolves if unset)
+async fn wait_for_max_periods(trader: &Trader, max_periods: Option<u64>) {
+    if max_periods.is_none() {
+        return std::future::pending().await;
+    }
+    let mut announced = false;
+    loop {
+        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+        if !trader.max_periods_reached().await {
+            continue;
+        }
+        if !announced {
+            crate::log_println!("🛑 max_periods reached - no new buys, waiting for open positions to settle");
+            announced = true;
+        }
+        if !trader.has_open_positions().await {
+            return;
+        }
+    }
+}
+
 async fn get_or_discover_markets(
     api: &PolymarketApi,
-    _config: &Config,
+    config: &Config,
 ) -> Result<(crate::models::Market, crate::models::Market, crate::models::Market, crate::models::Market)> {
     
     let current_time = std::time::SystemTime::now()
@@ -422,7 +802,7 @@ async fn get_or_discover_markets(
     let mut seen_ids = std::collections::HashSet::new();
     
     // Discover ETH, BTC, and Solana markets (each can try multiple slug prefixes, e.g. Solana: ["solana","sol"])
-    let eth_market = discover_market(api, "ETH", &["eth"], current_time, &mut seen_ids).await
+    let eth_market = discover_market(api, "ETH", &["eth"], current_time, &mut seen_ids, &config.discovery).await
         .unwrap_or_else(|_| {
             // If ETH market discovery fails, create a minimal market struct as fallback
             eprintln!("⚠️  Could not discover ETH market - using fallback");
@@ -444,17 +824,36 @@ async fn get_or_discover_markets(
     seen_ids.insert(eth_market.condition_id.clone());
     
     eprintln!("🔍 Discovering BTC market...");
-    let btc_market = discover_market(api, "BTC", &["btc"], current_time, &mut seen_ids).await
+    let btc_market = discover_market(api, "BTC", &["btc"], current_time, &mut seen_ids, &config.discovery).await
         .context("Failed to discover BTC market")?;
     seen_ids.insert(btc_market.condition_id.clone());
 
     // Discover Solana market
     eprintln!("🔍 Discovering Solana market...");
-    let solana_market = discover_solana_market(api, current_time, &mut seen_ids).await;
+    let solana_market = discover_solana_market(api, current_time, &mut seen_ids, &config.discovery).await;
 
     // Discover XRP market
     eprintln!("🔍 Discovering XRP market...");
-    let xrp_market = discover_xrp_market(api, current_time, &mut seen_ids).await;
+    let xrp_market = discover_xrp_market(api, current_time, &mut seen_ids, &config.discovery).await;
+
+    // discovery.strict: an enabled asset running on a fallback market is fatal (BTC already fails above)
+    if config.discovery.strict {
+        let missing: Vec<&str> = [
+            ("ETH", config.trading.enable_eth_trading, &eth_market),
+            ("Solana", config.trading.enable_solana_trading, &solana_market),
+            ("XRP", config.trading.enable_xrp_trading, &xrp_market),
+        ]
+        .into_iter()
+        .filter(|(_, enabled, market)| *enabled && market.condition_id.ends_with("_fallback"))
+        .map(|(name, _, _)| name)
+        .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("discovery.strict: could not discover market(s) for enabled asset(s): {}. Set the condition IDs in config.json, disable those assets, or set discovery.strict = false to run without them",
+                missing.join(", "));
+        }
+    }
+
+    check_market_assets(config, &[("ETH", &eth_market), ("BTC", &btc_market), ("Solana", &solana_market), ("XRP", &xrp_market)])?;
 
     if eth_market.condition_id == btc_market.condition_id && eth_market.condition_id != "dummy_eth_fallback" {
         anyhow::bail!("ETH and BTC markets have the same condition ID: {}. This is incorrect. Please set condition IDs manually in config.json", eth_market.condition_id);
@@ -482,15 +881,76 @@ async fn get_or_discover_markets(
     Ok((eth_market, btc_market, solana_market, xrp_market))
 }
 
+/// discovery.verify_asset: refuse (strict) or warn about a market whose question/slug doesn't name its asset,
+/// e.g. a misconfigured condition ID pointing BTC trading at an ETH market. Fallback markets are skipped
+fn check_market_assets(config: &Config, markets: &[(&str, &crate::models::Market)]) -> Result<()> {
+    if !config.discovery.verify_asset.unwrap_or(true) {
+        return Ok(());
+    }
+    let mismatched: Vec<String> = markets.iter()
+        .filter(|(asset, market)| !market.condition_id.ends_with("_fallback") && !market.names_asset(asset))
+        .map(|(asset, market)| format!("{} -> \"{}\" ({})", asset, market.question, market.slug))
+        .collect();
+    if mismatched.is_empty() {
+        return Ok(());
+    }
+    if config.discovery.strict {
+        anyhow::bail!("discovery.verify_asset: market metadata doesn't match the asset: {}. Check the condition IDs in config.json",
+            mismatched.join("; "));
+    }
+    for mismatch in &mismatched {
+        warn!("⚠️  Market metadata doesn't match the asset: {} - check the condition ID in config.json", mismatch);
+    }
+    Ok(())
+}
+
+/// Create and register synthetic ETH, BTC, Solana and XRP markets for a period (--synthetic)
+/// Each market lasts one period: scripted paths come from synthetic_paths, the rest are random walks
+fn create_synthetic_markets(
+    feed: &SyntheticPriceFeed,
+    config: &Config,
+    period: u64,
+) -> (crate::models::Market, crate::models::Market, crate::models::Market, crate::models::Market) {
+    let steps_per_period = period::seconds() * 1000 / config.trading.check_interval_ms.max(1);
+    let volatility = config.trading.synthetic_volatility.unwrap_or(0.01);
+    let create = |asset: &str, name: &str| {
+        let condition_id = format!("synthetic_{}_condition_{}", asset, period);
+        let up_token_id = format!("synthetic_{}_up_token_{}", asset, period);
+        let down_token_id = format!("synthetic_{}_down_token_{}", asset, period);
+        let path = match config.trading.synthetic_paths.as_ref().and_then(|paths| paths.get(asset)) {
+            Some(prices) if !prices.is_empty() => SyntheticPath::Scripted(prices.clone()),
+            _ => SyntheticPath::RandomWalk { start: 0.5, volatility, steps: steps_per_period },
+        };
+        feed.add_market(&condition_id, &up_token_id, &down_token_id, path);
+        eprintln!("🧪 Synthetic {} market: {}", name, condition_id);
+        crate::models::Market {
+            condition_id,
+            slug: period::slug(asset, period),
+            active: true,
+            closed: false,
+            market_id: None,
+            question: format!("{} Up or Down (synthetic)", name),
+            resolution_source: None,
+            end_date_iso: None,
+            end_date_iso_alt: None,
+            tokens: None,
+            clob_token_ids: None,
+            outcomes: None,
+        }
+    };
+    (create("eth", "ETH"), create("btc", "BTC"), create("solana", "Solana"), create("xrp", "XRP"))
+}
+
 /// Discover Solana 15m market. Tries slug prefixes ["solana", "sol"] via discover_market.
 /// Returns a dummy fallback if not found so the bot can run without Solana.
 async fn discover_solana_market(
     api: &PolymarketApi,
     current_time: u64,
     seen_ids: &mut std::collections::HashSet<String>,
+    discovery: &polymarket_arbitrage_bot::config::DiscoveryConfig,
 ) -> crate::models::Market {
     eprintln!("🔍 Discovering Solana market...");
-    if let Ok(market) = discover_market(api, "Solana", &["solana", "sol"], current_time, seen_ids).await {
+    if let Ok(market) = discover_market(api, "Solana", &["solana", "sol"], current_time, seen_ids, discovery).await {
         return market;
     }
     eprintln!("⚠️  Could not discover Solana 15-minute market (tried: solana, sol). Using fallback - Solana trading disabled for this run.");
@@ -517,9 +977,10 @@ async fn discover_xrp_market(
     api: &PolymarketApi,
     current_time: u64,
     seen_ids: &mut std::collections::HashSet<String>,
+    discovery: &polymarket_arbitrage_bot::config::DiscoveryConfig,
 ) -> crate::models::Market {
     eprintln!("🔍 Discovering XRP market...");
-    if let Ok(market) = discover_market(api, "XRP", &["xrp"], current_time, seen_ids).await {
+    if let Ok(market) = discover_market(api, "XRP", &["xrp"], current_time, seen_ids, discovery).await {
         return market;
     }
     eprintln!("⚠️  Could not discover XRP 15-minute market (tried: xrp). Using fallback - XRP trading disabled for this run.");
@@ -540,6 +1001,161 @@ async fn discover_xrp_market(
     }
 }
 
+/// Enabled assets as (display name, asset key, slug prefixes in the order discovery tries them); BTC is always on
+fn enabled_assets(config: &Config) -> Vec<(&'static str, &'static str, &'static [&'static str])> {
+    let t = &config.trading;
+    [
+        ("BTC", "btc", &["btc"][..], true),
+        ("ETH", "eth", &["eth"][..], t.enable_eth_trading),
+        ("Solana", "solana", &["solana", "sol"][..], t.enable_solana_trading),
+        ("XRP", "xrp", &["xrp"][..], t.enable_xrp_trading),
+    ].into_iter()
+        .filter(|(_, _, _, enabled)| *enabled)
+        .map(|(name, asset_key, prefixes, _)| (name, asset_key, prefixes))
+        .collect()
+}
+
+/// --validate-markets: try every slug discovery would try for each enabled asset and print what Polymarket
+/// returns for it (condition ID, active/closed, token IDs, top of book) and which market discovery would pick
+/// Read-only - places no orders. Errors if an enabled asset has no usable market
+async fn validate_markets(api: &PolymarketApi, config: &Config) -> Result<()> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let current_period = period::start(now);
+    let mut seen_ids = std::collections::HashSet::new();
+    let mut missing = Vec::new();
+
+    println!("Market discovery report (current period: {}, {}s in)", current_period, now - current_period);
+    for (name, _, prefixes) in enabled_assets(config) {
+        println!();
+        println!("═══ {} (slug prefixes: {}) ═══", name, prefixes.join(", "));
+        let mut chosen: Option<crate::models::Market> = None;
+        for prefix in prefixes {
+            for offset in 0..=3u64 {
+                let period = current_period - offset * period::seconds();
+                let slug = period::slug(prefix, period);
+                let age = if offset == 0 { "current period".to_string() } else { format!("{} period(s) ago", offset) };
+                let market = match api.get_market_by_slug(&slug).await {
+                    Ok(market) => market,
+                    Err(e) => {
+                        println!("  ❌ {} ({}): not found - {}", slug, age, e);
+                        continue;
+                    }
+                };
+                println!("  ✅ {} ({}): condition {} | active: {} | closed: {}",
+                    slug, age, market.condition_id, market.active, market.closed);
+                println!("     Question: {}", market.question);
+                let outcomes: Vec<String> = market.outcomes.as_deref()
+                    .and_then(|outcomes| serde_json::from_str(outcomes).ok())
+                    .unwrap_or_default();
+                let token_ids = market.token_ids();
+                if token_ids.is_empty() {
+                    println!("     ⚠️  No token IDs listed");
+                }
+                for (i, token_id) in token_ids.iter().enumerate() {
+                    let outcome = outcomes.get(i).cloned().unwrap_or_else(|| format!("Token {}", i + 1));
+                    let book = match api.get_orderbook(token_id).await {
+                        Ok(book) => {
+                            let level = |entry: Option<&crate::models::OrderBookEntry>| entry
+                                .map(|e| format!("${} x {}", e.price, e.size))
+                                .unwrap_or_else(|| "none".to_string());
+                            format!("best bid {} | best ask {}", level(book.best_bid()), level(book.best_ask()))
+                        }
+                        Err(e) => format!("orderbook unavailable - {}", e),
+                    };
+                    println!("     {} token {}: {}", outcome, token_id, book);
+                }
+                if chosen.is_none() && market.active && !market.closed && !seen_ids.contains(&market.condition_id) {
+                    chosen = Some(market);
+                }
+            }
+        }
+        match chosen {
+            Some(market) => {
+                println!("  ➡️  Discovery would use {} ({}){}", market.slug, market.condition_id,
+                    if config.discovery.require_book { " once its orderbook is non-empty (discovery.require_book)" } else { "" });
+                seen_ids.insert(market.condition_id);
+            }
+            None => {
+                println!("  ❌ No usable market: none of these slugs is an active, open {} market", name);
+                missing.push(name);
+            }
+        }
+    }
+
+    println!();
+    if !missing.is_empty() {
+        anyhow::bail!("no usable market for enabled asset(s): {} - see the report above for the slugs Polymarket returned",
+            missing.join(", "));
+    }
+    println!("✅ Every enabled asset has a usable market");
+    Ok(())
+}
+
+/// Markets of the last startup.lookback_periods periods (before the current one) for every enabled asset
+async fn find_stale_markets(api: &PolymarketApi, config: &Config) -> Vec<polymarket_arbitrage_bot::trader::PeriodMarket> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let current_period = period::start(now);
+    let lookback = config.startup.lookback_periods.unwrap_or(DEFAULT_STARTUP_LOOKBACK_PERIODS);
+    
+    let mut markets = Vec::new();
+    for (_, asset_key, prefixes) in enabled_assets(config) {
+        for offset in 1..=lookback {
+            let period = current_period - offset * period::seconds();
+            for prefix in prefixes {
+                if let Ok(market) = api.get_market_by_slug(&period::slug(prefix, period)).await {
+                    markets.push(polymarket_arbitrage_bot::trader::PeriodMarket {
+                        asset_key: asset_key.to_string(),
+                        period_timestamp: period,
+                        condition_id: market.condition_id,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+    markets
+}
+
+/// discovery.require_book: accept the market only if its first token's book has a bid or ask, or once
+/// empty_book_grace_secs have passed since its period started (then with a warning)
+async fn book_ready(
+    api: &PolymarketApi,
+    market_name: &str,
+    market: &crate::models::Market,
+    period_start: u64,
+    current_time: u64,
+    discovery: &polymarket_arbitrage_bot::config::DiscoveryConfig,
+) -> bool {
+    if !discovery.require_book {
+        return true;
+    }
+    let Some(token_id) = market.token_ids().into_iter().next() else {
+        warn!("{} market {} lists no token ids - can't check its orderbook", market_name, market.slug);
+        return true;
+    };
+    let empty = match api.get_orderbook(&token_id).await {
+        Ok(book) if !book.bids.is_empty() || !book.asks.is_empty() => return true,
+        Ok(_) => "its orderbook is empty".to_string(),
+        Err(e) => format!("its orderbook couldn't be read ({})", e),
+    };
+    let into_period = current_time.saturating_sub(period_start);
+    let grace = discovery.empty_book_grace_secs.unwrap_or(DEFAULT_EMPTY_BOOK_GRACE_SECS);
+    if into_period >= grace {
+        warn!("{} market {}: {} {}s into the period - accepting it anyway (empty_book_grace_secs = {})",
+            market_name, market.slug, empty, into_period, grace);
+        return true;
+    }
+    eprintln!("⏳ {} market {}: {} {}s into the period - not using it yet (accepted after {}s)",
+        market_name, market.slug, empty, into_period, grace);
+    false
+}
+
 /// Discover a 15-minute up/down market by trying each slug prefix in order.
 /// For each prefix: try current period, then previous 3 periods.
 /// Pattern: {prefix}-updown-15m-{timestamp} (e.g. btc-updown-15m-1769116500, sol-updown-15m-1769116500).
@@ -549,8 +1165,9 @@ async fn discover_market(
     slug_prefixes: &[&str],
     current_time: u64,
     seen_ids: &mut std::collections::HashSet<String>,
+    discovery: &polymarket_arbitrage_bot::config::DiscoveryConfig,
 ) -> Result<crate::models::Market> {
-    let rounded_time = (current_time / 900) * 900; // Round to nearest 15 minutes
+    let rounded_time = period::start(current_time);
 
     for (i, prefix) in slug_prefixes.iter().enumerate() {
         if i > 0 {
@@ -558,9 +1175,10 @@ async fn discover_market(
         }
 
         // Try current period with this prefix
-        let slug = format!("{}-updown-15m-{}", prefix, rounded_time);
+        let slug = period::slug(prefix, rounded_time);
     if let Ok(market) = api.get_market_by_slug(&slug).await {
-        if !seen_ids.contains(&market.condition_id) && market.active && !market.closed {
+        if !seen_ids.contains(&market.condition_id) && market.active && !market.closed
+            && book_ready(api, market_name, &market, rounded_time, current_time, discovery).await {
             eprintln!("Found {} market by slug: {} | Condition ID: {}", market_name, market.slug, market.condition_id);
             return Ok(market);
         }
@@ -568,11 +1186,12 @@ async fn discover_market(
     
         // Try previous periods with this prefix
     for offset in 1..=3 {
-            let try_time = rounded_time - (offset * 900);
-            let try_slug = format!("{}-updown-15m-{}", prefix, try_time);
+            let try_time = rounded_time - (offset * period::seconds());
+            let try_slug = period::slug(prefix, try_time);
         eprintln!("Trying previous {} market by slug: {}", market_name, try_slug);
         if let Ok(market) = api.get_market_by_slug(&try_slug).await {
-            if !seen_ids.contains(&market.condition_id) && market.active && !market.closed {
+            if !seen_ids.contains(&market.condition_id) && market.active && !market.closed
+                && book_ready(api, market_name, &market, try_time, current_time, discovery).await {
                 eprintln!("Found {} market by slug: {} | Condition ID: {}", market_name, market.slug, market.condition_id);
                 return Ok(market);
             }
@@ -0,0 +1,280 @@
+//! Prometheus metrics for alerting on bot health from outside the process.
+//!
+//! Counters and gauges live in one process-wide `Metrics` value that the trader, the API client and the
+//! monitor update as they go. `serve` exposes them in the Prometheus text format on `GET /metrics`
+//! (enabled by `metrics.listen_addr`).
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Upper bounds (seconds) of the order latency histogram buckets
+const ORDER_LATENCY_BUCKETS: [f64; 8] = [0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0];
+/// Longest request head read before answering; anything longer is answered from what was read
+const MAX_REQUEST_BYTES: usize = 8192;
+
+static METRICS: Metrics = Metrics::new();
+
+/// Request and error counts for one API call kind
+#[derive(Debug, Clone, Copy, Default)]
+struct ApiCalls {
+    requests: u64,
+    errors: u64,
+}
+
+#[derive(Debug)]
+struct LatencyHistogram {
+    buckets: [u64; ORDER_LATENCY_BUCKETS.len()], // Non-cumulative counts; rendered cumulatively
+    sum_secs: f64,
+    count: u64,
+}
+
+struct Metrics {
+    buys_executed: AtomicU64,
+    sells_executed: AtomicU64,
+    settlements_won: AtomicU64,
+    settlements_lost: AtomicU64,
+    realized_pnl_bits: AtomicU64, // f64 bits - running sum of settled PnL since start
+    open_positions: AtomicU64,
+    market_period: AtomicU64,
+    time_remaining_secs: AtomicU64,
+    last_snapshot_unix: AtomicU64,
+    api_calls: Mutex<BTreeMap<&'static str, ApiCalls>>,
+    order_latency: Mutex<LatencyHistogram>,
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            buys_executed: AtomicU64::new(0),
+            sells_executed: AtomicU64::new(0),
+            settlements_won: AtomicU64::new(0),
+            settlements_lost: AtomicU64::new(0),
+            realized_pnl_bits: AtomicU64::new(0), // 0.0_f64
+            open_positions: AtomicU64::new(0),
+            market_period: AtomicU64::new(0),
+            time_remaining_secs: AtomicU64::new(0),
+            last_snapshot_unix: AtomicU64::new(0),
+            api_calls: Mutex::new(BTreeMap::new()),
+            order_latency: Mutex::new(LatencyHistogram {
+                buckets: [0; ORDER_LATENCY_BUCKETS.len()],
+                sum_secs: 0.0,
+                count: 0,
+            }),
+        }
+    }
+}
+
+/// Count one API call and whether it failed
+pub fn record_api_call(method: &'static str, ok: bool) {
+    let mut calls = METRICS.api_calls.lock().unwrap();
+    let entry = calls.entry(method).or_default();
+    entry.requests += 1;
+    if !ok {
+        entry.errors += 1;
+    }
+}
+
+/// Await an API call, counting it (and its failure) under `method`
+pub async fn observe_api<T>(method: &'static str, call: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    let result = call.await;
+    record_api_call(method, result.is_ok());
+    result
+}
+
+/// Await an order placement, recording its latency and, if the exchange accepted it, an executed trade on `side`
+pub async fn observe_order<T>(method: &'static str, side: &str, call: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    let started = std::time::Instant::now();
+    let result = observe_api(method, call).await;
+    record_order_latency(started.elapsed());
+    if result.is_ok() {
+        let counter = if side.eq_ignore_ascii_case("SELL") { &METRICS.sells_executed } else { &METRICS.buys_executed };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+    result
+}
+
+fn record_order_latency(elapsed: Duration) {
+    let secs = elapsed.as_secs_f64();
+    let mut histogram = METRICS.order_latency.lock().unwrap();
+    if let Some(bucket) = ORDER_LATENCY_BUCKETS.iter().position(|bound| secs <= *bound) {
+        histogram.buckets[bucket] += 1;
+    }
+    histogram.sum_secs += secs;
+    histogram.count += 1;
+}
+
+/// Count a settled position (sell or resolution) and add its PnL to the realized total
+pub fn record_settlement(pnl: f64) {
+    let counter = if pnl >= 0.0 { &METRICS.settlements_won } else { &METRICS.settlements_lost };
+    counter.fetch_add(1, Ordering::Relaxed);
+    let _ = METRICS.realized_pnl_bits.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+        Some((f64::from_bits(bits) + pnl).to_bits())
+    });
+}
+
+/// Filled positions still waiting to be sold or redeemed
+pub fn set_open_positions(count: usize) {
+    METRICS.open_positions.store(count as u64, Ordering::Relaxed);
+}
+
+/// The period of the latest market snapshot and its time to close
+pub fn set_market_period(period_timestamp: u64, time_remaining_seconds: u64) {
+    METRICS.market_period.store(period_timestamp, Ordering::Relaxed);
+    METRICS.time_remaining_secs.store(time_remaining_seconds, Ordering::Relaxed);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    METRICS.last_snapshot_unix.store(now, Ordering::Relaxed);
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", name, help, name, kind));
+}
+
+/// All metrics in the Prometheus text exposition format
+pub fn render() -> String {
+    let m = &METRICS;
+    let mut out = String::new();
+
+    write_header(&mut out, "polymarket_bot_trades_executed_total", "counter", "Orders accepted by the exchange");
+    out.push_str(&format!("polymarket_bot_trades_executed_total{{side=\"buy\"}} {}\n", m.buys_executed.load(Ordering::Relaxed)));
+    out.push_str(&format!("polymarket_bot_trades_executed_total{{side=\"sell\"}} {}\n", m.sells_executed.load(Ordering::Relaxed)));
+
+    write_header(&mut out, "polymarket_bot_settlements_total", "counter", "Positions settled by a sell or market resolution");
+    out.push_str(&format!("polymarket_bot_settlements_total{{result=\"win\"}} {}\n", m.settlements_won.load(Ordering::Relaxed)));
+    out.push_str(&format!("polymarket_bot_settlements_total{{result=\"loss\"}} {}\n", m.settlements_lost.load(Ordering::Relaxed)));
+
+    write_header(&mut out, "polymarket_bot_realized_pnl_usd", "gauge", "Realized PnL of settled positions since start (USD)");
+    out.push_str(&format!("polymarket_bot_realized_pnl_usd {}\n", f64::from_bits(m.realized_pnl_bits.load(Ordering::Relaxed))));
+
+    write_header(&mut out, "polymarket_bot_open_positions", "gauge", "Filled positions waiting to be sold or redeemed");
+    out.push_str(&format!("polymarket_bot_open_positions {}\n", m.open_positions.load(Ordering::Relaxed)));
+
+    write_header(&mut out, "polymarket_bot_market_period_timestamp", "gauge", "Start (unix seconds) of the monitored market period");
+    out.push_str(&format!("polymarket_bot_market_period_timestamp {}\n", m.market_period.load(Ordering::Relaxed)));
+    write_header(&mut out, "polymarket_bot_market_time_remaining_seconds", "gauge", "Seconds until the monitored period closes");
+    out.push_str(&format!("polymarket_bot_market_time_remaining_seconds {}\n", m.time_remaining_secs.load(Ordering::Relaxed)));
+    write_header(&mut out, "polymarket_bot_last_snapshot_timestamp_seconds", "gauge", "Unix time of the latest market snapshot");
+    out.push_str(&format!("polymarket_bot_last_snapshot_timestamp_seconds {}\n", m.last_snapshot_unix.load(Ordering::Relaxed)));
+
+    let calls = m.api_calls.lock().unwrap().clone();
+    write_header(&mut out, "polymarket_bot_api_requests_total", "counter", "API calls by method");
+    for (method, c) in &calls {
+        out.push_str(&format!("polymarket_bot_api_requests_total{{method=\"{}\"}} {}\n", method, c.requests));
+    }
+    write_header(&mut out, "polymarket_bot_api_errors_total", "counter", "Failed API calls by method");
+    for (method, c) in &calls {
+        out.push_str(&format!("polymarket_bot_api_errors_total{{method=\"{}\"}} {}\n", method, c.errors));
+    }
+
+    let histogram = m.order_latency.lock().unwrap();
+    write_header(&mut out, "polymarket_bot_order_latency_seconds", "histogram", "Time to place an order, accepted or not");
+    let mut cumulative = 0;
+    for (bound, count) in ORDER_LATENCY_BUCKETS.iter().zip(histogram.buckets.iter()) {
+        cumulative += count;
+        out.push_str(&format!("polymarket_bot_order_latency_seconds_bucket{{le=\"{}\"}} {}\n", bound, cumulative));
+    }
+    out.push_str(&format!("polymarket_bot_order_latency_seconds_bucket{{le=\"+Inf\"}} {}\n", histogram.count));
+    out.push_str(&format!("polymarket_bot_order_latency_seconds_sum {}\n", histogram.sum_secs));
+    out.push_str(&format!("polymarket_bot_order_latency_seconds_count {}\n", histogram.count));
+    out
+}
+
+/// Bind `listen_addr` (e.g. "127.0.0.1:9464") and answer `GET /metrics` in the background
+/// Returns the bound address; other paths get 404
+pub async fn serve(listen_addr: &str) -> Result<SocketAddr> {
+    let listener = tokio::net::TcpListener::bind(listen_addr).await
+        .with_context(|| format!("Failed to bind metrics endpoint on {}", listen_addr))?;
+    let local_addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream).await {
+                            log::debug!("Metrics request failed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => log::warn!("Metrics endpoint accept failed: {}", e),
+            }
+        }
+    });
+    Ok(local_addr)
+}
+
+async fn handle_connection(mut stream: tokio::net::TcpStream) -> Result<()> {
+    let (method, path) = read_request(&mut stream).await?;
+    if method == "GET" && path == "/metrics" {
+        respond(&mut stream, "200 OK", "text/plain; version=0.0.4", &render()).await
+    } else {
+        respond(&mut stream, "404 Not Found", "text/plain", "not found\n").await
+    }
+}
+
+/// Read an HTTP request head and return its method and path (query string dropped)
+pub(crate) async fn read_request(stream: &mut tokio::net::TcpStream) -> Result<(String, String)> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("").to_string();
+    let path = request_line.next().unwrap_or("").split('?').next().unwrap_or("").to_string();
+    Ok((method, path))
+}
+
+/// Write a complete response and close the connection
+pub(crate) async fn respond(stream: &mut tokio::net::TcpStream, status: &str, content_type: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn metrics_endpoint_serves_recorded_values_in_prometheus_format() {
+        record_api_call("dummy_metrics_method", true);
+        record_api_call("dummy_metrics_method", false);
+        observe_order("dummy_metrics_order", "SELL", async { Ok(()) }).await.unwrap();
+        set_market_period(1_700_000_100, 240);
+
+        let addr = serve("127.0.0.1:0").await.unwrap();
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("polymarket_bot_api_requests_total{method=\"dummy_metrics_method\"} 2\n"));
+        assert!(response.contains("polymarket_bot_api_errors_total{method=\"dummy_metrics_method\"} 1\n"));
+        assert!(response.contains("polymarket_bot_market_period_timestamp 1700000100\n"));
+        assert!(response.contains("# TYPE polymarket_bot_order_latency_seconds histogram\n"));
+        assert!(response.contains("polymarket_bot_order_latency_seconds_bucket{le=\"+Inf\"}"));
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 404"));
+    }
+}
@@ -23,6 +23,31 @@ pub struct Market {
     pub outcomes: Option<String>, // JSON string array like "[\"Up\", \"Down\"]"
 }
 
+impl Market {
+    /// Whether the question or slug names `asset` ("BTC", "ETH", "Solana" or "XRP") as a whole word,
+    /// by ticker or full name - e.g. "Bitcoin Up or Down" / "btc-updown-15m-..." for BTC
+    pub fn names_asset(&self, asset: &str) -> bool {
+        let keywords: &[&str] = match asset.to_lowercase().as_str() {
+            "btc" => &["btc", "bitcoin"],
+            "eth" => &["eth", "ethereum"],
+            "solana" | "sol" => &["sol", "solana"],
+            "xrp" => &["xrp", "ripple"],
+            _ => return false,
+        };
+        [&self.question, &self.slug].iter()
+            .flat_map(|text| text.split(|c: char| !c.is_ascii_alphanumeric()))
+            .any(|word| keywords.contains(&word.to_lowercase().as_str()))
+    }
+
+    /// CLOB token ids from clobTokenIds (a JSON string array), falling back to `tokens`
+    pub fn token_ids(&self) -> Vec<String> {
+        self.clob_token_ids.as_deref()
+            .and_then(|ids| serde_json::from_str::<Vec<String>>(ids).ok())
+            .or_else(|| self.tokens.as_ref().map(|tokens| tokens.iter().map(|t| t.token_id.clone()).collect()))
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Token {
     #[serde(rename = "tokenId")]
@@ -43,14 +68,261 @@ pub struct OrderBookEntry {
     pub size: Decimal,
 }
 
+impl OrderBook {
+    /// Highest bid, independent of the order the API returns the levels in
+    pub fn best_bid(&self) -> Option<&OrderBookEntry> {
+        self.bids.iter().max_by(|a, b| a.price.cmp(&b.price))
+    }
+
+    /// Lowest ask, independent of the order the API returns the levels in
+    pub fn best_ask(&self) -> Option<&OrderBookEntry> {
+        self.asks.iter().min_by(|a, b| a.price.cmp(&b.price))
+    }
+
+    /// Walk the asks from the best price up and buy with at most `budget` USD while the average fill price stays
+    /// within `max_slippage` (fraction) of the best ask. Returns (units, cost), or None when there are no asks
+    pub fn buy_within_slippage(&self, budget: f64, max_slippage: f64) -> Option<(f64, f64)> {
+        let to_f64 = |d: Decimal| f64::try_from(d).unwrap_or(0.0);
+        let mut asks: Vec<&OrderBookEntry> = self.asks.iter().collect();
+        asks.sort_by_key(|level| level.price);
+        let best_ask = to_f64(asks.first()?.price);
+        let max_average = best_ask * (1.0 + max_slippage);
+        let (mut units, mut cost) = (0.0, 0.0);
+        for level in asks {
+            let (price, size) = (to_f64(level.price), to_f64(level.size));
+            // Largest x with (cost + x * price) / (units + x) <= max_average
+            let within_slippage = if price <= max_average { size } else { (max_average * units - cost) / (price - max_average) };
+            let take = size.min(within_slippage).min((budget - cost) / price).max(0.0);
+            units += take;
+            cost += take * price;
+            if take < size {
+                break;
+            }
+        }
+        Some((units, cost))
+    }
+
+    /// Err if the best bid is at or above the best ask - the top of book can't be traded on
+    pub fn check_crossed(&self, token_id: &str) -> std::result::Result<(), CrossedBook> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => CrossedBook::check(token_id, bid.price, ask.price),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Orderbook whose best bid is at or above its best ask (crossed, or locked when equal)
+/// Returned as a typed error so callers can `downcast_ref` it out of an `anyhow::Error` and skip the token
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossedBook {
+    pub token_id: String,
+    pub best_bid: Decimal,
+    pub best_ask: Decimal,
+}
+
+impl CrossedBook {
+    pub fn check(token_id: &str, best_bid: Decimal, best_ask: Decimal) -> std::result::Result<(), CrossedBook> {
+        if best_bid >= best_ask {
+            Err(CrossedBook { token_id: token_id.to_string(), best_bid, best_ask })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Best bid equals best ask (zero spread) rather than strictly crossed
+    pub fn is_locked(&self) -> bool {
+        self.best_bid == self.best_ask
+    }
+}
+
+impl std::fmt::Display for CrossedBook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} orderbook for token {}: best bid {} >= best ask {}",
+            if self.is_locked() { "locked" } else { "crossed" }, self.token_id, self.best_bid, self.best_ask)
+    }
+}
+
+impl std::error::Error for CrossedBook {}
+
+/// Classified failure of an order or price request
+/// Carried inside the `anyhow::Error` the API returns (anyhow's `From` covers the conversion); callers
+/// branch on it with `ApiError::of(&e)` instead of matching on message text
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// Not enough tokens (SELL) or USDC (BUY) in the wallet - retrying won't help
+    InsufficientBalance(String),
+    /// Exchange not approved, or the backend's cached allowance is stale
+    InsufficientAllowance(String),
+    /// No opposing orders in the book
+    NoLiquidity(String),
+    /// FOK order could not be fully filled and was killed
+    FillKill(String),
+    /// 429 / rate limit response
+    RateLimited(String),
+    /// 401 / 403, or credentials the CLOB or relayer rejected - retrying won't help until the config is fixed
+    Auth(String),
+    /// Any other non-2xx HTTP status
+    Http(u16, String),
+    /// Request never got a response (timeout, connection, transport) - it may or may not have reached the exchange
+    Network(String),
+    /// Not sent: the endpoint's circuit breaker is open after repeated transient failures (network.retry)
+    CircuitOpen(String),
+    Other(String),
+}
+
+impl ApiError {
+    /// Classify an exchange/SDK error message. The single place message wording is interpreted - only for SDK
+    /// errors whose text is the only signal; HTTP failures are typed where they happen (`from_status`, `from_reqwest`)
+    pub fn classify(message: &str) -> ApiError {
+        let lower = message.to_lowercase();
+        let message = message.to_string();
+        if lower.contains("429") || lower.contains("rate limit") || lower.contains("too many requests") {
+            ApiError::RateLimited(message)
+        } else if ["unauthorized", "forbidden", "authentication failed", "invalid api key", "invalid signature"]
+            .iter()
+            .any(|pattern| lower.contains(pattern))
+        {
+            ApiError::Auth(message)
+        } else if lower.contains("allowance") {
+            ApiError::InsufficientAllowance(message)
+        } else if lower.contains("not enough balance") || (lower.contains("insufficient") && lower.contains("balance")) {
+            ApiError::InsufficientBalance(message)
+        } else if lower.contains("no opposing orders") || lower.contains("no orders found") {
+            ApiError::NoLiquidity(message)
+        } else if lower.contains("couldn't be fully filled") || lower.contains("fok orders") {
+            ApiError::FillKill(message)
+        } else if ["timed out", "timeout", "error sending request", "connection", "broken pipe", "eof"]
+            .iter()
+            .any(|pattern| lower.contains(pattern))
+        {
+            ApiError::Network(message)
+        } else {
+            ApiError::Other(message)
+        }
+    }
+
+    /// Error for a non-2xx response
+    pub fn from_status(status: reqwest::StatusCode, message: String) -> ApiError {
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            ApiError::RateLimited(message)
+        } else if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            ApiError::Auth(message)
+        } else {
+            ApiError::Http(status.as_u16(), message)
+        }
+    }
+
+    /// Error for a request that failed before a response arrived
+    pub fn from_reqwest(error: reqwest::Error) -> ApiError {
+        match error.status() {
+            Some(status) => ApiError::from_status(status, error.to_string()),
+            None => ApiError::Network(error.to_string()),
+        }
+    }
+
+    /// The `ApiError` carried by `e`, if any (as its cause or, see `typed`, as context)
+    pub fn of(e: &anyhow::Error) -> Option<&ApiError> {
+        e.downcast_ref::<ApiError>().or_else(|| e.chain().find_map(|cause| cause.downcast_ref::<ApiError>()))
+    }
+
+    /// `e` as it is if it carries an `ApiError` (set where the request failed), otherwise with `ApiError::Other`
+    /// attached, so every error leaving the API client can be matched on its variant. Errors without one were
+    /// raised by the client itself (configuration, parsing) and are never transient
+    pub fn typed(e: anyhow::Error) -> anyhow::Error {
+        if ApiError::of(&e).is_some() {
+            return e;
+        }
+        let other = ApiError::Other(format!("{:#}", e));
+        e.context(other)
+    }
+
+    /// The `ApiError` carried by `e`, or a classification of its message for errors raised outside the API
+    pub fn classify_error(e: &anyhow::Error) -> ApiError {
+        ApiError::of(e).cloned().unwrap_or_else(|| ApiError::classify(&format!("{:?}", e)))
+    }
+
+    /// Whether trying the same request again can succeed: balance errors and 4xx rejections never will,
+    /// unclassified errors are given the benefit of the doubt
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::InsufficientBalance(_) | ApiError::Auth(_) => false,
+            ApiError::Http(status, _) => *status >= 500,
+            _ => true,
+        }
+    }
+
+    /// Whether the failure says the endpoint itself is unhealthy (no response, 429, 5xx) rather than rejecting
+    /// this request. Only these are retried by the API client and count towards its circuit breakers
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ApiError::Network(_) | ApiError::RateLimited(_) => true,
+            ApiError::Http(status, _) => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Short description for trade history events
+    pub fn summary(&self) -> String {
+        match self {
+            ApiError::InsufficientBalance(_) => "not enough balance".to_string(),
+            ApiError::InsufficientAllowance(_) => "not enough allowance".to_string(),
+            ApiError::NoLiquidity(_) => "no liquidity / no buyers".to_string(),
+            ApiError::FillKill(_) => "order couldn't be fully filled (FOK)".to_string(),
+            ApiError::RateLimited(_) => "rate limited".to_string(),
+            ApiError::Auth(_) => "authentication failed".to_string(),
+            ApiError::Http(status, _) => format!("HTTP {}", status),
+            ApiError::Network(_) => "network error / timeout".to_string(),
+            ApiError::CircuitOpen(_) => "API circuit open".to_string(),
+            ApiError::Other(message) => message.lines()
+                .find(|line| !line.trim().is_empty() && !line.contains("Troubleshooting") && !line.contains("Order details"))
+                .unwrap_or("request failed")
+                .to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::InsufficientBalance(message) => write!(f, "Insufficient balance: {}", message),
+            ApiError::InsufficientAllowance(message) => write!(f, "Insufficient allowance: {}", message),
+            ApiError::NoLiquidity(message) => write!(f, "No liquidity: {}", message),
+            ApiError::FillKill(message) => write!(f, "Order not filled (FOK): {}", message),
+            ApiError::RateLimited(message) => write!(f, "Rate limited: {}", message),
+            ApiError::Auth(message) => write!(f, "Authentication failed: {}", message),
+            ApiError::Http(status, message) => write!(f, "HTTP {}: {}", status, message),
+            ApiError::Network(message) => write!(f, "Network error: {}", message),
+            ApiError::CircuitOpen(message) => write!(f, "Circuit open: {}", message),
+            ApiError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
 #[derive(Debug, Clone)]
 pub struct TokenPrice {
     pub token_id: String,
     pub bid: Option<Decimal>,
     pub ask: Option<Decimal>,
+    /// Price used for trigger detection when it differs from the executable CLOB price
+    /// (e.g. gamma midpoint in thin markets). None = use `bid`
+    pub signal: Option<Decimal>,
+    /// Resting size at the best bid / best ask of the orderbook (None = sizes not fetched)
+    pub bid_size: Option<Decimal>,
+    pub ask_size: Option<Decimal>,
+    /// Book was crossed or locked when fetched - bid/ask are not tradable and the token is skipped
+    pub crossed: bool,
+    /// When bid/ask were fetched (checked against trading.max_price_age_ms before acting on the quote)
+    pub fetched_at: std::time::Instant,
 }
 
 impl TokenPrice {
+    /// Price used for the trigger/range signal - falls back to the executable BID price
+    pub fn signal_price(&self) -> Option<Decimal> {
+        self.signal.or(self.bid)
+    }
+
     pub fn mid_price(&self) -> Option<Decimal> {
         match (self.bid, self.ask) {
             (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::from(2)),
@@ -63,6 +335,11 @@ impl TokenPrice {
     pub fn ask_price(&self) -> Decimal {
         self.ask.unwrap_or(Decimal::ZERO)
     }
+
+    /// Time since bid/ask were fetched
+    pub fn age(&self) -> std::time::Duration {
+        self.fetched_at.elapsed()
+    }
 }
 
 /// Order structure for creating orders (before signing)
@@ -103,6 +380,31 @@ pub struct OrderResponse {
     pub message: Option<String>,
 }
 
+/// A live (resting) order of ours on the CLOB
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: String, // "BUY" or "SELL"
+    pub price: f64,
+    pub original_size: f64,
+    pub size_matched: f64,
+    pub status: String,
+    pub created_at: i64, // Unix seconds
+}
+
+/// One of our fills on the CLOB (/data/trades)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnTrade {
+    pub trade_id: String,
+    pub token_id: String,
+    pub side: String, // "BUY" or "SELL"
+    pub size: f64,
+    pub price: f64,
+    pub status: String,
+    pub match_time: i64, // Unix seconds
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceResponse {
     pub balance: String,
@@ -126,8 +428,10 @@ pub struct MarketData {
 }
 
 /// Trade for momentum-based strategy (buy any token when price reaches 0.9 after 10 minutes)
-#[derive(Debug, Clone)]
+/// Serialized to trader::PENDING_TRADES_FILE so open trades survive a restart
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PendingTrade {
+    pub trade_id: String,             // Unique trade id (UUID) - hedge sub-trades reuse the parent id with a suffix
     pub token_id: String,             // Token ID (can be BTC Up/Down, ETH Up/Down)
     pub condition_id: String,          // Market condition ID (BTC or ETH)
     pub token_type: crate::detector::TokenType, // Type of token
@@ -136,7 +440,8 @@ pub struct PendingTrade {
     pub units: f64,                   // Total token shares purchased (expected)
     pub purchase_price: f64,          // Price at which token was purchased (BID)
     pub sell_price: f64,              // Target sell price (0.99 or 1.0)
-    pub timestamp: std::time::Instant, // When the trade was executed
+    #[serde(with = "instant_as_unix_secs")]
+    pub timestamp: std::time::Instant, // When the trade was executed (unix seconds when persisted)
     pub market_timestamp: u64,        // The 15-minute period timestamp
     pub sold: bool,                   // Whether the token has been sold
     pub confirmed_balance: Option<f64>, // Confirmed token balance in portfolio (None = not verified yet)
@@ -147,6 +452,53 @@ pub struct PendingTrade {
     pub sell_attempts: u32,        // Number of times we've tried to sell (to limit retries)
     pub redemption_attempts: u32,  // Number of times we've tried to redeem (to track failed redemptions)
     pub redemption_abandoned: bool, // If true, redemption failed too many times - don't block new positions
+    pub scale_out_levels_done: usize, // scale_out_schedule levels already sold into (levels trigger in ascending order)
+}
+
+/// (De)serialize an Instant as the unix time it corresponds to - Instants don't survive a restart
+mod instant_as_unix_secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+    fn unix_now() -> f64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64()
+    }
+
+    pub fn serialize<S: Serializer>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(unix_now() - instant.elapsed().as_secs_f64())
+    }
+
+    /// Times before this process started are clamped to the earliest Instant the platform can represent
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        let age = Duration::from_secs_f64((unix_now() - f64::deserialize(deserializer)?).max(0.0));
+        let now = Instant::now();
+        Ok(now.checked_sub(age).unwrap_or(now))
+    }
+}
+
+/// New unique trade id, generated once per entry and carried by every history event for that trade
+pub fn new_trade_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Trade id for a hedge sub-trade, traceable back to the trade it hedges
+pub fn hedge_trade_id(parent_trade_id: &str) -> String {
+    format!("{}-hedge", parent_trade_id)
+}
+
+/// Trade whose redemption was abandoned after max retries (persisted for `--recover-abandoned`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbandonedTrade {
+    #[serde(default)]
+    pub trade_id: String,         // Empty for entries recorded before trade ids existed
+    pub token_id: String,
+    pub condition_id: String,
+    pub market: String,           // Display name, e.g. "BTC Up"
+    pub outcome: String,          // "Up" or "Down" (passed to redeem_tokens)
+    pub units: f64,
+    pub purchase_price: f64,
+    pub market_timestamp: u64,
+    pub redemption_attempts: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -158,6 +510,18 @@ pub struct MarketToken {
     pub winner: bool,
 }
 
+/// One point of a token's price series from the CLOB prices-history endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PricePoint {
+    pub t: u64, // Unix seconds
+    pub p: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PricesHistory {
+    pub history: Vec<PricePoint>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketDetails {
     #[serde(rename = "accepting_order_timestamp")]
@@ -218,3 +582,160 @@ pub struct Rewards {
     pub rates: Option<serde_json::Value>,
 }
 
+
+/// Top of book pushed on the CLOB market WebSocket channel (None = that side of the book is empty)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketQuote {
+    pub token_id: String,
+    pub best_bid: Option<Decimal>,
+    pub best_ask: Option<Decimal>,
+}
+
+/// Event pushed on the CLOB user WebSocket channel for the authenticated API key
+#[derive(Debug, Clone)]
+pub enum UserEvent {
+    Trade(UserTrade),
+    OrderUpdate(UserOrderUpdate),
+}
+
+/// Trade (fill) notification from the user channel
+/// status goes MATCHED -> MINED -> CONFIRMED (or RETRYING / FAILED)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserTrade {
+    pub id: String,
+    pub asset_id: String,
+    pub market: String,
+    pub side: String, // "BUY" or "SELL"
+    pub size: Decimal,
+    pub price: Decimal,
+    pub status: String,
+    pub taker_order_id: Option<String>,
+    #[serde(default)]
+    pub maker_orders: Vec<UserMakerOrder>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserMakerOrder {
+    pub order_id: String,
+    pub asset_id: String,
+    pub matched_amount: Decimal,
+    pub price: Decimal,
+}
+
+/// Order placement/update/cancellation notification from the user channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserOrderUpdate {
+    pub id: String,
+    pub asset_id: String,
+    pub market: String,
+    pub side: String,
+    pub price: Decimal,
+    pub original_size: Decimal,
+    pub size_matched: Decimal,
+    #[serde(rename = "type")]
+    pub update_type: String, // "PLACEMENT", "UPDATE" or "CANCELLATION"
+}
+
+impl UserTrade {
+    /// Shares filled for one of our orders in this trade
+    /// Taker fills use the trade size; maker fills use the matched amount of the maker order
+    pub fn filled_size_for_order(&self, order_id: &str) -> Option<Decimal> {
+        if self.taker_order_id.as_deref() == Some(order_id) {
+            return Some(self.size);
+        }
+        let matched: Decimal = self.maker_orders.iter()
+            .filter(|m| m.order_id == order_id)
+            .map(|m| m.matched_amount)
+            .sum();
+        if matched > Decimal::ZERO {
+            Some(matched)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use rust_decimal_macros::dec;
+
+    fn book(json: &str) -> OrderBook {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn crossed_orderbook_is_flagged() {
+        // CLOB lists bids ascending and asks descending - the best levels are last
+        let crossed = book(r#"{
+            "bids": [{"price": "0.40", "size": "10"}, {"price": "0.55", "size": "5"}],
+            "asks": [{"price": "0.60", "size": "8"}, {"price": "0.52", "size": "3"}]
+        }"#);
+        assert_eq!(crossed.best_bid().unwrap().price, dec!(0.55));
+        assert_eq!(crossed.best_ask().unwrap().price, dec!(0.52));
+        let err = crossed.check_crossed("dummy_token").unwrap_err();
+        assert_eq!(err, CrossedBook { token_id: "dummy_token".to_string(), best_bid: dec!(0.55), best_ask: dec!(0.52) });
+        assert!(!err.is_locked());
+
+        // The typed error survives a round trip through anyhow so callers can skip on it
+        let wrapped: anyhow::Error = err.into();
+        assert!(wrapped.downcast_ref::<CrossedBook>().is_some());
+
+        let locked = book(r#"{"bids": [{"price": "0.50", "size": "1"}], "asks": [{"price": "0.50", "size": "1"}]}"#);
+        assert!(locked.check_crossed("dummy_token").unwrap_err().is_locked());
+
+        let healthy = book(r#"{"bids": [{"price": "0.48", "size": "1"}], "asks": [{"price": "0.50", "size": "1"}]}"#);
+        assert!(healthy.check_crossed("dummy_token").is_ok());
+
+        let one_sided = book(r#"{"bids": [], "asks": [{"price": "0.50", "size": "1"}]}"#);
+        assert!(one_sided.check_crossed("dummy_token").is_ok());
+    }
+
+    #[test]
+    fn buy_within_slippage_walks_the_asks_until_budget_or_slippage_runs_out() {
+        let thin = book(r#"{"bids": [], "asks": [
+            {"price": "0.70", "size": "100"}, {"price": "0.52", "size": "20"}, {"price": "0.50", "size": "10"}
+        ]}"#);
+        // $4 is covered by the best level alone
+        let (units, cost) = thin.buy_within_slippage(4.0, 0.02).unwrap();
+        assert!((units - 8.0).abs() < 1e-9 && (cost - 4.0).abs() < 1e-9);
+        // $20 would average above 0.51: all of 0.50, then 0.52 only while the average stays at 0.51
+        let (units, cost) = thin.buy_within_slippage(20.0, 0.02).unwrap();
+        assert!((units - 20.0).abs() < 1e-9 && (cost / units - 0.51).abs() < 1e-9);
+        // A loose limit is bounded by the budget instead
+        let (_, cost) = thin.buy_within_slippage(20.0, 0.5).unwrap();
+        assert!((cost - 20.0).abs() < 1e-9);
+        assert!(book(r#"{"bids": [], "asks": []}"#).buy_within_slippage(20.0, 0.02).is_none());
+    }
+
+    #[test]
+    fn api_errors_are_classified_and_survive_context() {
+        assert!(matches!(ApiError::classify("not enough balance / allowance"), ApiError::InsufficientAllowance(_)));
+        assert!(matches!(ApiError::classify("Insufficient token balance"), ApiError::InsufficientBalance(_)));
+        assert!(matches!(ApiError::classify("No opposing orders"), ApiError::NoLiquidity(_)));
+        assert!(matches!(ApiError::classify("order couldn't be fully filled. FOK orders are fully filled or killed"), ApiError::FillKill(_)));
+        assert!(matches!(ApiError::classify("HTTP 429 Too Many Requests"), ApiError::RateLimited(_)));
+        assert!(matches!(ApiError::classify("error sending request: operation timed out"), ApiError::Network(_)));
+        assert!(matches!(ApiError::classify("401 Unauthorized/Invalid api key"), ApiError::Auth(_)));
+        assert!(!ApiError::Auth(String::new()).is_retryable());
+        assert!(!ApiError::InsufficientBalance(String::new()).is_retryable());
+        assert!(!ApiError::Http(400, String::new()).is_retryable());
+        assert!(ApiError::Http(503, String::new()).is_retryable());
+        assert!(ApiError::Http(503, String::new()).is_transient());
+        assert!(!ApiError::FillKill(String::new()).is_transient());
+
+        // The variant is found through added context, so message wording can change freely
+        let wrapped = anyhow::Result::<()>::Err(ApiError::NoLiquidity("dummy".to_string()).into())
+            .context("Failed to post market order: wording that mentions allowance")
+            .unwrap_err();
+        assert_eq!(ApiError::classify_error(&wrapped), ApiError::NoLiquidity("dummy".to_string()));
+
+        // Errors not typed where they were raised are attached as Other, not re-read from their text
+        let typed = ApiError::typed(anyhow::anyhow!("Failed to fetch balance: operation timed out"));
+        assert!(matches!(ApiError::of(&typed), Some(ApiError::Other(_))));
+        assert!(format!("{:?}", typed).contains("Failed to fetch balance"));
+        let network = ApiError::typed(anyhow::Error::from(ApiError::Network("dummy timeout".to_string())).context("Failed to fetch balance"));
+        assert!(matches!(ApiError::of(&network), Some(ApiError::Network(_))));
+    }
+}
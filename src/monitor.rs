@@ -1,13 +1,20 @@
 use crate::api::PolymarketApi;
+use crate::config::{PriceSource, Transport};
+use crate::price_feed::PriceFeed;
 use crate::models::*;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
 use std::fs::OpenOptions;
 use std::io::Write;
 use chrono::Utc;
 
+/// Market channel silence after which websocket_with_poll_fallback polls REST, when
+/// trading.price_transport_stale_ms is not set
+pub const DEFAULT_PRICE_TRANSPORT_STALE_MS: u64 = 5000;
+
 pub struct MarketMonitor {
     api: Arc<PolymarketApi>,
     eth_market: Arc<tokio::sync::Mutex<crate::models::Market>>,
@@ -25,7 +32,7 @@ pub struct MarketMonitor {
     xrp_up_token_id: Arc<tokio::sync::Mutex<Option<String>>>,
     xrp_down_token_id: Arc<tokio::sync::Mutex<Option<String>>>,
     last_market_refresh: Arc<tokio::sync::Mutex<Option<std::time::Instant>>>,
-    current_period_timestamp: Arc<tokio::sync::Mutex<u64>>, // Track current 15-minute period
+    current_period_timestamp: Arc<tokio::sync::Mutex<u64>>, // Track current period (trading.period_seconds)
     btc_market_end_timestamp: Arc<tokio::sync::Mutex<Option<u64>>>, // Actual market end time from API
     eth_market_end_timestamp: Arc<tokio::sync::Mutex<Option<u64>>>, // Actual ETH market end time from API
     solana_market_end_timestamp: Arc<tokio::sync::Mutex<Option<u64>>>, // Actual Solana market end time from API
@@ -33,6 +40,17 @@ pub struct MarketMonitor {
     simulation_mode: bool,
     price_monitor_file: Option<Arc<tokio::sync::Mutex<std::fs::File>>>, // File for logging price monitoring data in simulation mode
     market_price_files: Arc<tokio::sync::Mutex<std::collections::HashMap<String, Arc<tokio::sync::Mutex<std::fs::File>>>>>, // Per-market price files
+    price_source: PriceSource, // Where the detection signal price comes from
+    max_blend_spread: rust_decimal::Decimal, // Blend: max CLOB spread before falling back to gamma midpoint
+    price_feed: Option<Arc<dyn PriceFeed>>, // Offline price feed (synthetic simulation) - bypasses the API when set
+    fetch_book_sizes: bool, // Also fetch top-of-book sizes (for the book imbalance filter)
+    price_transport: Transport, // Market channel, REST polling, or the channel with polling while it is stale
+    ws_stale_after: Duration, // Market channel silence after which the fallback mode polls
+    ws_quotes: Arc<std::sync::Mutex<std::collections::HashMap<String, MarketQuote>>>, // Latest market channel quote per token
+    ws_last_update: Arc<std::sync::Mutex<Option<std::time::Instant>>>, // When the market channel last delivered a quote
+    ws_task: tokio::sync::Mutex<Option<tokio::task::JoinHandle<()>>>, // Consumer of the current market channel subscription
+    ws_fallback_active: std::sync::atomic::AtomicBool, // Fallback mode is polling (logged on every switch)
+    snapshot_recorder: Option<SnapshotRecorder>, // Every snapshot is appended here (--record-snapshots)
 }
 
 #[derive(Debug, Clone)]
@@ -42,8 +60,148 @@ pub struct MarketSnapshot {
     pub solana_market: MarketData,
     pub xrp_market: MarketData,
     pub timestamp: std::time::Instant,
-    pub time_remaining_seconds: u64, // Time remaining in the current 15-minute period
-    pub period_timestamp: u64, // The 15-minute period timestamp (e.g., 1767796200)
+    pub time_remaining_seconds: u64, // Time remaining in the current period
+    pub period_timestamp: u64, // The period's start timestamp (e.g., 1767796200)
+}
+
+/// One line of a snapshot recording (JSON lines, see `SnapshotRecorder`): a `MarketSnapshot` with the wall-clock
+/// time it was taken in place of its Instants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedSnapshot {
+    pub recorded_at_ms: i64, // Unix milliseconds
+    pub period_timestamp: u64,
+    pub time_remaining_seconds: u64,
+    pub eth_market: RecordedMarket,
+    pub btc_market: RecordedMarket,
+    pub solana_market: RecordedMarket,
+    pub xrp_market: RecordedMarket,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedMarket {
+    pub condition_id: String,
+    pub market_name: String,
+    pub up_token: Option<RecordedQuote>,
+    pub down_token: Option<RecordedQuote>,
+}
+
+/// `TokenPrice` without its fetch time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedQuote {
+    pub token_id: String,
+    pub bid: Option<rust_decimal::Decimal>,
+    pub ask: Option<rust_decimal::Decimal>,
+    pub signal: Option<rust_decimal::Decimal>,
+    pub bid_size: Option<rust_decimal::Decimal>,
+    pub ask_size: Option<rust_decimal::Decimal>,
+    #[serde(default)]
+    pub crossed: bool,
+}
+
+impl RecordedSnapshot {
+    pub fn from_snapshot(snapshot: &MarketSnapshot, recorded_at_ms: i64) -> Self {
+        Self {
+            recorded_at_ms,
+            period_timestamp: snapshot.period_timestamp,
+            time_remaining_seconds: snapshot.time_remaining_seconds,
+            eth_market: RecordedMarket::from_market(&snapshot.eth_market),
+            btc_market: RecordedMarket::from_market(&snapshot.btc_market),
+            solana_market: RecordedMarket::from_market(&snapshot.solana_market),
+            xrp_market: RecordedMarket::from_market(&snapshot.xrp_market),
+        }
+    }
+
+    /// The snapshot as the detector sees it; quotes count as fetched just now, so they pass max_price_age_ms
+    pub fn to_snapshot(&self) -> MarketSnapshot {
+        let now = std::time::Instant::now();
+        MarketSnapshot {
+            eth_market: self.eth_market.to_market(now),
+            btc_market: self.btc_market.to_market(now),
+            solana_market: self.solana_market.to_market(now),
+            xrp_market: self.xrp_market.to_market(now),
+            timestamp: now,
+            time_remaining_seconds: self.time_remaining_seconds,
+            period_timestamp: self.period_timestamp,
+        }
+    }
+
+    /// The four markets with their asset keys ("eth", "btc", "solana", "xrp")
+    pub fn markets(&self) -> [(&'static str, &RecordedMarket); 4] {
+        [("eth", &self.eth_market), ("btc", &self.btc_market), ("solana", &self.solana_market), ("xrp", &self.xrp_market)]
+    }
+}
+
+impl RecordedMarket {
+    fn from_market(market: &MarketData) -> Self {
+        let quote = |price: &Option<TokenPrice>| price.as_ref().map(|p| RecordedQuote {
+            token_id: p.token_id.clone(),
+            bid: p.bid,
+            ask: p.ask,
+            signal: p.signal,
+            bid_size: p.bid_size,
+            ask_size: p.ask_size,
+            crossed: p.crossed,
+        });
+        Self {
+            condition_id: market.condition_id.clone(),
+            market_name: market.market_name.clone(),
+            up_token: quote(&market.up_token),
+            down_token: quote(&market.down_token),
+        }
+    }
+
+    fn to_market(&self, fetched_at: std::time::Instant) -> MarketData {
+        MarketData {
+            condition_id: self.condition_id.clone(),
+            market_name: self.market_name.clone(),
+            up_token: self.up_token.as_ref().map(|q| q.to_token_price(fetched_at)),
+            down_token: self.down_token.as_ref().map(|q| q.to_token_price(fetched_at)),
+        }
+    }
+}
+
+impl RecordedQuote {
+    pub fn to_token_price(&self, fetched_at: std::time::Instant) -> TokenPrice {
+        TokenPrice {
+            token_id: self.token_id.clone(),
+            bid: self.bid,
+            ask: self.ask,
+            signal: self.signal,
+            bid_size: self.bid_size,
+            ask_size: self.ask_size,
+            crossed: self.crossed,
+            fetched_at,
+        }
+    }
+}
+
+/// Appends every snapshot the monitor takes to a JSON lines file, for `backtest --replay`
+pub struct SnapshotRecorder {
+    file: std::sync::Mutex<std::fs::File>,
+    path: String,
+}
+
+impl SnapshotRecorder {
+    /// Open `path` for appending (a recording can span several runs)
+    pub fn open(path: &str) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {} for recording snapshots", path))?;
+        Ok(Self { file: std::sync::Mutex::new(file), path: path.to_string() })
+    }
+
+    /// Append one snapshot (best effort - a failed write is logged and the snapshot skipped)
+    pub fn record(&self, snapshot: &MarketSnapshot) {
+        let recorded = RecordedSnapshot::from_snapshot(snapshot, Utc::now().timestamp_millis());
+        let result = serde_json::to_string(&recorded)
+            .map_err(anyhow::Error::from)
+            .and_then(|line| Ok(writeln!(self.file.lock().unwrap(), "{}", line)?));
+        if let Err(e) = result {
+            warn!("Failed to record snapshot to {}: {}", self.path, e);
+        }
+    }
 }
 
 impl MarketMonitor {
@@ -56,12 +214,12 @@ impl MarketMonitor {
         check_interval_ms: u64,
         simulation_mode: bool,
     ) -> Result<Self> {
-        // Calculate current 15-minute period timestamp
+        // Calculate current period timestamp
         let current_time = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        let current_period = (current_time / 900) * 900; // Round to nearest 15 minutes
+        let current_period = crate::period::start(current_time);
         
         // Create price monitor file if in simulation mode
         let price_monitor_file = if simulation_mode {
@@ -104,12 +262,162 @@ impl MarketMonitor {
             simulation_mode,
             price_monitor_file,
             market_price_files: Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new())),
+            price_source: PriceSource::ClobTop,
+            max_blend_spread: rust_decimal::Decimal::new(5, 2),
+            price_feed: None,
+            fetch_book_sizes: false,
+            price_transport: Transport::Poll,
+            ws_stale_after: Duration::from_millis(DEFAULT_PRICE_TRANSPORT_STALE_MS),
+            ws_quotes: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            ws_last_update: Arc::new(std::sync::Mutex::new(None)),
+            ws_task: tokio::sync::Mutex::new(None),
+            ws_fallback_active: std::sync::atomic::AtomicBool::new(false),
+            snapshot_recorder: None,
         })
     }
 
-    /// Update markets when a new 15-minute period starts
+    /// Use a different price source for the detection signal (default: CLOB top of book)
+    pub fn with_price_source(mut self, price_source: PriceSource, max_blend_spread: f64) -> Self {
+        self.price_source = price_source;
+        self.max_blend_spread = rust_decimal::Decimal::from_f64_retain(max_blend_spread)
+            .unwrap_or(self.max_blend_spread);
+        self
+    }
+
+    /// Read prices and market tokens from `price_feed` instead of the API (e.g. synthetic simulation).
+    /// The feed is advanced by one step per snapshot
+    pub fn with_price_feed(mut self, price_feed: Arc<dyn PriceFeed>) -> Self {
+        self.price_feed = Some(price_feed);
+        self
+    }
+
+    /// Also fetch top-of-book bid/ask sizes with every price (one extra orderbook request per token)
+    pub fn with_book_sizes(mut self, fetch_book_sizes: bool) -> Self {
+        self.fetch_book_sizes = fetch_book_sizes;
+        self
+    }
+
+    /// Get CLOB prices from the market WebSocket channel, REST polling (the default), or the channel with REST polling
+    /// while it has been silent for `stale_after_ms`. Ignored with a price feed
+    pub fn with_price_transport(mut self, price_transport: Transport, stale_after_ms: u64) -> Self {
+        self.price_transport = price_transport;
+        self.ws_stale_after = Duration::from_millis(stale_after_ms);
+        self
+    }
+
+    /// Append every snapshot to `recorder` before it is handed to the monitoring callback
+    pub fn with_snapshot_recorder(mut self, recorder: SnapshotRecorder) -> Self {
+        self.snapshot_recorder = Some(recorder);
+        self
+    }
+
+    /// Transport the latest prices came from: "websocket", "poll", or "poll (websocket stale)" while the fallback
+    /// mode is polling
+    pub fn active_transport(&self) -> &'static str {
+        match self.price_transport {
+            _ if self.price_feed.is_some() => "price feed",
+            Transport::Poll => "poll",
+            Transport::WebSocket => "websocket",
+            Transport::WebSocketWithPollFallback if self.ws_is_live() => "websocket",
+            Transport::WebSocketWithPollFallback => "poll (websocket stale)",
+        }
+    }
+
+    /// The market channel delivered a quote within the staleness window
+    fn ws_is_live(&self) -> bool {
+        self.ws_last_update.lock().unwrap()
+            .is_some_and(|at| at.elapsed() < self.ws_stale_after)
+    }
+
+    /// Quote to use for `token_id` instead of polling REST, with when the channel last delivered one.
+    /// None = poll (poll transport, or the fallback mode while the channel is stale or has no quote for the token)
+    fn ws_quote(&self, token_id: &str) -> Option<(Option<MarketQuote>, std::time::Instant)> {
+        let use_ws = match self.price_transport {
+            Transport::Poll => false,
+            Transport::WebSocket => true,
+            Transport::WebSocketWithPollFallback => self.ws_is_live(),
+        };
+        if !use_ws {
+            return None;
+        }
+        let quote = self.ws_quotes.lock().unwrap().get(token_id).cloned();
+        if quote.is_none() && self.price_transport == Transport::WebSocketWithPollFallback {
+            return None; // Streaming, but nothing for this token yet
+        }
+        let received_at = (*self.ws_last_update.lock().unwrap()).unwrap_or_else(std::time::Instant::now);
+        Some((quote, received_at))
+    }
+
+    /// Log when the fallback mode switches between the market channel and polling
+    fn log_transport_switch(&self) {
+        if self.price_transport != Transport::WebSocketWithPollFallback || self.price_feed.is_some() {
+            return;
+        }
+        let polling = !self.ws_is_live();
+        if self.ws_fallback_active.swap(polling, std::sync::atomic::Ordering::Relaxed) != polling {
+            if polling {
+                warn!("📡 No market channel update for {}ms - polling REST prices until it streams again",
+                    self.ws_stale_after.as_millis());
+            } else {
+                info!("📡 Market channel streaming again - using WebSocket prices");
+            }
+        }
+    }
+
+    /// (Re)subscribe the market channel to the current tokens; the previous subscription is dropped
+    async fn subscribe_price_stream(&self) {
+        if self.price_transport == Transport::Poll || self.price_feed.is_some() {
+            return;
+        }
+        let mut token_ids = Vec::new();
+        for token_id in [
+            &self.btc_up_token_id, &self.btc_down_token_id, &self.eth_up_token_id, &self.eth_down_token_id,
+            &self.solana_up_token_id, &self.solana_down_token_id, &self.xrp_up_token_id, &self.xrp_down_token_id,
+        ] {
+            if let Some(token_id) = token_id.lock().await.clone() {
+                token_ids.push(token_id);
+            }
+        }
+
+        let mut ws_task = self.ws_task.lock().await;
+        if let Some(previous) = ws_task.take() {
+            previous.abort(); // Drops its receiver, which closes the old socket
+        }
+        self.ws_quotes.lock().unwrap().clear();
+        *self.ws_last_update.lock().unwrap() = None;
+        if token_ids.is_empty() {
+            return;
+        }
+
+        let mut quotes_rx = match self.api.subscribe_market_quotes(token_ids).await {
+            Ok(rx) => rx,
+            Err(e) => {
+                warn!("Market channel unavailable - {}: {}", if self.price_transport == Transport::WebSocket {
+                    "no prices until it connects" } else { "polling REST prices" }, e);
+                return;
+            }
+        };
+        let quotes = self.ws_quotes.clone();
+        let last_update = self.ws_last_update.clone();
+        *ws_task = Some(tokio::spawn(async move {
+            while let Some(quote) = quotes_rx.recv().await {
+                quotes.lock().unwrap().insert(quote.token_id.clone(), quote);
+                *last_update.lock().unwrap() = Some(std::time::Instant::now());
+            }
+        }));
+    }
+
+    /// Market tokens from the price feed if set, otherwise from the API
+    async fn get_market_tokens(&self, condition_id: &str) -> Result<Vec<crate::models::MarketToken>> {
+        match &self.price_feed {
+            Some(feed) => feed.get_market_tokens(condition_id).await,
+            None => Ok(self.api.get_market(condition_id).await?.tokens),
+        }
+    }
+
+    /// Update markets when a new period starts
     pub async fn update_markets(&self, eth_market: crate::models::Market, btc_market: crate::models::Market, solana_market: crate::models::Market, xrp_market: crate::models::Market) -> Result<()> {
-        eprintln!("🔄 Updating to new 15-minute period markets...");
+        eprintln!("🔄 Updating to new period markets...");
         eprintln!("✅ ETH Market: {} ({}) - Active trading", eth_market.slug, eth_market.condition_id);
         eprintln!("✅ BTC Market: {} ({}) - Active trading", btc_market.slug, btc_market.condition_id);
         eprintln!("✅ Solana Market: {} ({}) - Active trading", solana_market.slug, solana_market.condition_id);
@@ -145,7 +453,7 @@ impl MarketMonitor {
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        let new_period = (current_time / 900) * 900;
+        let new_period = crate::period::start(current_time);
         *self.current_period_timestamp.lock().await = new_period;
         
         Ok(())
@@ -169,19 +477,19 @@ impl MarketMonitor {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            (current_time / 900) * 900
+            crate::period::start(current_time)
         } else {
             timestamp
         }
     }
 
-    /// Refresh market data once per period (15 minutes) to get token IDs
+    /// Refresh market data once per period (trading.period_seconds) to get token IDs
     async fn refresh_market_tokens(&self) -> Result<()> {
-        // Check if we need to refresh (every 15 minutes = 900 seconds)
+        // Check if we need to refresh: once per period (trading.period_seconds)
         let should_refresh = {
             let last_refresh = self.last_market_refresh.lock().await;
             last_refresh
-                .map(|last| last.elapsed().as_secs() >= 900)
+                .map(|last| last.elapsed().as_secs() >= crate::period::seconds())
                 .unwrap_or(true)
         };
 
@@ -201,8 +509,8 @@ impl MarketMonitor {
         };
 
         // Get ETH market details
-        if let Ok(eth_details) = self.api.get_market(&eth_condition_id).await {
-            for token in &eth_details.tokens {
+        if let Ok(eth_details) = self.get_market_tokens(&eth_condition_id).await {
+            for token in &eth_details {
                 let outcome_upper = token.outcome.to_uppercase();
                 if outcome_upper.contains("UP") || outcome_upper == "1" {
                     *self.eth_up_token_id.lock().await = Some(token.token_id.clone());
@@ -223,8 +531,8 @@ impl MarketMonitor {
         }
 
         // Get BTC market details
-        if let Ok(btc_details) = self.api.get_market(&btc_condition_id).await {
-            for token in &btc_details.tokens {
+        if let Ok(btc_details) = self.get_market_tokens(&btc_condition_id).await {
+            for token in &btc_details {
                 let outcome_upper = token.outcome.to_uppercase();
                 if outcome_upper.contains("UP") || outcome_upper == "1" {
                     *self.btc_up_token_id.lock().await = Some(token.token_id.clone());
@@ -246,8 +554,8 @@ impl MarketMonitor {
 
         // Get Solana market details (skip if dummy fallback - no real market)
         if solana_condition_id != "dummy_solana_fallback" {
-            if let Ok(solana_details) = self.api.get_market(&solana_condition_id).await {
-                for token in &solana_details.tokens {
+            if let Ok(solana_details) = self.get_market_tokens(&solana_condition_id).await {
+                for token in &solana_details {
                 let outcome_upper = token.outcome.to_uppercase();
                 if outcome_upper.contains("UP") || outcome_upper == "1" {
                     *self.solana_up_token_id.lock().await = Some(token.token_id.clone());
@@ -270,8 +578,8 @@ impl MarketMonitor {
 
         // Get XRP market details (skip if dummy fallback - no real market)
         if xrp_condition_id != "dummy_xrp_fallback" {
-            if let Ok(xrp_details) = self.api.get_market(&xrp_condition_id).await {
-                for token in &xrp_details.tokens {
+            if let Ok(xrp_details) = self.get_market_tokens(&xrp_condition_id).await {
+                for token in &xrp_details {
                     let outcome_upper = token.outcome.to_uppercase();
                     if outcome_upper.contains("UP") || outcome_upper == "1" {
                         *self.xrp_up_token_id.lock().await = Some(token.token_id.clone());
@@ -286,14 +594,22 @@ impl MarketMonitor {
         }
 
         *self.last_market_refresh.lock().await = Some(std::time::Instant::now());
+        self.subscribe_price_stream().await;
         Ok(())
     }
 
     /// Fetch current market data for both ETH and BTC markets
     /// Uses get_price() endpoint continuously for real-time prices
     pub async fn fetch_market_data(&self) -> Result<MarketSnapshot> {
-        // Refresh token IDs if needed (once per 15-minute period)
+        crate::record_monitor_snapshot();
+        // Offline feeds move one step per snapshot
+        if let Some(feed) = &self.price_feed {
+            feed.advance();
+        }
+
+        // Refresh token IDs if needed (once per period)
         self.refresh_market_tokens().await?;
+        self.log_transport_switch();
 
         // Get market slugs to extract timestamps
         let eth_market_guard = self.eth_market.lock().await;
@@ -332,8 +648,8 @@ impl MarketMonitor {
             .as_secs();
 
         // Calculate remaining time - use actual market end time from API if available
-        // Otherwise fall back to slug timestamp + 900 seconds
-        const PERIOD_DURATION: u64 = 900; // 15 minutes in seconds (fallback)
+        // Otherwise fall back to slug timestamp + one period
+        let period_duration = crate::period::seconds(); // Fallback market length
         
         // Try to use actual market end time from API
         let btc_market_end = {
@@ -358,24 +674,24 @@ impl MarketMonitor {
         let btc_period_end = if let Some(api_end_time) = btc_market_end {
             api_end_time // Use actual end time from API
         } else {
-            btc_market_timestamp + PERIOD_DURATION // Fallback to slug + 900s
+            btc_market_timestamp + period_duration // Fallback to slug + one period
         };
         
         let eth_period_end = if let Some(api_end_time) = eth_market_end {
             api_end_time // Use actual end time from API
         } else {
-            eth_market_timestamp + PERIOD_DURATION // Fallback to slug + 900s
+            eth_market_timestamp + period_duration // Fallback to slug + one period
         };
         
         let solana_period_end = if let Some(api_end_time) = solana_market_end {
             api_end_time // Use actual end time from API
         } else {
-            solana_market_timestamp + PERIOD_DURATION // Fallback to slug + 900s
+            solana_market_timestamp + period_duration // Fallback to slug + one period
         };
         let xrp_period_end = if let Some(api_end_time) = xrp_market_end {
             api_end_time // Use actual end time from API
         } else {
-            xrp_market_timestamp + PERIOD_DURATION // Fallback to slug + 900s
+            xrp_market_timestamp + period_duration // Fallback to slug + one period
         };
         
         let eth_remaining_secs = if eth_period_end > current_timestamp {
@@ -562,7 +878,11 @@ impl MarketMonitor {
             solana_up_str, solana_down_str,
             xrp_up_str, xrp_down_str,
             time_remaining_str);
-        eprintln!("{}", price_log_line);
+        // Transport only on the terminal - the price files keep their format
+        match self.price_transport {
+            Transport::Poll => eprintln!("{}", price_log_line),
+            _ => eprintln!("{} | 📡 {}", price_log_line, self.active_transport()),
+        }
         
         // Always log prices to files (both simulation and production/price monitor mode)
         let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
@@ -652,49 +972,154 @@ impl MarketMonitor {
     ) -> Option<TokenPrice> {
         let token_id = token_id.as_ref()?;
 
-        // Get BUY price (BID price - what we pay to buy, higher)
-        // get_price(token_id, "BUY") returns the BID price (what we pay to buy)
-        let buy_price = match self.api.get_price(token_id, "BUY").await {
-            Ok(price) => Some(price),
-            Err(e) => {
-                warn!("Failed to fetch {} {} BUY price: {}", market_name, outcome, e);
-                None
+        if let Some(feed) = &self.price_feed {
+            // Feed quotes are top of book (bid < ask); the monitor's bid is the price we pay to buy
+            return match feed.get_token_price(token_id).await {
+                Ok(Some(book)) => {
+                    if book.crossed {
+                        warn!("{} {} orderbook is crossed/locked (bid {:?} >= ask {:?}) - skipping until it clears",
+                            market_name, outcome, book.bid, book.ask);
+                    }
+                    Some(TokenPrice {
+                        token_id: token_id.clone(),
+                        bid: book.ask,
+                        ask: book.bid,
+                        signal: None,
+                        bid_size: book.bid_size,
+                        ask_size: book.ask_size,
+                        crossed: book.crossed,
+                        fetched_at: book.fetched_at,
+                    })
+                },
+                Ok(None) => None,
+                Err(e) => {
+                    warn!("Failed to fetch {} {} price from feed: {}", market_name, outcome, e);
+                    None
+                }
+            };
+        }
+
+        let (buy_price, sell_price, fetched_at) = match self.ws_quote(token_id) {
+            // Market channel: BUY price is the best ask, SELL price the best bid; the quote is as fresh as the stream
+            Some((quote, received_at)) => (
+                quote.as_ref().and_then(|q| q.best_ask),
+                quote.as_ref().and_then(|q| q.best_bid),
+                received_at,
+            ),
+            None => {
+                // Get BUY price (BID price - what we pay to buy, higher)
+                // get_price(token_id, "BUY") returns the BID price (what we pay to buy)
+                let buy_price = match self.api.get_price(token_id, "BUY").await {
+                    Ok(price) => Some(price),
+                    Err(e) => {
+                        warn!("Failed to fetch {} {} BUY price: {}", market_name, outcome, e);
+                        None
+                    }
+                };
+
+                // Get SELL price (ASK price - what we receive when selling, lower)
+                // get_price(token_id, "SELL") returns the ASK price (what we receive when selling)
+                let sell_price = match self.api.get_price(token_id, "SELL").await {
+                    Ok(price) => Some(price),
+                    Err(e) => {
+                        warn!("Failed to fetch {} {} SELL price: {}", market_name, outcome, e);
+                        None
+                    }
+                };
+
+                // Quote age is measured from here - the gamma/orderbook lookups below don't refresh bid/ask
+                (buy_price, sell_price, std::time::Instant::now())
             }
         };
 
-        // Get SELL price (ASK price - what we receive when selling, lower)
-        // get_price(token_id, "SELL") returns the ASK price (what we receive when selling)
-        let sell_price = match self.api.get_price(token_id, "SELL").await {
-            Ok(price) => Some(price),
-            Err(e) => {
-                warn!("Failed to fetch {} {} SELL price: {}", market_name, outcome, e);
-                None
+        // BUY price is the best ask and SELL price the best bid - SELL >= BUY means the book is crossed/locked
+        let crossed = match (buy_price, sell_price) {
+            (Some(buy), Some(sell)) => match crate::models::CrossedBook::check(token_id, sell, buy) {
+                Ok(()) => false,
+                Err(e) => {
+                    warn!("{} {}: {} - skipping until it clears", market_name, outcome, e);
+                    true
+                }
+            },
+            _ => false,
+        };
+
+        // Signal price per configured source - bid/ask stay executable CLOB prices
+        // A crossed book always falls back to the gamma midpoint so recorded prices aren't corrupted
+        let signal = if crossed {
+            self.fetch_gamma_signal(token_id, market_name, outcome).await
+        } else {
+            match self.price_source {
+                PriceSource::ClobTop => None,
+                PriceSource::GammaMidpoint => self.fetch_gamma_signal(token_id, market_name, outcome).await,
+                PriceSource::Blend => {
+                    let book_is_tight = match (buy_price, sell_price) {
+                        (Some(buy), Some(sell)) => buy - sell <= self.max_blend_spread,
+                        _ => false, // Empty side
+                    };
+                    if book_is_tight {
+                        None
+                    } else {
+                        self.fetch_gamma_signal(token_id, market_name, outcome).await
+                    }
+                }
             }
         };
 
-        if buy_price.is_some() || sell_price.is_some() {
+        // Top-of-book sizes cost an extra orderbook request - only fetched when a size-based filter needs them
+        let (bid_size, ask_size) = if self.fetch_book_sizes {
+            match self.api.get_orderbook(token_id).await {
+                Ok(orderbook) => (
+                    orderbook.best_bid().map(|e| e.size),
+                    orderbook.best_ask().map(|e| e.size),
+                ),
+                Err(e) => {
+                    warn!("Failed to fetch {} {} orderbook sizes: {}", market_name, outcome, e);
+                    (None, None)
+                }
+            }
+        } else {
+            (None, None)
+        };
+
+        if buy_price.is_some() || sell_price.is_some() || signal.is_some() {
             Some(TokenPrice {
                 token_id: token_id.clone(),
                 bid: buy_price,  // BID = BUY price (what we pay to buy, higher)
                 ask: sell_price, // ASK = SELL price (what we receive when selling, lower)
+                signal,
+                bid_size,
+                ask_size,
+                crossed,
+                fetched_at,
             })
         } else {
             None
         }
     }
 
+    async fn fetch_gamma_signal(&self, token_id: &str, market_name: &str, outcome: &str) -> Option<rust_decimal::Decimal> {
+        match self.api.get_gamma_midpoint(token_id).await {
+            Ok(price) => Some(price),
+            Err(e) => {
+                warn!("Failed to fetch {} {} gamma midpoint: {}", market_name, outcome, e);
+                None
+            }
+        }
+    }
+
     /// Fetch resolved prices when market is closed
     /// Returns prices based on market resolution: winner = $1.00, loser = $0.00
     async fn fetch_resolved_prices(&self, condition_id: &str) -> (Option<TokenPrice>, Option<TokenPrice>) {
-        match self.api.get_market(condition_id).await {
-            Ok(market) => {
+        match self.get_market_tokens(condition_id).await {
+            Ok(tokens) => {
                 // Find which token won
                 let mut up_token_id = None;
                 let mut down_token_id = None;
                 let mut up_winner = false;
                 let mut down_winner = false;
                 
-                for token in &market.tokens {
+                for token in &tokens {
                     let outcome_upper = token.outcome.to_uppercase();
                     if outcome_upper.contains("UP") || outcome_upper == "1" {
                         up_token_id = Some(token.token_id.clone());
@@ -713,6 +1138,11 @@ impl MarketMonitor {
                         token_id,
                         bid: Some(resolved_price),
                         ask: Some(resolved_price),
+                        signal: None,
+                        bid_size: None,
+                        ask_size: None,
+                        crossed: false,
+                        fetched_at: std::time::Instant::now(),
                     }
                 });
                 
@@ -722,6 +1152,11 @@ impl MarketMonitor {
                         token_id,
                         bid: Some(resolved_price),
                         ask: Some(resolved_price),
+                        signal: None,
+                        bid_size: None,
+                        ask_size: None,
+                        crossed: false,
+                        fetched_at: std::time::Instant::now(),
                     }
                 });
                 
@@ -764,9 +1199,15 @@ impl MarketMonitor {
             match self.fetch_market_data().await {
                 Ok(snapshot) => {
                     debug!("Market snapshot updated");
+                    crate::metrics::record_api_call("market_snapshot", true);
+                    crate::metrics::set_market_period(snapshot.period_timestamp, snapshot.time_remaining_seconds);
+                    if let Some(recorder) = &self.snapshot_recorder {
+                        recorder.record(&snapshot);
+                    }
                     callback(snapshot).await;
                 }
                 Err(e) => {
+                    crate::metrics::record_api_call("market_snapshot", false);
                     warn!("Error fetching market data: {}", e);
                 }
             }
@@ -0,0 +1,168 @@
+//! Push notifications of trade events to Telegram and Discord.
+//!
+//! `init` sets up one process-wide notifier from `notifications` in config.json; `notify` is then a cheap
+//! call from anywhere in the bot. Messages are filtered by event type, rate limited, and sent from a
+//! background task so a slow or failing webhook never holds up trading. Without `init` (or without a
+//! configured destination) `notify` does nothing.
+
+use crate::config::NotificationsConfig;
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Messages allowed in any 60 seconds when notifications.max_per_minute is not set
+const DEFAULT_MAX_PER_MINUTE: u32 = 20;
+/// Give up on a webhook request after this long
+const SEND_TIMEOUT_SECS: u64 = 10;
+
+static NOTIFIER: OnceLock<Notifier> = OnceLock::new();
+
+/// Trade event types that can be notified (each has a switch in notifications.events)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyEvent {
+    BuyExecuted,
+    StopLoss,
+    RedemptionFailed,
+    MarketResolved,
+}
+
+/// Sliding 60-second window of sent messages
+struct RateLimiter {
+    max_per_minute: usize,
+    sent: VecDeque<Instant>,
+    suppressed: u64, // Dropped since the last message that went out
+}
+
+impl RateLimiter {
+    fn new(max_per_minute: u32) -> Self {
+        Self { max_per_minute: max_per_minute.max(1) as usize, sent: VecDeque::new(), suppressed: 0 }
+    }
+
+    /// Some(messages dropped before this one) if a message may go out at `now`, None if it is dropped
+    fn admit(&mut self, now: Instant) -> Option<u64> {
+        while self.sent.front().is_some_and(|sent| now.duration_since(*sent) >= Duration::from_secs(60)) {
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= self.max_per_minute {
+            self.suppressed += 1;
+            return None;
+        }
+        self.sent.push_back(now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+struct Notifier {
+    client: reqwest::Client,
+    config: NotificationsConfig,
+    simulation: bool,
+    limiter: Mutex<RateLimiter>,
+}
+
+impl Notifier {
+    fn wants(&self, event: NotifyEvent) -> bool {
+        let events = &self.config.events;
+        match event {
+            NotifyEvent::BuyExecuted => events.buy_executed,
+            NotifyEvent::StopLoss => events.stop_loss,
+            NotifyEvent::RedemptionFailed => events.redemption_failed,
+            NotifyEvent::MarketResolved => events.market_resolved,
+        }
+    }
+
+    async fn send(&self, text: &str) {
+        if let (Some(token), Some(chat_id)) = (&self.config.telegram_bot_token, &self.config.telegram_chat_id) {
+            let url = format!("https://api.telegram.org/bot{}/sendMessage", token);
+            let body = serde_json::json!({ "chat_id": chat_id, "text": text });
+            if let Err(e) = self.post(&url, &body).await {
+                warn!("Telegram notification failed: {}", e);
+            }
+        }
+        if let Some(url) = &self.config.discord_webhook_url {
+            let body = serde_json::json!({ "content": text });
+            if let Err(e) = self.post(url, &body).await {
+                warn!("Discord notification failed: {}", e);
+            }
+        }
+    }
+
+    async fn post(&self, url: &str, body: &serde_json::Value) -> Result<()> {
+        let response = self.client.post(url).json(body).send().await
+            // reqwest errors can include the URL, which holds the bot token / webhook secret
+            .map_err(|e| anyhow::anyhow!("request failed: {}", e.without_url()))?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("HTTP {}", status);
+        }
+        Ok(())
+    }
+}
+
+/// Set up the process-wide notifier from `notifications` (no-op when no destination is configured)
+/// Requests go through `http_proxy` (network.http_proxy) like the API client's
+pub fn init(config: &NotificationsConfig, simulation: bool, http_proxy: Option<&str>) -> Result<()> {
+    if !config.is_enabled() {
+        return Ok(());
+    }
+    let mut builder = reqwest::Client::builder().timeout(Duration::from_secs(SEND_TIMEOUT_SECS));
+    if let Some(proxy) = http_proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy).context("Invalid network.http_proxy")?);
+    }
+    let client = builder.build()
+        .context("Failed to create notification HTTP client")?;
+    let notifier = Notifier {
+        client,
+        config: config.clone(),
+        simulation,
+        limiter: Mutex::new(RateLimiter::new(config.max_per_minute.unwrap_or(DEFAULT_MAX_PER_MINUTE))),
+    };
+    if NOTIFIER.set(notifier).is_err() {
+        anyhow::bail!("Notifier already initialized");
+    }
+    Ok(())
+}
+
+/// Send `message` for `event` in the background, if that event type is enabled and the rate limit allows
+pub fn notify(event: NotifyEvent, message: String) {
+    let Some(notifier) = NOTIFIER.get() else {
+        return;
+    };
+    if !notifier.wants(event) {
+        return;
+    }
+    let Some(suppressed) = notifier.limiter.lock().unwrap().admit(Instant::now()) else {
+        debug!("Notification rate limit reached - dropped: {}", message);
+        return;
+    };
+    let mut text = if notifier.simulation { format!("[SIM] {}", message) } else { message };
+    if suppressed > 0 {
+        text.push_str(&format!("\n({} earlier notification(s) dropped by the rate limit)", suppressed));
+    }
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    runtime.spawn(async move {
+        notifier.send(&text).await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_drops_over_the_limit_and_reports_them_once_the_window_frees() {
+        let mut limiter = RateLimiter::new(2);
+        let start = Instant::now();
+        assert_eq!(limiter.admit(start), Some(0));
+        assert_eq!(limiter.admit(start + Duration::from_secs(10)), Some(0));
+        assert_eq!(limiter.admit(start + Duration::from_secs(20)), None);
+        assert_eq!(limiter.admit(start + Duration::from_secs(30)), None);
+        // The first message leaves the window: the next one goes out and carries the dropped count
+        assert_eq!(limiter.admit(start + Duration::from_secs(60)), Some(2));
+        assert_eq!(limiter.admit(start + Duration::from_secs(61)), None);
+        assert_eq!(limiter.admit(start + Duration::from_secs(70)), Some(1));
+    }
+}
@@ -0,0 +1,118 @@
+//! Order lifecycle tracking from the CLOB order status API.
+//!
+//! Token balances lag fills and can't tell a partially filled order from one that
+//! hasn't filled at all. `OrderTracker` asks the exchange for each tracked order by
+//! id and reports where it is in its lifecycle and how much of it has matched, so
+//! `Trader` can move pending trades on real fill data.
+
+use crate::api_client::ApiClient;
+use crate::models::OpenOrder;
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Where an order is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderState {
+    /// Resting on the book (LIVE, DELAYED or UNMATCHED), possibly partially matched
+    Open,
+    /// Fully matched (MATCHED)
+    Filled,
+    /// Cancelled or expired (CANCELED); `size_matched` is what filled before that
+    Cancelled,
+    /// A status this bot doesn't know - treated as still open
+    Unknown,
+}
+
+impl OrderState {
+    /// Map a CLOB order status ("LIVE", "MATCHED", "CANCELED", ...) onto a lifecycle state
+    pub fn from_status(status: &str) -> Self {
+        match status.to_uppercase().as_str() {
+            "LIVE" | "DELAYED" | "UNMATCHED" => OrderState::Open,
+            "MATCHED" => OrderState::Filled,
+            "CANCELED" | "CANCELLED" => OrderState::Cancelled,
+            _ => OrderState::Unknown,
+        }
+    }
+
+    /// Filled or cancelled - the order will not match any further
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, OrderState::Filled | OrderState::Cancelled)
+    }
+}
+
+/// One order's state and fill progress as reported by the exchange
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderStatus {
+    pub order_id: String,
+    pub state: OrderState,
+    pub original_size: f64,
+    pub size_matched: f64,
+}
+
+impl From<OpenOrder> for OrderStatus {
+    fn from(order: OpenOrder) -> Self {
+        Self {
+            state: OrderState::from_status(&order.status),
+            order_id: order.order_id,
+            original_size: order.original_size,
+            size_matched: order.size_matched,
+        }
+    }
+}
+
+impl OrderStatus {
+    /// Some, but not all, of the order has matched
+    pub fn is_partially_filled(&self) -> bool {
+        self.size_matched > 0.000001 && self.size_matched + 0.000001 < self.original_size
+    }
+}
+
+/// Polls order status by order id and remembers how much of each order was matched when last seen
+pub struct OrderTracker {
+    api: Arc<dyn ApiClient>,
+    last_matched: Mutex<HashMap<String, f64>>, // Key: order_id, value: size_matched at the previous poll
+}
+
+impl OrderTracker {
+    pub fn new(api: Arc<dyn ApiClient>) -> Self {
+        Self { api, last_matched: Mutex::new(HashMap::new()) }
+    }
+
+    /// Status of each order, fetched `concurrency` at a time
+    /// Orders whose status couldn't be fetched are missing from the map (callers fall back to balance polling)
+    pub async fn poll(&self, mut order_ids: Vec<String>, concurrency: usize) -> HashMap<String, OrderStatus> {
+        order_ids.sort();
+        order_ids.dedup();
+        futures_util::stream::iter(order_ids)
+            .map(|order_id| {
+                let api = self.api.clone();
+                async move {
+                    let status = match api.get_order(&order_id).await {
+                        Ok(order) => Some(OrderStatus::from(order)),
+                        Err(e) => {
+                            log::debug!("Order status lookup for {} failed: {}", order_id, e);
+                            None
+                        }
+                    };
+                    (order_id, status)
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .filter_map(|(order_id, status)| async move { status.map(|s| (order_id, s)) })
+            .collect()
+            .await
+    }
+
+    /// Shares matched since the previous call for this order (0.0 when nothing new matched)
+    pub fn newly_matched(&self, status: &OrderStatus) -> f64 {
+        let mut last_matched = self.last_matched.lock().unwrap();
+        let previous = last_matched.insert(status.order_id.clone(), status.size_matched).unwrap_or(0.0);
+        (status.size_matched - previous).max(0.0)
+    }
+
+    /// Stop remembering an order once its trade has moved on
+    pub fn forget(&self, order_id: &str) {
+        self.last_matched.lock().unwrap().remove(order_id);
+    }
+}
@@ -0,0 +1,72 @@
+//! Market period length and slug format (`trading.period_seconds` / `trading.slug_template`).
+//!
+//! Up/down markets run for fixed periods that start on multiples of the period length since the Unix epoch,
+//! and their slugs embed the asset prefix and period start, e.g. "btc-updown-15m-1767726000". Both are
+//! process-wide and set once at startup with `configure`; until then the 15-minute defaults apply.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Period length in seconds when trading.period_seconds is not set (15 minutes)
+pub const DEFAULT_PERIOD_SECONDS: u64 = 900;
+/// Market slug when trading.slug_template is not set; {period} is the period length in minutes
+pub const DEFAULT_SLUG_TEMPLATE: &str = "{prefix}-updown-{period}m-{timestamp}";
+
+static PERIOD_SECONDS: AtomicU64 = AtomicU64::new(DEFAULT_PERIOD_SECONDS);
+static SLUG_TEMPLATE: RwLock<Option<String>> = RwLock::new(None); // None = DEFAULT_SLUG_TEMPLATE
+
+/// Use `period_seconds`-long periods and `slug_template` for market slugs from now on (called once at startup)
+pub fn configure(period_seconds: u64, slug_template: Option<&str>) {
+    PERIOD_SECONDS.store(period_seconds.max(60), Ordering::Relaxed);
+    *SLUG_TEMPLATE.write().unwrap() = slug_template.map(str::to_string);
+}
+
+/// Length of one market period in seconds
+pub fn seconds() -> u64 {
+    PERIOD_SECONDS.load(Ordering::Relaxed)
+}
+
+/// Start of the period containing `timestamp`
+pub fn start(timestamp: u64) -> u64 {
+    timestamp / seconds() * seconds()
+}
+
+/// Market slug of `prefix` ("btc", "eth", "solana", ...) for the period starting at `timestamp`
+pub fn slug(prefix: &str, timestamp: u64) -> String {
+    render(prefix, &timestamp.to_string())
+}
+
+/// Whether `slug` is one of `prefix`'s period markets (any period start)
+pub fn is_market_slug(prefix: &str, slug: &str) -> bool {
+    let template = render(prefix, "\u{0}");
+    let (head, tail) = template.split_once('\u{0}').unwrap_or((template.as_str(), ""));
+    slug.len() > head.len() + tail.len() && slug.starts_with(head) && slug.ends_with(tail)
+}
+
+fn render(prefix: &str, timestamp: &str) -> String {
+    let template = SLUG_TEMPLATE.read().unwrap();
+    render_template(template.as_deref().unwrap_or(DEFAULT_SLUG_TEMPLATE), seconds(), prefix, timestamp)
+}
+
+fn render_template(template: &str, period_seconds: u64, prefix: &str, timestamp: &str) -> String {
+    template
+        .replace("{prefix}", prefix)
+        .replace("{period}", &(period_seconds / 60).to_string())
+        .replace("{timestamp}", timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slug_templates_render_prefix_period_and_timestamp() {
+        assert_eq!(render_template(DEFAULT_SLUG_TEMPLATE, 900, "btc", "1767726000"), "btc-updown-15m-1767726000");
+        assert_eq!(render_template(DEFAULT_SLUG_TEMPLATE, 3600, "eth", "1767726000"), "eth-updown-60m-1767726000");
+        assert_eq!(render_template("{prefix}-updown-1h-{timestamp}", 3600, "sol", "1767726000"), "sol-updown-1h-1767726000");
+        // Defaults until configure() is called
+        assert!(is_market_slug("btc", "btc-updown-15m-1767726000"));
+        assert!(!is_market_slug("btc", "eth-updown-15m-1767726000"));
+        assert!(!is_market_slug("btc", "btc-updown-15m-"));
+    }
+}
@@ -0,0 +1,372 @@
+use crate::api::PolymarketApi;
+use crate::models::*;
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Source of token prices and market results for the simulation path
+/// `LivePriceFeed` reads the real CLOB; `SyntheticPriceFeed` generates offline, seeded price paths
+#[async_trait]
+pub trait PriceFeed: Send + Sync {
+    /// Top of book for a token (bid = best bid, ask = best ask). None if the book is empty
+    /// A crossed/locked book is returned with `crossed` set rather than as an error
+    async fn get_token_price(&self, token_id: &str) -> Result<Option<TokenPrice>>;
+
+    /// Market tokens with outcome and current price (used to resolve Up/Down token ids)
+    async fn get_market_tokens(&self, condition_id: &str) -> Result<Vec<MarketToken>>;
+
+    /// Whether the market is closed and whether `token_id` is the winning outcome
+    async fn get_market_result(&self, condition_id: &str, token_id: &str) -> Result<(bool, bool)>;
+
+    /// Advance time by one step (once per monitor snapshot). No-op for live prices
+    fn advance(&self) {}
+}
+
+/// Live prices from the Polymarket CLOB
+pub struct LivePriceFeed {
+    api: Arc<PolymarketApi>,
+}
+
+impl LivePriceFeed {
+    pub fn new(api: Arc<PolymarketApi>) -> Self {
+        Self { api }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for LivePriceFeed {
+    async fn get_token_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
+        let orderbook = self.api.get_orderbook(token_id).await?;
+        let best_bid = orderbook.best_bid();
+        let best_ask = orderbook.best_ask();
+        if best_bid.is_none() && best_ask.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(TokenPrice {
+            token_id: token_id.to_string(),
+            bid: best_bid.map(|e| e.price),
+            ask: best_ask.map(|e| e.price),
+            signal: None,
+            bid_size: best_bid.map(|e| e.size),
+            ask_size: best_ask.map(|e| e.size),
+            crossed: orderbook.check_crossed(token_id).is_err(),
+            fetched_at: std::time::Instant::now(),
+        }))
+    }
+
+    async fn get_market_tokens(&self, condition_id: &str) -> Result<Vec<MarketToken>> {
+        Ok(self.api.get_market(condition_id).await?.tokens)
+    }
+
+    async fn get_market_result(&self, condition_id: &str, token_id: &str) -> Result<(bool, bool)> {
+        let market = self.api.get_market(condition_id).await?;
+        let is_winner = market.tokens.iter()
+            .any(|t| t.token_id == token_id && t.winner);
+        Ok((market.closed, is_winner))
+    }
+}
+
+/// How a synthetic market's Up price evolves
+#[derive(Debug, Clone)]
+pub enum SyntheticPath {
+    /// Fixed Up prices, one per step; the market closes on the last one
+    Scripted(Vec<f64>),
+    /// Seeded random walk of the Up price; the market closes after `steps` steps
+    RandomWalk { start: f64, volatility: f64, steps: u64 },
+}
+
+struct SyntheticMarket {
+    up_token_id: String,
+    down_token_id: String,
+    path: SyntheticPath,
+    step: u64,
+    up_price: f64,
+}
+
+impl SyntheticMarket {
+    fn is_closed(&self) -> bool {
+        match &self.path {
+            SyntheticPath::Scripted(prices) => self.step + 1 >= prices.len() as u64,
+            SyntheticPath::RandomWalk { steps, .. } => self.step >= *steps,
+        }
+    }
+}
+
+/// Deterministic offline price generator
+/// Each market has one Up price path; Down is priced as 1 - Up. Prices are quoted with a fixed
+/// half-spread around the path price and clamped to the tradable 0.01-0.99 range
+pub struct SyntheticPriceFeed {
+    markets: Mutex<HashMap<String, SyntheticMarket>>, // Key: condition_id
+    half_spread: f64,
+    book_size: Option<f64>,
+    rng_state: Mutex<u64>,
+}
+
+impl SyntheticPriceFeed {
+    pub fn new(seed: u64, half_spread: f64) -> Self {
+        Self {
+            markets: Mutex::new(HashMap::new()),
+            half_spread,
+            book_size: None,
+            // xorshift must not start at zero
+            rng_state: Mutex::new(seed.max(1)),
+        }
+    }
+
+    /// Quote `size` shares at both the bid and the ask (default: no size, so depth never limits fills)
+    pub fn with_book_size(mut self, size: f64) -> Self {
+        self.book_size = Some(size);
+        self
+    }
+
+    /// Register a market with its Up/Down token ids and price path
+    pub fn add_market(&self, condition_id: &str, up_token_id: &str, down_token_id: &str, path: SyntheticPath) {
+        let up_price = match &path {
+            SyntheticPath::Scripted(prices) => prices.first().copied().unwrap_or(0.5),
+            SyntheticPath::RandomWalk { start, .. } => *start,
+        };
+        self.markets.lock().unwrap().insert(condition_id.to_string(), SyntheticMarket {
+            up_token_id: up_token_id.to_string(),
+            down_token_id: down_token_id.to_string(),
+            path,
+            step: 0,
+            up_price,
+        });
+    }
+
+    /// xorshift64* - uniform value in [-1, 1)
+    fn next_unit(&self) -> f64 {
+        let mut state = self.rng_state.lock().unwrap();
+        let mut x = *state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        *state = x;
+        let value = x.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11; // 53 random bits
+        (value as f64 / (1u64 << 53) as f64) * 2.0 - 1.0
+    }
+
+    fn quote(&self, token_id: &str, price: f64) -> TokenPrice {
+        let to_decimal = |p: f64| {
+            let clamped = p.clamp(0.01, 0.99);
+            Decimal::from_f64_retain(clamped).unwrap_or(Decimal::ZERO).round_dp(4)
+        };
+        TokenPrice {
+            token_id: token_id.to_string(),
+            bid: Some(to_decimal(price - self.half_spread)),
+            ask: Some(to_decimal(price + self.half_spread)),
+            signal: None,
+            bid_size: self.book_size.and_then(Decimal::from_f64_retain),
+            ask_size: self.book_size.and_then(Decimal::from_f64_retain),
+            crossed: false,
+            fetched_at: std::time::Instant::now(),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for SyntheticPriceFeed {
+    async fn get_token_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
+        let markets = self.markets.lock().unwrap();
+        for market in markets.values() {
+            if market.up_token_id == token_id {
+                return Ok(Some(self.quote(token_id, market.up_price)));
+            }
+            if market.down_token_id == token_id {
+                return Ok(Some(self.quote(token_id, 1.0 - market.up_price)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn get_market_tokens(&self, condition_id: &str) -> Result<Vec<MarketToken>> {
+        let markets = self.markets.lock().unwrap();
+        let market = markets.get(condition_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown synthetic market: {}", condition_id))?;
+        let closed = market.is_closed();
+        let up_price = Decimal::from_f64_retain(market.up_price).unwrap_or(Decimal::ZERO).round_dp(4);
+        Ok(vec![
+            MarketToken {
+                outcome: "Up".to_string(),
+                price: up_price,
+                token_id: market.up_token_id.clone(),
+                winner: closed && market.up_price >= 0.5,
+            },
+            MarketToken {
+                outcome: "Down".to_string(),
+                price: Decimal::ONE - up_price,
+                token_id: market.down_token_id.clone(),
+                winner: closed && market.up_price < 0.5,
+            },
+        ])
+    }
+
+    async fn get_market_result(&self, condition_id: &str, token_id: &str) -> Result<(bool, bool)> {
+        let markets = self.markets.lock().unwrap();
+        let market = markets.get(condition_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown synthetic market: {}", condition_id))?;
+        if !market.is_closed() {
+            return Ok((false, false));
+        }
+        let up_won = market.up_price >= 0.5;
+        let is_winner = if token_id == market.up_token_id { up_won } else { !up_won };
+        Ok((true, is_winner))
+    }
+
+    fn advance(&self) {
+        let mut markets = self.markets.lock().unwrap();
+        // Iterate in key order so the random walk is reproducible for a given seed
+        let mut condition_ids: Vec<String> = markets.keys().cloned().collect();
+        condition_ids.sort();
+        for condition_id in condition_ids {
+            let market = markets.get_mut(&condition_id).unwrap();
+            if market.is_closed() {
+                continue;
+            }
+            market.step += 1;
+            market.up_price = match &market.path {
+                SyntheticPath::Scripted(prices) => prices[market.step as usize],
+                SyntheticPath::RandomWalk { volatility, .. } => {
+                    (market.up_price + self.next_unit() * volatility).clamp(0.01, 0.99)
+                }
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::TokenType;
+    use crate::simulation::SimulationTracker;
+
+    const CONDITION_ID: &str = "dummy_synthetic_test_market";
+    const UP_TOKEN: &str = "synthetic_test_market_up_token_id";
+    const DOWN_TOKEN: &str = "synthetic_test_market_down_token_id";
+
+    #[tokio::test]
+    async fn scripted_path_fills_limit_buy_and_resolves_up() {
+        let feed = Arc::new(SyntheticPriceFeed::new(7, 0.01));
+        feed.add_market(CONDITION_ID, UP_TOKEN, DOWN_TOKEN, SyntheticPath::Scripted(vec![0.80, 0.70, 0.60, 0.95]));
+
+        let log_path = std::env::temp_dir().join("synthetic_feed_test_simulation.toml");
+        let tracker = SimulationTracker::new(log_path.to_str().unwrap(), feed.clone()).unwrap();
+        tracker.add_limit_order(
+            UP_TOKEN.to_string(),
+            TokenType::BtcUp,
+            CONDITION_ID.to_string(),
+            0.65,
+            10.0,
+            "BUY".to_string(),
+            0,
+        ).await;
+
+        // Steps 0 and 1: ask 0.81 / 0.71 > 0.65 - no fill
+        tracker.refresh_and_check_limit_orders().await;
+        feed.advance();
+        tracker.refresh_and_check_limit_orders().await;
+        assert!(!tracker.has_position(UP_TOKEN).await);
+
+        // Step 2: ask 0.61 <= 0.65 - fills at the ask
+        feed.advance();
+        tracker.refresh_and_check_limit_orders().await;
+        let positions = tracker.get_all_positions().await;
+        assert_eq!(positions.len(), 1);
+        assert!((positions[0].purchase_price - 0.61).abs() < 1e-9);
+
+        // Step 3 is the last scripted price - market closes with Up winning
+        assert_eq!(feed.get_market_result(CONDITION_ID, UP_TOKEN).await.unwrap(), (false, false));
+        feed.advance();
+        assert_eq!(feed.get_market_result(CONDITION_ID, UP_TOKEN).await.unwrap(), (true, true));
+        assert_eq!(feed.get_market_result(CONDITION_ID, DOWN_TOKEN).await.unwrap(), (true, false));
+
+        let (spent, earned, pnl) = tracker.resolve_market_positions(CONDITION_ID, true).await;
+        assert!((spent - 6.1).abs() < 1e-9);
+        assert!((earned - 10.0).abs() < 1e-9);
+        assert!((pnl - 3.9).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn starting_bankroll_refuses_unaffordable_buys() {
+        let feed = Arc::new(SyntheticPriceFeed::new(7, 0.01));
+        feed.add_market(CONDITION_ID, UP_TOKEN, DOWN_TOKEN, SyntheticPath::Scripted(vec![0.50, 0.95]));
+
+        let log_path = std::env::temp_dir().join("synthetic_feed_bankroll_test_simulation.toml");
+        let tracker = SimulationTracker::new(log_path.to_str().unwrap(), feed.clone()).unwrap();
+        tracker.set_starting_bankroll(Some(8.0));
+
+        // 10 Up @ ask 0.51 = $5.10 fits in $8.00
+        tracker.add_limit_order(UP_TOKEN.to_string(), TokenType::BtcUp, CONDITION_ID.to_string(), 0.60, 10.0, "BUY".to_string(), 0).await;
+        tracker.refresh_and_check_limit_orders().await;
+        assert!(tracker.has_position(UP_TOKEN).await);
+        assert!((tracker.cash().unwrap() - 2.9).abs() < 1e-9);
+
+        // Another $5.10 doesn't fit in the $2.90 left - skipped and dropped
+        tracker.add_limit_order(DOWN_TOKEN.to_string(), TokenType::BtcDown, CONDITION_ID.to_string(), 0.60, 10.0, "BUY".to_string(), 0).await;
+        tracker.refresh_and_check_limit_orders().await;
+        assert!(!tracker.has_position(DOWN_TOKEN).await);
+        assert_eq!(tracker.get_pending_order_count().await, 0);
+        assert!((tracker.cash().unwrap() - 2.9).abs() < 1e-9);
+
+        // Up wins: 10 shares pay out $10.00
+        feed.advance();
+        tracker.resolve_market_positions(CONDITION_ID, true).await;
+        assert!((tracker.cash().unwrap() - 12.9).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn fill_model_fills_partially_against_depth_with_slippage_and_fees() {
+        let feed = Arc::new(SyntheticPriceFeed::new(7, 0.01).with_book_size(4.0));
+        feed.add_market(CONDITION_ID, UP_TOKEN, DOWN_TOKEN, SyntheticPath::Scripted(vec![0.50, 0.95]));
+
+        let log_path = std::env::temp_dir().join("synthetic_feed_fill_model_test_simulation.toml");
+        let tracker = SimulationTracker::new(log_path.to_str().unwrap(), feed.clone()).unwrap();
+        tracker.set_fill_model(crate::config::SimFillModel {
+            slippage_bps: Some(100.0),
+            book_share: Some(0.5),
+            taker_fee_bps: Some(200.0),
+            maker_fee_bps: Some(0.0),
+        });
+        tracker.add_limit_order(UP_TOKEN.to_string(), TokenType::BtcUp, CONDITION_ID.to_string(), 0.60, 5.0, "BUY".to_string(), 0).await;
+
+        // Half of the 4 shares at the ask per check, at 0.51 + 1% slippage; the first fill pays the taker fee
+        tracker.refresh_and_check_limit_orders().await;
+        let position = tracker.get_position(UP_TOKEN).await.unwrap();
+        assert!((position.units - 2.0).abs() < 1e-9);
+        assert!((position.investment_amount - 2.0 * 0.5151 * 1.02).abs() < 1e-9);
+        assert!(tracker.has_pending_order(UP_TOKEN, "BUY").await);
+
+        // The rest fills over the next two checks as a resting (maker) order
+        tracker.refresh_and_check_limit_orders().await;
+        tracker.refresh_and_check_limit_orders().await;
+        let position = tracker.get_position(UP_TOKEN).await.unwrap();
+        let investment = 2.0 * 0.5151 * 1.02 + 3.0 * 0.5151;
+        assert!((position.units - 5.0).abs() < 1e-9);
+        assert!((position.investment_amount - investment).abs() < 1e-9);
+        assert!(!tracker.has_pending_order(UP_TOKEN, "BUY").await);
+
+        feed.advance();
+        let (spent, earned, pnl) = tracker.resolve_market_positions(CONDITION_ID, true).await;
+        assert!((spent - investment).abs() < 1e-9);
+        assert!((earned - 5.0).abs() < 1e-9);
+        assert!((pnl - (5.0 - investment)).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn random_walk_is_reproducible_for_a_seed() {
+        let run = |seed: u64| async move {
+            let feed = SyntheticPriceFeed::new(seed, 0.01);
+            feed.add_market(CONDITION_ID, UP_TOKEN, DOWN_TOKEN, SyntheticPath::RandomWalk { start: 0.5, volatility: 0.02, steps: 50 });
+            let mut prices = Vec::new();
+            for _ in 0..50 {
+                feed.advance();
+                prices.push(feed.get_token_price(UP_TOKEN).await.unwrap().unwrap().ask);
+            }
+            prices
+        };
+        assert_eq!(run(42).await, run(42).await);
+        assert_ne!(run(42).await, run(43).await);
+    }
+}
@@ -0,0 +1,205 @@
+//! Historical prices for backtests from the CLOB prices-history endpoint (`backtest --fetch-history N`).
+//!
+//! For each ended period and backtested asset the market is found by its slug, and the price series of its Up
+//! and Down tokens are downloaded at one-minute fidelity. Each market is cached as one JSON file under
+//! `HISTORY_CACHE_DIR` once it has resolved, so re-running a backtest reads the cache instead of the API.
+//! The series are merged into `backtest::PriceSnapshot`s - bid and ask are both the recorded price, carried
+//! forward between points - with a final $1.00 / $0.00 snapshot at close from the market's winner, and then
+//! backtested the same way as the recorded history/ price logs.
+
+use crate::api::PolymarketApi;
+use crate::backtest::PriceSnapshot;
+use crate::config::Config;
+use crate::models::PricePoint;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Where downloaded markets are cached
+pub const HISTORY_CACHE_DIR: &str = "history_cache";
+/// Minutes between the points of a downloaded series
+const FIDELITY_MINUTES: u32 = 1;
+
+/// Price series of one asset's market in one period, as cached on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketHistory {
+    pub condition_id: String,
+    pub up_won: Option<bool>, // None until the market has resolved (not cached until then)
+    pub up: Vec<PricePoint>,
+    pub down: Vec<PricePoint>,
+}
+
+/// Slug prefixes tried for a backtest asset ("BTC", "ETH", "SOL", "XRP")
+fn slug_prefixes(asset: &str) -> &'static [&'static str] {
+    match asset {
+        "BTC" => &["btc"],
+        "ETH" => &["eth"],
+        "SOL" => &["solana", "sol"],
+        _ => &["xrp"],
+    }
+}
+
+/// `asset`'s market for the period starting at `period`, from the cache or downloaded (and cached once resolved)
+pub async fn market_history(api: &PolymarketApi, asset: &str, period: u64, cache_dir: &Path) -> Result<MarketHistory> {
+    let cache_file = cache_dir.join(format!("{}_{}.json", asset.to_lowercase(), period));
+    if let Ok(content) = std::fs::read_to_string(&cache_file) {
+        if let Ok(history) = serde_json::from_str(&content) {
+            return Ok(history);
+        }
+        log::warn!("Ignoring unreadable cache file {}", cache_file.display());
+    }
+
+    let mut market = None;
+    for prefix in slug_prefixes(asset) {
+        if let Ok(found) = api.get_market_by_slug(&crate::period::slug(prefix, period)).await {
+            market = Some(found);
+            break;
+        }
+    }
+    let market = market.with_context(|| format!("No {} market found for period {}", asset, period))?;
+    let details = api.get_market(&market.condition_id).await?;
+    let token = |outcome: &str| details.tokens.iter()
+        .find(|t| t.outcome.eq_ignore_ascii_case(outcome))
+        .with_context(|| format!("{} market for period {} has no {} token", asset, period, outcome));
+    let (up_token, down_token) = (token("Up")?, token("Down")?);
+
+    let end = period + crate::period::seconds();
+    let history = MarketHistory {
+        condition_id: market.condition_id.clone(),
+        up_won: (details.closed && up_token.winner != down_token.winner).then_some(up_token.winner),
+        up: api.get_prices_history(&up_token.token_id, period, end, FIDELITY_MINUTES).await?,
+        down: api.get_prices_history(&down_token.token_id, period, end, FIDELITY_MINUTES).await?,
+    };
+    if history.up_won.is_some() {
+        std::fs::create_dir_all(cache_dir)
+            .with_context(|| format!("Failed to create {}", cache_dir.display()))?;
+        std::fs::write(&cache_file, serde_json::to_string(&history)?)
+            .with_context(|| format!("Failed to write {}", cache_file.display()))?;
+    }
+    Ok(history)
+}
+
+/// Set `asset`'s Up or Down quote of `snapshot` to `price` (bid and ask)
+fn set_price(snapshot: &mut PriceSnapshot, asset: &str, up: bool, price: f64) {
+    let (bid, ask) = match (asset, up) {
+        ("BTC", true) => (&mut snapshot.btc_up_bid, &mut snapshot.btc_up_ask),
+        ("BTC", false) => (&mut snapshot.btc_down_bid, &mut snapshot.btc_down_ask),
+        ("ETH", true) => (&mut snapshot.eth_up_bid, &mut snapshot.eth_up_ask),
+        ("ETH", false) => (&mut snapshot.eth_down_bid, &mut snapshot.eth_down_ask),
+        ("SOL", true) => (&mut snapshot.solana_up_bid, &mut snapshot.solana_up_ask),
+        ("SOL", false) => (&mut snapshot.solana_down_bid, &mut snapshot.solana_down_ask),
+        (_, true) => (&mut snapshot.xrp_up_bid, &mut snapshot.xrp_up_ask),
+        (_, false) => (&mut snapshot.xrp_down_bid, &mut snapshot.xrp_down_ask),
+    };
+    *bid = Some(price);
+    *ask = Some(price);
+}
+
+fn empty_snapshot(timestamp: u64, period_end: u64) -> PriceSnapshot {
+    PriceSnapshot {
+        timestamp: chrono::DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default(),
+        time_remaining_seconds: period_end.saturating_sub(timestamp),
+        btc_up_bid: None, btc_up_ask: None, btc_down_bid: None, btc_down_ask: None,
+        eth_up_bid: None, eth_up_ask: None, eth_down_bid: None, eth_down_ask: None,
+        solana_up_bid: None, solana_up_ask: None, solana_down_bid: None, solana_down_ask: None,
+        xrp_up_bid: None, xrp_up_ask: None, xrp_down_bid: None, xrp_down_ask: None,
+    }
+}
+
+/// One period's snapshots from its markets (asset, history): a snapshot per distinct point in time, every
+/// token quoted at its latest price so far, and a resolved snapshot at close for markets with a winner
+pub fn period_snapshots(period: u64, markets: &[(&str, MarketHistory)]) -> Vec<PriceSnapshot> {
+    let period_end = period + crate::period::seconds();
+    let mut points: BTreeMap<u64, Vec<(&str, bool, f64)>> = BTreeMap::new();
+    for (asset, history) in markets {
+        for (up, series) in [(true, &history.up), (false, &history.down)] {
+            for point in series.iter().filter(|point| point.t >= period && point.t < period_end) {
+                points.entry(point.t).or_default().push((asset, up, point.p));
+            }
+        }
+    }
+
+    let mut snapshots: Vec<PriceSnapshot> = Vec::new();
+    for (timestamp, prices) in points {
+        let mut snapshot = snapshots.last().cloned().unwrap_or_else(|| empty_snapshot(timestamp, period_end));
+        snapshot.timestamp = chrono::DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default();
+        snapshot.time_remaining_seconds = period_end.saturating_sub(timestamp);
+        for (asset, up, price) in prices {
+            set_price(&mut snapshot, asset, up, price);
+        }
+        snapshots.push(snapshot);
+    }
+
+    let resolved: Vec<_> = markets.iter().filter_map(|(asset, history)| Some((*asset, history.up_won?))).collect();
+    if !resolved.is_empty() {
+        let mut last = snapshots.last().cloned().unwrap_or_else(|| empty_snapshot(period_end, period_end));
+        last.timestamp = chrono::DateTime::from_timestamp(period_end as i64, 0).unwrap_or_default();
+        last.time_remaining_seconds = 0;
+        for (asset, up_won) in resolved {
+            set_price(&mut last, asset, true, if up_won { 1.0 } else { 0.0 });
+            set_price(&mut last, asset, false, if up_won { 0.0 } else { 1.0 });
+        }
+        snapshots.push(last);
+    }
+    snapshots
+}
+
+/// The last `count` ended periods of the assets the backtest covers, as (period, snapshots) oldest first
+/// Markets that can't be found or downloaded are skipped with a warning
+pub async fn fetch_periods(api: &PolymarketApi, config: &Config, count: u64, cache_dir: &Path) -> Vec<(u64, Vec<PriceSnapshot>)> {
+    let assets: Vec<&str> = crate::backtest::backtest_assets(config).into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(asset, _)| asset)
+        .collect();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let current = crate::period::start(now);
+    let mut periods = Vec::new();
+    for back in (1..=count).rev() {
+        let Some(period) = current.checked_sub(back * crate::period::seconds()) else {
+            continue;
+        };
+        let mut markets = Vec::new();
+        for asset in &assets {
+            match market_history(api, asset, period, cache_dir).await {
+                Ok(history) => markets.push((*asset, history)),
+                Err(e) => eprintln!("⚠️  Skipping {} period {}: {:#}", asset, period, e),
+            }
+        }
+        let snapshots = period_snapshots(period, &markets);
+        if !snapshots.is_empty() {
+            periods.push((period, snapshots));
+        }
+    }
+    periods
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_series_merge_into_snapshots_that_resolve_at_close() {
+        let period = 1_700_000_100;
+        let point = |t: u64, p: f64| PricePoint { t: period + t, p };
+        let btc = MarketHistory {
+            condition_id: "dummy_condition_id".to_string(),
+            up_won: Some(true),
+            up: vec![point(0, 0.50), point(60, 0.55), point(120, 0.70)],
+            down: vec![point(0, 0.50), point(120, 0.30)],
+        };
+
+        let snapshots = period_snapshots(period, &[("BTC", btc)]);
+        assert_eq!(snapshots.len(), 4);
+        // The Down price carries forward until its next point
+        assert_eq!((snapshots[1].btc_up_bid, snapshots[1].btc_down_ask), (Some(0.55), Some(0.50)));
+        assert_eq!(snapshots[1].time_remaining_seconds, crate::period::seconds() - 60);
+        assert_eq!(snapshots[2].btc_down_bid, Some(0.30));
+        let close = snapshots.last().unwrap();
+        assert_eq!((close.time_remaining_seconds, close.btc_up_ask, close.btc_down_ask), (0, Some(1.0), Some(0.0)));
+        assert_eq!(snapshots[0].eth_up_bid, None);
+    }
+}
@@ -0,0 +1,197 @@
+//! Retries and circuit breakers for API calls (`network.retry`).
+//!
+//! `Retrier` wraps one call at a time: transient failures (no response, 429, 5xx - see `ApiError::is_transient`)
+//! are retried with exponential backoff and jitter, anything else is returned to the caller right away. Every
+//! endpoint has its own circuit breaker: after `breaker_failures` transient failures in a row its calls fail
+//! with `ApiError::CircuitOpen` without being sent, until `breaker_open_secs` have passed and a trial call
+//! gets through. Non-idempotent calls (order placement, redemption) go through the breaker but are never retried.
+//! Errors leave the retrier typed (`ApiError::typed`), so callers can match on the variant.
+
+use crate::config::RetryConfig;
+use crate::models::ApiError;
+use anyhow::Result;
+use log::warn;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Attempts per call when network.retry.max_attempts is not set
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+/// First retry delay in milliseconds when network.retry.base_delay_ms is not set
+pub const DEFAULT_BASE_DELAY_MS: u64 = 200;
+/// Longest retry delay in milliseconds when network.retry.max_delay_ms is not set
+pub const DEFAULT_MAX_DELAY_MS: u64 = 5000;
+/// Transient failures in a row that open a circuit when network.retry.breaker_failures is not set
+pub const DEFAULT_BREAKER_FAILURES: u32 = 5;
+/// Seconds an open circuit rejects calls when network.retry.breaker_open_secs is not set
+pub const DEFAULT_BREAKER_OPEN_SECS: u64 = 30;
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub breaker_failures: u32, // 0 = breakers disabled
+    pub breaker_open: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &RetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1),
+            base_delay: Duration::from_millis(config.base_delay_ms.unwrap_or(DEFAULT_BASE_DELAY_MS)),
+            max_delay: Duration::from_millis(config.max_delay_ms.unwrap_or(DEFAULT_MAX_DELAY_MS)),
+            breaker_failures: config.breaker_failures.unwrap_or(DEFAULT_BREAKER_FAILURES),
+            breaker_open: Duration::from_secs(config.breaker_open_secs.unwrap_or(DEFAULT_BREAKER_OPEN_SECS)),
+        }
+    }
+
+    /// Pause before retry number `retry` (1 = first retry): base * 2^(retry-1), capped at max_delay,
+    /// then jittered to a random point in its upper half so concurrent callers don't retry in lockstep
+    pub fn delay(&self, retry: u32) -> Duration {
+        let exponential = self.base_delay.saturating_mul(1u32 << retry.saturating_sub(1).min(16));
+        let capped = exponential.min(self.max_delay).as_millis() as u64;
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .subsec_nanos() as u64;
+        let half = capped / 2;
+        Duration::from_millis(capped - half + nanos % (half + 1))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&RetryConfig::default())
+    }
+}
+
+#[derive(Default)]
+struct Breaker {
+    failures: u32, // Transient failures in a row
+    open_until: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct Retrier {
+    policy: RetryPolicy,
+    breakers: Mutex<HashMap<&'static str, Breaker>>, // Key: endpoint (ApiClient method name)
+}
+
+impl Retrier {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy, breakers: Mutex::new(HashMap::new()) }
+    }
+
+    /// Run `call` against `endpoint`, retrying transient failures if `idempotent`
+    pub async fn call<T, F, Fut>(&self, endpoint: &'static str, idempotent: bool, mut call: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let attempts = if idempotent { self.policy.max_attempts } else { 1 };
+        let mut attempt = 1;
+        loop {
+            self.admit(endpoint)?;
+            let result = call().await;
+            let transient = match &result {
+                Ok(_) => false,
+                Err(e) => ApiError::of(e).is_some_and(ApiError::is_transient),
+            };
+            let open = self.record(endpoint, transient);
+            match result {
+                Err(e) if transient && !open && attempt < attempts => {
+                    let delay = self.policy.delay(attempt);
+                    log::debug!("{} failed (attempt {}/{}), retrying in {}ms: {}", endpoint, attempt, attempts, delay.as_millis(), e);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result.map_err(ApiError::typed),
+            }
+        }
+    }
+
+    /// Err(CircuitOpen) while the endpoint's circuit is open. Once the open period is over one caller is let
+    /// through as the trial and the circuit is re-armed until that call's outcome is recorded
+    fn admit(&self, endpoint: &'static str) -> Result<()> {
+        let mut breakers = self.breakers.lock().unwrap();
+        let breaker = breakers.entry(endpoint).or_default();
+        if let Some(open_until) = breaker.open_until {
+            let now = Instant::now();
+            if now < open_until {
+                return Err(ApiError::CircuitOpen(format!("{} failed {} times in a row - retrying in {}s",
+                    endpoint, breaker.failures, (open_until - now).as_secs() + 1)).into());
+            }
+            breaker.open_until = Some(now + self.policy.breaker_open);
+        }
+        Ok(())
+    }
+
+    /// Count a transient failure towards the endpoint's breaker, or close it again on any other outcome
+    /// Returns whether the circuit is open now
+    fn record(&self, endpoint: &'static str, transient: bool) -> bool {
+        let mut breakers = self.breakers.lock().unwrap();
+        let breaker = breakers.entry(endpoint).or_default();
+        if !transient {
+            if breaker.open_until.take().is_some() {
+                eprintln!("🔌 API circuit closed: {} is responding again", endpoint);
+            }
+            breaker.failures = 0;
+            return false;
+        }
+        breaker.failures += 1;
+        let threshold = self.policy.breaker_failures;
+        if threshold > 0 && breaker.failures >= threshold {
+            if breaker.failures == threshold {
+                warn!("🔌 API circuit open: {} failed {} times in a row - pausing it for {}s",
+                    endpoint, breaker.failures, self.policy.breaker_open.as_secs());
+            }
+            breaker.open_until = Some(Instant::now() + self.policy.breaker_open);
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn transient_failures_are_retried_and_open_the_circuit() {
+        let retrier = Retrier::new(RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            breaker_failures: 4,
+            breaker_open: Duration::from_secs(60),
+        });
+        let calls = AtomicU32::new(0);
+        let failing = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(ApiError::Network("dummy timeout".to_string()).into())
+        };
+
+        // Three attempts, then the error is returned
+        assert!(retrier.call("get_orderbook", true, failing).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        // The fourth failure in a row opens the circuit: later calls aren't sent at all
+        let e = retrier.call("get_orderbook", true, failing).await.unwrap_err();
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        assert!(matches!(ApiError::of(&e), Some(ApiError::Network(_))));
+        let e = retrier.call("get_orderbook", true, failing).await.unwrap_err();
+        assert!(matches!(ApiError::of(&e), Some(ApiError::CircuitOpen(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        // Breakers are per endpoint; rejections and non-idempotent calls are not retried
+        let rejected = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(ApiError::Http(404, "dummy".to_string()).into())
+        };
+        assert!(retrier.call("get_market", true, rejected).await.is_err());
+        assert!(retrier.call("place_order", false, failing).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 6);
+        assert!(retrier.call("get_market", true, || async { Ok(()) }).await.is_ok());
+    }
+}
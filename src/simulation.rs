@@ -1,5 +1,7 @@
 use crate::models::*;
+use crate::config::SimFillModel;
 use crate::detector::TokenType;
+use crate::price_feed::PriceFeed;
 use rust_decimal::Decimal;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -20,6 +22,8 @@ pub struct SimulatedLimitOrder {
     pub timestamp: std::time::Instant,
     pub period_timestamp: u64,
     pub filled: bool,
+    pub filled_size: f64, // Filled so far (partial fills under sim.fill_model.book_share)
+    pub resting: bool, // Survived a price check without filling in full - later fills pay the maker fee
 }
 
 /// Represents an open position in simulation
@@ -37,6 +41,8 @@ pub struct SimulatedPosition {
     pub sold: bool,
     pub sell_price_actual: Option<f64>, // Actual sell price when sold
     pub sell_timestamp: Option<std::time::Instant>,
+    pub sold_units: f64, // Units sold by partial SELL fills so far
+    pub sell_proceeds: f64, // Cash received for sold_units, net of fees
 }
 
 /// Simulation tracker for tracking orders, positions, and PnL
@@ -49,10 +55,24 @@ pub struct SimulationTracker {
     total_invested: Arc<Mutex<f64>>,
     // Price trend tracking: Key: (period_timestamp, token_id)
     price_trackers: Arc<Mutex<HashMap<(u64, String), PriceTrendTracker>>>,
+    // Where simulated fills get their prices (live CLOB or synthetic)
+    price_feed: Arc<dyn PriceFeed>,
+    // Cash ledger when sim.starting_bankroll is set (None = unlimited cash)
+    // std Mutex so the bankroll can be set from the synchronous Trader builder
+    bankroll: std::sync::Mutex<Option<SimBankroll>>,
+    // Slippage, depth and fees applied to fills (sim.fill_model)
+    fill_model: std::sync::Mutex<SimFillModel>,
+}
+
+/// Simulated cash: buys are debited, sells and resolutions credited
+#[derive(Debug, Clone, Copy)]
+struct SimBankroll {
+    starting: f64,
+    cash: f64,
 }
 
 impl SimulationTracker {
-    pub fn new(log_file_path: &str) -> Result<Self> {
+    pub fn new(log_file_path: &str, price_feed: Arc<dyn PriceFeed>) -> Result<Self> {
         // Create history directory if it doesn't exist
         std::fs::create_dir_all("history").context("Failed to create history directory")?;
         
@@ -70,9 +90,84 @@ impl SimulationTracker {
             total_realized_pnl: Arc::new(Mutex::new(0.0)),
             total_invested: Arc::new(Mutex::new(0.0)),
             price_trackers: Arc::new(Mutex::new(HashMap::new())),
+            price_feed,
+            bankroll: std::sync::Mutex::new(None),
+            fill_model: std::sync::Mutex::new(SimFillModel::default()),
         })
     }
 
+    /// Start tracking cash from `starting_bankroll`; simulated buys that cost more than the cash left are refused
+    /// None (the default) leaves cash unlimited
+    pub fn set_starting_bankroll(&self, starting_bankroll: Option<f64>) {
+        *self.bankroll.lock().unwrap() = starting_bankroll.map(|starting| SimBankroll { starting, cash: starting });
+    }
+
+    /// Use `fill_model` for slippage, depth-limited partial fills and fees on later fills
+    pub fn set_fill_model(&self, fill_model: SimFillModel) {
+        *self.fill_model.lock().unwrap() = fill_model;
+    }
+
+    /// Cash left, or None when no bankroll is tracked
+    pub fn cash(&self) -> Option<f64> {
+        self.bankroll.lock().unwrap().map(|b| b.cash)
+    }
+
+    /// Add (or with a negative amount, remove) cash. No-op without a bankroll
+    fn credit_cash(&self, amount: f64) {
+        if let Some(bankroll) = self.bankroll.lock().unwrap().as_mut() {
+            bankroll.cash += amount;
+        }
+    }
+
+    /// Price feed used for simulated fills and market results
+    pub fn price_feed(&self) -> Arc<dyn PriceFeed> {
+        self.price_feed.clone()
+    }
+
+    /// Fetch current prices for the given tokens from the price feed (tokens without a book are skipped)
+    pub async fn fetch_prices(&self, token_ids: &[String]) -> HashMap<String, TokenPrice> {
+        let mut current_prices = HashMap::new();
+        for token_id in token_ids {
+            if current_prices.contains_key(token_id) {
+                continue;
+            }
+            match self.price_feed.get_token_price(token_id).await {
+                Ok(Some(token_price)) => {
+                    if token_price.ask.is_none() {
+                        self.log_to_file(&format!(
+                            "⚠️  SIMULATION: No ask price available for token {} (BUY orders may not fill)",
+                            &token_id[..16]
+                        )).await;
+                    }
+                    current_prices.insert(token_id.clone(), token_price);
+                }
+                Ok(None) => {
+                    self.log_to_file(&format!(
+                        "⚠️  SIMULATION: Empty orderbook for token {}",
+                        &token_id[..16]
+                    )).await;
+                }
+                Err(e) => {
+                    self.log_to_file(&format!(
+                        "⚠️  SIMULATION: Failed to fetch orderbook for token {}: {}",
+                        &token_id[..16],
+                        e
+                    )).await;
+                }
+            }
+        }
+        current_prices
+    }
+
+    /// Fetch prices for all pending limit orders and fill those whose price was reached
+    /// Returns the prices used so callers can reuse them for summaries
+    pub async fn refresh_and_check_limit_orders(&self) -> HashMap<String, TokenPrice> {
+        let token_ids = self.get_pending_order_token_ids().await;
+        let current_prices = self.fetch_prices(&token_ids).await;
+        self.check_limit_orders(&current_prices).await;
+        current_prices
+    }
+
     /// Get or create a market-specific log file
     /// Skips dummy markets - they should only log to simulation.toml
     async fn get_market_file(&self, condition_id: &str, period_timestamp: u64) -> Result<Arc<Mutex<std::fs::File>>> {
@@ -159,6 +254,8 @@ impl SimulationTracker {
             timestamp: std::time::Instant::now(),
             period_timestamp,
             filled: false,
+            filled_size: 0.0,
+            resting: false,
         };
         
         let mut orders = self.pending_limit_orders.lock().await;
@@ -197,19 +294,21 @@ impl SimulationTracker {
     }
 
     /// Cancel a simulated limit order (removes it from pending tracking)
-    pub async fn cancel_limit_order(&self, token_id: &str, side: &str) {
+    /// Returns the size it had filled before the cancel (0 if there was no such order)
+    pub async fn cancel_limit_order(&self, token_id: &str, side: &str) -> f64 {
         let order_key = format!("{}_{}", token_id, side);
         let mut orders = self.pending_limit_orders.lock().await;
-        let existed = orders.remove(&order_key).is_some();
+        let cancelled = orders.remove(&order_key);
         let remaining = orders.values().filter(|o| !o.filled).count();
         drop(orders);
 
-        if existed {
+        if cancelled.is_some() {
             self.log_to_file(&format!(
                 "🛑 SIMULATION: Limit order cancelled - Token: {} Side: {} | Remaining unfilled: {}",
                 token_id, side, remaining
             )).await;
         }
+        cancelled.map_or(0.0, |o| o.filled_size)
     }
 
     /// Track price for trend analysis
@@ -473,58 +572,117 @@ impl SimulationTracker {
         for key in orders_to_fill {
             self.fill_limit_order(&key, current_prices).await;
         }
+
+        // Whatever is still unfilled now rests on the book
+        let mut orders = self.pending_limit_orders.lock().await;
+        for order in orders.values_mut().filter(|o| !o.filled && current_prices.contains_key(&o.token_id)) {
+            order.resting = true;
+        }
     }
 
     /// Fill a limit order and create a position (for BUY) or close a position (for SELL)
+    /// Under sim.fill_model the fill may be partial (capped by top-of-book size), slipped and charged a fee
     async fn fill_limit_order(&self, order_key: &str, current_prices: &HashMap<String, TokenPrice>) {
+        let fill_model = self.fill_model.lock().unwrap().clone();
         let mut orders = self.pending_limit_orders.lock().await;
         let order = match orders.get_mut(order_key) {
             Some(o) if !o.filled => o,
             _ => return,
         };
         
+        let to_f64 = |d: Option<Decimal>| d.and_then(|d| d.to_string().parse::<f64>().ok());
+        let price_data = current_prices.get(&order.token_id);
+        let (touch, book_size) = match order.side.as_str() {
+            "BUY" => (price_data.and_then(|p| to_f64(p.ask)), price_data.and_then(|p| to_f64(p.ask_size))),
+            "SELL" => (price_data.and_then(|p| to_f64(p.bid)), price_data.and_then(|p| to_f64(p.bid_size))),
+            _ => (None, None),
+        };
+        let touch = touch.unwrap_or(order.target_price);
+        let slippage = fill_model.slippage_bps.unwrap_or(0.0) / 10000.0;
         let fill_price = match order.side.as_str() {
-            "BUY" => {
-                current_prices.get(&order.token_id)
-                    .and_then(|p| p.ask)
-                    .map(|ask| ask.to_string().parse::<f64>().unwrap_or(order.target_price))
-                    .unwrap_or(order.target_price)
-            }
-            "SELL" => {
-                current_prices.get(&order.token_id)
-                    .and_then(|p| p.bid)
-                    .map(|bid| bid.to_string().parse::<f64>().unwrap_or(order.target_price))
-                    .unwrap_or(order.target_price)
-            }
-            _ => order.target_price,
+            "BUY" => (touch * (1.0 + slippage)).min(order.target_price).max(touch),
+            "SELL" => (touch * (1.0 - slippage)).max(order.target_price).min(touch),
+            _ => touch,
         };
         
-        order.filled = true;
+        let remaining = order.size - order.filled_size;
+        let fill_size = match (fill_model.book_share, book_size) {
+            (Some(share), Some(size)) => remaining.min(size * share),
+            _ => remaining,
+        };
+        if fill_size <= 0.0 {
+            return;
+        }
+        let completes = fill_size >= remaining - 1e-9;
+        let fee_bps = if order.resting { fill_model.maker_fee_bps } else { fill_model.taker_fee_bps };
+        let fee_rate = fee_bps.unwrap_or(0.0) / 10000.0;
+        let fill_label = if completes && order.filled_size == 0.0 {
+            "FILLED".to_string()
+        } else {
+            format!("{} ({:.6}/{:.6})", if completes { "FILLED" } else { "PARTIALLY FILLED" }, order.filled_size + fill_size, order.size)
+        };
+        
+        if order.side == "BUY" {
+            let cost = fill_size * fill_price * (1.0 + fee_rate);
+            if let Some(cash) = self.cash() {
+                if cost > cash + 1e-9 {
+                    let skip_msg = format!(
+                        "💸 SIMULATION: Insufficient funds - skipping BUY of {} ({:.6} @ ${:.6} = ${:.2}, cash: ${:.2})",
+                        order.token_id,
+                        fill_size,
+                        fill_price,
+                        cost,
+                        cash
+                    );
+                    let (condition_id, period_timestamp) = (order.condition_id.clone(), order.period_timestamp);
+                    orders.remove(order_key);
+                    drop(orders);
+                    self.log_to_file(&skip_msg).await;
+                    self.log_to_market(&condition_id, period_timestamp, &skip_msg).await;
+                    return;
+                }
+            }
+        }
+        
+        let earlier_fills = order.filled_size > 0.0;
+        order.filled_size += fill_size;
+        order.filled = completes;
         
         match order.side.as_str() {
             "BUY" => {
-                // Create a new position
-                let investment_amount = order.size * fill_price;
+                // Create a new position (or add to the one earlier partial fills of this order opened)
+                let fee = fill_size * fill_price * fee_rate;
+                let investment_amount = fill_size * fill_price + fee;
+                self.credit_cash(-investment_amount);
                 let position_key = order.token_id.clone();
                 
-                let position = SimulatedPosition {
-                    token_id: order.token_id.clone(),
-                    token_type: order.token_type.clone(),
-                    condition_id: order.condition_id.clone(),
-                    purchase_price: fill_price,
-                    units: order.size,
-                    investment_amount,
-                    sell_price: None, // Will be set when sell order is placed
-                    purchase_timestamp: std::time::Instant::now(),
-                    period_timestamp: order.period_timestamp,
-                    sold: false,
-                    sell_price_actual: None,
-                    sell_timestamp: None,
-                };
-                
                 {
                     let mut positions = self.positions.lock().await;
-                    positions.insert(position_key, position);
+                    match positions.get_mut(&position_key).filter(|p| earlier_fills && !p.sold) {
+                        Some(position) => {
+                            position.units += fill_size;
+                            position.investment_amount += investment_amount;
+                            position.purchase_price = position.investment_amount / position.units;
+                        }
+                        None => {
+                            positions.insert(position_key, SimulatedPosition {
+                                token_id: order.token_id.clone(),
+                                token_type: order.token_type.clone(),
+                                condition_id: order.condition_id.clone(),
+                                purchase_price: investment_amount / fill_size, // Fee included
+                                units: fill_size,
+                                investment_amount,
+                                sell_price: None, // Will be set when sell order is placed
+                                purchase_timestamp: std::time::Instant::now(),
+                                period_timestamp: order.period_timestamp,
+                                sold: false,
+                                sell_price_actual: None,
+                                sell_timestamp: None,
+                                sold_units: 0.0,
+                                sell_proceeds: 0.0,
+                            });
+                        }
+                    }
                 }
                 
                 {
@@ -538,12 +696,14 @@ impl SimulationTracker {
                 };
                 
                 let fill_msg = format!(
-                    "✅ SIMULATION: Limit BUY order FILLED - Token: {} ({}), Fill Price: ${:.6}, Size: {:.6}, Investment: ${:.2}",
+                    "✅ SIMULATION: Limit BUY order {} - Token: {} ({}), Fill Price: ${:.6}, Size: {:.6}, Investment: ${:.2}{}",
+                    fill_label,
                     order.token_id,
                     token_type_str,
                     fill_price,
-                    order.size,
-                    investment_amount
+                    fill_size,
+                    investment_amount,
+                    if fee > 0.0 { format!(" (fee ${:.4})", fee) } else { String::new() }
                 );
                 self.log_to_file(&fill_msg).await;
                 self.log_to_market(&order.condition_id, order.period_timestamp, &fill_msg).await;
@@ -559,15 +719,29 @@ impl SimulationTracker {
                 )).await;
             }
             "SELL" => {
-                // Close an existing position
+                // Close an existing position (partial fills reduce it; the last fill closes what is left)
                 let mut positions = self.positions.lock().await;
                 if let Some(position) = positions.get_mut(&order.token_id) {
                     if !position.sold {
-                        position.sold = true;
-                        position.sell_price_actual = Some(fill_price);
-                        position.sell_timestamp = Some(std::time::Instant::now());
+                        let units = if completes { position.units } else { fill_size.min(position.units) };
+                        let fee = units * fill_price * fee_rate;
+                        let proceeds = units * fill_price - fee;
+                        let realized_pnl = proceeds - position.purchase_price * units;
+                        self.credit_cash(proceeds);
                         
-                        let realized_pnl = (fill_price - position.purchase_price) * position.units;
+                        position.units -= units;
+                        position.investment_amount -= position.purchase_price * units;
+                        position.sold_units += units;
+                        position.sell_proceeds += proceeds;
+                        if completes || position.units <= 1e-9 {
+                            // A sold position describes what was sold
+                            position.sold = true;
+                            position.units = position.sold_units;
+                            position.investment_amount = position.purchase_price * position.sold_units;
+                            position.sell_price_actual = Some(position.sell_proceeds / position.sold_units);
+                            position.sell_timestamp = Some(std::time::Instant::now());
+                            order.filled = true;
+                        }
                         
                         {
                             let mut total_pnl = self.total_realized_pnl.lock().await;
@@ -580,12 +754,14 @@ impl SimulationTracker {
                         };
                         
                         let sell_msg = format!(
-                            "✅ SIMULATION: Limit SELL order FILLED - Token: {} ({}), Fill Price: ${:.6}, Size: {:.6}, Realized PnL: ${:.2}",
+                            "✅ SIMULATION: Limit SELL order {} - Token: {} ({}), Fill Price: ${:.6}, Size: {:.6}, Realized PnL: ${:.2}{}",
+                            fill_label,
                             order.token_id,
                             token_type_str,
                             fill_price,
-                            order.size,
-                            realized_pnl
+                            units,
+                            realized_pnl,
+                            if fee > 0.0 { format!(" (fee ${:.4})", fee) } else { String::new() }
                         );
                         self.log_to_file(&sell_msg).await;
                         self.log_to_market(&order.condition_id, order.period_timestamp, &sell_msg).await;
@@ -676,6 +852,26 @@ impl SimulationTracker {
             }
         }
         
+        let bankroll = *self.bankroll.lock().unwrap();
+        if let Some(bankroll) = bankroll {
+            // Equity marks open positions at mid, like unrealized PnL
+            let open_value: f64 = open_positions.iter()
+                .map(|pos| {
+                    let current_price = current_prices.get(&pos.token_id)
+                        .and_then(|p| p.mid_price())
+                        .map(|p| p.to_string().parse::<f64>().unwrap_or(pos.purchase_price))
+                        .unwrap_or(pos.purchase_price);
+                    current_price * pos.units
+                })
+                .sum();
+            summary.push_str(&format!(
+                "\nStarting Bankroll: ${:.2}\nCash: ${:.2}\nEquity: ${:.2}\n",
+                bankroll.starting,
+                bankroll.cash,
+                bankroll.cash + open_value
+            ));
+        }
+        
         summary.push_str("═══════════════════════════════════════════════════════════\n");
         summary
     }
@@ -692,6 +888,12 @@ impl SimulationTracker {
         positions.contains_key(token_id)
     }
 
+    /// Whether a limit order on `side` for the token is still (partly) unfilled
+    pub async fn has_pending_order(&self, token_id: &str, side: &str) -> bool {
+        let orders = self.pending_limit_orders.lock().await;
+        orders.get(&format!("{}_{}", token_id, side)).is_some_and(|o| !o.filled)
+    }
+
     /// Get all token IDs from open positions
     pub async fn get_position_token_ids(&self) -> Vec<String> {
         let positions = self.positions.lock().await;
@@ -701,6 +903,11 @@ impl SimulationTracker {
             .collect()
     }
 
+    /// Position for a token, open or sold
+    pub async fn get_position(&self, token_id: &str) -> Option<SimulatedPosition> {
+        self.positions.lock().await.get(token_id).cloned()
+    }
+
     /// Get all positions (for market closure checking)
     pub async fn get_all_positions(&self) -> Vec<SimulatedPosition> {
         let positions = self.positions.lock().await;
@@ -764,6 +971,7 @@ impl SimulationTracker {
             
             total_spent_for_market += position_cost;
             total_earned_for_market += position_value;
+            self.credit_cash(position_value);
             
             // Update position as sold
             {
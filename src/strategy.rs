@@ -0,0 +1,524 @@
+use crate::config::{Config, MeanReversionConfig, TwoSidedEntryConfig};
+use crate::detector::{BuyOpportunity, PriceDetector, TokenType};
+use crate::models::MarketData;
+use crate::monitor::MarketSnapshot;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Strategy used when `trading.strategy_name` is not set
+pub const DEFAULT_STRATEGY: &str = "momentum";
+/// Built-in strategies selectable with `trading.strategy_name`
+pub const STRATEGY_NAMES: &[&str] = &["momentum", "mean_reversion", "two_sided_entry"];
+
+/// What the core loop knows when it asks a strategy for entries
+#[derive(Debug, Clone, Default)]
+pub struct StrategyState {
+    /// Period the snapshot belongs to
+    pub period_timestamp: u64,
+    /// Token types with an open (unsold) position in this period - the core loop skips these anyway
+    pub held: HashSet<TokenType>,
+}
+
+/// A buy that filled, as reported back to the strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyFill {
+    pub token_id: String,
+    pub token_type: TokenType,
+    pub condition_id: String,
+    pub period_timestamp: u64,
+    pub price: f64,
+    pub units: f64,
+}
+
+/// Entry logic: turns a market snapshot into buy opportunities
+/// Sizing, risk limits, order placement and exits stay in `Trader`; a strategy only decides what to buy and when
+/// The core loop calls `evaluate` on every snapshot and `reset_period` when a new period starts; `Trader` calls
+/// `on_fill` when one of the bot's buys fills (see `Trader::with_strategy`)
+#[async_trait]
+pub trait Strategy: Send + Sync {
+    /// Name used in logs and in `trading.strategy_name`
+    fn name(&self) -> &str;
+
+    /// Buy opportunities for this snapshot (empty = nothing to do)
+    async fn evaluate(&self, snapshot: &MarketSnapshot, state: &StrategyState) -> Vec<BuyOpportunity>;
+
+    /// A buy filled - entries of this strategy as well as hedges the trader placed around them
+    async fn on_fill(&self, _fill: &StrategyFill) {}
+
+    /// A new period started - clear per-period state
+    async fn reset_period(&self) {}
+}
+
+/// Buy a token once its price reaches trigger_price (up to max_buy_price) after min_elapsed_minutes
+/// The threshold logic lives in `PriceDetector`, which `Trader` also uses for buy/sell cycle tracking
+pub struct MomentumStrategy {
+    detector: Arc<PriceDetector>,
+}
+
+impl MomentumStrategy {
+    pub fn new(detector: Arc<PriceDetector>) -> Self {
+        Self { detector }
+    }
+}
+
+#[async_trait]
+impl Strategy for MomentumStrategy {
+    fn name(&self) -> &str {
+        "momentum"
+    }
+
+    async fn evaluate(&self, snapshot: &MarketSnapshot, _state: &StrategyState) -> Vec<BuyOpportunity> {
+        self.detector.detect_opportunities(snapshot).await
+    }
+
+    async fn reset_period(&self) {
+        self.detector.reset_period().await;
+    }
+}
+
+/// `strategy.mean_reversion` with defaults filled in
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeanReversionParams {
+    pub oversold_price: f64,
+    pub min_price: f64,
+    pub sell_price: f64,
+    pub min_elapsed_minutes: u64,
+    pub min_time_remaining_seconds: u64,
+}
+
+impl From<&MeanReversionConfig> for MeanReversionParams {
+    fn from(config: &MeanReversionConfig) -> Self {
+        Self {
+            oversold_price: config.oversold_price.unwrap_or(0.20),
+            min_price: config.min_price.unwrap_or(0.05),
+            sell_price: config.sell_price.unwrap_or(0.35),
+            min_elapsed_minutes: config.min_elapsed_minutes.unwrap_or(10),
+            min_time_remaining_seconds: config.min_time_remaining_seconds.unwrap_or(60),
+        }
+    }
+}
+
+/// Late in the period, buy the cheaper token of a market once it has overshot down to oversold_price,
+/// betting on a bounce to sell_price. At most one entry per token per period
+pub struct MeanReversionStrategy {
+    params: MeanReversionParams,
+    enable_eth_trading: bool,
+    enable_solana_trading: bool,
+    enable_xrp_trading: bool,
+    entered: Mutex<HashSet<String>>, // Token ids entered this period
+}
+
+impl MeanReversionStrategy {
+    pub fn new(params: MeanReversionParams, enable_eth_trading: bool, enable_solana_trading: bool, enable_xrp_trading: bool) -> Self {
+        Self {
+            params,
+            enable_eth_trading,
+            enable_solana_trading,
+            enable_xrp_trading,
+            entered: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Entry on the cheaper side of `market`, if it is oversold
+    fn check_market(&self, market: &MarketData, up: TokenType, down: TokenType, snapshot: &MarketSnapshot, time_elapsed_seconds: u64) -> Option<BuyOpportunity> {
+        let (token, token_type, buy_price) = match (buy_price(&market.up_token), buy_price(&market.down_token)) {
+            (Some((up_token, up_price)), Some((_, down_price))) if up_price <= down_price => (up_token, up, up_price),
+            (Some(_), Some((down_token, down_price))) => (down_token, down, down_price),
+            _ => return None, // Need both sides to know which one is cheaper
+        };
+        if buy_price > self.params.oversold_price || buy_price < self.params.min_price {
+            return None;
+        }
+
+        eprintln!("🎯 {} MEAN-REVERSION BUY: BID=${:.3} <= oversold ${:.3} | Elapsed: {}m | Remaining: {}s | Target: ${:.3}",
+            token_type.display_name(), buy_price, self.params.oversold_price,
+            time_elapsed_seconds / 60, snapshot.time_remaining_seconds, self.params.sell_price);
+
+        Some(BuyOpportunity {
+            condition_id: market.condition_id.clone(),
+            token_id: token.token_id.clone(),
+            token_type,
+            bid_price: buy_price,
+            period_timestamp: snapshot.period_timestamp,
+            time_remaining_seconds: snapshot.time_remaining_seconds,
+            time_elapsed_seconds,
+            use_market_order: false,
+            investment_amount_override: None,
+            sell_price_override: Some(self.params.sell_price),
+            is_individual_hedge: false,
+            is_standard_hedge: false,
+            dual_limit_shares: None,
+        })
+    }
+}
+
+/// Monitor quote's BUY price (`bid`) - None for a missing or crossed quote
+fn buy_price(token: &Option<crate::models::TokenPrice>) -> Option<(&crate::models::TokenPrice, f64)> {
+    token.as_ref()
+        .filter(|t| !t.crossed)
+        .and_then(|t| t.bid.map(|bid| (t, f64::try_from(bid).unwrap_or(0.0))))
+}
+
+#[async_trait]
+impl Strategy for MeanReversionStrategy {
+    fn name(&self) -> &str {
+        "mean_reversion"
+    }
+
+    async fn evaluate(&self, snapshot: &MarketSnapshot, state: &StrategyState) -> Vec<BuyOpportunity> {
+        if snapshot.time_remaining_seconds == 0 || snapshot.time_remaining_seconds < self.params.min_time_remaining_seconds {
+            return Vec::new();
+        }
+        let time_elapsed_seconds = crate::period::seconds().saturating_sub(snapshot.time_remaining_seconds);
+        if time_elapsed_seconds < self.params.min_elapsed_minutes * 60 {
+            return Vec::new();
+        }
+
+        let mut markets = vec![(&snapshot.btc_market, TokenType::BtcUp, TokenType::BtcDown)];
+        if self.enable_eth_trading {
+            markets.push((&snapshot.eth_market, TokenType::EthUp, TokenType::EthDown));
+        }
+        if self.enable_solana_trading {
+            markets.push((&snapshot.solana_market, TokenType::SolanaUp, TokenType::SolanaDown));
+        }
+        if self.enable_xrp_trading {
+            markets.push((&snapshot.xrp_market, TokenType::XrpUp, TokenType::XrpDown));
+        }
+
+        let mut entered = self.entered.lock().await;
+        let mut opportunities = Vec::new();
+        for (market, up, down) in markets {
+            // One entry per market per period
+            if state.held.contains(&up) || state.held.contains(&down) {
+                continue;
+            }
+            if let Some(opportunity) = self.check_market(market, up, down, snapshot, time_elapsed_seconds) {
+                if entered.insert(opportunity.token_id.clone()) {
+                    opportunities.push(opportunity);
+                }
+            }
+        }
+        opportunities
+    }
+
+    async fn reset_period(&self) {
+        self.entered.lock().await.clear();
+    }
+}
+
+/// `strategy.two_sided_entry` with defaults filled in
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoSidedEntryParams {
+    pub max_combined_price: f64,
+    pub min_time_remaining_seconds: u64,
+}
+
+impl From<&TwoSidedEntryConfig> for TwoSidedEntryParams {
+    fn from(config: &TwoSidedEntryConfig) -> Self {
+        Self {
+            max_combined_price: config.max_combined_price.unwrap_or(0.97),
+            min_time_remaining_seconds: config.min_time_remaining_seconds.unwrap_or(60),
+        }
+    }
+}
+
+/// Buy both tokens of a market once Up + Down cost at most max_combined_price, in equal shares, and hold both
+/// to resolution: one side pays $1 per share, so the pair returns more than it cost whichever way it resolves.
+/// Both legs are market buys (`use_market_order`), which `Trader` keeps until closure and redeems per token.
+/// At most one pair per market per period
+pub struct TwoSidedEntryStrategy {
+    params: TwoSidedEntryParams,
+    trade_amount: f64, // Total spent on a pair (both legs together)
+    enable_eth_trading: bool,
+    enable_solana_trading: bool,
+    enable_xrp_trading: bool,
+    entered: Mutex<HashSet<String>>, // Condition ids entered this period
+}
+
+impl TwoSidedEntryStrategy {
+    pub fn new(params: TwoSidedEntryParams, trade_amount: f64, enable_eth_trading: bool, enable_solana_trading: bool, enable_xrp_trading: bool) -> Self {
+        Self {
+            params,
+            trade_amount,
+            enable_eth_trading,
+            enable_solana_trading,
+            enable_xrp_trading,
+            entered: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Up and Down legs for `market`, if the pair is cheap enough
+    fn check_market(&self, market: &MarketData, up: TokenType, down: TokenType, snapshot: &MarketSnapshot, time_elapsed_seconds: u64) -> Vec<BuyOpportunity> {
+        let (Some((up_token, up_price)), Some((down_token, down_price))) = (buy_price(&market.up_token), buy_price(&market.down_token)) else {
+            return Vec::new();
+        };
+        let combined = up_price + down_price;
+        if up_price <= 0.0 || down_price <= 0.0 || combined > self.params.max_combined_price {
+            return Vec::new();
+        }
+
+        // Equal shares on both sides: the winner's payout (shares x $1) exceeds the pair's cost (shares x combined)
+        let shares = self.trade_amount / combined;
+        eprintln!("🎯 {} TWO-SIDED ENTRY: Up ${:.3} + Down ${:.3} = ${:.3} <= ${:.3} | {:.2} shares each | Cost: ${:.2} | Payout: ${:.2}",
+            market.market_name, up_price, down_price, combined, self.params.max_combined_price,
+            shares, self.trade_amount, shares);
+
+        [(up_token, up, up_price), (down_token, down, down_price)].into_iter()
+            .map(|(token, token_type, price)| BuyOpportunity {
+                condition_id: market.condition_id.clone(),
+                token_id: token.token_id.clone(),
+                token_type,
+                bid_price: price,
+                period_timestamp: snapshot.period_timestamp,
+                time_remaining_seconds: snapshot.time_remaining_seconds,
+                time_elapsed_seconds,
+                use_market_order: true, // Held until closure, like the other multi-leg entries
+                investment_amount_override: Some(shares * price),
+                sell_price_override: None,
+                is_individual_hedge: false,
+                is_standard_hedge: false,
+                dual_limit_shares: None,
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Strategy for TwoSidedEntryStrategy {
+    fn name(&self) -> &str {
+        "two_sided_entry"
+    }
+
+    async fn evaluate(&self, snapshot: &MarketSnapshot, state: &StrategyState) -> Vec<BuyOpportunity> {
+        if snapshot.time_remaining_seconds == 0 || snapshot.time_remaining_seconds < self.params.min_time_remaining_seconds {
+            return Vec::new();
+        }
+        let time_elapsed_seconds = crate::period::seconds().saturating_sub(snapshot.time_remaining_seconds);
+
+        let mut markets = vec![(&snapshot.btc_market, TokenType::BtcUp, TokenType::BtcDown)];
+        if self.enable_eth_trading {
+            markets.push((&snapshot.eth_market, TokenType::EthUp, TokenType::EthDown));
+        }
+        if self.enable_solana_trading {
+            markets.push((&snapshot.solana_market, TokenType::SolanaUp, TokenType::SolanaDown));
+        }
+        if self.enable_xrp_trading {
+            markets.push((&snapshot.xrp_market, TokenType::XrpUp, TokenType::XrpDown));
+        }
+
+        let mut entered = self.entered.lock().await;
+        let mut opportunities = Vec::new();
+        for (market, up, down) in markets {
+            // Either leg already held means the pair (or another entry) is in place
+            if state.held.contains(&up) || state.held.contains(&down) {
+                continue;
+            }
+            let legs = self.check_market(market, up, down, snapshot, time_elapsed_seconds);
+            if !legs.is_empty() && entered.insert(market.condition_id.clone()) {
+                opportunities.extend(legs);
+            }
+        }
+        opportunities
+    }
+
+    async fn reset_period(&self) {
+        self.entered.lock().await.clear();
+    }
+}
+
+/// Build the strategy named by `trading.strategy_name` (default: momentum)
+pub fn from_config(config: &Config, detector: Arc<PriceDetector>) -> Result<Arc<dyn Strategy>> {
+    let name = config.trading.strategy_name.as_deref().unwrap_or(DEFAULT_STRATEGY);
+    match name {
+        "momentum" => Ok(Arc::new(MomentumStrategy::new(detector))),
+        "mean_reversion" => Ok(Arc::new(MeanReversionStrategy::new(
+            MeanReversionParams::from(&config.strategy.mean_reversion),
+            config.trading.enable_eth_trading,
+            config.trading.enable_solana_trading,
+            config.trading.enable_xrp_trading,
+        ))),
+        "two_sided_entry" => Ok(Arc::new(TwoSidedEntryStrategy::new(
+            TwoSidedEntryParams::from(&config.strategy.two_sided_entry),
+            config.trading.fixed_trade_amount,
+            config.trading.enable_eth_trading,
+            config.trading.enable_solana_trading,
+            config.trading.enable_xrp_trading,
+        ))),
+        other => anyhow::bail!("Unknown strategy \"{}\" (expected one of: {})", other, STRATEGY_NAMES.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TokenPrice;
+    use crate::price_feed::{PriceFeed, SyntheticPath, SyntheticPriceFeed};
+    use crate::simulation::SimulationTracker;
+
+    const CONDITION_ID: &str = "dummy_mean_reversion_test_market";
+    const UP_TOKEN: &str = "mean_reversion_test_market_up_token_id";
+    const DOWN_TOKEN: &str = "mean_reversion_test_market_down_token_id";
+
+    fn empty_market(name: &str) -> MarketData {
+        MarketData { condition_id: format!("dummy_{}", name), market_name: name.to_string(), up_token: None, down_token: None }
+    }
+
+    /// Snapshot as the monitor builds it from the feed: monitor bid = BUY price (book ask), ask = SELL price (book bid)
+    async fn snapshot(feed: &dyn PriceFeed, time_remaining_seconds: u64) -> MarketSnapshot {
+        let quote = |book: TokenPrice| TokenPrice { bid: book.ask, ask: book.bid, ..book };
+        let up = feed.get_token_price(UP_TOKEN).await.unwrap().map(quote);
+        let down = feed.get_token_price(DOWN_TOKEN).await.unwrap().map(quote);
+        MarketSnapshot {
+            eth_market: empty_market("ETH"),
+            btc_market: MarketData { condition_id: CONDITION_ID.to_string(), market_name: "BTC".to_string(), up_token: up, down_token: down },
+            solana_market: empty_market("Solana"),
+            xrp_market: empty_market("XRP"),
+            timestamp: std::time::Instant::now(),
+            time_remaining_seconds,
+            period_timestamp: 0,
+        }
+    }
+
+    /// V-shaped Up path: sells off to 0.15, then rebounds. Down (1 - Up) is the expensive side throughout
+    fn v_shaped_feed() -> Arc<SyntheticPriceFeed> {
+        let feed = Arc::new(SyntheticPriceFeed::new(7, 0.01));
+        feed.add_market(CONDITION_ID, UP_TOKEN, DOWN_TOKEN,
+            SyntheticPath::Scripted(vec![0.45, 0.30, 0.18, 0.15, 0.22, 0.30, 0.40, 0.55]));
+        feed
+    }
+
+    #[tokio::test]
+    async fn mean_reversion_enters_the_dip_and_exits_on_the_bounce() {
+        let strategy = MeanReversionStrategy::new(MeanReversionParams::from(&MeanReversionConfig::default()), false, false, false);
+        let state = StrategyState::default();
+
+        // Too early in the period (500s elapsed < 10 minutes) - no entry even at the bottom of the dip
+        let early = v_shaped_feed();
+        early.advance();
+        early.advance();
+        assert!(strategy.evaluate(&snapshot(early.as_ref(), 400).await, &state).await.is_empty());
+
+        let feed = v_shaped_feed();
+        let log_path = std::env::temp_dir().join("mean_reversion_test_simulation.toml");
+        let tracker = SimulationTracker::new(log_path.to_str().unwrap(), feed.clone()).unwrap();
+
+        // Mirrors Trader's simulation path: limit BUY at the opportunity price, limit SELL at its target once filled
+        let mut entries = Vec::new();
+        let mut sell_placed = false;
+        for step in 0..8u64 {
+            let opportunities = strategy.evaluate(&snapshot(feed.as_ref(), 240 - step * 10).await, &state).await;
+            for opportunity in &opportunities {
+                tracker.add_limit_order(opportunity.token_id.clone(), opportunity.token_type.clone(),
+                    opportunity.condition_id.clone(), opportunity.bid_price, 10.0, "BUY".to_string(), 0).await;
+                entries.push((step, opportunity.clone()));
+            }
+            tracker.refresh_and_check_limit_orders().await;
+            if !sell_placed && tracker.has_position(UP_TOKEN).await {
+                let (_, entry) = &entries[0];
+                tracker.add_limit_order(entry.token_id.clone(), entry.token_type.clone(), entry.condition_id.clone(),
+                    entry.sell_price_override.unwrap(), 10.0, "SELL".to_string(), 0).await;
+                sell_placed = true;
+            }
+            feed.advance();
+        }
+
+        // One entry, on the cheap Up token, at the first step priced at or below 0.20 (0.18 + half spread)
+        assert_eq!(entries.len(), 1);
+        let (step, entry) = &entries[0];
+        assert_eq!(*step, 2);
+        assert_eq!(entry.token_type, TokenType::BtcUp);
+        assert!((entry.bid_price - 0.19).abs() < 1e-9);
+        assert_eq!(entry.sell_price_override, Some(0.35));
+
+        // Exit on the bounce: first bid >= 0.35 is 0.39 (path 0.40 - half spread)
+        let position = tracker.get_position(UP_TOKEN).await.unwrap();
+        assert!(position.sold);
+        assert!((position.purchase_price - 0.19).abs() < 1e-9);
+        assert!((position.sell_price_actual.unwrap() - 0.39).abs() < 1e-9);
+        assert!(tracker.get_position(DOWN_TOKEN).await.is_none());
+        let (_, _, realized_pnl) = tracker.get_total_spending_and_earnings().await;
+        assert!((realized_pnl - 2.0).abs() < 1e-9);
+    }
+
+    /// Fixed book asks per token; the market is already closed with `up_wins` deciding the winner
+    struct MispricedFeed {
+        asks: std::collections::HashMap<String, f64>,
+        up_wins: bool,
+    }
+
+    #[async_trait]
+    impl PriceFeed for MispricedFeed {
+        async fn get_token_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
+            let decimal = |p: f64| rust_decimal::Decimal::from_f64_retain(p).unwrap().round_dp(4);
+            Ok(self.asks.get(token_id).map(|&ask| TokenPrice {
+                token_id: token_id.to_string(),
+                bid: Some(decimal(ask - 0.01)),
+                ask: Some(decimal(ask)),
+                signal: None,
+                bid_size: None,
+                ask_size: None,
+                crossed: false,
+                fetched_at: std::time::Instant::now(),
+            }))
+        }
+
+        async fn get_market_tokens(&self, _condition_id: &str) -> Result<Vec<crate::models::MarketToken>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_market_result(&self, _condition_id: &str, token_id: &str) -> Result<(bool, bool)> {
+            Ok((true, (token_id == UP_TOKEN) == self.up_wins))
+        }
+    }
+
+    #[tokio::test]
+    async fn two_sided_entry_profits_either_way_when_the_pair_costs_less_than_one() {
+        let strategy = TwoSidedEntryStrategy::new(TwoSidedEntryParams::from(&TwoSidedEntryConfig::default()), 10.0, false, false, false);
+        let state = StrategyState::default();
+
+        // Up 0.50 + Down 0.49 = 0.99 is above the 0.97 default - no entry
+        let fair = MispricedFeed { asks: [(UP_TOKEN.to_string(), 0.50), (DOWN_TOKEN.to_string(), 0.49)].into(), up_wins: true };
+        let fair: Arc<dyn PriceFeed> = Arc::new(fair);
+        assert!(strategy.evaluate(&snapshot(fair.as_ref(), 800).await, &state).await.is_empty());
+
+        for up_wins in [true, false] {
+            strategy.reset_period().await;
+            let feed: Arc<dyn PriceFeed> = Arc::new(MispricedFeed {
+                asks: [(UP_TOKEN.to_string(), 0.45), (DOWN_TOKEN.to_string(), 0.50)].into(),
+                up_wins,
+            });
+            let legs = strategy.evaluate(&snapshot(feed.as_ref(), 800).await, &state).await;
+
+            // Two market legs, equal shares, $10 across both, held to resolution
+            assert_eq!(legs.len(), 2);
+            assert_eq!(legs[0].token_type, TokenType::BtcUp);
+            assert_eq!(legs[1].token_type, TokenType::BtcDown);
+            assert!(legs.iter().all(|leg| leg.use_market_order && leg.sell_price_override.is_none()));
+            let shares: Vec<f64> = legs.iter().map(|leg| leg.investment_amount_override.unwrap() / leg.bid_price).collect();
+            assert!((shares[0] - shares[1]).abs() < 1e-9);
+            let cost: f64 = legs.iter().map(|leg| leg.investment_amount_override.unwrap()).sum();
+            assert!((cost - 10.0).abs() < 1e-9);
+
+            // Only one pair per market per period
+            assert!(strategy.evaluate(&snapshot(feed.as_ref(), 790).await, &state).await.is_empty());
+
+            // Mirrors Trader's simulation path: limit BUY per leg, both settled when the market resolves
+            let log_path = std::env::temp_dir().join("two_sided_entry_test_simulation.toml");
+            let tracker = SimulationTracker::new(log_path.to_str().unwrap(), feed.clone()).unwrap();
+            for leg in &legs {
+                tracker.add_limit_order(leg.token_id.clone(), leg.token_type.clone(), leg.condition_id.clone(),
+                    leg.bid_price, leg.investment_amount_override.unwrap() / leg.bid_price, "BUY".to_string(), 0).await;
+            }
+            tracker.refresh_and_check_limit_orders().await;
+            assert!(tracker.has_position(UP_TOKEN).await && tracker.has_position(DOWN_TOKEN).await);
+
+            let (spent, earned, pnl) = tracker.resolve_market_positions(CONDITION_ID, up_wins).await;
+            assert!((spent - 10.0).abs() < 1e-9);
+            assert!((earned - shares[0]).abs() < 1e-9);
+            assert!((pnl - (10.0 / 0.95 - 10.0)).abs() < 1e-9);
+        }
+    }
+}
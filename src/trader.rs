@@ -1,26 +1,269 @@
 use crate::api::PolymarketApi;
+use crate::api_client::ApiClient;
 use crate::models::*;
 use crate::detector::{BuyOpportunity, TokenType, PriceDetector};
-use crate::config::TradingConfig;
+use crate::config::{EntryOrderType, MarketOrderTif, NoBidPolicy, RoundingMode, StopLoss, TokenVerification, TradingConfig};
 use crate::monitor::MarketSnapshot;
 use crate::simulation::SimulationTracker;
+use crate::price_feed::{LivePriceFeed, PriceFeed};
+use crate::order_tracker::{OrderState, OrderTracker};
 use anyhow::Result;
+use futures_util::StreamExt;
 use log::{warn, debug};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use std::collections::HashMap;
 use std::str::FromStr;
 
+/// File where trades with abandoned redemptions are persisted for `--recover-abandoned`
+pub const ABANDONED_TRADES_FILE: &str = "abandoned_trades.json";
+
+/// Open trades of the market order bot, rewritten after every change so a restart picks them up (production only)
+pub const PENDING_TRADES_FILE: &str = "pending_trades.json";
+
+/// A buy order that hasn't answered after this long is treated as possibly submitted (see `place_buy_idempotent`)
+const BUY_ORDER_TIMEOUT_SECS: u64 = 15;
+/// Attempts for a buy whose earlier attempts are confirmed not to have filled
+const MAX_BUY_ATTEMPTS: u32 = 2;
+/// Time to let a possibly-submitted buy match before looking up our trades
+const BUY_SETTLE_DELAY_MS: u64 = 2000;
+/// Orderbook snapshots taken around orders when `debug.dump_orderbook_on_trade` is enabled
+pub const ORDERBOOK_DUMPS_FILE: &str = "orderbook_dumps.jsonl";
+/// One JSON rollup per market (period + asset) the bot traded, appended once the market has closed
+pub const PERIOD_SUMMARIES_FILE: &str = "period_summaries.jsonl";
+/// Longest rolling PnL window - older settlements are dropped from the ring
+const ROLLING_PNL_MAX_WINDOW: std::time::Duration = std::time::Duration::from_secs(24 * 3600);
+/// Size decimals when no per-asset size_decimals is configured (CLOB maximum for most markets)
+const DEFAULT_SIZE_DECIMALS: u32 = 2;
+/// Post-buy balance checks when balance_confirm.max_attempts is not set
+const DEFAULT_BALANCE_CONFIRM_ATTEMPTS: u32 = 5;
+/// Delay between post-buy balance checks when balance_confirm.delay_ms is not set
+const DEFAULT_BALANCE_CONFIRM_DELAY_MS: u64 = 1000;
+/// Attempts of the profit-target / stop-loss sell loops when sell.max_retries is not set
+const DEFAULT_SELL_MAX_RETRIES: u32 = 20;
+/// Delay between sell attempts when sell.retry_delay_ms is not set
+const DEFAULT_SELL_RETRY_DELAY_MS: u64 = 1500;
+/// SELL price at which asymmetric_exit sells a position as the likely winner when winner_confidence_price is not set
+const DEFAULT_WINNER_CONFIDENCE_PRICE: f64 = 0.95;
+/// Distance from the peg price that triggers a re-peg when sell.repeg_threshold is not set
+const DEFAULT_REPEG_THRESHOLD: f64 = 0.02;
+/// Price tick of the Up/Down markets - a pegged sell rests this far below the best ask
+const PEG_TICK: f64 = 0.01;
+/// Wait after placing a limit sell before checking it rests, when verify_resting_delay_ms is not set
+const DEFAULT_VERIFY_RESTING_DELAY_MS: u64 = 1000;
+/// Re-placements of a limit sell that isn't resting, when verify_resting_retries is not set
+const DEFAULT_VERIFY_RESTING_RETRIES: u32 = 2;
+/// Concurrent balance checks in the startup portfolio sync when portfolio_sync_concurrency is not set
+const DEFAULT_PORTFOLIO_SYNC_CONCURRENCY: usize = 4;
+/// Concurrent price/balance fetches per check_pending_trades tick when price_fetch_concurrency is not set
+const DEFAULT_PRICE_FETCH_CONCURRENCY: usize = 8;
+/// Smallest buy the CLOB accepts (USD) - a compounded trade size never shrinks below it
+const MIN_ORDER_AMOUNT: f64 = 1.0;
+/// How long to look for a limit buy whose response was lost when trading.lost_order_lookup_secs is not set
+const DEFAULT_LOST_ORDER_LOOKUP_SECS: u64 = 5;
+/// Allowed clock difference between us and the CLOB when matching a lost order by its creation time
+const LOST_ORDER_CLOCK_SKEW_SECS: i64 = 5;
+/// Minutes an open position is held without exit orders before it moves to the standard-hedge flow (trading.dual_limit_hedge_after_minutes unset)
+const DEFAULT_DUAL_LIMIT_HEDGE_AFTER_MINUTES: u64 = 10;
+/// Hedge limit sells as [price, fraction] when trading.hedge_sell_ladder is not set: the full position at each price
+const DEFAULT_HEDGE_SELL_LADDER: [(f64, f64); 2] = [(0.93, 1.0), (0.98, 1.0)];
+/// Seconds a maker buy rests before the unfilled part goes to market when trading.maker_timeout_secs is not set
+const DEFAULT_MAKER_TIMEOUT_SECS: u64 = 10;
+
+/// Outcome of a `--recover-abandoned` sweep
+#[derive(Debug, Default)]
+pub struct AbandonedRecovery {
+    pub recovered: usize,       // Redeemed successfully
+    pub already_settled: usize, // No balance left (redeemed elsewhere) - cleared
+    pub still_failing: usize,   // Still holding a balance, redemption failed again
+}
+
+/// Outcome of cancelling the bot's resting orders on shutdown
+#[derive(Debug, Default)]
+pub struct ShutdownCancel {
+    pub cancelled: usize, // Orders taken off the book
+    pub failed: usize,    // Cancel (or the open-order lookup) failed - check the book manually
+}
+
+/// SELL price and orderbook of a token, fetched up front for the sell checks in check_pending_trades
+struct SellQuote {
+    price: std::result::Result<f64, String>,
+    fetched_at: std::time::Instant, // When the price came back (for max_price_age_ms)
+    book: Result<Option<TokenPrice>>,
+}
+
+/// Outcome of startup flattening (startup.flatten_stale_positions)
+#[derive(Debug, Default)]
+pub struct StartupFlatten {
+    pub redeeming: usize, // Routed to redemption (claim at market closure)
+    pub sold: usize,      // Market-sold (ended but unresolved market, startup.sell_unresolved)
+    pub failed: usize,    // Couldn't be checked - left as they were
+}
+
+/// Outcome of the startup check for positions the bot doesn't track (startup.adopt_untracked)
+#[derive(Debug, Default)]
+pub struct UntrackedPositions {
+    pub found: usize,   // Untracked tokens with a balance
+    pub adopted: usize, // Taken over as managed positions
+    pub failed: usize,  // Markets/tokens that couldn't be checked
+}
+
+/// Rollup of everything the bot did in one market (period + asset), built from its settlements and written to
+/// `PERIOD_SUMMARIES_FILE` once the market has closed and none of its positions is still open
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct PeriodSummary {
+    pub period: u64,
+    pub asset: String, // "btc", "eth", "solana" or "xrp"
+    pub condition_id: String,
+    pub outcome: Option<String>, // "up" / "down"; None if every position was exited before resolution
+    pub entries: usize,          // Distinct trades settled (a scaled-out trade counts once)
+    pub exits: u32,              // Sells before resolution (each scale-out level counts)
+    pub shares: f64,
+    pub cost: f64,
+    pub proceeds: f64,           // Sell revenue plus resolution value
+    pub fees: Option<f64>,       // Not tracked yet - order responses don't report fees
+    pub net_pnl: f64,
+    pub max_favorable_excursion: Option<f64>, // Best sell price minus purchase price while held (per share)
+    pub max_adverse_excursion: Option<f64>,   // Worst sell price minus purchase price while held (per share)
+    #[serde(skip)]
+    trade_ids: std::collections::HashSet<String>,
+}
+
+impl PeriodSummary {
+    /// Add a settled part of entry `entry_id`: a sell before resolution (`exited`) or the resolution itself
+    fn add_settlement(&mut self, entry_id: &str, units: f64, cost: f64, proceeds: f64, exited: bool) {
+        self.trade_ids.insert(entry_id.to_string());
+        self.entries = self.trade_ids.len();
+        if exited {
+            self.exits += 1;
+        }
+        self.shares += units;
+        self.cost += cost;
+        self.proceeds += proceeds;
+        self.net_pnl += proceeds - cost;
+        crate::dashboard::record_settlement(&self.asset, self.period, units, cost, proceeds, exited);
+    }
+}
+
+/// Price a sell pegged to the book rests at: one tick below the lowest ask of other sellers (`own` orders are
+/// taken out of the book first, so a pegged sell never undercuts itself). If that would reach the best bid, it
+/// joins the best ask instead. None when nobody else is selling
+fn peg_sell_price(book: &OrderBook, own: &[OpenOrder]) -> Option<f64> {
+    let best_ask = book.asks.iter()
+        .filter_map(|level| {
+            let price = f64::try_from(level.price).ok()?;
+            let size = f64::try_from(level.size).unwrap_or(0.0);
+            let own_size: f64 = own.iter()
+                .filter(|o| (o.price - price).abs() < 1e-9)
+                .map(|o| o.original_size - o.size_matched)
+                .sum();
+            (size - own_size > 1e-9).then_some(price)
+        })
+        .fold(None, |best: Option<f64>, price| Some(best.map_or(price, |b| b.min(price))))?;
+    let best_bid = book.best_bid().and_then(|level| f64::try_from(level.price).ok());
+    let pegged = ((best_ask - PEG_TICK) * 100.0).round() / 100.0;
+    Some(match best_bid {
+        Some(bid) if pegged <= bid + 1e-9 => best_ask,
+        _ => pegged,
+    })
+}
+
+/// Price a maker buy rests at: `improvement` above the best bid, capped one tick below the best ask so it never
+/// crosses the spread. None when that leaves no valid price
+fn maker_buy_price(bid: f64, ask: Option<f64>, improvement: f64) -> Option<f64> {
+    let mut price = ((bid + improvement) * 100.0).round() / 100.0;
+    if let Some(ask) = ask {
+        price = price.min(((ask - PEG_TICK) * 100.0).round() / 100.0);
+    }
+    (price > 0.0).then_some(price)
+}
+
+/// Period summary of a market, started on first use
+fn period_summary_entry<'a>(
+    summaries: &'a mut HashMap<String, PeriodSummary>,
+    condition_id: &str,
+    period: u64,
+    token_type: &TokenType,
+) -> &'a mut PeriodSummary {
+    summaries.entry(condition_id.to_string()).or_insert_with(|| PeriodSummary {
+        period,
+        asset: token_type.asset_key().to_string(),
+        condition_id: condition_id.to_string(),
+        ..Default::default()
+    })
+}
+
+/// A market of one asset and period whose tokens the startup checks look at
+#[derive(Debug, Clone)]
+pub struct PeriodMarket {
+    pub asset_key: String, // "btc", "eth", "solana" or "xrp"
+    pub period_timestamp: u64,
+    pub condition_id: String,
+}
+
+/// One open position for `Trader::export_positions`, serialized in the shape of Polymarket's data API
+/// `/positions` records (asset, conditionId, outcome, size, avgPrice, curPrice, ...)
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRecord {
+    #[serde(rename = "asset")]
+    pub token_id: String,
+    pub condition_id: String,
+    pub outcome: String,            // "Up" / "Down"
+    pub size: f64,
+    pub avg_price: Option<f64>,     // Size-weighted purchase price; None for untracked holdings (cost unknown)
+    pub cur_price: Option<f64>,     // Mark: current SELL price (best bid); None if it couldn't be fetched
+    pub initial_value: Option<f64>, // size * avgPrice
+    pub current_value: Option<f64>, // size * curPrice
+    pub cash_pnl: Option<f64>,      // currentValue - initialValue
+    pub period: u64,
+    pub tracked: bool,              // false = held in the wallet but not managed by the bot
+    #[serde(skip)]
+    pub asset: String,              // "btc", "eth", "solana" or "xrp" (not part of the data API shape)
+}
+
+/// Token balance held in a market without a pending trade tracking it
+struct UntrackedHolding {
+    market: PeriodMarket,
+    token_id: String,
+    token_type: TokenType,
+    shares: f64,
+    last_price: f64, // Market's last traded price (gamma)
+}
 
 pub struct Trader {
-    api: Arc<PolymarketApi>,
-    config: TradingConfig,
+    api: Arc<dyn ApiClient>,
+    config: std::sync::RwLock<Arc<TradingConfig>>, // Swapped as a whole on a config reload (reload_config)
     simulation_mode: bool,
     total_profit: Arc<Mutex<f64>>,
     trades_executed: Arc<Mutex<u64>>,
-    pending_trades: Arc<Mutex<HashMap<String, PendingTrade>>>, // Key: period_timestamp
+    pending_trades: Arc<PendingTradeStore>, // Key: period_timestamp
     detector: Option<Arc<PriceDetector>>, // Optional detector reference for cycle tracking
     simulation_tracker: Option<Arc<SimulationTracker>>, // Simulation tracker for PnL and position tracking
+    completed_periods: Arc<Mutex<std::collections::HashSet<u64>>>, // Periods that have ended (for max_periods)
+    last_buy_at: Arc<Mutex<Option<std::time::Instant>>>, // Last entry that placed an order (for min_seconds_between_buys)
+    started_at: std::time::Instant, // Process start (for warmup_seconds - applied once, not per period)
+    orderbook_dump_levels: Option<usize>, // Levels per side to dump around orders (None = dumps disabled)
+    consecutive_losses: Arc<Mutex<u32>>, // Losing settlements in a row (reset on a win)
+    loss_cooldown_until: Arc<Mutex<Option<std::time::Instant>>>, // New buys halted until then (max_consecutive_losses)
+    closure_check_lock: Arc<Mutex<()>>, // Serializes check_market_closure (periodic tick vs resolution watcher)
+    settlements: Arc<Mutex<std::collections::VecDeque<(std::time::Instant, f64)>>>, // Realized PnL events (rolling_pnl)
+    schedule: crate::config::ScheduleConfig, // UTC trading hours for new buys (empty = always)
+    starting_balance: Arc<Mutex<Option<f64>>>, // Collateral account_pnl is measured against (None = no snapshot yet)
+    net_deposits: Arc<Mutex<f64>>, // Known deposits minus withdrawals since the snapshot (record_transfer)
+    wallet_balances: Arc<Mutex<Vec<(String, f64)>>>, // Last USDC balance read per trading wallet (polymarket.wallets)
+    insufficient_funds: Arc<Mutex<bool>>, // Last pre-buy USDC check couldn't cover the buy (logged once per transition)
+    stop_loss_peaks: std::sync::Mutex<HashMap<String, f64>>, // Key: trade_id, highest price seen since entry (trailing stop)
+    paused: std::sync::atomic::AtomicBool, // New entries held (control socket pause/resume)
+    maker_buys: std::sync::Mutex<HashMap<String, (std::time::Instant, BuyOpportunity)>>, // Key: limit trade key, resting maker buys with when they were placed
+    balance_confirm: crate::config::BalanceConfirmConfig, // Post-buy balance polling
+    resolution: crate::config::ResolutionConfig, // When an ended market counts as settled
+    redemption: crate::config::RedemptionConfig, // Which resolved positions are worth redeeming
+    sell: crate::config::SellConfig, // Sell loop retries and limit sell pegging
+    period_summaries: Arc<Mutex<HashMap<String, PeriodSummary>>>, // Key: condition_id, until written out
+    order_tracker: Arc<OrderTracker>, // Limit buy status and matched size by order id
+    risk: RiskManager, // Per-asset sizing, exposure cap and daily loss limit
+    strategy: Option<Arc<dyn crate::strategy::Strategy>>, // Told about buy fills (None = fills aren't reported)
 }
 
 impl Trader {
@@ -29,149 +272,295 @@ impl Trader {
     }
 
     pub fn new(api: Arc<PolymarketApi>, config: TradingConfig, simulation_mode: bool, detector: Option<Arc<PriceDetector>>) -> Result<Self> {
+        let price_feed: Arc<dyn PriceFeed> = Arc::new(LivePriceFeed::new(api.clone()));
+        Self::new_with_price_feed(api, config, simulation_mode, detector, price_feed)
+    }
+
+    /// Same as `new`, but simulation prices and market results come from `price_feed`
+    /// (e.g. a `SyntheticPriceFeed` for fully offline `--sim --synthetic` runs)
+    pub fn new_with_price_feed(
+        api: Arc<dyn ApiClient>,
+        config: TradingConfig,
+        simulation_mode: bool,
+        detector: Option<Arc<PriceDetector>>,
+        price_feed: Arc<dyn PriceFeed>,
+    ) -> Result<Self> {
         let simulation_tracker = if simulation_mode {
-            Some(Arc::new(SimulationTracker::new("simulation.toml")?))
+            Some(Arc::new(SimulationTracker::new("simulation.toml", price_feed)?))
         } else {
             None
         };
         
         Ok(Self {
+            order_tracker: Arc::new(OrderTracker::new(api.clone())),
             api,
-            config,
+            config: std::sync::RwLock::new(Arc::new(config)),
             simulation_mode,
             total_profit: Arc::new(Mutex::new(0.0)),
             trades_executed: Arc::new(Mutex::new(0)),
-            pending_trades: Arc::new(Mutex::new(HashMap::new())),
+            pending_trades: Arc::new(PendingTradeStore::default()),
             detector,
             simulation_tracker,
+            completed_periods: Arc::new(Mutex::new(std::collections::HashSet::new())),
+            last_buy_at: Arc::new(Mutex::new(None)),
+            started_at: std::time::Instant::now(),
+            orderbook_dump_levels: None,
+            consecutive_losses: Arc::new(Mutex::new(0)),
+            loss_cooldown_until: Arc::new(Mutex::new(None)),
+            closure_check_lock: Arc::new(Mutex::new(())),
+            settlements: Arc::new(Mutex::new(std::collections::VecDeque::new())),
+            schedule: crate::config::ScheduleConfig::default(),
+            starting_balance: Arc::new(Mutex::new(None)),
+            net_deposits: Arc::new(Mutex::new(0.0)),
+            wallet_balances: Arc::new(Mutex::new(Vec::new())),
+            insufficient_funds: Arc::new(Mutex::new(false)),
+            stop_loss_peaks: std::sync::Mutex::new(HashMap::new()),
+            paused: std::sync::atomic::AtomicBool::new(false),
+            maker_buys: std::sync::Mutex::new(HashMap::new()),
+            balance_confirm: crate::config::BalanceConfirmConfig::default(),
+            resolution: crate::config::ResolutionConfig::default(),
+            redemption: crate::config::RedemptionConfig::default(),
+            sell: crate::config::SellConfig::default(),
+            period_summaries: Arc::new(Mutex::new(HashMap::new())),
+            risk: RiskManager::default(),
+            strategy: None,
         })
     }
 
+    /// Trading settings in effect right now; hold on to the returned Arc to use one consistent set across awaits
+    fn config(&self) -> Arc<TradingConfig> {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Replace the trading settings of the running bot (config reload). Open positions keep the targets they
+    /// were entered with; the new values apply to everything decided from now on
+    pub fn reload_config(&self, config: TradingConfig) {
+        *self.config.write().unwrap() = Arc::new(config);
+    }
+
+    /// Log the top `levels` orderbook levels of the traded token to `ORDERBOOK_DUMPS_FILE` before and after
+    /// every buy/sell order (post-mortem diagnostics). None disables the dumps
+    pub fn with_orderbook_dumps(mut self, levels: Option<usize>) -> Self {
+        self.orderbook_dump_levels = levels;
+        self
+    }
+
+    /// Only place new buys inside the schedule's UTC windows (open positions are managed regardless)
+    pub fn with_schedule(mut self, schedule: crate::config::ScheduleConfig) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Attempts and delay for the token balance poll after a confirmed market buy
+    pub fn with_balance_confirm(mut self, balance_confirm: crate::config::BalanceConfirmConfig) -> Self {
+        self.balance_confirm = balance_confirm;
+        self
+    }
+
+    /// Attempts and delay of the profit-target and stop-loss sell retry loops, and limit sell pegging
+    pub fn with_sell_config(mut self, sell: crate::config::SellConfig) -> Self {
+        self.sell = sell;
+        self
+    }
+
+    /// Whether ended markets settle on the winner alone or only once the market is also closed
+    pub fn with_resolution(mut self, resolution: crate::config::ResolutionConfig) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Resolved positions worth less than redemption.min_value_to_redeem are closed without redeeming them
+    pub fn with_redemption(mut self, redemption: crate::config::RedemptionConfig) -> Self {
+        self.redemption = redemption;
+        self
+    }
+
+    /// Persist pending trades to `path` after every change and start from the trades saved there by a previous run.
+    /// Reloaded trades are reconciled against token balances by `sync_trades_with_portfolio`
+    pub fn with_trade_persistence(mut self, path: &str) -> Result<Self> {
+        let store = PendingTradeStore::open(path)?;
+        let restored = store.trades.try_lock().map(|trades| trades.len()).unwrap_or(0);
+        if restored > 0 {
+            crate::log_println!("📂 Restored {} pending trade(s) from {}", restored, path);
+        }
+        self.pending_trades = Arc::new(store);
+        Ok(self)
+    }
+
+    /// Simulated account cash (sim.starting_bankroll) and fill model (sim.fill_model); no-op outside simulation mode
+    pub fn with_sim(self, sim: &crate::config::SimConfig) -> Self {
+        if let Some(tracker) = &self.simulation_tracker {
+            tracker.set_starting_bankroll(sim.starting_bankroll);
+            tracker.set_fill_model(sim.fill_model.clone());
+        }
+        self
+    }
+
+    /// Report buy fills to the entry strategy (`Strategy::on_fill`)
+    pub fn with_strategy(mut self, strategy: Arc<dyn crate::strategy::Strategy>) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Tell the entry strategy, if any, that a buy filled
+    async fn report_fill(&self, fill: crate::strategy::StrategyFill) {
+        crate::dashboard::record_buy(fill.token_type.asset_key(), fill.period_timestamp, fill.units, fill.price);
+        if let Some(strategy) = &self.strategy {
+            strategy.on_fill(&fill).await;
+        }
+    }
+
+    /// Poll the token balance until it is nonzero or balance_confirm.max_attempts run out
+    /// Returns the last balance read (0.0 if the tokens never showed up), or None if every check failed
+    async fn wait_for_token_balance(&self, token_id: &str) -> Option<f64> {
+        let attempts = self.balance_confirm.max_attempts.unwrap_or(DEFAULT_BALANCE_CONFIRM_ATTEMPTS).max(1);
+        let delay = tokio::time::Duration::from_millis(
+            self.balance_confirm.delay_ms.unwrap_or(DEFAULT_BALANCE_CONFIRM_DELAY_MS));
+        let mut last_balance = None;
+        for attempt in 1..=attempts {
+            match self.api.check_balance_allowance(token_id).await {
+                Ok((balance, _)) => {
+                    let balance_f64 = f64::try_from(balance / rust_decimal::Decimal::from(1_000_000u64)).unwrap_or(0.0);
+                    if balance_f64 > 0.0 {
+                        return Some(balance_f64);
+                    }
+                    last_balance = Some(balance_f64);
+                    debug!("Balance check {}/{}: no tokens yet for {}", attempt, attempts, &token_id[..16]);
+                }
+                Err(e) => debug!("Balance check {}/{} failed for {}: {}", attempt, attempts, &token_id[..16], e),
+            }
+            if attempt < attempts {
+                tokio::time::sleep(delay).await;
+            }
+        }
+        last_balance
+    }
+
+    /// Append one orderbook snapshot (both sides, top N levels) for `token_id` to the dump file
+    async fn dump_orderbook(&self, token_id: &str, event: &str, trade_id: &str) {
+        let Some(levels) = self.orderbook_dump_levels else {
+            return;
+        };
+        let orderbook = match self.api.get_orderbook(token_id).await {
+            Ok(orderbook) => orderbook,
+            Err(e) => {
+                warn!("Orderbook dump ({}) failed for {}: {}", event, &token_id[..16], e);
+                return;
+            }
+        };
+        let side = |entries: &[OrderBookEntry]| -> Vec<serde_json::Value> {
+            entries.iter()
+                .take(levels)
+                .map(|e| serde_json::json!({ "price": e.price, "size": e.size }))
+                .collect()
+        };
+        let line = serde_json::json!({
+            "timestamp": chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            "event": event,
+            "trade_id": trade_id,
+            "token_id": token_id,
+            "bids": side(&orderbook.bids),
+            "asks": side(&orderbook.asks),
+        });
+        let result = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(ORDERBOOK_DUMPS_FILE)
+            .and_then(|mut file| {
+                use std::io::Write;
+                writeln!(file, "{}", line)
+            });
+        if let Err(e) = result {
+            warn!("Failed to write {}: {}", ORDERBOOK_DUMPS_FILE, e);
+        }
+    }
+
     /// Helper function to place hedge sell orders with retry logic
     /// This is called from a background task after waiting 7 seconds
+    /// Places one limit sell per hedge_sell_ladder rung: fraction x sell_size at the rung's price
     async fn place_hedge_sell_orders_with_retry(
-        api: &PolymarketApi,
-        pending_trades: &Arc<tokio::sync::Mutex<std::collections::HashMap<String, PendingTrade>>>,
+        api: &dyn ApiClient,
+        pending_trades: &Arc<PendingTradeStore>,
         trade_key: &str,
-        token_id: &str,
-        token_type: &crate::detector::TokenType,
-        period_timestamp: u64,
+        trade: &PendingTrade,
         sell_size: f64,
         is_standard_hedge: bool,
+        plan: &HedgeSellPlan,
     ) {
+        let (token_id, token_type, ladder) = (&trade.token_id, &trade.token_type, &plan.ladder);
         const MAX_RETRIES: u32 = 3;
         const RETRY_DELAY_SECS: u64 = 2;
         
         let hedge_type = if is_standard_hedge { "STANDARD HEDGE" } else { "INDIVIDUAL HEDGE" };
         
         crate::log_println!("═══════════════════════════════════════════════════════════");
-        crate::log_println!("📤 PLACING 2 LIMIT SELL ORDERS FOR {}", hedge_type);
+        crate::log_println!("📤 PLACING {} LIMIT SELL ORDERS FOR {}", ladder.len(), hedge_type);
         crate::log_println!("═══════════════════════════════════════════════════════════");
         crate::log_println!("📊 Order Details:");
         crate::log_println!("   Token: {}", token_type.display_name());
         crate::log_println!("   Token ID: {}", token_id);
-        crate::log_println!("   Order 1: ${:.2} at $0.93, Size: {:.6} shares", sell_size * 0.93, sell_size);
-        crate::log_println!("   Order 2: ${:.2} at $0.98, Size: {:.6} shares", sell_size * 0.98, sell_size);
+        for (i, (price, fraction)) in ladder.iter().enumerate() {
+            let size = sell_size * fraction;
+            crate::log_println!("   Order {}: ${:.2} at ${:.2}, Size: {:.6} shares", i + 1, size * price, price, size);
+        }
         crate::log_println!("");
         
         use crate::models::OrderRequest;
         
-        // Place first order at $0.93 with retry
-        let mut order1_placed = false;
-        for attempt in 1..=MAX_RETRIES {
-            let sell_order_1 = OrderRequest {
-                token_id: token_id.to_string(),
-                side: "SELL".to_string(),
-                size: format!("{:.2}", sell_size),
-                price: format!("{:.2}", 0.93),
-                order_type: "LIMIT".to_string(),
-            };
-            
-            match api.place_order(&sell_order_1).await {
-                Ok(response) => {
-                    crate::log_println!("   ✅ LIMIT SELL ORDER 1 PLACED FOR {} (attempt {})", hedge_type, attempt);
-                    crate::log_println!("      Token: {}", token_type.display_name());
-                    crate::log_println!("      Order ID: {:?}", response.order_id);
-                    crate::log_println!("      Limit Price: $0.93");
-                    crate::log_println!("      Size: {:.6} shares", sell_size);
-                    
-                    let order_id_str = response.order_id.as_ref()
-                        .map(|id| format!("{:?}", id))
-                        .unwrap_or_else(|| "N/A".to_string());
-                    let sell_event = format!(
-                        "LIMIT SELL ORDER ({}) | Market: {} | Period: {} | Token: {} | Limit Price: $0.93 | Size: {:.6} | Order ID: {}",
-                        hedge_type,
-                        token_type.display_name(),
-                        period_timestamp,
-                        &token_id[..16],
-                        sell_size,
-                        order_id_str
-                    );
-                    crate::log_trading_event(&sell_event);
-                    order1_placed = true;
-                    break;
-                }
-                Err(e) => {
-                    if attempt < MAX_RETRIES {
-                        crate::log_println!("   ⚠️  Failed to place limit sell order 1 (attempt {}): {} - retrying in {} seconds...", attempt, e, RETRY_DELAY_SECS);
-                        tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_DELAY_SECS)).await;
-                    } else {
-                        eprintln!("   ❌ FAILED TO PLACE LIMIT SELL ORDER 1 FOR {} after {} attempts: {}", hedge_type, MAX_RETRIES, e);
-                        warn!("Failed to place limit sell order 1 for {} after {} attempts: {}", hedge_type, MAX_RETRIES, e);
+        let mut any_placed = false;
+        for (i, (price, fraction)) in ladder.iter().enumerate() {
+            let order_number = i + 1;
+            let size = sell_size * fraction;
+            for attempt in 1..=MAX_RETRIES {
+                let sell_order = OrderRequest {
+                    token_id: token_id.to_string(),
+                    side: "SELL".to_string(),
+                    size: format_size(size, plan.size_decimals, plan.size_rounding),
+                    price: format!("{:.2}", price),
+                    order_type: "LIMIT".to_string(),
+                };
+                
+                match api.place_order(&sell_order).await {
+                    Ok(response) => {
+                        crate::log_println!("   ✅ LIMIT SELL ORDER {} PLACED FOR {} (attempt {})", order_number, hedge_type, attempt);
+                        crate::log_println!("      Token: {}", token_type.display_name());
+                        crate::log_println!("      Order ID: {:?}", response.order_id);
+                        crate::log_println!("      Limit Price: ${:.2}", price);
+                        crate::log_println!("      Size: {:.6} shares", size);
+                        
+                        let order_id_str = response.order_id.as_ref()
+                            .map(|id| format!("{:?}", id))
+                            .unwrap_or_else(|| "N/A".to_string());
+                        let sell_event = format!(
+                            "LIMIT SELL ORDER ({}) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.2} | Size: {:.6} | Order ID: {} | Trade ID: {}",
+                            hedge_type,
+                            token_type.display_name(),
+                            trade.market_timestamp,
+                            &token_id[..16],
+                            price,
+                            size,
+                            order_id_str,
+                            trade.trade_id
+                        );
+                        crate::log_trading_event(&sell_event);
+                        any_placed = true;
+                        break;
                     }
-                }
-            }
-        }
-        
-        // Place second order at $0.98 with retry
-        let mut order2_placed = false;
-        for attempt in 1..=MAX_RETRIES {
-            let sell_order_2 = OrderRequest {
-                token_id: token_id.to_string(),
-                side: "SELL".to_string(),
-                size: format!("{:.2}", sell_size),
-                price: format!("{:.2}", 0.98),
-                order_type: "LIMIT".to_string(),
-            };
-            
-            match api.place_order(&sell_order_2).await {
-                Ok(response) => {
-                    crate::log_println!("   ✅ LIMIT SELL ORDER 2 PLACED FOR {} (attempt {})", hedge_type, attempt);
-                    crate::log_println!("      Token: {}", token_type.display_name());
-                    crate::log_println!("      Order ID: {:?}", response.order_id);
-                    crate::log_println!("      Limit Price: $0.98");
-                    crate::log_println!("      Size: {:.6} shares", sell_size);
-                    
-                    let order_id_str = response.order_id.as_ref()
-                        .map(|id| format!("{:?}", id))
-                        .unwrap_or_else(|| "N/A".to_string());
-                    let sell_event = format!(
-                        "LIMIT SELL ORDER ({}) | Market: {} | Period: {} | Token: {} | Limit Price: $0.98 | Size: {:.6} | Order ID: {}",
-                        hedge_type,
-                        token_type.display_name(),
-                        period_timestamp,
-                        &token_id[..16],
-                        sell_size,
-                        order_id_str
-                    );
-                    crate::log_trading_event(&sell_event);
-                    order2_placed = true;
-                    break;
-                }
-                Err(e) => {
-                    if attempt < MAX_RETRIES {
-                        crate::log_println!("   ⚠️  Failed to place limit sell order 2 (attempt {}): {} - retrying in {} seconds...", attempt, e, RETRY_DELAY_SECS);
-                        tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_DELAY_SECS)).await;
-                    } else {
-                        eprintln!("   ❌ FAILED TO PLACE LIMIT SELL ORDER 2 FOR {} after {} attempts: {}", hedge_type, MAX_RETRIES, e);
-                        warn!("Failed to place limit sell order 2 for {} after {} attempts: {}", hedge_type, MAX_RETRIES, e);
+                    Err(e) => {
+                        if attempt < MAX_RETRIES {
+                            crate::log_println!("   ⚠️  Failed to place limit sell order {} (attempt {}): {} - retrying in {} seconds...", order_number, attempt, e, RETRY_DELAY_SECS);
+                            tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_DELAY_SECS)).await;
+                        } else {
+                            eprintln!("   ❌ FAILED TO PLACE LIMIT SELL ORDER {} FOR {} after {} attempts: {}", order_number, hedge_type, MAX_RETRIES, e);
+                            warn!("Failed to place limit sell order {} for {} after {} attempts: {}", order_number, hedge_type, MAX_RETRIES, e);
+                        }
                     }
                 }
             }
         }
         
         // Mark that sell orders have been placed (if at least one succeeded)
-        if order1_placed || order2_placed {
+        if any_placed {
             let mut pending = pending_trades.lock().await;
             if let Some(t) = pending.get_mut(trade_key) {
                 t.limit_sell_orders_placed = true;
@@ -179,6 +568,73 @@ impl Trader {
         }
     }
 
+    /// Limit sells for a standard or individual hedge: trading.hedge_sell_ladder, or the full position at $0.93 and $0.98
+    fn hedge_sell_ladder(&self) -> Vec<(f64, f64)> {
+        self.config().hedge_sell_ladder.clone().unwrap_or_else(|| DEFAULT_HEDGE_SELL_LADDER.to_vec())
+    }
+
+    /// The hedge sell ladder with the size precision of `token_type`'s asset, for a background sell task
+    fn hedge_sell_plan(&self, token_type: &crate::detector::TokenType) -> HedgeSellPlan {
+        HedgeSellPlan {
+            size_decimals: self.size_decimals(token_type),
+            size_rounding: self.config().size_rounding,
+            ladder: self.hedge_sell_ladder(),
+        }
+    }
+
+    /// "$0.93 and $0.98"-style list of the ladder's prices for log lines
+    fn describe_hedge_sell_ladder(ladder: &[(f64, f64)]) -> String {
+        let prices: Vec<String> = ladder.iter().map(|(price, _)| format!("${:.2}", price)).collect();
+        match prices.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+            _ => prices.join(""),
+        }
+    }
+
+    /// Move open positions held longer than `dual_limit_hedge_after_minutes` without exit orders into the
+    /// standard-hedge flow and place their hedge_sell_ladder limit sells. Called from `check_pending_trades`;
+    /// each trade is promoted at most once (its `limit_sell_orders_placed` flag is set when it is claimed)
+    pub async fn promote_standard_hedges(&self) {
+        let hedge_after_minutes = self.config().dual_limit_hedge_after_minutes.unwrap_or(DEFAULT_DUAL_LIMIT_HEDGE_AFTER_MINUTES);
+        let hedge_after = std::time::Duration::from_secs(hedge_after_minutes * 60);
+        let claimed = {
+            let mut pending = self.pending_trades.lock().await;
+            claim_standard_hedges(&mut pending, hedge_after, std::time::Instant::now())
+        };
+
+        for (trade_key, trade) in claimed {
+            crate::log_println!(
+                "⏰ {} held for {}+ minutes without exit orders - moving to standard hedge (key: {})",
+                trade.token_type.display_name(), hedge_after_minutes, trade_key
+            );
+            crate::log_trading_event(&format!(
+                "STANDARD HEDGE PROMOTION | Market: {} | Period: {} | Held: {}+ min | Trade ID: {}",
+                trade.token_type.display_name(), trade.market_timestamp, hedge_after_minutes, trade.trade_id
+            ));
+            if self.simulation_mode {
+                crate::log_println!("🎮 SIMULATION: Standard hedge limit sells would be placed at {}",
+                    Self::describe_hedge_sell_ladder(&self.hedge_sell_ladder()));
+                continue;
+            }
+
+            let api_clone = self.api.clone();
+            let pending_trades_clone = self.pending_trades.clone();
+            let sell_size = trade.confirmed_balance.unwrap_or(trade.units);
+            let plan = self.hedge_sell_plan(&trade.token_type);
+            tokio::spawn(async move {
+                Self::place_hedge_sell_orders_with_retry(
+                    api_clone.as_ref(),
+                    &pending_trades_clone,
+                    &trade_key,
+                    &trade,
+                    sell_size,
+                    true, // is_standard_hedge
+                    &plan,
+                ).await;
+            });
+        }
+    }
+
     /// Get the opposite token ID for a given token type and condition ID
     /// Returns the token ID of the opposite token (Up <-> Down)
     async fn get_opposite_token_id(&self, token_type: &TokenType, condition_id: &str) -> Result<String> {
@@ -204,6 +660,38 @@ impl Trader {
         anyhow::bail!("Could not find opposite token for {} in market {}", token_type.display_name(), condition_id)
     }
     
+    /// Stop price for a trade now quoted at `current_price`, under the configured stop-loss rule:
+    /// - fixed: its price - None for entries bought at or below it (e.g. mean-reversion dips), which would
+    ///   otherwise be stopped out on the first price check
+    /// - trailing: `percent` below the highest price seen since entry (the purchase price to begin with)
+    /// - time-based: like fixed, but None until the trade has been held `after_minutes`
+    fn stop_loss_for(&self, trade: &PendingTrade, current_price: f64) -> Option<f64> {
+        match self.config().stop_loss()? {
+            StopLoss::Fixed { price } => Some(price).filter(|stop| trade.purchase_price > *stop),
+            StopLoss::Trailing { percent } => {
+                let mut peaks = self.stop_loss_peaks.lock().unwrap();
+                let peak = peaks.entry(trade.trade_id.clone()).or_insert(trade.purchase_price);
+                *peak = peak.max(current_price);
+                Some(*peak * (1.0 - percent / 100.0))
+            }
+            StopLoss::TimeBased { price, after_minutes } => Some(price)
+                .filter(|stop| trade.purchase_price > *stop)
+                .filter(|_| trade.timestamp.elapsed() >= std::time::Duration::from_secs(after_minutes * 60)),
+        }
+    }
+
+    /// Core-loop state handed to the entry strategy for a snapshot of `period_timestamp`
+    pub async fn strategy_state(&self, period_timestamp: u64) -> crate::strategy::StrategyState {
+        let pending = self.pending_trades.lock().await;
+        crate::strategy::StrategyState {
+            period_timestamp,
+            held: pending.values()
+                .filter(|t| t.market_timestamp == period_timestamp && !t.sold && !t.redemption_abandoned)
+                .map(|t| t.token_type.clone())
+                .collect(),
+        }
+    }
+
     /// Returns true if we have an unsold position of the same type (BTC or ETH) in this period
     /// Note: 
     /// - Trades with failed redemptions (redemption_abandoned = true) don't block new positions
@@ -291,6 +779,218 @@ impl Trader {
         Ok(())
     }
     
+    /// Write the pending trades to disk now instead of on the background writer, so nothing is lost on exit
+    pub async fn save_pending_trades(&self) {
+        self.pending_trades.flush().await;
+    }
+
+    /// Cancel every open order on the tokens of unsettled trades (limit buys and resting sells) and write the
+    /// pending trades to disk. Trades whose sells were cancelled are marked as having none, so the next start
+    /// places them again; cancelled unfilled buys are dropped by the order status check on that start
+    /// Simulation has no resting orders to cancel and nothing persisted - only the summary applies
+    pub async fn shutdown(&self) -> ShutdownCancel {
+        let mut report = ShutdownCancel::default();
+        if self.simulation_mode {
+            return report;
+        }
+        let token_ids: std::collections::BTreeSet<String> = {
+            let pending = self.pending_trades.lock().await;
+            pending.values()
+                .filter(|t| !t.sold && !t.redemption_abandoned)
+                .map(|t| t.token_id.clone())
+                .collect()
+        };
+
+        let mut sells_cancelled = std::collections::HashSet::new();
+        for token_id in token_ids {
+            let (cancelled, failed) = self.cancel_open_orders(&token_id, "Shutdown", "SHUTDOWN CANCEL").await;
+            if cancelled.iter().any(|order| order.side.eq_ignore_ascii_case("SELL")) {
+                sells_cancelled.insert(token_id.clone());
+            }
+            report.cancelled += cancelled.len();
+            report.failed += failed;
+        }
+
+        let mut pending = self.pending_trades.lock().await;
+        for trade in pending.values_mut() {
+            if sells_cancelled.contains(&trade.token_id) && !trade.sold {
+                trade.limit_sell_orders_placed = false;
+            }
+        }
+        drop(pending);
+        // Written even when nothing changed and without waiting on the writer task, so the file reflects the final state
+        self.pending_trades.flush().await;
+        report
+    }
+
+    /// Cancel every open order on `token_id`, logging each as an `event` line in history.toml
+    /// Returns the cancelled orders and how many cancels (or the open-order lookup) failed
+    async fn cancel_open_orders(&self, token_id: &str, context: &str, event: &str) -> (Vec<OpenOrder>, usize) {
+        let orders = match self.api.get_open_orders(token_id).await {
+            Ok(orders) => orders,
+            Err(e) => {
+                warn!("{}: failed to list open orders for {}: {}", context, &token_id[..16], e);
+                return (Vec::new(), 1);
+            }
+        };
+        let (mut cancelled, mut failed) = (Vec::new(), 0);
+        for order in orders {
+            match self.api.cancel_order(&order.order_id).await {
+                Ok(()) => {
+                    crate::log_println!("   ❎ Cancelled {} {} @ ${:.2} (order {})",
+                        order.side, &token_id[..16], order.price, order.order_id);
+                    crate::log_trading_event(&format!(
+                        "{} | Token: {} | Side: {} | Price: ${:.2} | Remaining: {:.6} | Order ID: {}",
+                        event, &token_id[..16], order.side, order.price,
+                        order.original_size - order.size_matched, order.order_id
+                    ));
+                    cancelled.push(order);
+                }
+                Err(e) => {
+                    warn!("{}: failed to cancel order {}: {}", context, order.order_id, e);
+                    failed += 1;
+                }
+            }
+        }
+        (cancelled, failed)
+    }
+
+    /// Hold (`true`) or allow (`false`) new entries; positions already open keep being managed and sold
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Cancel order `order_id` on request (control socket). A limit buy placed with it is confirmed at whatever it
+    /// matched before the cancel, or dropped from tracking if nothing did; a cancelled resting sell leaves its trade
+    /// open. Simulation only knows the orders of its unfilled limit buys
+    pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let limit_buy = {
+            let pending = self.pending_trades.lock().await;
+            pending.iter()
+                .find(|(_, t)| t.order_id.as_deref() == Some(order_id) && !t.buy_order_confirmed)
+                .map(|(key, trade)| (key.clone(), trade.clone()))
+        };
+        // None: the matched size is unknown and order tracking settles the cancelled buy
+        let matched = if self.simulation_mode {
+            let Some((_, trade)) = &limit_buy else {
+                anyhow::bail!("no unfilled simulated limit buy has order id {}", order_id);
+            };
+            match &self.simulation_tracker {
+                Some(tracker) => Some(tracker.cancel_limit_order(&trade.token_id, "BUY").await),
+                None => Some(0.0),
+            }
+        } else {
+            self.api.cancel_order(order_id).await?;
+            if limit_buy.is_some() {
+                match self.api.get_order(order_id).await {
+                    Ok(order) => {
+                        self.order_tracker.forget(order_id);
+                        Some(order.size_matched)
+                    }
+                    Err(e) => {
+                        warn!("Limit buy order {} cancelled but its matched size is unknown - left to order tracking: {}", order_id, e);
+                        None
+                    }
+                }
+            } else {
+                Some(0.0)
+            }
+        };
+        crate::log_println!("❎ CONTROL: cancelled order {}", order_id);
+        crate::log_trading_event(&format!("CONTROL CANCEL | Order ID: {} | Limit Buy Dropped: {} | Matched: {:.6}",
+            order_id, limit_buy.is_some() && matched.is_some_and(|m| m <= 0.000001), matched.unwrap_or(0.0)));
+        if let Some((key, trade)) = limit_buy {
+            match matched {
+                Some(matched) if matched > 0.000001 => {
+                    crate::log_println!("   Limit buy {} had matched {:.6} of {:.6} shares - keeping those", key, matched, trade.units);
+                    let initial_balance = trade.confirmed_balance.unwrap_or(0.0);
+                    self.handle_limit_buy_fill(&key, &trade, initial_balance, initial_balance + matched).await;
+                }
+                Some(_) => {
+                    self.pending_trades.lock().await.remove(&key);
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Sell every open position in `token_id` at the current bid (FAK) on request (control socket), after
+    /// cancelling the token's resting orders. Shares the FAK sell doesn't fill stay tracked. Returns the shares sold
+    pub async fn force_sell(&self, token_id: &str) -> Result<f64> {
+        let trades: Vec<(String, PendingTrade)> = {
+            let pending = self.pending_trades.lock().await;
+            pending.iter()
+                .filter(|(_, t)| t.token_id == token_id && !t.sold && t.buy_order_confirmed)
+                .map(|(key, trade)| (key.clone(), trade.clone()))
+                .collect()
+        };
+        if trades.is_empty() {
+            anyhow::bail!("no open position in token {}", token_id);
+        }
+        let bid = f64::try_from(self.api.get_price(token_id, "SELL").await?).unwrap_or(0.0);
+        if bid <= 0.0 {
+            anyhow::bail!("token {} has no bid to sell into", token_id);
+        }
+        if self.simulation_mode {
+            if let Some(tracker) = &self.simulation_tracker {
+                tracker.cancel_limit_order(token_id, "SELL").await;
+            }
+        } else {
+            self.cancel_open_orders(token_id, "Force sell", "FORCE SELL CANCEL").await;
+        }
+
+        let mut sold = 0.0;
+        for (key, trade) in trades {
+            let units = trade.confirmed_balance.unwrap_or(trade.units);
+            crate::log_println!("🔨 FORCE SELL: {} (period {}) selling {:.6} shares at ${:.6}",
+                trade.token_type.display_name(), trade.market_timestamp, units, bid);
+            let sell_started = chrono::Utc::now().timestamp() - LOST_ORDER_CLOCK_SKEW_SECS;
+            self.execute_sell(&key, &trade, units, bid, Some("FAK"), bid < trade.purchase_price).await?;
+            let filled = self.fak_sold_size(&trade, units, sell_started, "Force sell").await;
+            let trade_sold = filled.unwrap_or(units);
+            crate::log_trading_event(&format!(
+                "FORCE SELL | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Proceeds: ${:.6} | Trade ID: {}",
+                trade.token_type.display_name(), trade.market_timestamp, bid, trade_sold, bid * trade_sold, trade.trade_id));
+            self.settle_fak_exit(&key, &trade, units, filled, bid, "Force sell").await;
+            sold += trade_sold;
+        }
+        Ok(sold)
+    }
+
+    /// Redeem the positions in resolved market `condition_id` now (control socket): they are marked to be claimed,
+    /// abandoned redemptions get a fresh set of attempts, and a market closure check runs right away instead of on
+    /// the next tick. redemption.min_value_to_redeem still applies. Returns the number of trades routed
+    pub async fn force_redeem(&self, condition_id: &str) -> Result<usize> {
+        let market = self.api.get_market(condition_id).await?;
+        if !market.closed {
+            anyhow::bail!("market {} has not resolved yet", condition_id);
+        }
+        let routed = {
+            let mut pending = self.pending_trades.lock().await;
+            let mut routed = 0;
+            for trade in pending.values_mut().filter(|t| t.condition_id == condition_id && !t.sold && t.buy_order_confirmed) {
+                trade.claim_on_closure = true;
+                trade.no_sell = true;
+                trade.redemption_abandoned = false;
+                trade.redemption_attempts = 0;
+                routed += 1;
+            }
+            routed
+        };
+        if routed == 0 {
+            anyhow::bail!("no open position in market {}", condition_id);
+        }
+        crate::log_println!("🔨 FORCE REDEEM: {} trade(s) in market {} routed to redemption", routed, condition_id);
+        crate::log_trading_event(&format!("FORCE REDEEM | Condition: {} | Trades: {}", condition_id, routed));
+        self.check_market_closure().await?;
+        Ok(routed)
+    }
+
     /// Sync pending trades with actual portfolio balance
     /// Checks if tokens are still in portfolio - if balance is 0, mark as sold (already redeemed)
     /// This prevents the bot from trying to redeem already-redeemed tokens
@@ -311,21 +1011,36 @@ impl Trader {
         let mut updated_count = 0;
         let mut removed_count = 0;
         
-        for (key, trade) in pending_trades {
-            // Skip if already sold
-            if trade.sold {
-                continue;
-            }
-            
+        // Fetch balances concurrently (bounded to respect rate limits), then apply the results one by one
+        let concurrency = self.config().portfolio_sync_concurrency.unwrap_or(DEFAULT_PORTFOLIO_SYNC_CONCURRENCY).max(1);
+        let mut results: Vec<_> = futures_util::stream::iter(pending_trades.into_iter().filter(|(_, trade)| !trade.sold))
+            .map(|(key, trade)| {
+                let api = self.api.clone();
+                async move {
+                    let balance = api.check_balance_allowance(&trade.token_id).await;
+                    (key, trade, balance)
+                }
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+        results.sort_by(|a, b| a.0.cmp(&b.0)); // Stable log order regardless of completion order
+        
+        for (key, trade, balance_result) in results {
             // Check actual token balance
-            match self.api.check_balance_allowance(&trade.token_id).await {
+            match balance_result {
                 Ok((balance, _)) => {
                     // Conditional tokens use 1e6 as base unit (like USDC)
                     // Convert from smallest unit to actual shares
                     let balance_decimal = balance / rust_decimal::Decimal::from(1_000_000u64);
                     let balance_f64 = f64::try_from(balance_decimal).unwrap_or(0.0);
                     
-                    if balance_f64 == 0.0 {
+                    if balance_f64 == 0.0 && !trade.buy_order_confirmed {
+                        // Buy restored from a previous run that hasn't filled yet - check_pending_trades keeps
+                        // watching its order
+                        crate::log_println!("   ⏳ Trade {}: buy not filled yet - still tracking order {}",
+                            &trade.token_id[..16], trade.order_id.as_deref().unwrap_or("(none)"));
+                    } else if balance_f64 == 0.0 {
                         // Balance is 0 - tokens were already redeemed
                         crate::log_println!("   ✅ Trade {}: Token balance is 0 - already redeemed, marking as sold", 
                             &trade.token_id[..16]);
@@ -372,23 +1087,738 @@ impl Trader {
         Ok(())
     }
 
-    /// Mark position as closed (for stop-loss re-entry)
-    pub async fn mark_position_closed(&self, period_timestamp: u64) {
-        let mut pending = self.pending_trades.lock().await;
-        for (_, trade) in pending.iter_mut() {
-            if trade.market_timestamp == period_timestamp && !trade.sold {
+    /// Token balances in `markets` that no pending trade tracks, plus the number of markets/tokens that
+    /// couldn't be checked. `context` prefixes the warnings
+    async fn untracked_holdings(&self, markets: &[PeriodMarket], context: &str) -> (Vec<UntrackedHolding>, usize) {
+        let mut holdings = Vec::new();
+        let mut failed = 0;
+        for market in markets {
+            let details = match self.api.get_market(&market.condition_id).await {
+                Ok(details) => details,
+                Err(e) => {
+                    warn!("{}: could not load market {} (period {}): {}", context, market.condition_id, market.period_timestamp, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            for token in &details.tokens {
+                if self.pending_trades.lock().await.values().any(|t| t.token_id == token.token_id && !t.sold) {
+                    continue;
+                }
+                let outcome = token.outcome.to_uppercase();
+                let is_up = outcome.contains("UP") || outcome == "1";
+                let Some(token_type) = TokenType::from_asset(&market.asset_key, is_up) else {
+                    continue;
+                };
+                let shares = match self.api.check_balance_allowance(&token.token_id).await {
+                    Ok((balance, _)) => f64::try_from(balance / rust_decimal::Decimal::from(1_000_000u64)).unwrap_or(0.0),
+                    Err(e) => {
+                        warn!("{}: balance check failed for {} (period {}): {}", context, token_type.display_name(), market.period_timestamp, e);
+                        failed += 1;
+                        continue;
+                    }
+                };
+                if shares <= 0.0 {
+                    continue;
+                }
+                holdings.push(UntrackedHolding {
+                    market: market.clone(),
+                    token_id: token.token_id.clone(),
+                    token_type,
+                    shares,
+                    last_price: f64::try_from(token.price).unwrap_or(0.0),
+                });
+            }
+        }
+        (holdings, failed)
+    }
+    
+    /// The bot's open positions (pending trades in production, simulated positions in simulation), one record per
+    /// token with sizes summed and the purchase price size-weighted, marked at the current SELL price
+    /// Read-only: places and cancels nothing
+    pub async fn export_positions(&self) -> Vec<PositionRecord> {
+        // token_id -> (condition_id, token_type, period, size, cost)
+        let mut positions: HashMap<String, (String, TokenType, u64, f64, f64)> = HashMap::new();
+        let mut add = |token_id: &str, condition_id: &str, token_type: &TokenType, period: u64, size: f64, price: f64| {
+            let entry = positions.entry(token_id.to_string())
+                .or_insert_with(|| (condition_id.to_string(), token_type.clone(), period, 0.0, 0.0));
+            entry.3 += size;
+            entry.4 += size * price;
+        };
+        match &self.simulation_tracker {
+            Some(tracker) => {
+                for position in tracker.get_all_positions().await.iter().filter(|p| !p.sold && p.units > 0.0) {
+                    add(&position.token_id, &position.condition_id, &position.token_type, position.period_timestamp,
+                        position.units, position.purchase_price);
+                }
+            }
+            None => {
+                for trade in self.pending_trades.lock().await.values().filter(|t| !t.sold && t.buy_order_confirmed) {
+                    let size = trade.confirmed_balance.unwrap_or(trade.units);
+                    if size > 0.0 {
+                        add(&trade.token_id, &trade.condition_id, &trade.token_type, trade.market_timestamp, size, trade.purchase_price);
+                    }
+                }
+            }
+        }
+
+        let mut records = Vec::new();
+        for (token_id, (condition_id, token_type, period, size, cost)) in positions {
+            let avg_price = cost / size;
+            let mut record = self.position_record(token_id, condition_id, &token_type, period, size, true).await;
+            record.avg_price = Some(avg_price);
+            record.initial_value = Some(cost);
+            record.cash_pnl = record.current_value.map(|value| value - cost);
+            records.push(record);
+        }
+        records.sort_by(|a, b| (a.period, &a.token_id).cmp(&(b.period, &b.token_id)));
+        records
+    }
+
+    /// What the dashboard shows right now (`dashboard::serve`): open positions as in export_positions, every
+    /// pending trade, and the fills and realized PnL recorded since start
+    pub async fn dashboard_state(&self) -> crate::dashboard::DashboardState {
+        let positions = self.export_positions().await;
+        let mut pending_trades: Vec<PendingTrade> = self.pending_trades.lock().await.values().cloned().collect();
+        pending_trades.sort_by(|a, b| (a.market_timestamp, &a.trade_id).cmp(&(b.market_timestamp, &b.trade_id)));
+        let simulation_cash = self.simulation_tracker.as_ref().and_then(|tracker| tracker.cash());
+        crate::dashboard::DashboardState::new(self.simulation_mode, simulation_cash, positions, pending_trades)
+    }
+
+    /// export_positions plus tokens held in `markets` that the bot doesn't track (tracked: false, no cost basis)
+    /// Production only - balances come from the wallet
+    pub async fn export_held_positions(&self, markets: &[PeriodMarket]) -> Vec<PositionRecord> {
+        let mut records = self.export_positions().await;
+        let (holdings, _) = self.untracked_holdings(markets, "Position export").await;
+        for holding in holdings {
+            records.push(self.position_record(holding.token_id, holding.market.condition_id, &holding.token_type,
+                holding.market.period_timestamp, holding.shares, false).await);
+        }
+        records
+    }
+
+    /// Record for `size` shares of `token_id` marked at its current SELL price, without a cost basis
+    async fn position_record(&self, token_id: String, condition_id: String, token_type: &TokenType, period: u64, size: f64, tracked: bool) -> PositionRecord {
+        let cur_price = self.api.get_price(&token_id, "SELL").await.ok()
+            .and_then(|price| f64::try_from(price).ok());
+        PositionRecord {
+            token_id,
+            condition_id,
+            outcome: if token_type.is_up() { "Up" } else { "Down" }.to_string(),
+            size,
+            avg_price: None,
+            cur_price,
+            initial_value: None,
+            current_value: cur_price.map(|price| price * size),
+            cash_pnl: None,
+            period,
+            tracked,
+            asset: token_type.asset_key().to_string(),
+        }
+    }
+
+    /// Startup check for tokens held in the current markets that no pending trade tracks (a previous tool,
+    /// a manual trade, a lost state). Each one is logged; with `adopt` it becomes a managed position priced at
+    /// the best bid (0 without one), so the normal sell, stop-loss and closure logic applies to it
+    pub async fn check_untracked_positions(&self, markets: &[PeriodMarket], adopt: bool) -> UntrackedPositions {
+        let mut report = UntrackedPositions::default();
+        let (holdings, failed) = self.untracked_holdings(markets, "Untracked position check").await;
+        report.failed = failed;
+        for holding in holdings {
+            report.found += 1;
+            let name = holding.token_type.display_name();
+            if !adopt {
+                crate::log_println!("👀 UNTRACKED POSITION: {} (period {}) {:.6} shares - not managed by the bot (startup.adopt_untracked is off)",
+                    name, holding.market.period_timestamp, holding.shares);
+                continue;
+            }
+            let bid = self.api.get_price(&holding.token_id, "SELL").await.ok()
+                .and_then(|p| f64::try_from(p).ok())
+                .unwrap_or(0.0);
+            let trade = PendingTrade {
+                trade_id: new_trade_id(),
+                token_id: holding.token_id.clone(),
+                condition_id: holding.market.condition_id.clone(),
+                token_type: holding.token_type.clone(),
+                order_id: None,
+                investment_amount: bid * holding.shares,
+                units: holding.shares,
+                purchase_price: bid,
+                sell_price: self.config().sell_price,
+                timestamp: std::time::Instant::now(),
+                market_timestamp: holding.market.period_timestamp,
+                sold: false,
+                confirmed_balance: Some(holding.shares),
+                buy_order_confirmed: true,
+                limit_sell_orders_placed: false, // Gets its profit-target sell on the next check
+                no_sell: false,
+                claim_on_closure: false,
+                sell_attempts: 0,
+                redemption_attempts: 0,
+                redemption_abandoned: false,
+                scale_out_levels_done: 0,
+            };
+            crate::log_println!("📥 ADOPTED POSITION: {} (period {}) {:.6} shares at estimated ${:.6} (best bid) - now managed by the bot",
+                name, holding.market.period_timestamp, holding.shares, bid);
+            crate::log_trading_event(&format!(
+                "ADOPTED POSITION | Market: {} | Period: {} | Token: {} | Units: {:.6} | Estimated Price: ${:.6} | Trade ID: {}",
+                name, holding.market.period_timestamp, &holding.token_id[..16], holding.shares, bid, trade.trade_id));
+            self.pending_trades.lock().await.insert(format!("{}_{}_adopted", holding.market.period_timestamp, holding.token_id), trade);
+            report.adopted += 1;
+        }
+        report
+    }
+    
+    /// Startup safety for positions left over from earlier periods (tracked trades and tokens still held in `markets`):
+    /// closed markets, and ended-but-unresolved ones unless `sell_unresolved`, are routed to redemption at closure;
+    /// with `sell_unresolved` an ended market that is still trading is market-sold at the bid instead
+    /// Positions in the current period are left alone. Every action is logged
+    pub async fn flatten_stale_positions(&self, markets: &[PeriodMarket], sell_unresolved: bool) -> StartupFlatten {
+        let mut report = StartupFlatten::default();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let current_period = crate::period::start(now);
+        
+        // Tracked trades from earlier periods, then untracked tokens still held in recent markets
+        let mut stale: Vec<(String, PendingTrade)> = self.pending_trades.lock().await.iter()
+            .filter(|(_, t)| !t.sold && t.buy_order_confirmed && t.market_timestamp < current_period)
+            .map(|(key, trade)| (key.clone(), trade.clone()))
+            .collect();
+        let past: Vec<PeriodMarket> = markets.iter().filter(|m| m.period_timestamp < current_period).cloned().collect();
+        let (holdings, failed) = self.untracked_holdings(&past, "Startup flatten").await;
+        report.failed += failed;
+        for holding in holdings {
+            crate::log_println!("🧹 STARTUP FLATTEN: found untracked {} position from period {}: {:.6} shares",
+                holding.token_type.display_name(), holding.market.period_timestamp, holding.shares);
+            // Entry price is unknown - use the market's last price so settlement PnL reflects only what happens from now
+            let mark = holding.last_price;
+            stale.push((format!("{}_{}_startup", holding.market.period_timestamp, holding.token_id), PendingTrade {
+                trade_id: new_trade_id(),
+                token_id: holding.token_id,
+                condition_id: holding.market.condition_id,
+                token_type: holding.token_type,
+                order_id: None,
+                investment_amount: mark * holding.shares,
+                units: holding.shares,
+                purchase_price: mark,
+                sell_price: self.config().sell_price,
+                timestamp: std::time::Instant::now(),
+                market_timestamp: holding.market.period_timestamp,
+                sold: false,
+                confirmed_balance: Some(holding.shares),
+                buy_order_confirmed: true,
+                limit_sell_orders_placed: true,
+                no_sell: true,
+                claim_on_closure: true,
+                sell_attempts: 0,
+                redemption_attempts: 0,
+                redemption_abandoned: false,
+                scale_out_levels_done: 0,
+            }));
+        }
+        
+        for (key, mut trade) in stale {
+            let closed = match self.api.get_market(&trade.condition_id).await {
+                Ok(details) => details.closed,
+                Err(e) => {
+                    warn!("Startup flatten: could not check market for {} (period {}): {} - leaving it as is",
+                        trade.token_type.display_name(), trade.market_timestamp, e);
+                    report.failed += 1;
+                    continue;
+                }
+            };
+            let units = trade.confirmed_balance.unwrap_or(trade.units);
+            
+            if !closed && sell_unresolved {
+                let bid = self.api.get_price(&trade.token_id, "SELL").await.ok()
+                    .and_then(|p| f64::try_from(p).ok())
+                    .unwrap_or(0.0);
+                if bid > 0.0 {
+                    crate::log_println!("🧹 STARTUP FLATTEN: {} (period {}) market ended but still trading - selling {:.6} shares at ${:.6}",
+                        trade.token_type.display_name(), trade.market_timestamp, units, bid);
+                    match self.execute_sell(&key, &trade, units, bid, Some("FAK"), bid < trade.purchase_price).await {
+                        Ok(()) => {
+                            crate::log_trading_event(&format!(
+                                "STARTUP FLATTEN SELL | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Proceeds: ${:.6} | Trade ID: {}",
+                                trade.token_type.display_name(), trade.market_timestamp, bid, units, bid * units, trade.trade_id));
+                            self.pending_trades.lock().await.remove(&key);
+                            report.sold += 1;
+                            continue;
+                        }
+                        Err(e) => warn!("Startup flatten sell failed for {}: {} - routing it to redemption instead",
+                            trade.token_type.display_name(), e),
+                    }
+                } else {
+                    crate::log_println!("🧹 STARTUP FLATTEN: {} (period {}) has no bid - routing it to redemption instead of selling",
+                        trade.token_type.display_name(), trade.market_timestamp);
+                }
+            }
+            
+            crate::log_println!("🧹 STARTUP FLATTEN: {} (period {}) {:.6} shares - market {}, routed to redemption at closure",
+                trade.token_type.display_name(), trade.market_timestamp, units, if closed { "closed" } else { "ended" });
+            crate::log_trading_event(&format!(
+                "STARTUP FLATTEN REDEEM | Market: {} | Period: {} | Units: {:.6} | Market Closed: {} | Trade ID: {}",
+                trade.token_type.display_name(), trade.market_timestamp, units, closed, trade.trade_id));
+            trade.claim_on_closure = true;
+            trade.no_sell = true;
+            self.pending_trades.lock().await.insert(key, trade);
+            report.redeeming += 1;
+        }
+        report
+    }
+
+    /// Mark position as closed (for stop-loss re-entry)
+    pub async fn mark_position_closed(&self, period_timestamp: u64) {
+        let mut pending = self.pending_trades.lock().await;
+        for (_, trade) in pending.iter_mut() {
+            if trade.market_timestamp == period_timestamp && !trade.sold {
                 trade.sold = true;
                 crate::log_println!("   ✅ Position marked as closed (period: {}) - can re-buy if price recovers", period_timestamp);
             }
         }
     }
 
+    /// Enter a position for a detected opportunity using the configured entry order type
+    /// Hedge opportunities (individual, standard, dual-limit) always go through the market order path
+    /// so they keep their hedge-specific sizing and sell handling
+    pub async fn enter(&self, opportunity: &BuyOpportunity) -> Result<()> {
+        // Paused from the control socket: open positions are still managed, nothing new is bought
+        if self.is_paused() {
+            debug!("Trading paused - skipping new entry for {}", opportunity.token_type.display_name());
+            return Ok(());
+        }
+
+        if self.max_periods_reached().await {
+            debug!("max_periods reached - skipping new entry for {}", opportunity.token_type.display_name());
+            return Ok(());
+        }
+
+        // Startup warmup: snapshots keep flowing to the monitor/detector, but no buys until it ends
+        if let Some(warmup) = self.config().warmup_seconds {
+            let elapsed = self.started_at.elapsed().as_secs();
+            if elapsed < warmup {
+                eprintln!("⏳ WARMUP: skipping {} - {}s of {}s warmup elapsed",
+                    opportunity.token_type.display_name(), elapsed, warmup);
+                return Ok(());
+            }
+        }
+
+        // Trading hours: outside the schedule we keep managing positions but don't open new ones
+        if !self.schedule.allows(chrono::Utc::now()) {
+            eprintln!("🕒 OUTSIDE SCHEDULE: skipping {} - {} UTC is outside the configured trading windows",
+                opportunity.token_type.display_name(), chrono::Utc::now().format("%a %H:%M"));
+            return Ok(());
+        }
+
+        // Loss-streak circuit breaker: no new buys until the cooldown ends
+        if let Some(until) = *self.loss_cooldown_until.lock().await {
+            let now = std::time::Instant::now();
+            if now < until {
+                eprintln!("🧊 LOSS STREAK COOLDOWN: skipping {} - {}s remaining",
+                    opportunity.token_type.display_name(), (until - now).as_secs());
+                return Ok(());
+            }
+        }
+
+        // Risk budget: daily loss limit and the hard ceiling on capital at risk across all open positions
+        let config = self.config();
+        if config.max_total_notional.is_some() || config.daily_loss_limit.is_some() {
+            let open_notional = self.open_notional().await;
+            let entry_notional = self.entry_notional(opportunity).await;
+            if let Err(block) = self.risk.check_entry(&config, open_notional, entry_notional, chrono::Utc::now()) {
+                eprintln!("🛑 Buy blocked: {} {}", opportunity.token_type.display_name(), block);
+                return Ok(());
+            }
+        }
+
+        // Global cadence limit across all markets - a throttled opportunity is simply re-detected
+        // on the next snapshot if it is still valid. The lock is held through the entry so concurrent entries
+        // can't both get through, and only an entry that placed an order (a new tracked trade) counts as a buy
+        let Some(min_gap) = self.config().min_seconds_between_buys else {
+            return self.place_entry(opportunity).await;
+        };
+        let mut last_buy_at = self.last_buy_at.lock().await;
+        if let Some(last) = *last_buy_at {
+            let elapsed = last.elapsed().as_secs();
+            if elapsed < min_gap {
+                eprintln!("⏳ Buy throttled: {} - last buy {}s ago (min {}s between buys)",
+                    opportunity.token_type.display_name(), elapsed, min_gap);
+                return Ok(());
+            }
+        }
+        let known: std::collections::HashSet<String> = self.pending_trades.lock().await.values().map(|t| t.trade_id.clone()).collect();
+        let result = self.place_entry(opportunity).await;
+        if self.pending_trades.lock().await.values().any(|t| !known.contains(&t.trade_id)) {
+            *last_buy_at = Some(std::time::Instant::now());
+        }
+        result
+    }
+
+    /// Buy for an entry that passed the gates in `enter`, with the order type it calls for
+    async fn place_entry(&self, opportunity: &BuyOpportunity) -> Result<()> {
+        let is_hedge = opportunity.use_market_order
+            || opportunity.is_individual_hedge
+            || opportunity.is_standard_hedge;
+        if is_hedge {
+            return self.execute_buy(opportunity).await;
+        }
+
+        match self.config().entry_order_type {
+            EntryOrderType::MarketFok => self.execute_buy(opportunity).await,
+            EntryOrderType::Limit => {
+                self.execute_limit_buy(opportunity, true, opportunity.dual_limit_shares).await
+            }
+            EntryOrderType::Maker => self.execute_maker_buy(opportunity).await,
+        }
+    }
+
+    /// Track realized results for the rolling PnL windows, the daily loss limit, the loss-streak circuit breaker
+    /// and the detector's per-asset settlement cooldowns
+    /// A loss extends the streak, a win resets it; break-even settlements leave it unchanged.
+    /// Hitting max_consecutive_losses halts new buys for loss_streak_cooldown_secs and restarts the count
+    async fn record_settlement(&self, token_type: &TokenType, pnl: f64) {
+        self.settlements.lock().await.push_back((std::time::Instant::now(), pnl));
+        crate::metrics::record_settlement(pnl);
+        if let Some(ref detector) = self.detector {
+            detector.record_settlement(token_type.clone(), pnl).await;
+        }
+        if let Some(realized) = self.risk.record_settlement(&self.config(), pnl, chrono::Utc::now()) {
+            crate::log_println!("🛑 DAILY LOSS LIMIT: realized PnL today ${:.2} - no new buys until 00:00 UTC", realized);
+            crate::log_trading_event(&format!(
+                "DAILY LOSS LIMIT | Realized Today: ${:.6} | Limit: ${:.6}",
+                realized, self.config().daily_loss_limit.unwrap_or_default()
+            ));
+        }
+        
+        let Some(max_losses) = self.config().max_consecutive_losses else {
+            return;
+        };
+        let mut losses = self.consecutive_losses.lock().await;
+        if pnl > 0.0 {
+            *losses = 0;
+            return;
+        }
+        if pnl == 0.0 {
+            return;
+        }
+        *losses += 1;
+        if *losses < max_losses {
+            return;
+        }
+        let streak = *losses;
+        *losses = 0;
+        drop(losses);
+
+        let cooldown = self.config().loss_streak_cooldown_secs.unwrap_or_else(crate::period::seconds);
+        *self.loss_cooldown_until.lock().await = Some(std::time::Instant::now() + std::time::Duration::from_secs(cooldown));
+        crate::log_println!("🧊 LOSS STREAK COOLDOWN: {} consecutive losing settlements - no new buys for {}s", streak, cooldown);
+        crate::log_trading_event(&format!(
+            "LOSS STREAK COOLDOWN | Consecutive Losses: {} | Last PnL: ${:.6} | Cooldown: {}s",
+            streak, pnl, cooldown
+        ));
+    }
+
+    /// Add a settled part of a trade to its market's period summary
+    /// `exited` is true for a sell before resolution, false for the resolution itself
+    async fn record_period_settlement(&self, trade: &PendingTrade, units: f64, cost: f64, proceeds: f64, exited: bool) {
+        // Simulated positions are kept per token, so that's what identifies an entry in simulation
+        let entry_id = if self.simulation_mode { &trade.token_id } else { &trade.trade_id };
+        let mut summaries = self.period_summaries.lock().await;
+        period_summary_entry(&mut summaries, &trade.condition_id, trade.market_timestamp, &trade.token_type)
+            .add_settlement(entry_id, units, cost, proceeds, exited);
+    }
+
+    /// Track the best and worst sell price seen while `trade` is held, relative to its purchase price
+    async fn note_period_excursion(&self, trade: &PendingTrade, sell_price: f64) {
+        let move_per_share = sell_price - trade.purchase_price;
+        let mut summaries = self.period_summaries.lock().await;
+        let summary = period_summary_entry(&mut summaries, &trade.condition_id, trade.market_timestamp, &trade.token_type);
+        summary.max_favorable_excursion = Some(summary.max_favorable_excursion.map_or(move_per_share, |m| m.max(move_per_share)));
+        summary.max_adverse_excursion = Some(summary.max_adverse_excursion.map_or(move_per_share, |m| m.min(move_per_share)));
+    }
+
+    /// Record which way a market resolved for its period summary
+    async fn record_period_outcome(&self, condition_id: &str, period: u64, token_type: &TokenType, resolved_up: bool) {
+        let mut summaries = self.period_summaries.lock().await;
+        let summary = period_summary_entry(&mut summaries, condition_id, period, token_type);
+        summary.outcome = Some(if resolved_up { "up" } else { "down" }.to_string());
+    }
+
+    /// Append the summary of every closed market with no open position left to `PERIOD_SUMMARIES_FILE`
+    /// Waiting for the last open position keeps a period with several trades (re-entries, hedges) on one line
+    async fn write_period_summaries(&self) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let open_markets: std::collections::HashSet<String> = match &self.simulation_tracker {
+            Some(tracker) => tracker.get_all_positions().await.into_iter()
+                .filter(|position| !position.sold)
+                .map(|position| position.condition_id)
+                .collect(),
+            None => self.pending_trades.lock().await.values()
+                .filter(|trade| !trade.sold && !trade.redemption_abandoned)
+                .map(|trade| trade.condition_id.clone())
+                .collect(),
+        };
+
+        let mut summaries = self.period_summaries.lock().await;
+        let done: Vec<String> = summaries.iter()
+            .filter(|(condition_id, summary)| now + 30 >= summary.period + crate::period::seconds() && !open_markets.contains(*condition_id))
+            .map(|(condition_id, _)| condition_id.clone())
+            .collect();
+        for condition_id in done {
+            let Some(summary) = summaries.remove(&condition_id) else {
+                continue;
+            };
+            // Only price observations, nothing settled (e.g. an abandoned redemption)
+            if summary.entries == 0 {
+                continue;
+            }
+            let line = match serde_json::to_string(&summary) {
+                Ok(line) => line,
+                Err(e) => {
+                    warn!("Failed to serialize period summary for {}: {}", condition_id, e);
+                    continue;
+                }
+            };
+            let result = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(PERIOD_SUMMARIES_FILE)
+                .and_then(|mut file| {
+                    use std::io::Write;
+                    writeln!(file, "{}", line)
+                });
+            if let Err(e) = result {
+                warn!("Failed to write {}: {}", PERIOD_SUMMARIES_FILE, e);
+            }
+        }
+    }
+
+    /// Realized PnL of settlements within the last `window` (capped at ROLLING_PNL_MAX_WINDOW)
+    /// Settlements older than the longest window are pruned here, so the ring only holds recent events
+    pub async fn rolling_pnl(&self, window: std::time::Duration) -> f64 {
+        let now = std::time::Instant::now();
+        let mut settlements = self.settlements.lock().await;
+        while let Some((at, _)) = settlements.front() {
+            if now.duration_since(*at) <= ROLLING_PNL_MAX_WINDOW {
+                break;
+            }
+            settlements.pop_front();
+        }
+        settlements.iter()
+            .filter(|(at, _)| now.duration_since(*at) <= window)
+            .map(|(_, pnl)| pnl)
+            .sum()
+    }
+
+    /// "Rolling PnL: 1h $x | 24h $y" for summaries and status output
+    pub async fn rolling_pnl_summary(&self) -> String {
+        let hour = self.rolling_pnl(std::time::Duration::from_secs(3600)).await;
+        let day = self.rolling_pnl(std::time::Duration::from_secs(24 * 3600)).await;
+        format!("Rolling PnL: 1h ${:.6} | 24h ${:.6}", hour, day)
+    }
+
+    /// Record the collateral account_pnl is measured against: trading.starting_balance if set,
+    /// otherwise the current USDC balance
+    pub async fn snapshot_starting_balance(&self) -> Result<f64> {
+        let balance = match self.config().starting_balance {
+            Some(balance) => balance,
+            None => self.api.get_usdc_balance().await?,
+        };
+        *self.starting_balance.lock().await = Some(balance);
+        *self.net_deposits.lock().await = 0.0;
+        Ok(balance)
+    }
+
+    /// Record a deposit (positive) or withdrawal (negative) of collateral so account_pnl doesn't count it as PnL
+    pub async fn record_transfer(&self, amount: f64) {
+        *self.net_deposits.lock().await += amount;
+        crate::log_trading_event(&format!("COLLATERAL TRANSFER | Amount: ${:.6}", amount));
+    }
+
+    /// Fetch the USDC balance of every trading wallet and keep it for the account summary
+    pub async fn refresh_wallet_balances(&self) -> Result<Vec<(String, f64)>> {
+        let balances = self.api.wallet_usdc_balances().await?;
+        *self.wallet_balances.lock().await = balances.clone();
+        Ok(balances)
+    }
+
+    /// Current best bid for a token, fetched once per `marks` cache (one summary pass)
+    /// None if the book has no readable bid (empty or crossed book, ended market) or the request failed
+    async fn mark_price(&self, token_id: &str, marks: &mut HashMap<String, Option<f64>>) -> Option<f64> {
+        if let Some(mark) = marks.get(token_id) {
+            return *mark;
+        }
+        let mark = match self.api.get_best_price(token_id).await {
+            Ok(Some(TokenPrice { bid: Some(bid), .. })) => f64::try_from(bid).ok(),
+            Ok(_) => {
+                debug!("No bid for token {}", &token_id[..16]);
+                None
+            }
+            Err(e) => {
+                debug!("Failed to mark token {}: {}", &token_id[..16], e);
+                None
+            }
+        };
+        marks.insert(token_id.to_string(), mark);
+        mark
+    }
+
+    /// Mark value of open (filled, unsold) positions at the current best bid
+    /// Positions without a readable bid are marked at zero
+    async fn open_position_value(&self, marks: &mut HashMap<String, Option<f64>>) -> f64 {
+        let positions: Vec<(String, f64)> = {
+            let pending = self.pending_trades.lock().await;
+            pending.values()
+                .filter(|t| !t.sold && !t.redemption_abandoned && t.buy_order_confirmed)
+                .map(|t| (t.token_id.clone(), t.confirmed_balance.unwrap_or(t.units)))
+                .collect()
+        };
+        let mut value = 0.0;
+        for (token_id, units) in positions {
+            value += self.mark_price(&token_id, marks).await.unwrap_or(0.0) * units;
+        }
+        value
+    }
+
+    /// (collateral, open position value, starting balance, net deposits) - None before snapshot_starting_balance
+    async fn account_breakdown(&self, marks: &mut HashMap<String, Option<f64>>) -> Result<Option<(f64, f64, f64, f64)>> {
+        let Some(starting) = *self.starting_balance.lock().await else {
+            return Ok(None);
+        };
+        let deposits = *self.net_deposits.lock().await;
+        let collateral = self.refresh_wallet_balances().await?.iter().map(|(_, balance)| balance).sum();
+        let positions = self.open_position_value(marks).await;
+        Ok(Some((collateral, positions, starting, deposits)))
+    }
+
+    /// Account PnL = current collateral + open position mark value - starting balance - net deposits
+    /// Unlike total_profit this includes redemptions, fees and anything else that moved the wallet balance
+    /// None until snapshot_starting_balance has run
+    pub async fn account_pnl(&self) -> Result<Option<f64>> {
+        Ok(self.account_breakdown(&mut HashMap::new()).await?
+            .map(|(collateral, positions, starting, deposits)| collateral + positions - starting - deposits))
+    }
+
+    async fn account_pnl_summary(&self, marks: &mut HashMap<String, Option<f64>>) -> Option<String> {
+        match self.account_breakdown(marks).await {
+            Ok(Some((collateral, positions, starting, deposits))) => {
+                let mut summary = format!(
+                    "Account PnL: ${:.6} (collateral ${:.2} + open positions ${:.2} - starting ${:.2} - net deposits ${:.2})",
+                    collateral + positions - starting - deposits, collateral, positions, starting, deposits);
+                let wallets = self.wallet_balances.lock().await;
+                if wallets.len() > 1 {
+                    let per_wallet: Vec<String> = wallets.iter().map(|(label, balance)| format!("{} ${:.2}", label, balance)).collect();
+                    summary.push_str(&format!(" | Wallets: {}", per_wallet.join(", ")));
+                }
+                Some(summary)
+            }
+            Ok(None) => None,
+            Err(e) => Some(format!("Account PnL: unavailable ({})", e)),
+        }
+    }
+
+    /// Notional (units * purchase_price) of all unsold, non-abandoned trades, including resting limit buys
+    async fn open_notional(&self) -> f64 {
+        let pending = self.pending_trades.lock().await;
+        pending.values()
+            .filter(|t| !t.sold && !t.redemption_abandoned)
+            .map(|t| t.units * t.purchase_price)
+            .sum()
+    }
+
+    /// Trade size for the next buy of `token_type` before per-opportunity overrides: the asset's
+    /// trade_amount_by_asset entry or fixed_trade_amount, or in compounding mode base + realized PnL *
+    /// compound_fraction (see `compound_trade_amount`) with a description of its components
+    async fn trade_amount(&self, token_type: &TokenType) -> (f64, Option<String>) {
+        let asset_amount = RiskManager::asset_trade_amount(&self.config(), token_type);
+        if !self.config().compound {
+            return (asset_amount.unwrap_or(self.config().fixed_trade_amount), None);
+        }
+        let base = asset_amount.or(self.config().base_trade_amount).unwrap_or(self.config().fixed_trade_amount);
+        let fraction = self.config().compound_fraction.unwrap_or(1.0);
+        let realized = *self.total_profit.lock().await;
+        let amount = compound_trade_amount(base, realized, fraction, self.config().max_trade_amount);
+        let cap = self.config().max_trade_amount.map(|m| format!(", max ${:.2}", m)).unwrap_or_default();
+        (amount, Some(format!(
+            "base ${:.2} + realized PnL ${:.2} x {:.2} = ${:.2} (min ${:.2}{})",
+            base, realized, fraction, amount, MIN_ORDER_AMOUNT, cap)))
+    }
+
+    /// Notional an entry would add - mirrors the sizing in execute_buy / execute_limit_buy
+    async fn entry_notional(&self, opportunity: &BuyOpportunity) -> f64 {
+        let fixed_amount = match opportunity.investment_amount_override {
+            Some(amount) => amount,
+            None => self.trade_amount(&opportunity.token_type).await.0,
+        };
+        let is_limit_entry = matches!(self.config().entry_order_type, EntryOrderType::Limit | EntryOrderType::Maker)
+            && !(opportunity.use_market_order || opportunity.is_individual_hedge || opportunity.is_standard_hedge);
+        match opportunity.dual_limit_shares {
+            Some(shares) if is_limit_entry => shares * opportunity.bid_price,
+            _ => fixed_amount,
+        }
+    }
+
+    /// Largest part of `amount` whose expected average fill stays within `max_slippage` of the best ask
+    /// None when the book can't take a minimum-size order (the buy is skipped); `amount` if the book can't be read
+    async fn depth_capped_amount(&self, token_id: &str, amount: f64, max_slippage: f64) -> Option<f64> {
+        let orderbook = match self.api.get_orderbook(token_id).await {
+            Ok(orderbook) => orderbook,
+            Err(e) => {
+                warn!("Depth check failed for {} - buying the full ${:.2}: {}", &token_id[..16], amount, e);
+                return Some(amount);
+            }
+        };
+        let (units, cost) = orderbook.buy_within_slippage(amount, max_slippage).unwrap_or((0.0, 0.0));
+        if cost < MIN_ORDER_AMOUNT {
+            eprintln!("📉 Buy skipped: the book for {} can't fill ${:.2} within {:.1}% slippage (only ${:.2} available)",
+                &token_id[..16], MIN_ORDER_AMOUNT, max_slippage * 100.0, cost);
+            return None;
+        }
+        if cost < amount - 1e-9 {
+            crate::log_println!("📉 Buy shrunk by depth: ${:.2} -> ${:.2} ({:.6} shares at ~${:.4} avg, max slippage {:.1}%)",
+                amount, cost, units, cost / units, max_slippage * 100.0);
+        }
+        Some(cost.min(amount))
+    }
+
+    /// Whether the USDC balance the buy's wallet can spend covers a buy of `amount`, read right before the order is placed.
+    /// Only the switch into and out of "insufficient funds" is logged, so an empty wallet doesn't spam
+    /// a failed order per opportunity. A failed balance read lets the buy through (the exchange still rejects it)
+    async fn funds_available(&self, token_id: &str, token_type: &TokenType, amount: f64) -> bool {
+        let balance = match self.api.buy_usdc_balance(token_id).await {
+            Ok(balance) => balance,
+            Err(e) => {
+                warn!("USDC balance check before buy failed - placing the order anyway: {}", e);
+                return true;
+            }
+        };
+        let sufficient = balance + 1e-9 >= amount;
+        let mut insufficient_funds = self.insufficient_funds.lock().await;
+        if sufficient == *insufficient_funds {
+            *insufficient_funds = !sufficient;
+            if sufficient {
+                crate::log_println!("💵 Funds available again: USDC balance ${:.2} - buys resume", balance);
+                crate::log_trading_event(&format!("FUNDS AVAILABLE | Balance: ${:.6}", balance));
+            } else {
+                eprintln!("💸 INSUFFICIENT FUNDS: {} buy of ${:.2} skipped - USDC balance ${:.2}. Further buys are skipped quietly until the balance covers them",
+                    token_type.display_name(), amount, balance);
+                crate::log_trading_event(&format!("INSUFFICIENT FUNDS | Needed: ${:.6} | Balance: ${:.6}", amount, balance));
+            }
+        }
+        sufficient
+    }
+
     /// Execute buy when momentum opportunity is detected
     /// Buys any token (BTC Up/Down, ETH Up/Down) when price reaches trigger_price after 10 minutes
     pub async fn execute_buy(&self, opportunity: &BuyOpportunity) -> Result<()> {
         // Safety check: Verify time remaining is still sufficient before executing buy
         // This acts as a double-check in case market closed between detection and execution
-        let min_time_remaining = self.config.min_time_remaining_seconds.unwrap_or(30);
+        let min_time_remaining = self.config().min_time_remaining_seconds.unwrap_or(30);
         if opportunity.time_remaining_seconds < min_time_remaining {
             anyhow::bail!(
                 "❌ SAFETY CHECK FAILED: Insufficient time remaining for buy\n\
@@ -404,12 +1834,37 @@ impl Trader {
         }
         
         // Use investment amount override if provided (e.g., for individual hedges that need double amount)
-        let fixed_amount = opportunity.investment_amount_override.unwrap_or(self.config.fixed_trade_amount);
+        let fixed_amount = match opportunity.investment_amount_override {
+            Some(amount) => amount,
+            None => {
+                let (amount, compounding) = self.trade_amount(&opportunity.token_type).await;
+                if let Some(compounding) = compounding {
+                    crate::log_println!("📈 Compounded trade size: {}", compounding);
+                }
+                amount
+            }
+        };
+        
+        // Shrink the entry to what the book can absorb within trading.max_entry_slippage (hedges need their full size)
+        let is_hedge = opportunity.is_individual_hedge || opportunity.is_standard_hedge;
+        let fixed_amount = match self.config().max_entry_slippage {
+            Some(max_slippage) if !self.simulation_mode && !is_hedge => {
+                match self.depth_capped_amount(&opportunity.token_id, fixed_amount, max_slippage).await {
+                    Some(amount) => amount,
+                    None => return Ok(()),
+                }
+            }
+            _ => fixed_amount,
+        };
+        if !self.simulation_mode && !self.funds_available(&opportunity.token_id, &opportunity.token_type, fixed_amount).await {
+            return Ok(());
+        }
         
         // Calculate units for the token
         let units = fixed_amount / opportunity.bid_price;
         let total_cost = units * opportunity.bid_price;
-        let expected_profit_at_sell = (self.config.sell_price - opportunity.bid_price) * units;
+        let trade_id = new_trade_id(); // Correlates this trade's buy/sell/redemption events
+        let expected_profit_at_sell = (self.config().sell_price - opportunity.bid_price) * units;
         
         crate::log_println!("═══════════════════════════════════════════════════════════");
         crate::log_println!("💰 EXECUTING BUY ORDER");
@@ -418,6 +1873,7 @@ impl Trader {
         crate::log_println!("   Token Type: {}", opportunity.token_type.display_name());
         crate::log_println!("   Token ID: {}", opportunity.token_id);
         crate::log_println!("   Condition ID: {}", opportunity.condition_id);
+        crate::log_println!("   Trade ID: {}", trade_id);
         crate::log_println!("   Side: BUY");
         crate::log_println!("   Price: ${:.6}", opportunity.bid_price);
         crate::log_println!("   Units: {:.6}", units);
@@ -425,7 +1881,7 @@ impl Trader {
         crate::log_println!("   Investment Amount: ${:.2}", fixed_amount);
         crate::log_println!("");
         crate::log_println!("📈 Trade Parameters:");
-        crate::log_println!("   Target sell price: ${:.6}", self.config.sell_price);
+        crate::log_println!("   Target sell price: ${:.6}", self.config().sell_price);
         crate::log_println!("   Expected profit at sell: ${:.6}", expected_profit_at_sell);
         crate::log_println!("   Time elapsed: {}m {}s", 
               opportunity.time_elapsed_seconds / 60, opportunity.time_elapsed_seconds % 60);
@@ -461,6 +1917,7 @@ impl Trader {
                 let trade_key = format!("{}_{}_market", opportunity.period_timestamp, opportunity.token_id);
                 let mut pending = self.pending_trades.lock().await;
                 let trade = PendingTrade {
+                    trade_id: trade_id.clone(),
                     token_id: opportunity.token_id.clone(),
                     condition_id: opportunity.condition_id.clone(),
                     token_type: opportunity.token_type.clone(),
@@ -468,18 +1925,19 @@ impl Trader {
                     investment_amount: fixed_amount,
                     units,
                     purchase_price: opportunity.bid_price,
-                    sell_price: self.config.sell_price, // Not used in simulation - positions held until closure
+                    sell_price: opportunity.sell_price_override.unwrap_or(self.config().sell_price), // Only used when the strategy sets a sell target
                     timestamp: std::time::Instant::now(),
                     market_timestamp: opportunity.period_timestamp,
                     sold: false,
                     confirmed_balance: None,
                     buy_order_confirmed: false,
                     limit_sell_orders_placed: false, // No sell orders in simulation - hold until closure
-                    no_sell: true, // Mark as no_sell since we hold until market closure
-                    claim_on_closure: true, // Will claim at market closure
+                    no_sell: opportunity.sell_price_override.is_none(), // Hold until closure unless the strategy set a sell target
+                    claim_on_closure: opportunity.sell_price_override.is_none(), // Will claim at market closure
                     sell_attempts: 0,
                     redemption_attempts: 0,
                     redemption_abandoned: false,
+                    scale_out_levels_done: 0,
                 };
                 pending.insert(trade_key, trade);
             }
@@ -495,14 +1953,48 @@ impl Trader {
             crate::log_println!("      Token ID: {}", opportunity.token_id);
             crate::log_println!("      Side: BUY");
             crate::log_println!("      Amount: ${:.6} (market order - units determined by market price)", fixed_amount);
-            crate::log_println!("      Type: FOK (Fill-or-Kill)");
+            crate::log_println!("      Type: {}", match self.config().buy_order_tif {
+                MarketOrderTif::Fok => "FOK (Fill-or-Kill)",
+                MarketOrderTif::Fak => "FAK (Fill-and-Kill - allows partial fills)",
+            });
+            
+            // Timeouts/network errors are only re-submitted once our trade history shows the first attempt did not match
+            let api = self.api.clone();
+            let token_id = opportunity.token_id.clone();
+            let buy_order_tif = self.config().buy_order_tif.as_str();
+            let place = || {
+                let api = api.clone();
+                let token_id = token_id.clone();
+                async move {
+                    match tokio::time::timeout(
+                        tokio::time::Duration::from_secs(BUY_ORDER_TIMEOUT_SECS),
+                        api.place_market_order(&token_id, fixed_amount, "BUY", Some(buy_order_tif)),
+                    ).await {
+                        Ok(result) => result,
+                        Err(_) => Err(ApiError::Network(format!("Buy order timed out after {}s", BUY_ORDER_TIMEOUT_SECS)).into()),
+                    }
+                }
+            };
+            let since = chrono::Utc::now().timestamp() - LOST_ORDER_CLOCK_SKEW_SECS;
+            let matched = || {
+                let api = api.clone();
+                let token_id = token_id.clone();
+                async move {
+                    let trades = api.get_trades(&token_id, since).await?;
+                    Ok(trades.iter().filter(|t| t.side == "BUY").map(|t| t.size).sum::<f64>())
+                }
+            };
             
-            match self.api.place_market_order(
-                &opportunity.token_id,
-                fixed_amount,  // USD value for BUY market orders
-                "BUY",
-                Some("FOK"),
-            ).await {
+            self.dump_orderbook(&opportunity.token_id, "BEFORE BUY", &trade_id).await;
+            let buy_result = place_buy_idempotent(
+                place,
+                matched,
+                MAX_BUY_ATTEMPTS,
+                tokio::time::Duration::from_millis(BUY_SETTLE_DELAY_MS),
+            ).await;
+            self.dump_orderbook(&opportunity.token_id, "AFTER BUY", &trade_id).await;
+            
+            match buy_result {
                 Ok(response) => {
                     crate::log_println!("   ✅ ORDER PLACED SUCCESSFULLY");
                     crate::log_println!("      Order ID: {:?}", response.order_id);
@@ -521,18 +2013,8 @@ impl Trader {
                     let balance_f64 = if is_hedge_trade {
                         units // Use expected units
                     } else {
-                        // For non-hedge trades, still check balance (backward compatibility)
-                        let mut balance_f64 = 0.0;
-                        match self.api.check_balance_allowance(&opportunity.token_id).await {
-                            Ok((balance, _)) => {
-                                let balance_decimal = balance / rust_decimal::Decimal::from(1_000_000u64);
-                                balance_f64 = f64::try_from(balance_decimal).unwrap_or(0.0);
-                            }
-                            Err(_) => {
-                                balance_f64 = units; // Fallback to expected
-                            }
-                        }
-                        balance_f64
+                        // Token balance can lag the fill - poll until it shows up (fallback to expected if unreadable)
+                        self.wait_for_token_balance(&opportunity.token_id).await.unwrap_or(units)
                     };
                     
                     let balance_confirmed = is_hedge_trade || balance_f64 > 0.0;
@@ -559,12 +2041,13 @@ impl Trader {
                         // Track the trade with confirmed balance
                         // For dual limit bot: 
                         // - If is_individual_hedge is true, this is an individual hedge that should place a limit sell order at config.sell_price
-                        // - If is_standard_hedge is true, this is a standard hedge (after dual_limit_hedge_after_minutes) that should place a limit sell order at $0.98
+                        // - If is_standard_hedge is true, this is a standard hedge (after dual_limit_hedge_after_minutes) that should place its hedge_sell_ladder limit sells
                         // - If use_market_order is true but neither is_individual_hedge nor is_standard_hedge, this is a multi-market hedge that should be held until closure
                         let is_individual_hedge = opportunity.is_individual_hedge;
                         let is_standard_hedge = opportunity.is_standard_hedge;
                         let is_dual_limit_hedge = opportunity.use_market_order && !is_individual_hedge && !is_standard_hedge;
                         let trade = PendingTrade {
+                            trade_id: trade_id.clone(),
                             token_id: opportunity.token_id.clone(),
                             condition_id: opportunity.condition_id.clone(),
                             token_type: opportunity.token_type.clone(),
@@ -572,7 +2055,7 @@ impl Trader {
                             investment_amount: fixed_amount,
                             units: balance_f64, // Use actual confirmed balance
                             purchase_price: opportunity.bid_price,
-                            sell_price: self.config.sell_price,
+                            sell_price: opportunity.sell_price_override.unwrap_or(self.config().sell_price),
                             timestamp: std::time::Instant::now(),
                             market_timestamp: opportunity.period_timestamp,
                             sold: false,
@@ -584,6 +2067,7 @@ impl Trader {
                             sell_attempts: 0,
                             redemption_attempts: 0,
                             redemption_abandoned: false,
+                            scale_out_levels_done: 0,
                         };
                         
                         // Use token_id as key to track individual tokens
@@ -606,15 +2090,17 @@ impl Trader {
                         pending.insert(trade_key.clone(), trade.clone());
                         drop(pending);
                         
-                        // For standard hedges, spawn background task to place 2 limit sell orders at $0.93 and $0.98 after 7 seconds
-                        if is_standard_hedge && !self.simulation_mode {
+                        // Safety net against discovery/outcome mapping errors (may sell the position straight back)
+                        let flattened = self.verify_purchased_token(&trade_key, &trade).await;
+                        
+                        // For standard hedges, spawn background task to place the hedge_sell_ladder limit sells after 7 seconds
+                        if is_standard_hedge && !self.simulation_mode && !flattened {
                             let api_clone = self.api.clone();
                             let trade_key_clone = trade_key.clone();
-                            let token_id_clone = opportunity.token_id.clone();
-                            let token_type_clone = opportunity.token_type.clone();
-                            let period_timestamp = opportunity.period_timestamp;
+                            let trade_clone = trade.clone();
                             let pending_trades_clone = self.pending_trades.clone();
                             let sell_size = opportunity.dual_limit_shares.unwrap_or(units);
+                            let plan = self.hedge_sell_plan(&opportunity.token_type);
                             
                             // Spawn non-blocking background task
                             tokio::spawn(async move {
@@ -622,27 +2108,25 @@ impl Trader {
                                 tokio::time::sleep(tokio::time::Duration::from_secs(7)).await;
                                 
                                 Self::place_hedge_sell_orders_with_retry(
-                                    &api_clone,
+                                    api_clone.as_ref(),
                                     &pending_trades_clone,
                                     &trade_key_clone,
-                                    &token_id_clone,
-                                    &token_type_clone,
-                                    period_timestamp,
+                                    &trade_clone,
                                     sell_size,
                                     true, // is_standard_hedge
+                                    &plan,
                                 ).await;
                             });
                         }
                         
-                        // For individual hedges, spawn background task to place 2 limit sell orders at $0.93 and $0.98 after 7 seconds
-                        if is_individual_hedge && !self.simulation_mode {
+                        // For individual hedges, spawn background task to place the hedge_sell_ladder limit sells after 7 seconds
+                        if is_individual_hedge && !self.simulation_mode && !flattened {
                             let api_clone = self.api.clone();
                             let trade_key_clone = trade_key.clone();
-                            let token_id_clone = opportunity.token_id.clone();
-                            let token_type_clone = opportunity.token_type.clone();
-                            let period_timestamp = opportunity.period_timestamp;
+                            let trade_clone = trade.clone();
                             let pending_trades_clone = self.pending_trades.clone();
                             let sell_size = opportunity.dual_limit_shares.unwrap_or(units);
+                            let plan = self.hedge_sell_plan(&opportunity.token_type);
                             
                             // Spawn non-blocking background task
                             tokio::spawn(async move {
@@ -650,14 +2134,13 @@ impl Trader {
                                 tokio::time::sleep(tokio::time::Duration::from_secs(7)).await;
                                 
                                 Self::place_hedge_sell_orders_with_retry(
-                                    &api_clone,
+                                    api_clone.as_ref(),
                                     &pending_trades_clone,
                                     &trade_key_clone,
-                                    &token_id_clone,
-                                    &token_type_clone,
-                                    period_timestamp,
+                                    &trade_clone,
                                     sell_size,
                                     false, // is_standard_hedge (false = individual hedge)
+                                    &plan,
                                 ).await;
                             });
                         }
@@ -668,19 +2151,31 @@ impl Trader {
                             .map(|id| format!("{:?}", id))
                             .unwrap_or_else(|| "N/A".to_string());
                         let buy_event = format!(
-                            "BUY ORDER | Market: {} | Period: {} | Token: {} | Price: ${:.6} | Units: {:.6} | Cost: ${:.6} | Order ID: {} | Status: CONFIRMED",
+                            "BUY ORDER | Market: {} | Period: {} | Token: {} | Price: ${:.6} | Units: {:.6} | Cost: ${:.6} | Order ID: {} | Status: CONFIRMED | Trade ID: {}",
                             market_name,
                             opportunity.period_timestamp,
                             &opportunity.token_id[..16],
                             opportunity.bid_price,
                             balance_f64,
                             balance_f64 * opportunity.bid_price,
-                            order_id_str
+                            order_id_str,
+                            trade_id
                         );
                         crate::log_trading_event(&buy_event);
+                        crate::notifier::notify(crate::notifier::NotifyEvent::BuyExecuted, format!(
+                            "🟢 BUY {} | {:.2} shares @ ${:.2} (${:.2}) | Period {}",
+                            market_name, balance_f64, opportunity.bid_price, balance_f64 * opportunity.bid_price, opportunity.period_timestamp));
+                        self.report_fill(crate::strategy::StrategyFill {
+                            token_id: opportunity.token_id.clone(),
+                            token_type: opportunity.token_type.clone(),
+                            condition_id: opportunity.condition_id.clone(),
+                            period_timestamp: opportunity.period_timestamp,
+                            price: opportunity.bid_price,
+                            units: balance_f64,
+                        }).await;
                         
                         crate::log_println!("   ✅ Trade stored successfully. Will monitor price and sell at ${:.6} or market close.", 
-                              self.config.sell_price);
+                              self.config().sell_price);
                         
                         let mut trades = self.trades_executed.lock().await;
                         *trades += 1;
@@ -697,7 +2192,7 @@ impl Trader {
                         
                         // Log successful buy even if balance confirmation failed
                         let buy_event = format!(
-                            "BUY ORDER | Market: {} | Period: {} | Token: {} | Price: ${:.6} | Units: {:.6} | Cost: ${:.6} | Order ID: {} | Status: SUCCESS | Note: {}",
+                            "BUY ORDER | Market: {} | Period: {} | Token: {} | Price: ${:.6} | Units: {:.6} | Cost: ${:.6} | Order ID: {} | Status: SUCCESS | Note: {} | Trade ID: {}",
                             market_name,
                             opportunity.period_timestamp,
                             &opportunity.token_id[..16],
@@ -705,21 +2200,34 @@ impl Trader {
                             balance_f64,
                             balance_f64 * opportunity.bid_price,
                             order_id_str,
-                            status_note
+                            status_note,
+                            trade_id
                         );
                         crate::log_trading_event(&buy_event);
+                        crate::notifier::notify(crate::notifier::NotifyEvent::BuyExecuted, format!(
+                            "🟢 BUY {} | ~{:.2} shares @ ${:.2} | Period {} | {}",
+                            market_name, units, opportunity.bid_price, opportunity.period_timestamp, status_note));
+                        self.report_fill(crate::strategy::StrategyFill {
+                            token_id: opportunity.token_id.clone(),
+                            token_type: opportunity.token_type.clone(),
+                            condition_id: opportunity.condition_id.clone(),
+                            period_timestamp: opportunity.period_timestamp,
+                            price: opportunity.bid_price,
+                            units,
+                        }).await;
                         
                         warn!("⚠️  Balance mismatch: Expected ~{:.6} shares, but only have {:.6} shares", units, balance_f64);
                         warn!("   The buy order may not have executed fully, or price changed significantly");
                         // Still store the trade but mark as unconfirmed
                         // For dual limit bot: 
                         // - If is_individual_hedge is true, this is an individual hedge that should place a limit sell order at config.sell_price
-                        // - If is_standard_hedge is true, this is a standard hedge (after dual_limit_hedge_after_minutes) that should place a limit sell order at $0.98
+                        // - If is_standard_hedge is true, this is a standard hedge (after dual_limit_hedge_after_minutes) that should place its hedge_sell_ladder limit sells
                         // - If use_market_order is true but neither is_individual_hedge nor is_standard_hedge, this is a multi-market hedge that should be held until closure
                         let is_individual_hedge = opportunity.is_individual_hedge;
                         let is_standard_hedge = opportunity.is_standard_hedge;
                         let is_dual_limit_hedge = opportunity.use_market_order && !is_individual_hedge && !is_standard_hedge;
                         let trade = PendingTrade {
+                            trade_id: trade_id.clone(),
                             token_id: opportunity.token_id.clone(),
                             condition_id: opportunity.condition_id.clone(),
                             token_type: opportunity.token_type.clone(),
@@ -727,7 +2235,7 @@ impl Trader {
                             investment_amount: fixed_amount,
                             units: balance_f64, // Use actual balance
                             purchase_price: opportunity.bid_price,
-                            sell_price: self.config.sell_price,
+                            sell_price: opportunity.sell_price_override.unwrap_or(self.config().sell_price),
                             timestamp: std::time::Instant::now(),
                             market_timestamp: opportunity.period_timestamp,
                             sold: false,
@@ -739,6 +2247,7 @@ impl Trader {
                             sell_attempts: 0,
                             redemption_attempts: 0,
                             redemption_abandoned: false,
+                            scale_out_levels_done: 0,
                         };
                         
                         let trade_key = if is_individual_hedge {
@@ -787,30 +2296,18 @@ impl Trader {
                 
                 // Log structured buy order failure to history.toml (simplified error message)
                 let market_name = opportunity.token_type.display_name();
-                let error_msg = format!("{:?}", e);
-                let simple_error = if error_msg.contains("order couldn't be fully filled") || error_msg.contains("FOK orders") {
-                    "order couldn't be fully filled (FOK)".to_string()
-                } else if error_msg.contains("not enough balance") {
-                    "not enough balance / allowance".to_string()
-                } else if error_msg.contains("Insufficient") {
-                    "Insufficient balance or allowance".to_string()
-                } else {
-                    // Extract first meaningful line from error
-                    error_msg.lines()
-                        .find(|line| !line.trim().is_empty() && !line.contains("Troubleshooting") && !line.contains("Order details"))
-                        .unwrap_or("Order failed")
-                        .to_string()
-                };
+                let simple_error = ApiError::classify_error(&e).summary();
                 
                 let buy_event = format!(
-                    "BUY ORDER | Market: {} | Period: {} | Token: {} | Price: ${:.6} | Units: {:.6} | Cost: ${:.6} | Status: FAILED | Error: {}",
+                    "BUY ORDER | Market: {} | Period: {} | Token: {} | Price: ${:.6} | Units: {:.6} | Cost: ${:.6} | Status: FAILED | Error: {} | Trade ID: {}",
                     market_name,
                     opportunity.period_timestamp,
                     &opportunity.token_id[..16],
                     opportunity.bid_price,
                     units,
                     total_cost,
-                    simple_error
+                    simple_error,
+                    trade_id
                 );
                 crate::log_trading_event(&buy_event);
                 
@@ -825,6 +2322,7 @@ impl Trader {
                   opportunity.token_type.display_name(), &opportunity.token_id[..16]);
         
         let trade = PendingTrade {
+                trade_id: trade_id.clone(),
                 token_id: opportunity.token_id.clone(),
                 condition_id: opportunity.condition_id.clone(),
                 token_type: opportunity.token_type.clone(),
@@ -832,7 +2330,7 @@ impl Trader {
             investment_amount: fixed_amount,
             units,
                 purchase_price: opportunity.bid_price,
-            sell_price: self.config.sell_price,
+            sell_price: opportunity.sell_price_override.unwrap_or(self.config().sell_price),
             timestamp: std::time::Instant::now(),
             market_timestamp: opportunity.period_timestamp,
             sold: false,
@@ -844,6 +2342,7 @@ impl Trader {
                 sell_attempts: 0,
                 redemption_attempts: 0,
                 redemption_abandoned: false,
+                scale_out_levels_done: 0,
             };
             
             // Use token_id as key to track individual tokens
@@ -853,7 +2352,7 @@ impl Trader {
         drop(pending);
         
             crate::log_println!("   ✅ Trade stored successfully. Will monitor price and sell at ${:.6} or market close.", 
-              self.config.sell_price);
+              self.config().sell_price);
         
         let mut trades = self.trades_executed.lock().await;
         *trades += 1;
@@ -872,12 +2371,19 @@ impl Trader {
         place_sell_orders: bool,
         size_override: Option<f64>,
     ) -> Result<()> {
-        let fixed_amount = self.config.fixed_trade_amount;
+        let (fixed_amount, compounding) = self.trade_amount(&opportunity.token_type).await;
+        if let (Some(compounding), None) = (compounding, size_override) {
+            crate::log_println!("📈 Compounded trade size: {}", compounding);
+        }
         let units = size_override.unwrap_or_else(|| fixed_amount / opportunity.bid_price);
         let investment_amount = units * opportunity.bid_price;
+        if !self.simulation_mode && !self.funds_available(&opportunity.token_id, &opportunity.token_type, investment_amount).await {
+            return Ok(());
+        }
+        let trade_id = new_trade_id(); // Correlates this trade's buy/sell/redemption events
         
         // Only profit target sell price (stop-loss disabled for limit order version)
-        let sell_price = self.config.sell_price;
+        let sell_price = self.config().sell_price;
         
         crate::log_println!("═══════════════════════════════════════════════════════════");
         crate::log_println!("📋 PLACING LIMIT BUY ORDER");
@@ -886,6 +2392,7 @@ impl Trader {
         crate::log_println!("   Token Type: {}", opportunity.token_type.display_name());
         crate::log_println!("   Token ID: {}", opportunity.token_id);
         crate::log_println!("   Condition ID: {}", opportunity.condition_id);
+        crate::log_println!("   Trade ID: {}", trade_id);
         crate::log_println!("   Side: BUY (LIMIT)");
         crate::log_println!("   Limit Price: ${:.6}", opportunity.bid_price);
         crate::log_println!("   Size: {:.6} shares", units);
@@ -926,6 +2433,7 @@ impl Trader {
                 let trade_key = format!("{}_{}_limit", opportunity.period_timestamp, opportunity.token_id);
                 let mut pending = self.pending_trades.lock().await;
                 let trade = PendingTrade {
+                    trade_id: trade_id.clone(),
                     token_id: opportunity.token_id.clone(),
                     condition_id: opportunity.condition_id.clone(),
                     token_type: opportunity.token_type.clone(),
@@ -933,18 +2441,19 @@ impl Trader {
                     investment_amount,
                     units,
                     purchase_price: opportunity.bid_price,
-                    sell_price: self.config.sell_price, // Not used in simulation - positions held until closure
+                    sell_price: opportunity.sell_price_override.unwrap_or(self.config().sell_price), // Only used when the strategy sets a sell target
                     timestamp: std::time::Instant::now(),
                     market_timestamp: opportunity.period_timestamp,
                     sold: false,
                     confirmed_balance: None,
                     buy_order_confirmed: false,
                     limit_sell_orders_placed: false, // No sell orders in simulation - hold until closure
-                    no_sell: true, // Mark as no_sell since we hold until market closure
-                    claim_on_closure: true, // Will claim at market closure
+                    no_sell: opportunity.sell_price_override.is_none(), // Hold until closure unless the strategy set a sell target
+                    claim_on_closure: opportunity.sell_price_override.is_none(), // Will claim at market closure
                     sell_attempts: 0,
                     redemption_attempts: 0,
                     redemption_abandoned: false,
+                    scale_out_levels_done: 0,
                 };
                 pending.insert(trade_key, trade);
             }
@@ -956,8 +2465,8 @@ impl Trader {
         use rust_decimal::Decimal;
         use crate::models::OrderRequest;
         
-        // Format size to 2 decimal places (API requirement: maximum 2 decimal places)
-        let size_formatted = format!("{:.2}", units);
+        // Format size to the asset's size_decimals (default 2 - the CLOB's usual maximum)
+        let size_formatted = format_size(units, self.size_decimals(&opportunity.token_type), self.config().size_rounding);
         
         let order = OrderRequest {
             token_id: opportunity.token_id.clone(),
@@ -968,7 +2477,22 @@ impl Trader {
         };
         
         crate::log_println!("🚀 Placing limit buy order on exchange...");
-        match self.api.place_order(&order).await {
+        self.dump_orderbook(&opportunity.token_id, "BEFORE LIMIT BUY", &trade_id).await;
+        let placed_at = chrono::Utc::now().timestamp();
+        let order_result = match self.api.place_order(&order).await {
+            // The order may have reached the book even though the response didn't reach us
+            Err(e) if is_ambiguous_order_error(&e) => match self.find_lost_order(&order, placed_at, &e).await {
+                Some(recovered) => Ok(OrderResponse {
+                    order_id: Some(recovered.order_id),
+                    status: format!("{} (recovered after lost response)", recovered.status),
+                    message: Some(format!("Original error: {}", e)),
+                }),
+                None => Err(e),
+            },
+            result => result,
+        };
+        self.dump_orderbook(&opportunity.token_id, "AFTER LIMIT BUY", &trade_id).await;
+        match order_result {
             Ok(response) => {
                 crate::log_println!("   ✅ LIMIT BUY ORDER PLACED");
                 crate::log_println!("      Order ID: {:?}", response.order_id);
@@ -992,6 +2516,7 @@ impl Trader {
                 
                 // Store both sell prices in the trade (we'll use sell_price as the primary one for tracking)
                 let trade = PendingTrade {
+                    trade_id: trade_id.clone(),
                     token_id: opportunity.token_id.clone(),
                     condition_id: opportunity.condition_id.clone(),
                     token_type: opportunity.token_type.clone(),
@@ -1011,6 +2536,7 @@ impl Trader {
                     sell_attempts: 0,
                     redemption_attempts: 0,
                     redemption_abandoned: false,
+                    scale_out_levels_done: 0,
                 };
                 
                 pending.insert(trade_key, trade);
@@ -1030,14 +2556,15 @@ impl Trader {
                     .map(|id| format!("{:?}", id))
                     .unwrap_or_else(|| "N/A".to_string());
                 let buy_event = format!(
-                    "LIMIT BUY ORDER | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Target Sell: ${:.6} (profit only, stop-loss disabled)",
+                    "LIMIT BUY ORDER | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Target Sell: ${:.6} (profit only, stop-loss disabled) | Trade ID: {}",
                     opportunity.token_type.display_name(),
                     opportunity.period_timestamp,
                     &opportunity.token_id[..16],
                     opportunity.bid_price,
                     units,
                     order_id_str,
-                    sell_price
+                    sell_price,
+                    trade_id
                 );
                 crate::log_trading_event(&buy_event);
             }
@@ -1050,18 +2577,695 @@ impl Trader {
         Ok(())
     }
 
-    /// Check pending trades and sell when price reaches sell_price (0.99 or 1.0)
-    /// Also handles limit order fills: detects when limit buy orders fill and places limit sell orders
-    pub async fn check_pending_trades(&self) -> Result<()> {
-        // In simulation mode, check limit orders against current prices
-        if self.simulation_mode {
-            if let Some(tracker) = &self.simulation_tracker {
+    /// Maker entry (entry_order_type "maker"): rest a limit buy at the best bid plus maker_price_improvement, at
+    /// least one tick below the best ask, and hand it to `convert_expired_maker_buys` for its timeout. The CLOB
+    /// client can't flag orders post-only, so not crossing the spread is checked here against the book read right
+    /// before placing. Buys at market when the token has no bid to join
+    async fn execute_maker_buy(&self, opportunity: &BuyOpportunity) -> Result<()> {
+        let book = self.api.get_best_price(&opportunity.token_id).await.ok().flatten();
+        let to_f64 = |price: Option<rust_decimal::Decimal>| price.and_then(|p| f64::try_from(p).ok());
+        let improvement = self.config().maker_price_improvement.unwrap_or(0.0);
+        let Some(price) = book.as_ref().and_then(|b| maker_buy_price(to_f64(b.bid)?, to_f64(b.ask), improvement)) else {
+            crate::log_println!("⚠️  MAKER BUY: no bid to join for {} - buying at market", opportunity.token_type.display_name());
+            return self.execute_buy(opportunity).await;
+        };
+
+        let maker = BuyOpportunity { bid_price: price, ..opportunity.clone() };
+        self.execute_limit_buy(&maker, true, opportunity.dual_limit_shares).await?;
+        let key = format!("{}_{}_limit", opportunity.period_timestamp, opportunity.token_id);
+        let resting = self.pending_trades.lock().await.get(&key).is_some_and(|t| !t.buy_order_confirmed);
+        if !resting {
+            return Ok(()); // Not placed (e.g. insufficient funds)
+        }
+        let timeout = self.config().maker_timeout_secs.unwrap_or(DEFAULT_MAKER_TIMEOUT_SECS);
+        self.maker_buys.lock().unwrap().insert(key, (std::time::Instant::now(), opportunity.clone()));
+        crate::log_trading_event(&format!(
+            "MAKER BUY | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Best Ask: {} | Timeout: {}s",
+            opportunity.token_type.display_name(),
+            opportunity.period_timestamp,
+            &opportunity.token_id[..16],
+            price,
+            book.as_ref().and_then(|b| to_f64(b.ask)).map(|ask| format!("${:.6}", ask)).unwrap_or_else(|| "N/A".to_string()),
+            timeout
+        ));
+        Ok(())
+    }
+
+    /// Cancel the maker buys that have rested maker_timeout_secs without filling and buy what they didn't fill at
+    /// market. A partial fill is confirmed at its matched size first; buys that filled meanwhile are left alone.
+    /// The market buy is a new entry: it is skipped while trading is paused or the risk budget blocks it
+    async fn convert_expired_maker_buys(&self) {
+        let timeout = std::time::Duration::from_secs(self.config().maker_timeout_secs.unwrap_or(DEFAULT_MAKER_TIMEOUT_SECS));
+        let expired: Vec<(String, std::time::Instant, BuyOpportunity)> = {
+            let mut maker_buys = self.maker_buys.lock().unwrap();
+            let keys: Vec<String> = maker_buys.iter()
+                .filter(|(_, (placed_at, _))| placed_at.elapsed() >= timeout)
+                .map(|(key, _)| key.clone())
+                .collect();
+            keys.into_iter()
+                .filter_map(|key| maker_buys.remove(&key).map(|(placed_at, opportunity)| (key, placed_at, opportunity)))
+                .collect()
+        };
+
+        for (key, placed_at, opportunity) in expired {
+            let Some(trade) = self.pending_trades.lock().await.get(&key).cloned() else {
+                continue;
+            };
+            if trade.sold || trade.buy_order_confirmed {
+                continue;
+            }
+            let matched = if self.simulation_mode {
+                let Some(tracker) = &self.simulation_tracker else {
+                    continue;
+                };
+                if !tracker.has_pending_order(&trade.token_id, "BUY").await {
+                    continue; // Filled - confirmed by the simulation fill check
+                }
+                tracker.cancel_limit_order(&trade.token_id, "BUY").await
+            } else {
+                let Some(order_id) = trade.order_id.clone() else {
+                    continue;
+                };
+                if let Err(e) = self.api.cancel_order(&order_id).await {
+                    warn!("Maker buy {} could not be cancelled after its timeout - left resting: {}", key, e);
+                    continue;
+                }
+                match self.api.get_order(&order_id).await {
+                    Ok(order) => {
+                        self.order_tracker.forget(&order_id);
+                        order.size_matched
+                    }
+                    Err(e) => {
+                        warn!("Maker buy {} cancelled but its matched size is unknown - left to order tracking, no market buy: {}", key, e);
+                        continue;
+                    }
+                }
+            };
+
+            if matched > 0.000001 {
+                let initial_balance = trade.confirmed_balance.unwrap_or(0.0);
+                self.handle_limit_buy_fill(&key, &trade, initial_balance, initial_balance + matched).await;
+            } else {
+                self.pending_trades.lock().await.remove(&key);
+            }
+            let remaining = trade.units - matched;
+            let ask = self.api.get_best_price(&trade.token_id).await.ok().flatten()
+                .and_then(|b| b.ask)
+                .and_then(|ask| f64::try_from(ask).ok())
+                .filter(|ask| *ask > 0.0)
+                .unwrap_or(opportunity.bid_price);
+            crate::log_println!("⏱️  MAKER TIMEOUT: {} maker buy at ${:.2} filled {:.6} of {:.6} shares in {}s - cancelled",
+                trade.token_type.display_name(), trade.purchase_price, matched, trade.units, timeout.as_secs());
+            crate::log_trading_event(&format!(
+                "MAKER TIMEOUT | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Matched: {:.6} / {:.6} | Trade ID: {}",
+                trade.token_type.display_name(),
+                trade.market_timestamp,
+                &trade.token_id[..16],
+                trade.purchase_price,
+                matched,
+                trade.units,
+                trade.trade_id
+            ));
+            if matched > 0.000001 && remaining * ask < MIN_ORDER_AMOUNT {
+                crate::log_println!("   Remaining {:.6} shares are below the ${:.2} minimum order - keeping the partial fill only", remaining, MIN_ORDER_AMOUNT);
+                continue;
+            }
+
+            if self.is_paused() {
+                crate::log_println!("   Trading paused - not buying the remaining {:.6} shares at market", remaining);
+                continue;
+            }
+            let config = self.config();
+            if config.max_total_notional.is_some() || config.daily_loss_limit.is_some() {
+                let open_notional = self.open_notional().await;
+                if let Err(block) = self.risk.check_entry(&config, open_notional, remaining * ask, chrono::Utc::now()) {
+                    crate::log_println!("   🛑 Market buy of the remaining {:.6} shares blocked: {}", remaining, block);
+                    continue;
+                }
+            }
+
+            crate::log_println!("   Buying the remaining {:.6} shares at market (~${:.2})", remaining, ask);
+            let waited = placed_at.elapsed().as_secs();
+            let market = BuyOpportunity {
+                bid_price: ask,
+                time_remaining_seconds: opportunity.time_remaining_seconds.saturating_sub(waited),
+                time_elapsed_seconds: opportunity.time_elapsed_seconds + waited,
+                investment_amount_override: Some(remaining * ask),
+                ..opportunity
+            };
+            if let Err(e) = self.execute_buy(&market).await {
+                warn!("Market buy after maker timeout failed for {}: {}", key, e);
+            }
+        }
+    }
+
+    /// Place a limit sell; with trading.verify_resting, check shortly afterwards that it is live on the book
+    /// (or already filled) and re-place it if it was rejected or cancelled after being accepted
+    /// Errors once verify_resting_retries re-placements didn't rest either
+    async fn place_limit_sell(&self, order: &OrderRequest) -> Result<OrderResponse> {
+        let mut response = self.api.place_order(order).await?;
+        if !self.config().verify_resting || self.simulation_mode {
+            return Ok(response);
+        }
+        let delay = std::time::Duration::from_millis(self.config().verify_resting_delay_ms.unwrap_or(DEFAULT_VERIFY_RESTING_DELAY_MS));
+        let retries = self.config().verify_resting_retries.unwrap_or(DEFAULT_VERIFY_RESTING_RETRIES);
+        let size: f64 = order.size.parse().unwrap_or(0.0);
+        for attempt in 0..=retries {
+            tokio::time::sleep(delay).await;
+            let Some(order_id) = response.order_id.clone() else {
+                warn!("Can't verify limit sell on {} - no order ID in the response", &order.token_id[..16]);
+                return Ok(response);
+            };
+            let resting = match self.api.get_open_orders(&order.token_id).await {
+                Ok(orders) => orders.iter().any(|o| o.order_id == order_id),
+                Err(e) => {
+                    warn!("Can't verify limit sell {}: {} - assuming it rests", order_id, e);
+                    return Ok(response);
+                }
+            };
+            // Not on the book but the tokens are gone - it filled, which is just as good
+            let filled = !resting && match self.api.check_balance_only(&order.token_id).await {
+                Ok(balance) => f64::try_from(balance / rust_decimal::Decimal::from(1_000_000u64)).unwrap_or(0.0) + 0.000001 < size,
+                Err(_) => false,
+            };
+            if resting || filled {
+                debug!("Limit sell {} verified ({})", order_id, if resting { "resting" } else { "filled" });
+                return Ok(response);
+            }
+            crate::log_println!("   ⚠️  LIMIT SELL NOT RESTING: order {} ({} @ ${}) is not on the book{}",
+                order_id, order.size, order.price,
+                if attempt < retries { format!(" - re-placing ({}/{})", attempt + 1, retries) } else { String::new() });
+            crate::log_trading_event(&format!(
+                "LIMIT SELL NOT RESTING | Token: {} | Limit Price: ${} | Size: {} | Order ID: {} | Attempt: {}/{}",
+                &order.token_id[..16], order.price, order.size, order_id, attempt + 1, retries + 1));
+            if attempt == retries {
+                break;
+            }
+            response = self.api.place_order(order).await?;
+        }
+        anyhow::bail!("limit sell {} @ ${} did not rest on the book after {} re-placement(s)", order.size, order.price, retries)
+    }
+
+    /// sell.peg_to_book: move our resting limit sells to one tick below the best ask of other sellers (never below
+    /// sell.peg_min_price, or the highest purchase price among the token's positions), cancelling and re-placing
+    /// each one that is more than sell.repeg_threshold away from that price. Tokens are handled once even when
+    /// several positions share them; a sell that can't be re-placed after its cancel is logged and left to the
+    /// stop-loss and closure logic
+    async fn repeg_limit_sells(&self, trades: &[(String, PendingTrade)]) {
+        if !self.sell.peg_to_book || self.simulation_mode {
+            return;
+        }
+        let threshold = self.sell.repeg_threshold.unwrap_or(DEFAULT_REPEG_THRESHOLD);
+        let mut floors: HashMap<&str, (f64, &PendingTrade)> = HashMap::new();
+        for (_, trade) in trades.iter().filter(|(_, t)| !t.sold && !t.no_sell && t.buy_order_confirmed && t.limit_sell_orders_placed) {
+            let floor = self.sell.peg_min_price.unwrap_or(trade.purchase_price);
+            let entry = floors.entry(trade.token_id.as_str()).or_insert((floor, trade));
+            entry.0 = entry.0.max(floor);
+        }
+
+        for (token_id, (floor, trade)) in floors {
+            let resting: Vec<OpenOrder> = match self.api.get_open_orders(token_id).await {
+                Ok(orders) => orders.into_iter().filter(|o| o.side.eq_ignore_ascii_case("SELL")).collect(),
+                Err(e) => {
+                    debug!("Peg: could not fetch open orders for {}: {}", &token_id[..16], e);
+                    continue;
+                }
+            };
+            if resting.is_empty() {
+                continue;
+            }
+            let book = match self.api.get_orderbook(token_id).await {
+                Ok(book) => book,
+                Err(e) => {
+                    debug!("Peg: could not fetch orderbook for {}: {}", &token_id[..16], e);
+                    continue;
+                }
+            };
+            // No other seller to undercut - leave the sells where they are
+            let Some(peg) = peg_sell_price(&book, &resting) else {
+                continue;
+            };
+            let peg = peg.max(floor);
+
+            for order in resting {
+                let remaining = order.original_size - order.size_matched;
+                if (order.price - peg).abs() <= threshold + 1e-9 || remaining <= 0.000001 {
+                    continue;
+                }
+                if let Err(e) = self.api.cancel_order(&order.order_id).await {
+                    warn!("Peg: failed to cancel limit sell {} @ ${:.2}: {} - keeping it", order.order_id, order.price, e);
+                    continue;
+                }
+                let request = OrderRequest {
+                    token_id: token_id.to_string(),
+                    side: "SELL".to_string(),
+                    size: format_size(remaining, self.size_decimals(&trade.token_type), self.config().size_rounding),
+                    price: format!("{:.2}", peg),
+                    order_type: "LIMIT".to_string(),
+                };
+                match self.place_limit_sell(&request).await {
+                    Ok(response) => {
+                        let new_order_id = response.order_id.unwrap_or_else(|| "N/A".to_string());
+                        crate::log_println!("   📌 LIMIT SELL RE-PEGGED: {} {} shares ${:.2} -> ${:.2} (order {} -> {})",
+                            trade.token_type.display_name(), request.size, order.price, peg, order.order_id, new_order_id);
+                        crate::log_trading_event(&format!(
+                            "LIMIT SELL RE-PEGGED | Market: {} | Period: {} | Token: {} | Old Price: ${:.2} | New Price: ${:.2} | Size: {} | Old Order ID: {} | Order ID: {} | Trade ID: {}",
+                            trade.token_type.display_name(), trade.market_timestamp, &token_id[..16], order.price, peg,
+                            request.size, order.order_id, new_order_id, trade.trade_id));
+                    }
+                    Err(e) => {
+                        crate::log_println!("   ❌ LIMIT SELL RE-PEG FAILED: {} {} shares @ ${:.2} cancelled but not re-placed: {}",
+                            trade.token_type.display_name(), request.size, peg, e);
+                        crate::log_trading_event(&format!(
+                            "LIMIT SELL RE-PEG FAILED | Market: {} | Period: {} | Token: {} | Price: ${:.2} | Size: {} | Error: {} | Trade ID: {}",
+                            trade.token_type.display_name(), trade.market_timestamp, &token_id[..16], peg, request.size,
+                            e.to_string().chars().take(100).collect::<String>(), trade.trade_id));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Self-cross guard for the opposite-token hedge orders (trading.self_cross_guard): true when one of our
+    /// resting orders on the same token already does the job (same side and price) or would trade against
+    /// this one (opposite side at a crossing price). Logs SELF-CROSS AVOIDED; if open orders can't be fetched
+    /// the order is allowed
+    async fn would_self_cross(&self, order: &OrderRequest) -> bool {
+        if self.simulation_mode || !self.config().self_cross_guard.unwrap_or(true) {
+            return false;
+        }
+        let price: f64 = match order.price.parse() {
+            Ok(price) => price,
+            Err(_) => return false,
+        };
+        let resting = match self.api.get_open_orders(&order.token_id).await {
+            Ok(orders) => orders,
+            Err(e) => {
+                warn!("Self-cross guard: could not fetch open orders for {}: {} - placing the order anyway", &order.token_id[..16], e);
+                return false;
+            }
+        };
+        let conflict = resting.iter().find(|o| {
+            let same_side = o.side.eq_ignore_ascii_case(&order.side);
+            if same_side {
+                (o.price - price).abs() < 1e-9
+            } else if order.side.eq_ignore_ascii_case("BUY") {
+                o.price <= price + 1e-9 // Our resting ask would fill our bid
+            } else {
+                o.price >= price - 1e-9 // Our resting bid would fill our ask
+            }
+        });
+        let Some(conflict) = conflict else {
+            return false;
+        };
+        let reason = if conflict.side.eq_ignore_ascii_case(&order.side) { "duplicate of" } else { "would cross" };
+        crate::log_println!("   ⚠️  SELF-CROSS AVOIDED: {} {} @ ${:.2} {} resting {} @ ${:.2} (order {}) - not placing it",
+            order.side, &order.token_id[..16], price, reason, conflict.side, conflict.price, conflict.order_id);
+        crate::log_trading_event(&format!(
+            "SELF-CROSS AVOIDED | Token: {} | Side: {} | Price: ${:.2} | Size: {} | Resting: {} @ ${:.2} | Resting Order ID: {}",
+            &order.token_id[..16], order.side, price, order.size, conflict.side, conflict.price, conflict.order_id));
+        true
+    }
+
+    /// After a limit order failed with a transport error, look for it among our open orders for up to
+    /// trading.lost_order_lookup_secs. Orders already tracked by a pending trade are never adopted
+    /// Market buys don't rest on the book - `place_buy_idempotent` reconciles those through our CLOB trades
+    async fn find_lost_order(&self, order: &OrderRequest, placed_at: i64, error: &anyhow::Error) -> Option<OpenOrder> {
+        let window_secs = self.config().lost_order_lookup_secs.unwrap_or(DEFAULT_LOST_ORDER_LOOKUP_SECS);
+        if window_secs == 0 {
+            return None;
+        }
+        crate::log_println!("   🔎 Order response lost ({}) - looking for the order among open orders for up to {}s...",
+            ApiError::classify_error(error).summary(), window_secs);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(window_secs);
+        loop {
+            match self.api.get_open_orders(&order.token_id).await {
+                Ok(open_orders) => {
+                    let tracked: std::collections::HashSet<String> = self.pending_trades.lock().await.values()
+                        .filter_map(|t| t.order_id.clone())
+                        .collect();
+                    if let Some(found) = open_orders.into_iter()
+                        .find(|o| !tracked.contains(&o.order_id) && is_lost_order(o, order, placed_at))
+                    {
+                        crate::log_println!("   ✅ RECOVERED ORDER: {} {} {} @ {} is live on the book (order {}, status {}, matched {:.6})",
+                            order.side, order.size, &order.token_id[..16], order.price, found.order_id, found.status, found.size_matched);
+                        crate::log_trading_event(&format!(
+                            "RECOVERED ORDER | Token: {} | Side: {} | Price: {} | Size: {} | Order ID: {} | Original Error: {}",
+                            &order.token_id[..16], order.side, order.price, order.size, found.order_id, error));
+                        return Some(found);
+                    }
+                }
+                Err(e) => debug!("Open order lookup for {} failed: {}", &order.token_id[..16], e),
+            }
+            if std::time::Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        }
+        crate::log_println!("   ❌ No matching open order found within {}s - treating the order as not placed", window_secs);
+        None
+    }
+
+    /// Record a detected limit buy fill and place the profit-target sell order
+    /// Shared by balance polling in `check_pending_trades` and the user-channel event loop
+    async fn handle_limit_buy_fill(&self, key: &str, trade: &PendingTrade, initial_balance: f64, current_balance: f64) {
+        // Claim the fill first - the user channel, order status tracking and balance polling can all see it
+        {
+            let mut pending = self.pending_trades.lock().await;
+            match pending.get_mut(key) {
+                Some(t) if !t.buy_order_confirmed => {
+                    t.confirmed_balance = Some(current_balance);
+                    t.units = current_balance; // Update units to actual filled amount
+                    t.buy_order_confirmed = true;
+                }
+                _ => {
+                    debug!("Limit buy fill for {} already handled", key);
+                    return;
+                }
+            }
+        }
+
+        crate::notifier::notify(crate::notifier::NotifyEvent::BuyExecuted, format!(
+            "🟢 LIMIT BUY FILLED {} | {:.2} shares @ ${:.2} | Period {}",
+            trade.token_type.display_name(), current_balance - initial_balance, trade.purchase_price, trade.market_timestamp));
+        self.report_fill(crate::strategy::StrategyFill {
+            token_id: trade.token_id.clone(),
+            token_type: trade.token_type.clone(),
+            condition_id: trade.condition_id.clone(),
+            period_timestamp: trade.market_timestamp,
+            price: trade.purchase_price,
+            units: current_balance - initial_balance,
+        }).await;
+        crate::log_println!("═══════════════════════════════════════════════════════════");
+        crate::log_println!("✅ LIMIT BUY ORDER FILLED");
+        crate::log_println!("═══════════════════════════════════════════════════════════");
+        crate::log_println!("📊 Fill Details:");
+        crate::log_println!("   Token Type: {}", trade.token_type.display_name());
+        crate::log_println!("   Token ID: {}", trade.token_id);
+        crate::log_println!("   Initial Balance: {:.6} shares", initial_balance);
+        crate::log_println!("   Current Balance: {:.6} shares", current_balance);
+        crate::log_println!("   Filled Amount: {:.6} shares", current_balance - initial_balance);
+        crate::log_println!("   Purchase Price: ${:.6}", trade.purchase_price);
+        crate::log_println!("   Target Sell Price: ${:.6}", trade.sell_price);
+        crate::log_println!("");
+        
+        let filled = PendingTrade { confirmed_balance: Some(current_balance), units: current_balance, buy_order_confirmed: true, ..trade.clone() };
+        if self.verify_purchased_token(key, &filled).await {
+            return;
+        }
+        
+        // For no-sell mode, log confirmation only and skip sell placement
+        if trade.no_sell {
+            crate::log_println!("✅ No-sell mode: confirmation logged, no sell orders will be placed.");
+            let mut pending = self.pending_trades.lock().await;
+            if let Some(t) = pending.get_mut(key) {
+                t.limit_sell_orders_placed = true;
+            }
+            return;
+        }
+
+        // Place limit sell order immediately
+        if !self.simulation_mode {
+            crate::log_println!("📤 Placing limit sell order at ${:.6}...", trade.sell_price);
+            
+            use crate::models::OrderRequest;
+            let sell_order = OrderRequest {
+                token_id: trade.token_id.clone(),
+                side: "SELL".to_string(),
+                size: current_balance.to_string(),
+                price: trade.sell_price.to_string(),
+                order_type: "LIMIT".to_string(),
+            };
+            
+            self.dump_orderbook(&trade.token_id, "BEFORE LIMIT SELL", &trade.trade_id).await;
+            let sell_result = self.place_limit_sell(&sell_order).await;
+            self.dump_orderbook(&trade.token_id, "AFTER LIMIT SELL", &trade.trade_id).await;
+            match sell_result {
+                Ok(response) => {
+                    crate::log_println!("   ✅ LIMIT SELL ORDER PLACED");
+                    crate::log_println!("      Order ID: {:?}", response.order_id);
+                    crate::log_println!("      Limit Price: ${:.6}", trade.sell_price);
+                    crate::log_println!("      Size: {:.6} shares", current_balance);
+                    
+                    let order_id_str = response.order_id.as_ref()
+                        .map(|id| format!("{:?}", id))
+                        .unwrap_or_else(|| "N/A".to_string());
+                    let sell_event = format!(
+                        "LIMIT SELL ORDER | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Trade ID: {}",
+                        trade.token_type.display_name(),
+                        trade.market_timestamp,
+                        &trade.token_id[..16],
+                        trade.sell_price,
+                        current_balance,
+                        order_id_str,
+                        trade.trade_id
+                    );
+                    crate::log_trading_event(&sell_event);
+                }
+                Err(e) => {
+                    eprintln!("   ❌ FAILED TO PLACE LIMIT SELL ORDER: {}", e);
+                    warn!("Failed to place limit sell order after buy fill: {}", e);
+                }
+            }
+        } else {
+            crate::log_println!("🎮 SIMULATION: Limit sell order would be placed at ${:.6}", trade.sell_price);
+        }
+    }
+
+    /// Consume CLOB user-channel events and apply fills/cancels to pending trades
+    /// Runs until the event channel closes; balance polling in `check_pending_trades` stays as the fallback
+    pub async fn run_user_event_loop(&self, mut events: tokio::sync::mpsc::Receiver<UserEvent>) {
+        while let Some(event) = events.recv().await {
+            self.handle_user_event(event).await;
+        }
+        warn!("User event channel closed - fill detection falls back to balance polling");
+    }
+
+    async fn handle_user_event(&self, event: UserEvent) {
+        match event {
+            UserEvent::Trade(fill) => {
+                // MATCHED is the first notification for a fill; later statuses repeat the same trade
+                if fill.status != "MATCHED" {
+                    return;
+                }
+
+                let tracked: Vec<(String, PendingTrade)> = {
+                    let pending = self.pending_trades.lock().await;
+                    pending.iter()
+                        .filter(|(_, t)| !t.sold && t.token_id == fill.asset_id)
+                        .map(|(key, trade)| (key.clone(), trade.clone()))
+                        .collect()
+                };
+
+                for (key, trade) in tracked {
+                    // Fill of one of our limit buys - the order id tells us exactly how much filled
+                    let buy_fill = trade.order_id.as_deref()
+                        .and_then(|order_id| fill.filled_size_for_order(order_id));
+                    if let Some(filled) = buy_fill {
+                        if trade.buy_order_confirmed || !key.contains("_limit") {
+                            continue;
+                        }
+                        let filled = f64::try_from(filled).unwrap_or(0.0);
+                        let initial_balance = trade.confirmed_balance.unwrap_or(0.0);
+                        debug!("User channel: limit buy {} filled {:.6} shares (trade {})", key, filled, fill.id);
+                        self.handle_limit_buy_fill(&key, &trade, initial_balance, initial_balance + filled).await;
+                        continue;
+                    }
+
+                    // Any other fill on a held token is one of our sells - reconcile against the balance
+                    if trade.buy_order_confirmed && trade.limit_sell_orders_placed {
+                        self.reconcile_sell_fill(&key, &trade).await;
+                    }
+                }
+            }
+            UserEvent::OrderUpdate(update) => {
+                if update.update_type != "CANCELLATION" || update.size_matched > rust_decimal::Decimal::ZERO {
+                    return;
+                }
+                let mut pending = self.pending_trades.lock().await;
+                let cancelled_key = pending.iter()
+                    .find(|(key, t)| {
+                        key.contains("_limit")
+                            && !t.buy_order_confirmed
+                            && t.order_id.as_deref() == Some(update.id.as_str())
+                    })
+                    .map(|(key, _)| key.clone());
+                if let Some(key) = cancelled_key {
+                    pending.remove(&key);
+                    crate::log_println!("🛑 Limit buy {} was cancelled before filling - removed from tracking", key);
+                }
+            }
+        }
+    }
+
+    /// Look up the order status of every unconfirmed limit buy that has an order id and act on it:
+    /// fully matched -> confirmed at the matched size, cancelled -> confirmed at whatever matched first or
+    /// dropped if nothing did, still open -> keep waiting (new partial fills are logged)
+    /// Returns the keys of the trades whose status was known; the others fall back to balance polling
+    async fn track_limit_buy_orders(&self, pending_trades: &[(String, PendingTrade)]) -> std::collections::HashSet<String> {
+        let awaiting: Vec<(&String, &PendingTrade, &String)> = pending_trades.iter()
+            .filter(|(key, trade)| key.contains("_limit") && !trade.sold && !trade.buy_order_confirmed)
+            .filter_map(|(key, trade)| trade.order_id.as_ref().map(|order_id| (key, trade, order_id)))
+            .collect();
+        if awaiting.is_empty() {
+            return std::collections::HashSet::new();
+        }
+        
+        let concurrency = self.config().price_fetch_concurrency.unwrap_or(DEFAULT_PRICE_FETCH_CONCURRENCY);
+        let statuses = self.order_tracker.poll(awaiting.iter().map(|(_, _, id)| (*id).clone()).collect(), concurrency).await;
+        
+        let mut tracked = std::collections::HashSet::new();
+        for (key, trade, order_id) in awaiting {
+            let Some(status) = statuses.get(order_id) else {
+                continue; // Status lookup failed this tick
+            };
+            tracked.insert(key.clone());
+            
+            // The user channel may have handled this trade since the snapshot was taken
+            let still_waiting = self.pending_trades.lock().await.get(key.as_str())
+                .map(|t| !t.sold && !t.buy_order_confirmed)
+                .unwrap_or(false);
+            if !still_waiting {
+                self.order_tracker.forget(order_id);
+                continue;
+            }
+            
+            let newly_matched = self.order_tracker.newly_matched(status);
+            let initial_balance = trade.confirmed_balance.unwrap_or(0.0);
+            match status.state {
+                OrderState::Filled => {
+                    self.order_tracker.forget(order_id);
+                    self.handle_limit_buy_fill(key, trade, initial_balance, initial_balance + status.size_matched).await;
+                }
+                OrderState::Cancelled if status.size_matched > 0.000001 => {
+                    self.order_tracker.forget(order_id);
+                    crate::log_println!("⚠️  Limit buy {} was cancelled after a partial fill ({:.6} of {:.6} shares)",
+                        key, status.size_matched, status.original_size);
+                    self.handle_limit_buy_fill(key, trade, initial_balance, initial_balance + status.size_matched).await;
+                }
+                OrderState::Cancelled => {
+                    self.order_tracker.forget(order_id);
+                    self.pending_trades.lock().await.remove(key.as_str());
+                    crate::log_println!("🛑 Limit buy {} was cancelled before filling - removed from tracking", key);
+                }
+                OrderState::Open | OrderState::Unknown => {
+                    if newly_matched > 0.000001 {
+                        crate::log_println!("⏳ Limit buy {} partially filled: {:.6} of {:.6} shares matched",
+                            key, status.size_matched, status.original_size);
+                        crate::log_trading_event(&format!(
+                            "LIMIT BUY PARTIAL FILL | Market: {} | Period: {} | Token: {} | Matched: {:.6} / {:.6} | Order ID: {} | Trade ID: {}",
+                            trade.token_type.display_name(),
+                            trade.market_timestamp,
+                            &trade.token_id[..16],
+                            status.size_matched,
+                            status.original_size,
+                            order_id,
+                            trade.trade_id
+                        ));
+                    }
+                }
+            }
+        }
+        tracked
+    }
+
+    /// Check the token balance after a sell fill notification and mark the trade sold once it is flat
+    async fn reconcile_sell_fill(&self, key: &str, trade: &PendingTrade) {
+        use rust_decimal::Decimal;
+        let current_balance = match self.api.check_balance_only(&trade.token_id).await {
+            Ok(balance) => {
+                let balance_decimal = balance / Decimal::from(1_000_000u64);
+                f64::try_from(balance_decimal).unwrap_or(0.0)
+            }
+            Err(e) => {
+                debug!("User channel: balance check failed for {}: {}", key, e);
+                return;
+            }
+        };
+
+        let last_balance = trade.confirmed_balance.unwrap_or(0.0);
+        if last_balance > 0.000001 && current_balance < 0.000001 {
+            {
+                let mut pending = self.pending_trades.lock().await;
+                if let Some(t) = pending.get_mut(key) {
+                    t.sold = true;
+                    t.confirmed_balance = Some(0.0);
+                }
+            }
+            crate::log_println!("✅ LIMIT SELL ORDER FILLED (user channel): {} - {:.6} shares at ${:.6}",
+                trade.token_type.display_name(), last_balance, trade.sell_price);
+            let sell_event = format!(
+                "LIMIT SELL ORDER FILLED | Market: {} | Period: {} | Token: {} | Purchase Price: ${:.6} | Sell Price: ${:.6} | Shares Sold: {:.6} | Revenue: ${:.6} | Status: FILLED | Trade ID: {}",
+                trade.token_type.display_name(),
+                trade.market_timestamp,
+                &trade.token_id[..16],
+                trade.purchase_price,
+                trade.sell_price,
+                last_balance,
+                last_balance * trade.sell_price,
+                trade.trade_id
+            );
+            crate::log_trading_event(&sell_event);
+        }
+    }
+
+    /// Check pending trades and sell when price reaches sell_price (0.99 or 1.0)
+    /// Also handles limit order fills: detects when limit buy orders fill and places limit sell orders
+    /// Token balances in shares, fetched concurrently (trading.price_fetch_concurrency at a time)
+    /// Tokens whose balance couldn't be fetched are missing from the map
+    async fn fetch_balances(&self, mut token_ids: Vec<String>) -> HashMap<String, f64> {
+        token_ids.sort();
+        token_ids.dedup();
+        let concurrency = self.config().price_fetch_concurrency.unwrap_or(DEFAULT_PRICE_FETCH_CONCURRENCY).max(1);
+        futures_util::stream::iter(token_ids)
+            .map(|token_id| {
+                let api = self.api.clone();
+                async move {
+                    let balance = api.check_balance_only(&token_id).await.ok()
+                        .map(|b| f64::try_from(b / rust_decimal::Decimal::from(1_000_000u64)).unwrap_or(0.0));
+                    (token_id, balance)
+                }
+            })
+            .buffer_unordered(concurrency)
+            .filter_map(|(token_id, balance)| async move { balance.map(|b| (token_id, b)) })
+            .collect()
+            .await
+    }
+    
+    /// SELL price and orderbook for each token, fetched concurrently (trading.price_fetch_concurrency at a time)
+    async fn fetch_sell_quotes(&self, mut token_ids: Vec<String>) -> HashMap<String, SellQuote> {
+        token_ids.sort();
+        token_ids.dedup();
+        let concurrency = self.config().price_fetch_concurrency.unwrap_or(DEFAULT_PRICE_FETCH_CONCURRENCY).max(1);
+        futures_util::stream::iter(token_ids)
+            .map(|token_id| {
+                let api = self.api.clone();
+                async move {
+                    let price = api.get_price(&token_id, "SELL").await
+                        .map(|p| f64::try_from(p).unwrap_or(0.0))
+                        .map_err(|e| e.to_string());
+                    let fetched_at = std::time::Instant::now();
+                    let book = api.get_best_price(&token_id).await;
+                    (token_id, SellQuote { price, fetched_at, book })
+                }
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await
+    }
+    
+    pub async fn check_pending_trades(&self) -> Result<()> {
+        self.promote_standard_hedges().await;
+        self.convert_expired_maker_buys().await;
+        let open_positions = self.pending_trades.lock().await.values().filter(|t| Self::is_open_position(t)).count();
+        crate::metrics::set_open_positions(open_positions);
+
+        // In simulation mode, check limit orders against current prices
+        if self.simulation_mode {
+            if let Some(tracker) = &self.simulation_tracker {
                 // Log that we're checking
                 tracker.log_to_file("🔄 SIMULATION: check_pending_trades called").await;
                 
-                // Get current prices for all tokens we're tracking
-                let mut current_prices = HashMap::new();
-                
                 // In simulation mode, use simulation tracker's pending orders as source of truth
                 // Get token IDs from pending limit orders in simulation tracker
                 let pending_order_token_ids = tracker.get_pending_order_token_ids().await;
@@ -1072,63 +3276,18 @@ impl Trader {
                     pending_order_count
                 )).await;
                 
-                // Collect all unique token IDs we need prices for (from simulation tracker only)
-                let mut token_ids_to_fetch = std::collections::HashSet::new();
-                for token_id in &pending_order_token_ids {
-                    token_ids_to_fetch.insert(token_id.clone());
-                }
-                
-                if token_ids_to_fetch.is_empty() {
+                if pending_order_token_ids.is_empty() {
                     tracker.log_to_file("⚠️  SIMULATION: No token IDs to fetch prices for").await;
                     return Ok(());
                 }
                 
                 tracker.log_to_file(&format!(
                     "🔍 SIMULATION: Fetching prices for {} unique token(s)\n",
-                    token_ids_to_fetch.len()
+                    pending_order_token_ids.iter().collect::<std::collections::HashSet<_>>().len()
                 )).await;
                 
-                // Fetch prices for all tokens
-                for token_id in token_ids_to_fetch {
-                    if !current_prices.contains_key(&token_id) {
-                        // Fetch current price for this token using orderbook
-                        match self.api.get_orderbook(&token_id).await {
-                            Ok(orderbook) => {
-                                let bid = orderbook.bids.first().map(|e| e.price);
-                                let ask = orderbook.asks.first().map(|e| e.price);
-                                let token_price = TokenPrice {
-                                    token_id: token_id.clone(),
-                                    bid,
-                                    ask,
-                                };
-                                current_prices.insert(token_id.clone(), token_price);
-                                
-                                // Log if ask is missing (for BUY orders) or bid is missing (for SELL orders)
-                                if ask.is_none() {
-                                    if let Some(tracker) = &self.simulation_tracker {
-                                        let pending_order_token_ids = tracker.get_pending_order_token_ids().await;
-                                        if pending_order_token_ids.contains(&token_id) {
-                                            tracker.log_to_file(&format!(
-                                                "⚠️  SIMULATION: No ask price available for token {} (BUY orders may not fill)",
-                                                &token_id[..16]
-                                            )).await;
-                                        }
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                // Log API errors in simulation mode
-                                if let Some(tracker) = &self.simulation_tracker {
-                                    tracker.log_to_file(&format!(
-                                        "⚠️  SIMULATION: Failed to fetch orderbook for token {}: {}",
-                                        &token_id[..16],
-                                        e
-                                    )).await;
-                                }
-                            }
-                        }
-                    }
-                }
+                // Fetch prices through the tracker's price feed (live CLOB or synthetic) and check limit orders
+                let current_prices = tracker.refresh_and_check_limit_orders().await;
                 
                 // Log how many prices we fetched
                 if current_prices.is_empty() {
@@ -1140,9 +3299,6 @@ impl Trader {
                     )).await;
                 }
                 
-                // Check limit orders against current prices
-                tracker.check_limit_orders(&current_prices).await;
-                
                 // Log pending orders summary every check (to see what's happening)
                 tracker.log_pending_orders_summary(&current_prices).await;
                 
@@ -1158,25 +3314,67 @@ impl Trader {
                     // Check if this trade has a filled position (for both limit and market orders)
                     if !trade.buy_order_confirmed && !trade.sold {
                         // Check if the position exists in simulation tracker (order was filled)
-                        if tracker.has_position(&trade.token_id).await {
+                        // A partly filled buy keeps resting until it fills in full or is cancelled
+                        let filled_position = if tracker.has_pending_order(&trade.token_id, "BUY").await {
+                            None
+                        } else {
+                            tracker.get_position(&trade.token_id).await
+                        };
+                        if let Some(position) = filled_position {
                             // Order was filled in simulation - update trade status
                             // In simulation mode, we hold positions until market closure (no selling)
                             let mut pending = self.pending_trades.lock().await;
                             if let Some(t) = pending.get_mut(key.as_str()) {
                                 t.buy_order_confirmed = true;
+                                t.units = position.units;
                                 t.confirmed_balance = Some(t.units);
                                 
-                                tracker.log_to_file(&format!(
-                                    "✅ SIMULATION: Position confirmed for {} - holding until market closure (will claim at $1.00 if winning, $0.00 if losing)",
-                                    trade.token_type.display_name()
-                                )).await;
+                                if t.no_sell {
+                                    tracker.log_to_file(&format!(
+                                        "✅ SIMULATION: Position confirmed for {} - holding until market closure (will claim at $1.00 if winning, $0.00 if losing)",
+                                        trade.token_type.display_name()
+                                    )).await;
+                                } else {
+                                    // Strategy set its own exit - rest a limit sell at the target
+                                    tracker.add_limit_order(
+                                        t.token_id.clone(),
+                                        t.token_type.clone(),
+                                        t.condition_id.clone(),
+                                        t.sell_price,
+                                        t.units,
+                                        "SELL".to_string(),
+                                        t.market_timestamp,
+                                    ).await;
+                                    tracker.set_position_sell_price(&t.token_id, t.sell_price).await;
+                                }
                             }
+                            drop(pending);
+                            self.report_fill(crate::strategy::StrategyFill {
+                                token_id: trade.token_id.clone(),
+                                token_type: trade.token_type.clone(),
+                                condition_id: trade.condition_id.clone(),
+                                period_timestamp: trade.market_timestamp,
+                                price: position.purchase_price,
+                                units: position.units,
+                            }).await;
+                        }
+                    } else if trade.buy_order_confirmed && !trade.sold && !trade.no_sell {
+                        // Limit sell at the strategy's target filled in the tracker
+                        if let Some(position) = tracker.get_position(&trade.token_id).await.filter(|p| p.sold) {
+                            let exit_price = position.sell_price_actual.unwrap_or(trade.sell_price);
+                            let pnl = (exit_price - position.purchase_price) * position.units;
+                            if let Some(t) = self.pending_trades.lock().await.get_mut(key.as_str()) {
+                                t.sold = true;
+                            }
+                            self.record_settlement(&trade.token_type, pnl).await;
+                            self.record_period_settlement(trade, position.units, position.purchase_price * position.units,
+                                exit_price * position.units, true).await;
                         }
                     }
                 }
                 
-                // Note: In simulation mode, we don't create sell orders
-                // Positions will be resolved at market closure ($1 for winning tokens, $0 for losing tokens)
+                // Note: In simulation mode, sell orders are only created for strategies with their own sell target
+                // Other positions are resolved at market closure ($1 for winning tokens, $0 for losing tokens)
             }
         }
         
@@ -1191,6 +3389,23 @@ impl Trader {
             return Ok(());
         }
         
+        // Limit buys whose order status the exchange reported are driven by it; the rest fall back to balances
+        let tracked_buys = if self.simulation_mode {
+            std::collections::HashSet::new()
+        } else {
+            self.track_limit_buy_orders(&pending_trades).await
+        };
+        
+        // Fetch balances for every distinct token the checks below need, concurrently, once per tick
+        let balance_tokens: Vec<String> = pending_trades.iter()
+            .filter(|(key, _)| !tracked_buys.contains(key.as_str()))
+            .filter(|(key, trade)| !trade.sold && (!trade.buy_order_confirmed
+                || !(trade.no_sell || key.contains("_limit") || key.contains("_individual_hedge")
+                    || key.contains("_standard_hedge") || key.contains("_dual_limit_hedge"))))
+            .map(|(_, trade)| trade.token_id.clone())
+            .collect();
+        let balances = self.fetch_balances(balance_tokens).await;
+        
         // First, check for limit buy order fills
         for (key, trade) in &pending_trades {
             // Skip if already sold or already confirmed
@@ -1198,19 +3413,14 @@ impl Trader {
                 continue;
             }
             
-            // Check if this is a limit order (key contains "_limit")
-            if !key.contains("_limit") {
+            // Check if this is a limit order (key contains "_limit") not already handled by its order status
+            if !key.contains("_limit") || tracked_buys.contains(key.as_str()) {
                 continue;
             }
             
             // Check current balance to detect fill
-            use rust_decimal::Decimal;
-            let current_balance = match self.api.check_balance_only(&trade.token_id).await {
-                Ok(balance) => {
-                    let balance_decimal = balance / Decimal::from(1_000_000u64);
-                    f64::try_from(balance_decimal).unwrap_or(0.0)
-                }
-                Err(_) => continue,
+            let Some(&current_balance) = balances.get(&trade.token_id) else {
+                continue; // Balance fetch failed this tick
             };
             
             // Get initial balance from trade
@@ -1218,81 +3428,7 @@ impl Trader {
             
             // If balance increased, limit buy order filled
             if current_balance > initial_balance + 0.000001 { // Small threshold to account for rounding
-                crate::log_println!("═══════════════════════════════════════════════════════════");
-                crate::log_println!("✅ LIMIT BUY ORDER FILLED");
-                crate::log_println!("═══════════════════════════════════════════════════════════");
-                crate::log_println!("📊 Fill Details:");
-                crate::log_println!("   Token Type: {}", trade.token_type.display_name());
-                crate::log_println!("   Token ID: {}", trade.token_id);
-                crate::log_println!("   Initial Balance: {:.6} shares", initial_balance);
-                crate::log_println!("   Current Balance: {:.6} shares", current_balance);
-                crate::log_println!("   Filled Amount: {:.6} shares", current_balance - initial_balance);
-                crate::log_println!("   Purchase Price: ${:.6}", trade.purchase_price);
-                crate::log_println!("   Target Sell Price: ${:.6}", trade.sell_price);
-                crate::log_println!("");
-                
-                // Update trade with confirmed balance
-                {
-                    let mut pending = self.pending_trades.lock().await;
-                    if let Some(t) = pending.get_mut(key.as_str()) {
-                        t.confirmed_balance = Some(current_balance);
-                        t.units = current_balance; // Update units to actual filled amount
-                        t.buy_order_confirmed = true;
-                    }
-                }
-                
-                // For no-sell mode, log confirmation only and skip sell placement
-                if trade.no_sell {
-                    crate::log_println!("✅ No-sell mode: confirmation logged, no sell orders will be placed.");
-                    let mut pending = self.pending_trades.lock().await;
-                    if let Some(t) = pending.get_mut(key.as_str()) {
-                        t.limit_sell_orders_placed = true;
-                    }
-                    continue;
-                }
-
-                // Place limit sell order immediately
-                if !self.simulation_mode {
-                    crate::log_println!("📤 Placing limit sell order at ${:.6}...", trade.sell_price);
-                    
-                    use crate::models::OrderRequest;
-                    let sell_order = OrderRequest {
-                        token_id: trade.token_id.clone(),
-                        side: "SELL".to_string(),
-                        size: current_balance.to_string(),
-                        price: trade.sell_price.to_string(),
-                        order_type: "LIMIT".to_string(),
-                    };
-                    
-                    match self.api.place_order(&sell_order).await {
-                        Ok(response) => {
-                            crate::log_println!("   ✅ LIMIT SELL ORDER PLACED");
-                            crate::log_println!("      Order ID: {:?}", response.order_id);
-                            crate::log_println!("      Limit Price: ${:.6}", trade.sell_price);
-                            crate::log_println!("      Size: {:.6} shares", current_balance);
-                            
-                            let order_id_str = response.order_id.as_ref()
-                                .map(|id| format!("{:?}", id))
-                                .unwrap_or_else(|| "N/A".to_string());
-                            let sell_event = format!(
-                                "LIMIT SELL ORDER | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {}",
-                                trade.token_type.display_name(),
-                                trade.market_timestamp,
-                                &trade.token_id[..16],
-                                trade.sell_price,
-                                current_balance,
-                                order_id_str
-                            );
-                            crate::log_trading_event(&sell_event);
-                        }
-                        Err(e) => {
-                            eprintln!("   ❌ FAILED TO PLACE LIMIT SELL ORDER: {}", e);
-                            warn!("Failed to place limit sell order after buy fill: {}", e);
-                        }
-                    }
-                } else {
-                    crate::log_println!("🎮 SIMULATION: Limit sell order would be placed at ${:.6}", trade.sell_price);
-                }
+                self.handle_limit_buy_fill(key, trade, initial_balance, current_balance).await;
             }
         }
         
@@ -1322,13 +3458,8 @@ impl Trader {
             }
             
             // Get current balance
-            use rust_decimal::Decimal;
-            let current_balance = match self.api.check_balance_only(&trade.token_id).await {
-                Ok(balance) => {
-                    let balance_decimal = balance / Decimal::from(1_000_000u64);
-                    f64::try_from(balance_decimal).unwrap_or(0.0)
-                }
-                Err(_) => continue,
+            let Some(&current_balance) = balances.get(&trade.token_id) else {
+                continue; // Balance fetch failed this tick
             };
             
             // Only proceed if we have tokens
@@ -1338,7 +3469,7 @@ impl Trader {
             
             // Place limit sell order for bought token at sell_price (no hedge limit buy)
             if !self.simulation_mode {
-                let sell_price = self.config.sell_price;
+                let sell_price = self.config().sell_price;
                 
                 crate::log_println!("═══════════════════════════════════════════════════════════");
                 crate::log_println!("📤 PLACING ORDER AFTER MARKET BUY");
@@ -1357,12 +3488,12 @@ impl Trader {
                 let sell_order_profit = OrderRequest {
                     token_id: trade.token_id.clone(),
                     side: "SELL".to_string(),
-                    size: format!("{:.2}", current_balance), // Format to 2 decimal places
+                    size: format_size(current_balance, self.size_decimals(&trade.token_type), self.config().size_rounding),
                     price: format!("{:.2}", sell_price), // Format to 2 decimal places
                     order_type: "LIMIT".to_string(),
                 };
                 
-                match self.api.place_order(&sell_order_profit).await {
+                match self.place_limit_sell(&sell_order_profit).await {
                     Ok(response) => {
                         crate::log_println!("   ✅ LIMIT SELL ORDER #1 PLACED (Profit Target)");
                         crate::log_println!("      Token: {}", trade.token_type.display_name());
@@ -1374,13 +3505,14 @@ impl Trader {
                             .map(|id| format!("{:?}", id))
                             .unwrap_or_else(|| "N/A".to_string());
                         let sell_event = format!(
-                            "LIMIT SELL ORDER (PROFIT) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {}",
+                            "LIMIT SELL ORDER (PROFIT) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Trade ID: {}",
                             trade.token_type.display_name(),
                             trade.market_timestamp,
                             &trade.token_id[..16],
                             sell_price,
                             current_balance,
-                            order_id_str
+                            order_id_str,
+                            trade.trade_id
                         );
                         crate::log_trading_event(&sell_event);
                     }
@@ -1399,21 +3531,23 @@ impl Trader {
                 }
             } else {
                 crate::log_println!("🎮 SIMULATION: One order would be placed for market buy:");
-                crate::log_println!("   Limit SELL for {} at ${:.6} (profit target) - {:.6} shares", trade.token_type.display_name(), self.config.sell_price, current_balance);
+                crate::log_println!("   Limit SELL for {} at ${:.6} (profit target) - {:.6} shares", trade.token_type.display_name(), self.config().sell_price, current_balance);
             }
         }
         
         // Continue with regular sell checks for filled orders
         for (key, mut trade) in pending_trades {
+            // Fills already handled above (the snapshot predates them) must not place a second sell
+            let already_handled = self.pending_trades.lock().await.get(key.as_str())
+                .map(|t| t.sold || t.buy_order_confirmed)
+                .unwrap_or(true);
+            if already_handled {
+                continue;
+            }
             
             // Check current balance to detect fill
-            use rust_decimal::Decimal;
-            let current_balance = match self.api.check_balance_only(&trade.token_id).await {
-                Ok(balance) => {
-                    let balance_decimal = balance / Decimal::from(1_000_000u64);
-                    f64::try_from(balance_decimal).unwrap_or(0.0)
-                }
-                Err(_) => continue,
+            let Some(&current_balance) = balances.get(&trade.token_id) else {
+                continue; // Balance fetch failed this tick
             };
             
             // Get initial balance from trade
@@ -1421,6 +3555,17 @@ impl Trader {
             
             // If balance increased, limit buy order filled
             if current_balance > initial_balance + 0.000001 { // Small threshold to account for rounding
+                crate::notifier::notify(crate::notifier::NotifyEvent::BuyExecuted, format!(
+                    "🟢 LIMIT BUY FILLED {} | {:.2} shares @ ${:.2} | Period {}",
+                    trade.token_type.display_name(), current_balance - initial_balance, trade.purchase_price, trade.market_timestamp));
+                self.report_fill(crate::strategy::StrategyFill {
+                    token_id: trade.token_id.clone(),
+                    token_type: trade.token_type.clone(),
+                    condition_id: trade.condition_id.clone(),
+                    period_timestamp: trade.market_timestamp,
+                    price: trade.purchase_price,
+                    units: current_balance - initial_balance,
+                }).await;
                 crate::log_println!("═══════════════════════════════════════════════════════════");
                 crate::log_println!("✅ LIMIT BUY ORDER FILLED");
                 crate::log_println!("═══════════════════════════════════════════════════════════");
@@ -1432,7 +3577,7 @@ impl Trader {
                 crate::log_println!("   Filled Amount: {:.6} shares", current_balance - initial_balance);
                 crate::log_println!("   Purchase Price: ${:.6}", trade.purchase_price);
                 crate::log_println!("   Will place limit sell order:");
-                crate::log_println!("      - Sell at ${:.6} (profit target)", self.config.sell_price);
+                crate::log_println!("      - Sell at ${:.6} (profit target)", self.config().sell_price);
                 crate::log_println!("      - Stop-loss disabled for limit order version");
                 crate::log_println!("");
                 
@@ -1446,6 +3591,11 @@ impl Trader {
                     }
                 }
                 
+                let filled = PendingTrade { confirmed_balance: Some(current_balance), units: current_balance, buy_order_confirmed: true, ..trade.clone() };
+                if self.verify_purchased_token(&key, &filled).await {
+                    continue;
+                }
+                
                 // For no-sell mode (dual limit bot), log confirmation only and skip sell placement
                 if trade.no_sell {
                     crate::log_println!("✅ No-sell mode: confirmation logged, no sell orders will be placed.");
@@ -1458,7 +3608,7 @@ impl Trader {
                 
                 // Place ONE limit sell order at profit target (stop-loss disabled for limit order version)
                 if !self.simulation_mode {
-                    let sell_price = self.config.sell_price;
+                    let sell_price = self.config().sell_price;
                     
                     crate::log_println!("📤 Placing limit sell order:");
                     crate::log_println!("   Profit target: ${:.6}", sell_price);
@@ -1470,12 +3620,12 @@ impl Trader {
                     let sell_order_profit = OrderRequest {
                         token_id: trade.token_id.clone(),
                         side: "SELL".to_string(),
-                        size: format!("{:.2}", current_balance), // Format to 2 decimal places
+                        size: format_size(current_balance, self.size_decimals(&trade.token_type), self.config().size_rounding),
                         price: format!("{:.2}", sell_price), // Format to 2 decimal places
                         order_type: "LIMIT".to_string(),
                     };
                     
-                    match self.api.place_order(&sell_order_profit).await {
+                    match self.place_limit_sell(&sell_order_profit).await {
                         Ok(response) => {
                             crate::log_println!("   ✅ LIMIT SELL ORDER PLACED (Profit Target)");
                             crate::log_println!("      Order ID: {:?}", response.order_id);
@@ -1486,13 +3636,14 @@ impl Trader {
                                 .map(|id| format!("{:?}", id))
                                 .unwrap_or_else(|| "N/A".to_string());
                             let sell_event = format!(
-                                "LIMIT SELL ORDER (PROFIT) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {}",
+                                "LIMIT SELL ORDER (PROFIT) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Trade ID: {}",
                                 trade.token_type.display_name(),
                                 trade.market_timestamp,
                                 &trade.token_id[..16],
                                 sell_price,
                                 current_balance,
-                                order_id_str
+                                order_id_str,
+                                trade.trade_id
                             );
                             crate::log_trading_event(&sell_event);
                         }
@@ -1511,7 +3662,7 @@ impl Trader {
                     }
                 } else {
                     crate::log_println!("🎮 SIMULATION: Limit sell order would be placed:");
-                    crate::log_println!("   - Sell at ${:.6} (profit target)", self.config.sell_price);
+                    crate::log_println!("   - Sell at ${:.6} (profit target)", self.config().sell_price);
                     crate::log_println!("   (Stop-loss disabled for limit order version)");
                 }
             }
@@ -1546,13 +3697,8 @@ impl Trader {
             }
             
             // Check current balance - if it dropped to 0, a sell order filled
-            use rust_decimal::Decimal;
-            let current_balance = match self.api.check_balance_only(&trade.token_id).await {
-                Ok(balance) => {
-                    let balance_decimal = balance / Decimal::from(1_000_000u64);
-                    f64::try_from(balance_decimal).unwrap_or(0.0)
-                }
-                Err(_) => continue,
+            let Some(&current_balance) = balances.get(&trade.token_id) else {
+                continue; // Balance fetch failed this tick
             };
             
             // Get last known balance from trade
@@ -1594,7 +3740,7 @@ impl Trader {
                 
                 // Log the sell fill event
                 let sell_event = format!(
-                    "{} FILLED | Market: {} | Period: {} | Token: {} | Purchase Price: ${:.6} | Sell Price: ${:.6} | Shares Sold: {:.6} | Revenue: ${:.6} | Status: FILLED",
+                    "{} FILLED | Market: {} | Period: {} | Token: {} | Purchase Price: ${:.6} | Sell Price: ${:.6} | Shares Sold: {:.6} | Revenue: ${:.6} | Status: FILLED | Trade ID: {}",
                     trade_description,
                     trade.token_type.display_name(),
                     trade.market_timestamp,
@@ -1602,7 +3748,8 @@ impl Trader {
                     trade.purchase_price,
                     trade.sell_price,
                     last_balance,
-                    last_balance * trade.sell_price
+                    last_balance * trade.sell_price,
+                    trade.trade_id
                 );
                 crate::log_trading_event(&sell_event);
             }
@@ -1616,18 +3763,28 @@ impl Trader {
                 .collect()
         };
         
+        // Prices and orderbooks for all open positions, fetched concurrently before any decision is made
+        let quotes = self.fetch_sell_quotes(pending_trades_2.iter()
+            .filter(|(_, trade)| !trade.sold)
+            .map(|(_, trade)| trade.token_id.clone())
+            .collect()).await;
+        self.repeg_limit_sells(&pending_trades_2).await;
+        
         for (key, mut trade) in pending_trades_2 {
             // Skip if already sold
             if trade.sold {
                 continue;
             }
+            let Some(quote) = quotes.get(&trade.token_id) else {
+                continue;
+            };
             
             // Get current ASK price (what we receive when selling)
             // Also check if there are actual buyers in the orderbook before attempting to sell
-            let price_result = self.api.get_price(&trade.token_id, "SELL").await;
-            let current_ask_price = match price_result {
+            let price_fetched_at = quote.fetched_at;
+            let mut current_ask_price = match &quote.price {
                 Ok(p) => {
-                    let price_f64 = f64::try_from(p).unwrap_or(0.0);
+                    let price_f64 = *p;
                     // Log price check every 10th time to avoid spam (or use debug level)
                     debug!("Checking {} token {} ASK price: ${:.6} (target: ${:.6}, purchased at: ${:.6})", 
                            trade.token_type.display_name(), &trade.token_id[..16], price_f64, trade.sell_price, trade.purchase_price);
@@ -1638,19 +3795,27 @@ impl Trader {
                     continue; // Skip if can't get price
                 }
             };
+            if trade.buy_order_confirmed {
+                self.note_period_excursion(&trade, current_ask_price).await;
+            }
             
             // Check orderbook to verify there are actual buyers before attempting to sell
             // This prevents "No opposing orders" errors when there's no liquidity
-            let has_liquidity = match self.api.get_best_price(&trade.token_id).await {
+            let has_liquidity = match &quote.book {
                 Ok(Some(token_price)) => {
                     // Check if there are actual bids (buyers) in the orderbook
-                    token_price.bid.is_some()
+                    token_price.bid.is_some_and(|bid| bid > rust_decimal::Decimal::ZERO)
                 },
                 Ok(None) => {
                     debug!("No orderbook data for {} token {} - skipping sell attempt", 
                            trade.token_type.display_name(), &trade.token_id[..16]);
                     false
                 },
+                Err(e) if e.downcast_ref::<CrossedBook>().is_some() => {
+                    warn!("Skipping sell checks for {} token {}: {}",
+                          trade.token_type.display_name(), &trade.token_id[..16], e);
+                    false // Prices from a crossed book are not trustworthy
+                },
                 Err(e) => {
                     debug!("Failed to check orderbook for {} token {}: {} - will try anyway", 
                            trade.token_type.display_name(), &trade.token_id[..16], e);
@@ -1659,11 +3824,31 @@ impl Trader {
             };
             
             if !has_liquidity {
+                // A missing or zero bid goes to sell.no_bid_policy; a crossed book never does
+                if quote.book.is_ok() && self.try_no_bid_exit(&key, &trade, current_ask_price).await {
+                    continue;
+                }
                 debug!("Skipping sell for {} token {} - no buyers in orderbook (no liquidity)", 
                        trade.token_type.display_name(), &trade.token_id[..16]);
                 continue; // Skip this trade - no liquidity to sell into
             }
             
+            // The liquidity check may have taken a while - re-fetch the price rather than act on a stale one
+            if let Some(max_age_ms) = self.config().max_price_age_ms {
+                let age = price_fetched_at.elapsed();
+                if age > std::time::Duration::from_millis(max_age_ms) {
+                    crate::log_println!("⏳ STALE PRICE: {} SELL price ${:.6} is {}ms old > {}ms (max_price_age_ms) - re-fetching",
+                        trade.token_type.display_name(), current_ask_price, age.as_millis(), max_age_ms);
+                    match self.api.get_price(&trade.token_id, "SELL").await {
+                        Ok(price) => current_ask_price = f64::try_from(price).unwrap_or(0.0),
+                        Err(e) => {
+                            debug!("Failed to re-fetch SELL price for {} token {}: {}", trade.token_type.display_name(), &trade.token_id[..16], e);
+                            continue; // Don't act on the stale price
+                        }
+                    }
+                }
+            }
+            
             // Skip if already marked for claim (will be handled at market closure)
             if trade.claim_on_closure {
                 continue;
@@ -1676,10 +3861,30 @@ impl Trader {
                 continue;
             }
             
+            // asymmetric_exit: sell the likely winner now, hold the likely loser to resolution
+            if self.try_asymmetric_exit(&key, &trade, current_ask_price).await {
+                continue;
+            }
+            
+            // Position held past max_hold_secs - exit at the current bid regardless of the target
+            if self.try_max_hold_exit(&key, &trade, current_ask_price).await {
+                continue;
+            }
+            
+            // Sell part of the position as the price rallies through scale_out_schedule levels
+            if self.try_scale_out(&key, &trade, current_ask_price).await {
+                continue;
+            }
+            
             // OPPOSITE TOKEN STOP-LOSS: Check if opposite token price drops below (1 - stop_loss_price - 0.1)
-            // This protects against losses if the opposite token price crashes
+            // This protects against losses if the opposite token price crashes. Keyed on stop_loss_price (or a
+            // fixed `stop_loss`), so it stays on when the own-token stop is trailing or time-based
             if key.contains("_opposite_") {
-                if let Some(stop_loss_price) = self.config.stop_loss_price {
+                let opposite_stop_base = self.config().stop_loss_price.or(match self.config().stop_loss() {
+                    Some(StopLoss::Fixed { price }) => Some(price),
+                    _ => None,
+                });
+                if let Some(stop_loss_price) = opposite_stop_base {
                     let opposite_stop_loss_price = (1.0 - stop_loss_price) - 0.1; // e.g., (1.0 - 0.80) - 0.1 = 0.10
                     
                     // Check if price dropped below opposite token stop-loss threshold
@@ -1709,6 +3914,9 @@ impl Trader {
                         let units_to_sell = actual_balance;
                         
                         crate::log_println!("═══════════════════════════════════════════════════════════");
+                        crate::notifier::notify(crate::notifier::NotifyEvent::StopLoss, format!(
+                            "🛑 OPPOSITE TOKEN STOP-LOSS {} | Opposite ask ${:.2} <= ${:.2} | Bought @ ${:.2} | Period {}",
+                            trade.token_type.display_name(), current_ask_price, opposite_stop_loss_price, trade.purchase_price, trade.market_timestamp));
                         crate::log_println!("🛑 OPPOSITE TOKEN STOP-LOSS TRIGGERED");
                         crate::log_println!("═══════════════════════════════════════════════════════════");
                         crate::log_println!("⚠️  Opposite token price dropped below stop-loss threshold!");
@@ -1725,7 +3933,7 @@ impl Trader {
                         crate::log_println!("🔄 Executing stop-loss sell for opposite token...");
                         
                         // Execute stop-loss sell for opposite token
-                        match self.execute_sell(&key, &mut trade, units_to_sell, current_ask_price, Some("FAK"), true).await {
+                        match self.execute_sell(&key, &mut trade, units_to_sell, current_ask_price, None, true).await {
                             Ok(_) => {
                                 crate::log_println!("   ✅ OPPOSITE TOKEN STOP-LOSS SELL EXECUTED");
                                 
@@ -1740,13 +3948,14 @@ impl Trader {
                                 
                                 // Log the sell event
                                 let sell_event = format!(
-                                    "OPPOSITE TOKEN STOP-LOSS SELL | Market: {} | Period: {} | Token: {} | Purchase Price: ${:.6} | Sell Price: ${:.6} | Shares Sold: {:.6} | Status: FILLED",
+                                    "OPPOSITE TOKEN STOP-LOSS SELL | Market: {} | Period: {} | Token: {} | Purchase Price: ${:.6} | Sell Price: ${:.6} | Shares Sold: {:.6} | Status: FILLED | Trade ID: {}",
                                     trade.token_type.display_name(),
                                     trade.market_timestamp,
                                     &trade.token_id[..16],
                                     trade.purchase_price,
                                     current_ask_price,
-                                    units_to_sell
+                                    units_to_sell,
+                                    trade.trade_id
                                 );
                                 crate::log_trading_event(&sell_event);
                                 
@@ -1767,7 +3976,7 @@ impl Trader {
             // Only apply to trades that have limit_sell_orders_placed (new strategy) and are NOT hedge trades
             // Skip all hedge trades (individual, standard, and multi-market) - they use limit sell orders or hold until closure
             if trade.limit_sell_orders_placed && !key.contains("_limit") && !key.contains("_individual_hedge") && !key.contains("_standard_hedge") && !key.contains("_dual_limit_hedge") {
-                if let Some(stop_loss_price) = self.config.stop_loss_price {
+                if let Some(stop_loss_price) = self.stop_loss_for(&trade, current_ask_price) {
                     // Only trigger stop-loss if price is at or below threshold
                     if current_ask_price <= stop_loss_price {
                         // CRITICAL: Re-check actual balance before selling
@@ -1795,6 +4004,9 @@ impl Trader {
                         let units_to_sell = actual_balance;
                         
                         crate::log_println!("═══════════════════════════════════════════════════════════");
+                        crate::notifier::notify(crate::notifier::NotifyEvent::StopLoss, format!(
+                            "🛑 STOP-LOSS {} | Ask ${:.2} (stop ${:.2}) | Bought @ ${:.2} | Period {}",
+                            trade.token_type.display_name(), current_ask_price, stop_loss_price, trade.purchase_price, trade.market_timestamp));
                         crate::log_println!("🛑 STOP-LOSS TRIGGERED - NEW STRATEGY");
                         crate::log_println!("═══════════════════════════════════════════════════════════");
                         crate::log_println!("⚠️  Price dropped to stop-loss threshold!");
@@ -1811,7 +4023,7 @@ impl Trader {
                         crate::log_println!("🔄 Executing stop-loss sell and placing opposite token order...");
                         
                         // Execute stop-loss sell
-                        match self.execute_sell(&key, &mut trade, units_to_sell, current_ask_price, Some("FAK"), true).await {
+                        match self.execute_sell(&key, &mut trade, units_to_sell, current_ask_price, None, true).await {
                             Ok(_) => {
                                 crate::log_println!("   ✅ STOP-LOSS SELL EXECUTED");
                                 
@@ -1844,64 +4056,69 @@ impl Trader {
                                             let opposite_sell_order = OrderRequest {
                                                 token_id: opposite_token_id.clone(),
                                                 side: "SELL".to_string(),
-                                                size: format!("{:.2}", opposite_balance),
+                                                size: format_size(opposite_balance, self.size_decimals(&trade.token_type), self.config().size_rounding),
                                                 price: format!("{:.2}", opposite_sell_price),
                                                 order_type: "LIMIT".to_string(),
                                             };
                                             
-                                            match self.api.place_order(&opposite_sell_order).await {
-                                                Ok(response) => {
-                                                    crate::log_println!("   ✅ LIMIT SELL ORDER PLACED FOR OPPOSITE TOKEN");
-                                                    crate::log_println!("      Order ID: {:?}", response.order_id);
-                                                    crate::log_println!("      Limit Price: ${:.6}", opposite_sell_price);
+                                            if !self.would_self_cross(&opposite_sell_order).await {
+                                                match self.place_limit_sell(&opposite_sell_order).await {
+                                                    Ok(response) => {
+                                                        crate::log_println!("   ✅ LIMIT SELL ORDER PLACED FOR OPPOSITE TOKEN");
+                                                        crate::log_println!("      Order ID: {:?}", response.order_id);
+                                                        crate::log_println!("      Limit Price: ${:.6}", opposite_sell_price);
                                                     
-                                                    let order_id_str = response.order_id.as_ref()
-                                                        .map(|id| format!("{:?}", id))
-                                                        .unwrap_or_else(|| "N/A".to_string());
-                                                    let sell_event = format!(
-                                                        "LIMIT SELL ORDER (OPPOSITE AFTER STOP-LOSS) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {}",
-                                                        opposite_token_type.display_name(),
-                                                        trade.market_timestamp,
-                                                        &opposite_token_id[..16],
-                                                        opposite_sell_price,
-                                                        opposite_balance,
-                                                        order_id_str
-                                                    );
-                                                    crate::log_trading_event(&sell_event);
+                                                        let order_id_str = response.order_id.as_ref()
+                                                            .map(|id| format!("{:?}", id))
+                                                            .unwrap_or_else(|| "N/A".to_string());
+                                                        let sell_event = format!(
+                                                            "LIMIT SELL ORDER (OPPOSITE AFTER STOP-LOSS) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Trade ID: {}",
+                                                            opposite_token_type.display_name(),
+                                                            trade.market_timestamp,
+                                                            &opposite_token_id[..16],
+                                                            opposite_sell_price,
+                                                            opposite_balance,
+                                                            order_id_str,
+                                                            hedge_trade_id(&trade.trade_id)
+                                                        );
+                                                        crate::log_trading_event(&sell_event);
                                                     
-                                                    // Create a PendingTrade entry to track this opposite token limit sell order
-                                                    let opposite_trade = PendingTrade {
-                                                        token_id: opposite_token_id.clone(),
-                                                        condition_id: trade.condition_id.clone(),
-                                                        token_type: opposite_token_type.clone(),
-                                                        order_id: response.order_id.clone(),
-                                                        investment_amount: opposite_balance * opposite_buy_price,
-                                                        units: opposite_balance,
-                                                        purchase_price: opposite_buy_price,
-                                                        sell_price: opposite_sell_price,
-                                                        timestamp: std::time::Instant::now(),
-                                                        market_timestamp: trade.market_timestamp,
-                                                        sold: false,
-                                                        confirmed_balance: Some(opposite_balance),
-                                                        buy_order_confirmed: true,
-                                                        limit_sell_orders_placed: true,
-                                                        no_sell: false,
-                                                        claim_on_closure: false,
-                                                        sell_attempts: 0,
-                                                        redemption_attempts: 0,
-                                                        redemption_abandoned: false,
-                                                    };
+                                                        // Create a PendingTrade entry to track this opposite token limit sell order
+                                                        let opposite_trade = PendingTrade {
+                                                            trade_id: hedge_trade_id(&trade.trade_id),
+                                                            token_id: opposite_token_id.clone(),
+                                                            condition_id: trade.condition_id.clone(),
+                                                            token_type: opposite_token_type.clone(),
+                                                            order_id: response.order_id.clone(),
+                                                            investment_amount: opposite_balance * opposite_buy_price,
+                                                            units: opposite_balance,
+                                                            purchase_price: opposite_buy_price,
+                                                            sell_price: opposite_sell_price,
+                                                            timestamp: std::time::Instant::now(),
+                                                            market_timestamp: trade.market_timestamp,
+                                                            sold: false,
+                                                            confirmed_balance: Some(opposite_balance),
+                                                            buy_order_confirmed: true,
+                                                            limit_sell_orders_placed: true,
+                                                            no_sell: false,
+                                                            claim_on_closure: false,
+                                                            sell_attempts: 0,
+                                                            redemption_attempts: 0,
+                                                            redemption_abandoned: false,
+                                                            scale_out_levels_done: 0,
+                                                        };
                                                     
-                                                    let opposite_trade_key = format!("{}_opposite_{}", trade.market_timestamp, opposite_token_id);
-                                                    let mut pending = self.pending_trades.lock().await;
-                                                    pending.insert(opposite_trade_key, opposite_trade);
-                                                    drop(pending);
+                                                        let opposite_trade_key = format!("{}_opposite_{}", trade.market_timestamp, opposite_token_id);
+                                                        let mut pending = self.pending_trades.lock().await;
+                                                        pending.insert(opposite_trade_key, opposite_trade);
+                                                        drop(pending);
                                                     
-                                                    crate::log_println!("   📊 Tracking opposite token limit sell order (will monitor for fill)");
-                                                }
-                                                Err(e) => {
-                                                    eprintln!("   ❌ FAILED TO PLACE LIMIT SELL FOR OPPOSITE TOKEN: {}", e);
-                                                    warn!("Failed to place limit sell for opposite token: {}", e);
+                                                        crate::log_println!("   📊 Tracking opposite token limit sell order (will monitor for fill)");
+                                                    }
+                                                    Err(e) => {
+                                                        eprintln!("   ❌ FAILED TO PLACE LIMIT SELL FOR OPPOSITE TOKEN: {}", e);
+                                                        warn!("Failed to place limit sell for opposite token: {}", e);
+                                                    }
                                                 }
                                             }
                                         } else {
@@ -1918,65 +4135,70 @@ impl Trader {
                                             let opposite_buy_order = OrderRequest {
                                                 token_id: opposite_token_id.clone(),
                                                 side: "BUY".to_string(),
-                                                size: format!("{:.2}", opposite_buy_size),
+                                                size: format_size(opposite_buy_size, self.size_decimals(&trade.token_type), self.config().size_rounding),
                                                 price: format!("{:.2}", opposite_buy_price),
                                                 order_type: "LIMIT".to_string(),
                                             };
                                             
-                                            match self.api.place_order(&opposite_buy_order).await {
-                                                Ok(response) => {
-                                                    crate::log_println!("   ✅ LIMIT BUY ORDER PLACED FOR OPPOSITE TOKEN");
-                                                    crate::log_println!("      Order ID: {:?}", response.order_id);
-                                                    crate::log_println!("      Limit Price: ${:.6}", opposite_buy_price);
-                                                    crate::log_println!("      Size: {:.6} shares", opposite_buy_size);
+                                            if !self.would_self_cross(&opposite_buy_order).await {
+                                                match self.api.place_order(&opposite_buy_order).await {
+                                                    Ok(response) => {
+                                                        crate::log_println!("   ✅ LIMIT BUY ORDER PLACED FOR OPPOSITE TOKEN");
+                                                        crate::log_println!("      Order ID: {:?}", response.order_id);
+                                                        crate::log_println!("      Limit Price: ${:.6}", opposite_buy_price);
+                                                        crate::log_println!("      Size: {:.6} shares", opposite_buy_size);
                                                     
-                                                    let order_id_str = response.order_id.as_ref()
-                                                        .map(|id| format!("{:?}", id))
-                                                        .unwrap_or_else(|| "N/A".to_string());
-                                                    let buy_event = format!(
-                                                        "LIMIT BUY ORDER (OPPOSITE AFTER STOP-LOSS) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {}",
-                                                        opposite_token_type.display_name(),
-                                                        trade.market_timestamp,
-                                                        &opposite_token_id[..16],
-                                                        opposite_buy_price,
-                                                        opposite_buy_size,
-                                                        order_id_str
-                                                    );
-                                                    crate::log_trading_event(&buy_event);
+                                                        let order_id_str = response.order_id.as_ref()
+                                                            .map(|id| format!("{:?}", id))
+                                                            .unwrap_or_else(|| "N/A".to_string());
+                                                        let buy_event = format!(
+                                                            "LIMIT BUY ORDER (OPPOSITE AFTER STOP-LOSS) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Trade ID: {}",
+                                                            opposite_token_type.display_name(),
+                                                            trade.market_timestamp,
+                                                            &opposite_token_id[..16],
+                                                            opposite_buy_price,
+                                                            opposite_buy_size,
+                                                            order_id_str,
+                                                            hedge_trade_id(&trade.trade_id)
+                                                        );
+                                                        crate::log_trading_event(&buy_event);
                                                     
-                                                    // Create a PendingTrade entry to track this opposite token limit buy order
-                                                    let opposite_trade = PendingTrade {
-                                                        token_id: opposite_token_id.clone(),
-                                                        condition_id: trade.condition_id.clone(),
-                                                        token_type: opposite_token_type.clone(),
-                                                        order_id: response.order_id.clone(),
-                                                        investment_amount: opposite_buy_size * opposite_buy_price,
-                                                        units: opposite_buy_size,
-                                                        purchase_price: opposite_buy_price,
-                                                        sell_price: (1.0 - stop_loss_price) + 0.1, // Will sell at 0.30 when filled
-                                                        timestamp: std::time::Instant::now(),
-                                                        market_timestamp: trade.market_timestamp,
-                                                        sold: false,
-                                                        confirmed_balance: Some(0.0), // Not filled yet
-                                                        buy_order_confirmed: false, // Limit buy not confirmed yet
-                                                        limit_sell_orders_placed: false, // Will place sell order after buy fills
-                                                        no_sell: false,
-                                                        claim_on_closure: false,
-                                                        sell_attempts: 0,
-                                                        redemption_attempts: 0,
-                                                        redemption_abandoned: false,
-                                                    };
+                                                        // Create a PendingTrade entry to track this opposite token limit buy order
+                                                        let opposite_trade = PendingTrade {
+                                                            trade_id: hedge_trade_id(&trade.trade_id),
+                                                            token_id: opposite_token_id.clone(),
+                                                            condition_id: trade.condition_id.clone(),
+                                                            token_type: opposite_token_type.clone(),
+                                                            order_id: response.order_id.clone(),
+                                                            investment_amount: opposite_buy_size * opposite_buy_price,
+                                                            units: opposite_buy_size,
+                                                            purchase_price: opposite_buy_price,
+                                                            sell_price: (1.0 - stop_loss_price) + 0.1, // Will sell at 0.30 when filled
+                                                            timestamp: std::time::Instant::now(),
+                                                            market_timestamp: trade.market_timestamp,
+                                                            sold: false,
+                                                            confirmed_balance: Some(0.0), // Not filled yet
+                                                            buy_order_confirmed: false, // Limit buy not confirmed yet
+                                                            limit_sell_orders_placed: false, // Will place sell order after buy fills
+                                                            no_sell: false,
+                                                            claim_on_closure: false,
+                                                            sell_attempts: 0,
+                                                            redemption_attempts: 0,
+                                                            redemption_abandoned: false,
+                                                            scale_out_levels_done: 0,
+                                                        };
                                                     
-                                                    let opposite_trade_key = format!("{}_opposite_limit_{}", trade.market_timestamp, opposite_token_id);
-                                                    let mut pending = self.pending_trades.lock().await;
-                                                    pending.insert(opposite_trade_key, opposite_trade);
-                                                    drop(pending);
+                                                        let opposite_trade_key = format!("{}_opposite_limit_{}", trade.market_timestamp, opposite_token_id);
+                                                        let mut pending = self.pending_trades.lock().await;
+                                                        pending.insert(opposite_trade_key, opposite_trade);
+                                                        drop(pending);
                                                     
-                                                    crate::log_println!("   📊 Tracking opposite token limit buy order (will monitor for fill and place sell order)");
-                                                }
-                                                Err(e) => {
-                                                    eprintln!("   ❌ FAILED TO PLACE LIMIT BUY ORDER FOR OPPOSITE TOKEN: {}", e);
-                                                    warn!("Failed to place limit buy order for opposite token: {}", e);
+                                                        crate::log_println!("   📊 Tracking opposite token limit buy order (will monitor for fill and place sell order)");
+                                                    }
+                                                    Err(e) => {
+                                                        eprintln!("   ❌ FAILED TO PLACE LIMIT BUY ORDER FOR OPPOSITE TOKEN: {}", e);
+                                                        warn!("Failed to place limit buy order for opposite token: {}", e);
+                                                    }
                                                 }
                                             }
                                         }
@@ -2021,7 +4243,7 @@ impl Trader {
             // Check for stop-loss condition first (before checking for profit sell)
             // Stop-loss: sell if price drops below stop_loss_price to limit losses
             // Note: If stop-loss sell fails, keep retrying until sold OR price recovers above stop_loss_price
-            if let Some(stop_loss_price) = self.config.stop_loss_price {
+            if let Some(stop_loss_price) = self.stop_loss_for(&trade, current_ask_price) {
                 // Only trigger stop-loss if price is below threshold
                 // If price recovers above stop_loss_price, cancel stop-loss attempt
                 if current_ask_price < stop_loss_price {
@@ -2053,6 +4275,9 @@ impl Trader {
                     let units_to_sell = actual_balance;
                     
                     crate::log_println!("═══════════════════════════════════════════════════════════");
+                    crate::notifier::notify(crate::notifier::NotifyEvent::StopLoss, format!(
+                        "🛑 STOP-LOSS {} | Ask ${:.2} (stop ${:.2}) | Bought @ ${:.2} | Period {}",
+                        trade.token_type.display_name(), current_ask_price, stop_loss_price, trade.purchase_price, trade.market_timestamp));
                     crate::log_println!("🛑 STOP-LOSS TRIGGERED - NEW STRATEGY");
                     crate::log_println!("═══════════════════════════════════════════════════════════");
                     crate::log_println!("⚠️  Price dropped to stop-loss threshold!");
@@ -2069,7 +4294,7 @@ impl Trader {
                     crate::log_println!("🔄 Executing stop-loss sell and placing opposite token order...");
                     
                     // Execute stop-loss sell
-                    match self.execute_sell(&key, &mut trade, units_to_sell, current_ask_price, Some("FAK"), true).await {
+                    match self.execute_sell(&key, &mut trade, units_to_sell, current_ask_price, None, true).await {
                         Ok(_) => {
                             crate::log_println!("   ✅ STOP-LOSS SELL EXECUTED");
                             
@@ -2102,64 +4327,69 @@ impl Trader {
                                         let opposite_sell_order = OrderRequest {
                                             token_id: opposite_token_id.clone(),
                                             side: "SELL".to_string(),
-                                            size: format!("{:.2}", opposite_balance),
+                                            size: format_size(opposite_balance, self.size_decimals(&trade.token_type), self.config().size_rounding),
                                             price: format!("{:.2}", opposite_sell_price),
                                             order_type: "LIMIT".to_string(),
                                         };
                                         
-                                        match self.api.place_order(&opposite_sell_order).await {
-                                            Ok(response) => {
-                                                crate::log_println!("   ✅ LIMIT SELL ORDER PLACED FOR OPPOSITE TOKEN");
-                                                crate::log_println!("      Order ID: {:?}", response.order_id);
-                                                crate::log_println!("      Limit Price: ${:.6}", opposite_sell_price);
+                                        if !self.would_self_cross(&opposite_sell_order).await {
+                                            match self.place_limit_sell(&opposite_sell_order).await {
+                                                Ok(response) => {
+                                                    crate::log_println!("   ✅ LIMIT SELL ORDER PLACED FOR OPPOSITE TOKEN");
+                                                    crate::log_println!("      Order ID: {:?}", response.order_id);
+                                                    crate::log_println!("      Limit Price: ${:.6}", opposite_sell_price);
                                                 
-                                                let order_id_str = response.order_id.as_ref()
-                                                    .map(|id| format!("{:?}", id))
-                                                    .unwrap_or_else(|| "N/A".to_string());
-                                                let sell_event = format!(
-                                                    "LIMIT SELL ORDER (OPPOSITE AFTER STOP-LOSS) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {}",
-                                                    opposite_token_type.display_name(),
-                                                    trade.market_timestamp,
-                                                    &opposite_token_id[..16],
-                                                    opposite_sell_price,
-                                                    opposite_balance,
-                                                    order_id_str
-                                                );
-                                                crate::log_trading_event(&sell_event);
+                                                    let order_id_str = response.order_id.as_ref()
+                                                        .map(|id| format!("{:?}", id))
+                                                        .unwrap_or_else(|| "N/A".to_string());
+                                                    let sell_event = format!(
+                                                        "LIMIT SELL ORDER (OPPOSITE AFTER STOP-LOSS) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Trade ID: {}",
+                                                        opposite_token_type.display_name(),
+                                                        trade.market_timestamp,
+                                                        &opposite_token_id[..16],
+                                                        opposite_sell_price,
+                                                        opposite_balance,
+                                                        order_id_str,
+                                                        hedge_trade_id(&trade.trade_id)
+                                                    );
+                                                    crate::log_trading_event(&sell_event);
                                                 
-                                                // Create a PendingTrade entry to track this opposite token limit sell order
-                                                let opposite_trade = PendingTrade {
-                                                    token_id: opposite_token_id.clone(),
-                                                    condition_id: trade.condition_id.clone(),
-                                                    token_type: opposite_token_type.clone(),
-                                                    order_id: response.order_id.clone(),
-                                                    investment_amount: opposite_balance * opposite_buy_price,
-                                                    units: opposite_balance,
-                                                    purchase_price: opposite_buy_price,
-                                                    sell_price: opposite_sell_price,
-                                                    timestamp: std::time::Instant::now(),
-                                                    market_timestamp: trade.market_timestamp,
-                                                    sold: false,
-                                                    confirmed_balance: Some(opposite_balance),
-                                                    buy_order_confirmed: true,
-                                                    limit_sell_orders_placed: true,
-                                                    no_sell: false,
-                                                    claim_on_closure: false,
-                                                    sell_attempts: 0,
-                                                    redemption_attempts: 0,
-                                                    redemption_abandoned: false,
-                                                };
+                                                    // Create a PendingTrade entry to track this opposite token limit sell order
+                                                    let opposite_trade = PendingTrade {
+                                                        trade_id: hedge_trade_id(&trade.trade_id),
+                                                        token_id: opposite_token_id.clone(),
+                                                        condition_id: trade.condition_id.clone(),
+                                                        token_type: opposite_token_type.clone(),
+                                                        order_id: response.order_id.clone(),
+                                                        investment_amount: opposite_balance * opposite_buy_price,
+                                                        units: opposite_balance,
+                                                        purchase_price: opposite_buy_price,
+                                                        sell_price: opposite_sell_price,
+                                                        timestamp: std::time::Instant::now(),
+                                                        market_timestamp: trade.market_timestamp,
+                                                        sold: false,
+                                                        confirmed_balance: Some(opposite_balance),
+                                                        buy_order_confirmed: true,
+                                                        limit_sell_orders_placed: true,
+                                                        no_sell: false,
+                                                        claim_on_closure: false,
+                                                        sell_attempts: 0,
+                                                        redemption_attempts: 0,
+                                                        redemption_abandoned: false,
+                                                        scale_out_levels_done: 0,
+                                                    };
                                                 
-                                                let opposite_trade_key = format!("{}_opposite_{}", trade.market_timestamp, opposite_token_id);
-                                                let mut pending = self.pending_trades.lock().await;
-                                                pending.insert(opposite_trade_key, opposite_trade);
-                                                drop(pending);
+                                                    let opposite_trade_key = format!("{}_opposite_{}", trade.market_timestamp, opposite_token_id);
+                                                    let mut pending = self.pending_trades.lock().await;
+                                                    pending.insert(opposite_trade_key, opposite_trade);
+                                                    drop(pending);
                                                 
-                                                crate::log_println!("   📊 Tracking opposite token limit sell order (will monitor for fill)");
-                                            }
-                                            Err(e) => {
-                                                eprintln!("   ❌ FAILED TO PLACE LIMIT SELL FOR OPPOSITE TOKEN: {}", e);
-                                                warn!("Failed to place limit sell for opposite token: {}", e);
+                                                    crate::log_println!("   📊 Tracking opposite token limit sell order (will monitor for fill)");
+                                                }
+                                                Err(e) => {
+                                                    eprintln!("   ❌ FAILED TO PLACE LIMIT SELL FOR OPPOSITE TOKEN: {}", e);
+                                                    warn!("Failed to place limit sell for opposite token: {}", e);
+                                                }
                                             }
                                         }
                                     } else {
@@ -2176,65 +4406,70 @@ impl Trader {
                                         let opposite_buy_order = OrderRequest {
                                             token_id: opposite_token_id.clone(),
                                             side: "BUY".to_string(),
-                                            size: format!("{:.2}", opposite_buy_size),
+                                            size: format_size(opposite_buy_size, self.size_decimals(&trade.token_type), self.config().size_rounding),
                                             price: format!("{:.2}", opposite_buy_price),
                                             order_type: "LIMIT".to_string(),
                                         };
                                         
-                                        match self.api.place_order(&opposite_buy_order).await {
-                                            Ok(response) => {
-                                                crate::log_println!("   ✅ LIMIT BUY ORDER PLACED FOR OPPOSITE TOKEN");
-                                                crate::log_println!("      Order ID: {:?}", response.order_id);
-                                                crate::log_println!("      Limit Price: ${:.6}", opposite_buy_price);
-                                                crate::log_println!("      Size: {:.6} shares", opposite_buy_size);
-                                                
-                                                let order_id_str = response.order_id.as_ref()
-                                                    .map(|id| format!("{:?}", id))
-                                                    .unwrap_or_else(|| "N/A".to_string());
-                                                let buy_event = format!(
-                                                    "LIMIT BUY ORDER (OPPOSITE AFTER STOP-LOSS) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {}",
-                                                    opposite_token_type.display_name(),
-                                                    trade.market_timestamp,
-                                                    &opposite_token_id[..16],
-                                                    opposite_buy_price,
-                                                    opposite_buy_size,
-                                                    order_id_str
-                                                );
-                                                crate::log_trading_event(&buy_event);
-                                                
-                                                // Create a PendingTrade entry to track this opposite token limit buy order
-                                                let opposite_trade = PendingTrade {
-                                                    token_id: opposite_token_id.clone(),
-                                                    condition_id: trade.condition_id.clone(),
-                                                    token_type: opposite_token_type.clone(),
-                                                    order_id: response.order_id.clone(),
-                                                    investment_amount: opposite_buy_size * opposite_buy_price,
-                                                    units: opposite_buy_size,
-                                                    purchase_price: opposite_buy_price,
-                                                    sell_price: (1.0 - stop_loss_price) + 0.1, // Will sell at 0.30 when filled
-                                                    timestamp: std::time::Instant::now(),
-                                                    market_timestamp: trade.market_timestamp,
-                                                    sold: false,
-                                                    confirmed_balance: Some(0.0), // Not filled yet
-                                                    buy_order_confirmed: false, // Limit buy not confirmed yet
-                                                    limit_sell_orders_placed: false, // Will place sell order after buy fills
-                                                    no_sell: false,
-                                                    claim_on_closure: false,
-                                                    sell_attempts: 0,
-                                                    redemption_attempts: 0,
-                                                    redemption_abandoned: false,
-                                                };
-                                                
-                                                let opposite_trade_key = format!("{}_opposite_limit_{}", trade.market_timestamp, opposite_token_id);
-                                                let mut pending = self.pending_trades.lock().await;
-                                                pending.insert(opposite_trade_key, opposite_trade);
-                                                drop(pending);
+                                        if !self.would_self_cross(&opposite_buy_order).await {
+                                            match self.api.place_order(&opposite_buy_order).await {
+                                                Ok(response) => {
+                                                    crate::log_println!("   ✅ LIMIT BUY ORDER PLACED FOR OPPOSITE TOKEN");
+                                                    crate::log_println!("      Order ID: {:?}", response.order_id);
+                                                    crate::log_println!("      Limit Price: ${:.6}", opposite_buy_price);
+                                                    crate::log_println!("      Size: {:.6} shares", opposite_buy_size);
                                                 
-                                                crate::log_println!("   📊 Tracking opposite token limit buy order (will monitor for fill and place sell order)");
-                                            }
-                                            Err(e) => {
-                                                eprintln!("   ❌ FAILED TO PLACE LIMIT BUY ORDER FOR OPPOSITE TOKEN: {}", e);
-                                                warn!("Failed to place limit buy order for opposite token: {}", e);
+                                                    let order_id_str = response.order_id.as_ref()
+                                                        .map(|id| format!("{:?}", id))
+                                                        .unwrap_or_else(|| "N/A".to_string());
+                                                    let buy_event = format!(
+                                                        "LIMIT BUY ORDER (OPPOSITE AFTER STOP-LOSS) | Market: {} | Period: {} | Token: {} | Limit Price: ${:.6} | Size: {:.6} | Order ID: {} | Trade ID: {}",
+                                                        opposite_token_type.display_name(),
+                                                        trade.market_timestamp,
+                                                        &opposite_token_id[..16],
+                                                        opposite_buy_price,
+                                                        opposite_buy_size,
+                                                        order_id_str,
+                                                        hedge_trade_id(&trade.trade_id)
+                                                    );
+                                                    crate::log_trading_event(&buy_event);
+                                                
+                                                    // Create a PendingTrade entry to track this opposite token limit buy order
+                                                    let opposite_trade = PendingTrade {
+                                                        trade_id: hedge_trade_id(&trade.trade_id),
+                                                        token_id: opposite_token_id.clone(),
+                                                        condition_id: trade.condition_id.clone(),
+                                                        token_type: opposite_token_type.clone(),
+                                                        order_id: response.order_id.clone(),
+                                                        investment_amount: opposite_buy_size * opposite_buy_price,
+                                                        units: opposite_buy_size,
+                                                        purchase_price: opposite_buy_price,
+                                                        sell_price: (1.0 - stop_loss_price) + 0.1, // Will sell at 0.30 when filled
+                                                        timestamp: std::time::Instant::now(),
+                                                        market_timestamp: trade.market_timestamp,
+                                                        sold: false,
+                                                        confirmed_balance: Some(0.0), // Not filled yet
+                                                        buy_order_confirmed: false, // Limit buy not confirmed yet
+                                                        limit_sell_orders_placed: false, // Will place sell order after buy fills
+                                                        no_sell: false,
+                                                        claim_on_closure: false,
+                                                        sell_attempts: 0,
+                                                        redemption_attempts: 0,
+                                                        redemption_abandoned: false,
+                                                        scale_out_levels_done: 0,
+                                                    };
+                                                
+                                                    let opposite_trade_key = format!("{}_opposite_limit_{}", trade.market_timestamp, opposite_token_id);
+                                                    let mut pending = self.pending_trades.lock().await;
+                                                    pending.insert(opposite_trade_key, opposite_trade);
+                                                    drop(pending);
+                                                
+                                                    crate::log_println!("   📊 Tracking opposite token limit buy order (will monitor for fill and place sell order)");
+                                                }
+                                                Err(e) => {
+                                                    eprintln!("   ❌ FAILED TO PLACE LIMIT BUY ORDER FOR OPPOSITE TOKEN: {}", e);
+                                                    warn!("Failed to place limit buy order for opposite token: {}", e);
+                                                }
                                             }
                                         }
                                     }
@@ -2274,7 +4509,7 @@ impl Trader {
             // Check for stop-loss condition first (before checking for profit sell)
             // Stop-loss: sell if price drops below stop_loss_price to limit losses
             // Note: If stop-loss sell fails, keep retrying until sold OR price recovers above stop_loss_price
-            if let Some(stop_loss_price) = self.config.stop_loss_price {
+            if let Some(stop_loss_price) = self.stop_loss_for(&trade, current_ask_price) {
                 // Only trigger stop-loss if price is below threshold
                 // If price recovers above stop_loss_price, cancel stop-loss attempt
                 if current_ask_price < stop_loss_price {
@@ -2307,6 +4542,9 @@ impl Trader {
                     let loss = (current_ask_price - trade.purchase_price) * units_to_sell;
                     
                     crate::log_println!("═══════════════════════════════════════════════════════════");
+                    crate::notifier::notify(crate::notifier::NotifyEvent::StopLoss, format!(
+                        "🛑 STOP-LOSS {} | Ask ${:.2} (stop ${:.2}) | Bought @ ${:.2} | Period {}",
+                        trade.token_type.display_name(), current_ask_price, stop_loss_price, trade.purchase_price, trade.market_timestamp));
                     crate::log_println!("🛑 STOP-LOSS TRIGGERED");
                     crate::log_println!("═══════════════════════════════════════════════════════════");
                     crate::log_println!("⚠️  Price dropped below stop-loss threshold!");
@@ -2341,8 +4579,8 @@ impl Trader {
                     
                     // Optimized retry loop for stop-loss: try selling ASAP, retry immediately on failure
                     // Stop if price recovers above stop-loss threshold (safe level)
-                    let max_retry_attempts = 20; // Maximum retry attempts
-                    let retry_delay_ms = 1500; // 1.5 seconds between retries
+                    let max_retry_attempts = self.sell.max_retries.unwrap_or(DEFAULT_SELL_MAX_RETRIES).max(1);
+                    let retry_delay_ms = self.sell.retry_delay_ms.unwrap_or(DEFAULT_SELL_RETRY_DELAY_MS);
                     let mut sell_succeeded = false;
                     let mut last_price = current_ask_price;
                     
@@ -2363,7 +4601,7 @@ impl Trader {
                         }
                         
                         // Execute stop-loss sell with FAK (Fill-and-Kill) to allow partial fills
-                        let sell_result = self.execute_sell(&key, &mut trade, units_to_sell, last_price, Some("FAK"), true).await;
+                        let sell_result = self.execute_sell(&key, &mut trade, units_to_sell, last_price, None, true).await;
                         
                         match sell_result {
                             Ok(_) => {
@@ -2372,6 +4610,11 @@ impl Trader {
                             }
                             Err(e) => {
                                 let error_str = e.to_string();
+                                let api_error = ApiError::classify_error(&e);
+                                if !api_error.is_retryable() {
+                                    crate::log_println!("   ❌ Stop-loss sell attempt {} failed: {} - not retryable, will re-check on next cycle", attempt, api_error.summary());
+                                    break;
+                                }
                                 
                                 // Check if price recovered above stop-loss threshold (safe level)
                                 // If price recovers, stop retrying
@@ -2380,48 +4623,38 @@ impl Trader {
                                     Err(_) => last_price, // Use last known price if fetch fails
                                 };
                                 
-                                // Stop retrying if price recovered above stop-loss threshold
-                                if let Some(stop_loss_threshold) = self.config.stop_loss_price {
-                                    if current_price_check >= stop_loss_threshold {
-                                        crate::log_println!("   ⏸️  Price recovered above stop-loss threshold (${:.6} >= ${:.6}) - stopping retry", 
-                                            current_price_check, stop_loss_threshold);
-                                        crate::log_println!("   💡 Position is safe - will monitor for profit sell or stop-loss again");
-                                        
-                                        // Update trade (don't mark as sold, keep monitoring)
-                                        let mut pending = self.pending_trades.lock().await;
-                                        if let Some(t) = pending.get_mut(key.as_str()) {
-                                            *t = trade.clone();
-                                        }
-                                        drop(pending);
-                                        
-                                        // Log the failure
-                                        let market_name = trade.token_type.display_name();
-                                        let error_msg = format!("{:?}", e);
-                                        let simple_error = if error_msg.contains("not enough balance") || error_msg.contains("allowance") {
-                                            "not enough balance / allowance".to_string()
-                                        } else if error_msg.contains("No opposing orders") || error_msg.contains("no orders found") {
-                                            "no liquidity / no buyers".to_string()
-                                        } else if error_msg.contains("couldn't be fully filled") {
-                                            "insufficient liquidity".to_string()
-                                        } else {
-                                            error_msg.lines().next().unwrap_or("Sell failed").to_string()
-                                        };
-                                        
-                                        let sell_event = format!(
-                                            "SELL ORDER (STOP-LOSS) | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Revenue: ${:.6} | Loss: ${:.6} | Status: FAILED | Attempt: {} | Error: {} | Stopped: Price recovered",
-                                            market_name,
-                                            trade.market_timestamp,
-                                            current_price_check,
-                                            units_to_sell,
-                                            current_price_check * units_to_sell,
-                                            loss,
-                                            attempt,
-                                            simple_error
-                                        );
-                                        crate::log_trading_event(&sell_event);
-                                        
-                                        break; // Stop retrying - price recovered
+                                // Stop retrying if price recovered above the stop this trade triggered at
+                                if current_price_check >= stop_loss_price {
+                                    crate::log_println!("   ⏸️  Price recovered above stop-loss threshold (${:.6} >= ${:.6}) - stopping retry", 
+                                        current_price_check, stop_loss_price);
+                                    crate::log_println!("   💡 Position is safe - will monitor for profit sell or stop-loss again");
+                                    
+                                    // Update trade (don't mark as sold, keep monitoring)
+                                    let mut pending = self.pending_trades.lock().await;
+                                    if let Some(t) = pending.get_mut(key.as_str()) {
+                                        *t = trade.clone();
                                     }
+                                    drop(pending);
+                                    
+                                    // Log the failure
+                                    let market_name = trade.token_type.display_name();
+                                    let simple_error = api_error.summary();
+                                    
+                                    let sell_event = format!(
+                                        "SELL ORDER (STOP-LOSS) | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Revenue: ${:.6} | Loss: ${:.6} | Status: FAILED | Attempt: {} | Error: {} | Stopped: Price recovered | Trade ID: {}",
+                                        market_name,
+                                        trade.market_timestamp,
+                                        current_price_check,
+                                        units_to_sell,
+                                        current_price_check * units_to_sell,
+                                        loss,
+                                        attempt,
+                                        simple_error,
+                                        trade.trade_id
+                                    );
+                                    crate::log_trading_event(&sell_event);
+                                    
+                                    break; // Stop retrying - price recovered
                                 }
                                 
                                 last_price = current_price_check;
@@ -2466,14 +4699,15 @@ impl Trader {
                     drop(pending);
                     
                     crate::log_println!("   ✅ Stop-loss sell executed successfully");
-                    crate::log_println!("   💡 Position closed - can re-buy if price goes back up over ${:.6}", self.config.trigger_price);
+                    crate::log_println!("   💡 Position closed - can re-buy if price goes back up over ${:.6}", self.config().trigger_price);
                     continue; // Move to next trade
                 }
             }
             
-            // Check if we've reached the sell price (0.99 or 1.0)
+            // Check if we've reached the sell price (0.99 or 1.0, or the escalation schedule's target near close)
             // Also check if price is >= 1.0 (market resolution - token is worth $1)
-            if current_ask_price >= trade.sell_price || current_ask_price >= 1.0 {
+            let target_price = self.effective_sell_price(&trade);
+            if current_ask_price >= target_price || current_ask_price >= 1.0 {
                 // CRITICAL: Re-check actual balance before selling
                 // The stored units might be 0 if balance check failed initially, but tokens may have arrived later
                 let actual_balance = match self.api.check_balance_allowance(&trade.token_id).await {
@@ -2507,8 +4741,11 @@ impl Trader {
                 crate::log_println!("📈 SELL CONDITION MET");
                 crate::log_println!("═══════════════════════════════════════════════════════════");
                 crate::log_println!("💰 Current Price: ${:.6}", current_ask_price);
-                crate::log_println!("🎯 Target Price: ${:.6}", trade.sell_price);
-                crate::log_println!("   Condition: {} >= {}", current_ask_price, trade.sell_price);
+                crate::log_println!("🎯 Target Price: ${:.6}", target_price);
+                if target_price < trade.sell_price {
+                    crate::log_println!("   ⏬ Escalated from ${:.6} (sell_price_schedule)", trade.sell_price);
+                }
+                crate::log_println!("   Condition: {} >= {}", current_ask_price, target_price);
                 crate::log_println!("");
                 crate::log_println!("📊 Trade Details:");
                 crate::log_println!("   Token Type: {}", trade.token_type.display_name());
@@ -2524,8 +4761,8 @@ impl Trader {
                 
                 // Optimized retry loop: try selling ASAP, retry immediately on failure
                 // Stop if price recovers to safe level (drops below sell_price)
-                let max_retry_attempts = 20; // Maximum retry attempts
-                let retry_delay_ms = 1500; // 1.5 seconds between retries
+                let max_retry_attempts = self.sell.max_retries.unwrap_or(DEFAULT_SELL_MAX_RETRIES).max(1);
+                let retry_delay_ms = self.sell.retry_delay_ms.unwrap_or(DEFAULT_SELL_RETRY_DELAY_MS);
                 let mut sell_succeeded = false;
                 let mut last_price = current_ask_price;
                 
@@ -2547,7 +4784,7 @@ impl Trader {
                     }
                     
                     // Execute sell with FAK (Fill-and-Kill) to allow partial fills
-                    let sell_result = self.execute_sell(&key, &mut trade, units_to_sell, last_price, Some("FAK"), false).await;
+                    let sell_result = self.execute_sell(&key, &mut trade, units_to_sell, last_price, None, false).await;
                     
                     match sell_result {
                         Ok(_) => {
@@ -2556,6 +4793,11 @@ impl Trader {
                         }
                         Err(e) => {
                             let error_str = e.to_string();
+                            let api_error = ApiError::classify_error(&e);
+                            if !api_error.is_retryable() {
+                                crate::log_println!("   ❌ Sell attempt {} failed: {} - not retryable, will re-check on next cycle", attempt, api_error.summary());
+                                break;
+                            }
                             
                             // Check if price recovered to safe level (dropped below sell_price)
                             // If price drops significantly, stop retrying
@@ -2565,10 +4807,10 @@ impl Trader {
                             };
                             
                             // Stop retrying if price dropped below sell_price (recovered to safe level)
-                            if current_price_check < trade.sell_price {
+                            if current_price_check < target_price {
                                 crate::log_println!("   ⏸️  Price recovered to safe level (${:.6} < ${:.6}) - stopping retry", 
-                                    current_price_check, trade.sell_price);
-                                crate::log_println!("   💡 Will retry when price reaches ${:.6} again", trade.sell_price);
+                                    current_price_check, target_price);
+                                crate::log_println!("   💡 Will retry when price reaches ${:.6} again", target_price);
                                 
                                 // Update trade with current attempt count
                 let mut pending = self.pending_trades.lock().await;
@@ -2579,22 +4821,10 @@ impl Trader {
                                 
                                 // Log the failure
                                 let market_name = trade.token_type.display_name();
-                                let error_msg = format!("{:?}", e);
-                                let simple_error = if error_msg.contains("not enough balance") || error_msg.contains("allowance") {
-                                    "not enough balance / allowance".to_string()
-                                } else if error_msg.contains("No opposing orders") || error_msg.contains("no orders found") {
-                                    "no liquidity / no buyers".to_string()
-                                } else if error_msg.contains("couldn't be fully filled") {
-                                    "insufficient liquidity".to_string()
-                                } else {
-                                    error_msg.lines()
-                                        .find(|line| !line.trim().is_empty() && !line.contains("Troubleshooting"))
-                                        .unwrap_or("Sell failed")
-                                        .to_string()
-                                };
+                                let simple_error = api_error.summary();
                                 
                                 let sell_event = format!(
-                                    "SELL ORDER (PROFIT) | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Revenue: ${:.6} | Profit: ${:.6} | Status: FAILED | Attempt: {}/{} | Error: {} | Stopped: Price recovered",
+                                    "SELL ORDER (PROFIT) | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Revenue: ${:.6} | Profit: ${:.6} | Status: FAILED | Attempt: {}/{} | Error: {} | Stopped: Price recovered | Trade ID: {}",
                                     market_name,
                                     trade.market_timestamp,
                                     current_price_check,
@@ -2603,7 +4833,8 @@ impl Trader {
                                     (current_price_check - trade.purchase_price) * units_to_sell,
                                     attempt,
                                     max_retry_attempts,
-                                    simple_error
+                                    simple_error,
+                                    trade.trade_id
                                 );
                                 crate::log_trading_event(&sell_event);
                                 
@@ -2665,9 +4896,492 @@ impl Trader {
         Ok(())
     }
 
+    /// Size precision for orders on this token's asset (size_decimals, default 2)
+    fn size_decimals(&self, token_type: &crate::detector::TokenType) -> u32 {
+        self.config().size_decimals.as_ref()
+            .and_then(|decimals| decimals.get(token_type.asset_key()).copied())
+            .unwrap_or(DEFAULT_SIZE_DECIMALS)
+    }
+
+    /// Profit-sell target for a trade: its own sell_price, lowered by sell_price_schedule as the
+    /// trade's market approaches close (market closes one period after market_timestamp)
+    fn effective_sell_price(&self, trade: &PendingTrade) -> f64 {
+        if self.config().sell_price_schedule.is_none() {
+            return trade.sell_price;
+        }
+        let current_timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let seconds_until_close = (trade.market_timestamp + crate::period::seconds()).saturating_sub(current_timestamp);
+        trade.sell_price.min(self.config().scheduled_sell_price(seconds_until_close))
+    }
+
+    /// Emergency exit for a losing position shortly before close
+    /// Sells at market (FAK) when the trade is inside emergency_exit_seconds_before_close, is underwater vs its
+    /// purchase price and the current bid is still above emergency_exit_min_price. Hedge/hold trades are skipped.
+    /// Returns true if the position was sold and removed from pending trades
+    async fn try_emergency_exit(&self, key: &str, trade: &PendingTrade, seconds_until_close: u64) -> bool {
+        let (Some(window), Some(min_price)) = (
+            self.config().emergency_exit_seconds_before_close,
+            self.config().emergency_exit_min_price,
+        ) else {
+            return false;
+        };
+        if seconds_until_close > window {
+            return false;
+        }
+        let is_hedge = key.contains("hedge") || key.contains("opposite");
+        if is_hedge || trade.no_sell || !trade.buy_order_confirmed || trade.redemption_abandoned {
+            return false;
+        }
+        
+        // SELL price = what we receive when selling (current bid)
+        let current_bid = match self.api.get_price(&trade.token_id, "SELL").await {
+            Ok(price) => f64::try_from(price).unwrap_or(0.0),
+            Err(e) => {
+                debug!("Emergency exit: failed to fetch bid for {}: {}", trade.token_type.display_name(), e);
+                return false;
+            }
+        };
+        if current_bid >= trade.purchase_price || current_bid <= min_price {
+            return false; // Not underwater, or too cheap to be worth more than holding
+        }
+        if let Err(e) = self.api.get_best_price(&trade.token_id).await {
+            if e.downcast_ref::<CrossedBook>().is_some() {
+                warn!("Emergency exit: skipping {} - {}", trade.token_type.display_name(), e);
+                return false;
+            }
+        }
+        
+        let units = trade.confirmed_balance.unwrap_or(trade.units);
+        crate::log_println!("🚨 EMERGENCY EXIT: {} (period {}) underwater at ${:.6} (bought ${:.6}) with {}s to close - selling {:.6} shares at market",
+            trade.token_type.display_name(), trade.market_timestamp, current_bid, trade.purchase_price, seconds_until_close, units);
+        
+        let sell_started = chrono::Utc::now().timestamp() - LOST_ORDER_CLOCK_SKEW_SECS;
+        if let Err(e) = self.execute_sell(key, trade, units, current_bid, Some("FAK"), true).await {
+            warn!("Emergency exit sell failed for {}: {} - will retry on next check", trade.token_type.display_name(), e);
+            return false;
+        }
+        
+        let filled = self.fak_sold_size(trade, units, sell_started, "Emergency exit").await;
+        let sold = filled.unwrap_or(units);
+        
+        let recovered = current_bid * sold;
+        let exit_event = format!(
+            "EMERGENCY EXIT | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Recovered: ${:.6} | Loss: ${:.6} | Seconds To Close: {} | Trade ID: {}",
+            trade.token_type.display_name(),
+            trade.market_timestamp,
+            current_bid,
+            sold,
+            recovered,
+            recovered - trade.purchase_price * sold,
+            seconds_until_close,
+            trade.trade_id
+        );
+        crate::log_trading_event(&exit_event);
+        
+        self.settle_fak_exit(key, trade, units, filled, current_bid, "Emergency exit").await;
+        true
+    }
+
+    /// Shares of a market (FAK) sell of `units` placed at `sell_started` that actually sold: FAK can fill only partly,
+    /// and our CLOB trades (listed before the balance settles) tell how much did. Simulation fills in full
+    /// Returns None if the trades couldn't be looked up
+    async fn fak_sold_size(&self, trade: &PendingTrade, units: f64, sell_started: i64, context: &str) -> Option<f64> {
+        if self.simulation_mode {
+            return Some(units);
+        }
+        match self.api.get_trades(&trade.token_id, sell_started).await {
+            Ok(trades) => Some(trades.iter().filter(|t| t.side == "SELL").map(|t| t.size).sum::<f64>().min(units)),
+            Err(e) => {
+                warn!("{}: couldn't look up how much of {} sold: {}", context, trade.token_type.display_name(), e);
+                None
+            }
+        }
+    }
+
+    /// Track what a market (FAK) sell of a trade's whole position (`units` at `price`) left, given what it `filled`
+    /// (fak_sold_size): a full fill completes the cycle and drops the trade, an unsold rest stays tracked and an
+    /// unknown fill holds whatever is left to closure, where redemption checks the actual balance
+    async fn settle_fak_exit(&self, key: &str, trade: &PendingTrade, units: f64, filled: Option<f64>, price: f64, context: &str) {
+        match filled {
+            Some(sold) if units - sold > 0.000001 => {
+                // execute_sell booked every share at the price, so the unsold rest carries it as its cost
+                let remaining = units - sold;
+                crate::log_println!("   ⚠️  {} sold {:.6} of {:.6} shares - still tracking the other {:.6}", context, sold, units, remaining);
+                if let Some(t) = self.pending_trades.lock().await.get_mut(key) {
+                    t.units = remaining;
+                    t.confirmed_balance = Some(remaining);
+                    t.purchase_price = price;
+                    t.investment_amount = remaining * price;
+                }
+            }
+            Some(_) => {
+                if let Some(ref detector) = self.detector {
+                    detector.mark_cycle_completed(trade.token_type.clone()).await;
+                }
+                self.pending_trades.lock().await.remove(key);
+            }
+            None => {
+                crate::log_println!("   ⚠️  {} fill unknown for {} - holding any unsold shares until market closure", context, trade.token_type.display_name());
+                if let Some(t) = self.pending_trades.lock().await.get_mut(key) {
+                    t.purchase_price = price;
+                    t.no_sell = true;
+                    t.claim_on_closure = true;
+                }
+            }
+        }
+    }
+
+    /// Post-buy check (verify_purchased_token) that the bought token belongs to the trade's market and is the
+    /// intended Up/Down outcome, using fresh get_market metadata. A mismatch is logged as TOKEN MISMATCH; with
+    /// "flatten" the position is also sold at market. Skipped in simulation and when the metadata can't be fetched.
+    /// Returns true if the position was flattened and removed from pending trades
+    async fn verify_purchased_token(&self, key: &str, trade: &PendingTrade) -> bool {
+        if self.config().verify_purchased_token == TokenVerification::Off || self.simulation_mode {
+            return false;
+        }
+        let market = match self.api.get_market(&trade.condition_id).await {
+            Ok(market) => market,
+            Err(e) => {
+                warn!("Could not verify purchased {} token (market metadata unavailable): {}", trade.token_type.display_name(), e);
+                return false;
+            }
+        };
+        let Some(problem) = token_mismatch(&market, &trade.token_id, &trade.token_type) else {
+            debug!("Verified purchased token {} is {} of market {}", &trade.token_id[..16], trade.token_type.display_name(), &trade.condition_id[..16]);
+            return false;
+        };
+        
+        let flatten = self.config().verify_purchased_token == TokenVerification::Flatten;
+        crate::log_println!("═══════════════════════════════════════════════════════════");
+        crate::log_println!("🚨 TOKEN MISMATCH: bought token {} as {} but {}", trade.token_id, trade.token_type.display_name(), problem);
+        crate::log_println!("   Condition ID: {} | Period: {} | Units: {:.6}", trade.condition_id, trade.market_timestamp,
+            trade.confirmed_balance.unwrap_or(trade.units));
+        crate::log_println!("   💡 Discovery or outcome mapping may be wrong - check the market's slug and outcomes");
+        crate::log_println!("   Action: {}", if flatten { "selling the position at market" } else { "position kept (verify_purchased_token = \"warn\")" });
+        crate::log_println!("═══════════════════════════════════════════════════════════");
+        crate::log_trading_event(&format!(
+            "TOKEN MISMATCH | Market: {} | Period: {} | Token: {} | Condition: {} | Problem: {} | Action: {} | Trade ID: {}",
+            trade.token_type.display_name(),
+            trade.market_timestamp,
+            &trade.token_id[..16],
+            &trade.condition_id[..16],
+            problem,
+            if flatten { "FLATTEN" } else { "WARN" },
+            trade.trade_id
+        ));
+        if !flatten {
+            return false;
+        }
+        
+        let units = trade.confirmed_balance.unwrap_or(trade.units);
+        let current_bid = match self.api.get_price(&trade.token_id, "SELL").await {
+            Ok(price) => f64::try_from(price).unwrap_or(0.0),
+            Err(e) => {
+                warn!("TOKEN MISMATCH flatten: failed to fetch bid for {}: {} - position kept", trade.token_type.display_name(), e);
+                return false;
+            }
+        };
+        if let Err(e) = self.execute_sell(key, trade, units, current_bid, Some("FAK"), true).await {
+            warn!("TOKEN MISMATCH flatten sell failed for {}: {} - position kept", trade.token_type.display_name(), e);
+            return false;
+        }
+        self.pending_trades.lock().await.remove(key);
+        true
+    }
+
+    /// asymmetric_exit: market-sell (FAK) a position whose SELL price reached winner_confidence_price, and mark one
+    /// that is below its purchase price claim_on_closure so no later check sells it early. The decision is logged
+    /// per trade. Shares a partly filled winner sell leaves stay tracked. Returns true if the position was handled
+    /// (sold, or marked to be claimed)
+    async fn try_asymmetric_exit(&self, key: &str, trade: &PendingTrade, current_bid: f64) -> bool {
+        if !self.config().asymmetric_exit {
+            return false;
+        }
+        let is_hedge = key.contains("hedge") || key.contains("opposite");
+        if is_hedge || trade.no_sell || trade.claim_on_closure || !trade.buy_order_confirmed || trade.redemption_abandoned {
+            return false;
+        }
+        let confidence = self.config().winner_confidence_price.unwrap_or(DEFAULT_WINNER_CONFIDENCE_PRICE);
+        let units = trade.confirmed_balance.unwrap_or(trade.units);
+        
+        if current_bid >= confidence {
+            crate::log_println!("🏆 ASYMMETRIC EXIT - SELL WINNER: {} (period {}) SELL price ${:.6} >= ${:.6} (winner_confidence_price) - selling {:.6} shares (bought ${:.6})",
+                trade.token_type.display_name(), trade.market_timestamp, current_bid, confidence, units, trade.purchase_price);
+            let sell_started = chrono::Utc::now().timestamp() - LOST_ORDER_CLOCK_SKEW_SECS;
+            if let Err(e) = self.execute_sell(key, trade, units, current_bid, Some("FAK"), false).await {
+                warn!("Asymmetric exit winner sell failed for {}: {} - will retry on next check", trade.token_type.display_name(), e);
+                return false;
+            }
+            let filled = self.fak_sold_size(trade, units, sell_started, "Asymmetric exit").await;
+            let sold = filled.unwrap_or(units);
+            crate::log_trading_event(&format!(
+                "ASYMMETRIC EXIT | Decision: SELL WINNER | Market: {} | Period: {} | Price: ${:.6} | Confidence: ${:.6} | Units: {:.6} | PnL: ${:.6} | Trade ID: {}",
+                trade.token_type.display_name(), trade.market_timestamp, current_bid, confidence, sold,
+                (current_bid - trade.purchase_price) * sold, trade.trade_id));
+            self.settle_fak_exit(key, trade, units, filled, current_bid, "Asymmetric exit").await;
+            return true;
+        }
+        
+        if current_bid < trade.purchase_price {
+            crate::log_println!("🧊 ASYMMETRIC EXIT - HOLD LOSER: {} (period {}) SELL price ${:.6} < purchase ${:.6} - not selling, {:.6} shares will be claimed at closure",
+                trade.token_type.display_name(), trade.market_timestamp, current_bid, trade.purchase_price, units);
+            crate::log_trading_event(&format!(
+                "ASYMMETRIC EXIT | Decision: HOLD LOSER | Market: {} | Period: {} | Price: ${:.6} | Purchase Price: ${:.6} | Units: {:.6} | Trade ID: {}",
+                trade.token_type.display_name(), trade.market_timestamp, current_bid, trade.purchase_price, units, trade.trade_id));
+            if let Some(t) = self.pending_trades.lock().await.get_mut(key) {
+                t.claim_on_closure = true;
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Forced exit for a position held longer than max_hold_secs
+    /// Sells at market (FAK) at the current bid whether or not the sell target was reached. Hedge/opposite trades,
+    /// no_sell and claim_on_closure positions are skipped, as are books without a bid (the caller checks liquidity).
+    /// Shares the FAK sell doesn't fill stay tracked and go on the next check. Returns true if the sell was placed
+    async fn try_max_hold_exit(&self, key: &str, trade: &PendingTrade, current_bid: f64) -> bool {
+        let Some(max_hold_secs) = self.config().max_hold_secs else {
+            return false;
+        };
+        let held = trade.timestamp.elapsed();
+        if held < std::time::Duration::from_secs(max_hold_secs) {
+            return false;
+        }
+        let is_hedge = key.contains("hedge") || key.contains("opposite");
+        if is_hedge || trade.no_sell || trade.claim_on_closure || !trade.buy_order_confirmed || trade.redemption_abandoned {
+            return false;
+        }
+        if current_bid <= 0.0 {
+            return false;
+        }
+        
+        let units = trade.confirmed_balance.unwrap_or(trade.units);
+        crate::log_println!("⌛ MAX HOLD EXIT: {} (period {}) held {}s (max {}s) - selling {:.6} shares at ${:.6} (bought ${:.6}, target ${:.6})",
+            trade.token_type.display_name(), trade.market_timestamp, held.as_secs(), max_hold_secs, units, current_bid,
+            trade.purchase_price, trade.sell_price);
+        
+        let is_loss = current_bid < trade.purchase_price;
+        let sell_started = chrono::Utc::now().timestamp() - LOST_ORDER_CLOCK_SKEW_SECS;
+        if let Err(e) = self.execute_sell(key, trade, units, current_bid, Some("FAK"), is_loss).await {
+            warn!("Max hold exit sell failed for {}: {} - will retry on next check", trade.token_type.display_name(), e);
+            return false;
+        }
+        let filled = self.fak_sold_size(trade, units, sell_started, "Max hold exit").await;
+        let sold = filled.unwrap_or(units);
+        
+        let proceeds = current_bid * sold;
+        let exit_event = format!(
+            "MAX HOLD EXIT | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Proceeds: ${:.6} | PnL: ${:.6} | Held: {}s | Trade ID: {}",
+            trade.token_type.display_name(),
+            trade.market_timestamp,
+            current_bid,
+            sold,
+            proceeds,
+            proceeds - trade.purchase_price * sold,
+            held.as_secs(),
+            trade.trade_id
+        );
+        crate::log_trading_event(&exit_event);
+        
+        self.settle_fak_exit(key, trade, units, filled, current_bid, "Max hold exit").await;
+        true
+    }
+
+    /// sell.no_bid_policy for a position whose book has no bid: dump_at_any_price market-sells it (FAK, whatever fills)
+    /// and lowball_limit rests a limit sell at sell.lowball_price, which the resting-sell fill check then watches.
+    /// Our resting sells on the token (the profit target) are cancelled first; a lowball sell already resting is
+    /// left as is. Hedge/opposite trades, no_sell and claim_on_closure positions wait for closure like
+    /// wait_for_close. Returns true if the position was handled this check
+    async fn try_no_bid_exit(&self, key: &str, trade: &PendingTrade, current_price: f64) -> bool {
+        let is_hedge = key.contains("hedge") || key.contains("opposite") || key.contains("_limit");
+        if is_hedge || trade.no_sell || trade.claim_on_closure || !trade.buy_order_confirmed || trade.redemption_abandoned {
+            return false;
+        }
+        let lowball_price = match (self.sell.no_bid_policy, self.sell.lowball_price) {
+            (NoBidPolicy::WaitForClose, _) | (NoBidPolicy::LowballLimit, None) => return false,
+            (NoBidPolicy::LowballLimit, Some(_)) if self.simulation_mode => return false,
+            (_, price) => price,
+        };
+        
+        if trade.limit_sell_orders_placed && !self.simulation_mode {
+            let resting: Vec<OpenOrder> = match self.api.get_open_orders(&trade.token_id).await {
+                Ok(orders) => orders.into_iter().filter(|o| o.side.eq_ignore_ascii_case("SELL")).collect(),
+                Err(e) => {
+                    debug!("No-bid exit: could not fetch open orders for {}: {}", &trade.token_id[..16], e);
+                    return false;
+                }
+            };
+            if self.sell.no_bid_policy == NoBidPolicy::LowballLimit
+                && resting.iter().any(|o| lowball_price.is_some_and(|price| o.price <= price + 1e-9)) {
+                return false;
+            }
+            for order in resting {
+                if let Err(e) = self.api.cancel_order(&order.order_id).await {
+                    warn!("No-bid exit: failed to cancel limit sell {} @ ${:.2}: {} - keeping the position as is",
+                        order.order_id, order.price, e);
+                    return false;
+                }
+            }
+        }
+        
+        let units = trade.confirmed_balance.unwrap_or(trade.units);
+        match self.sell.no_bid_policy {
+            NoBidPolicy::WaitForClose => false,
+            NoBidPolicy::DumpAtAnyPrice => {
+                crate::log_println!("🪂 NO BID DUMP: {} (period {}) has no bid - market-selling {:.6} shares at any price (bought ${:.6})",
+                    trade.token_type.display_name(), trade.market_timestamp, units, trade.purchase_price);
+                let is_loss = current_price < trade.purchase_price;
+                let sell_started = chrono::Utc::now().timestamp() - LOST_ORDER_CLOCK_SKEW_SECS;
+                if let Err(e) = self.execute_sell(key, trade, units, current_price, Some("FAK"), is_loss).await {
+                    warn!("No-bid dump sell failed for {}: {} - will retry on next check", trade.token_type.display_name(), e);
+                    return false;
+                }
+                // With no bid a partial fill, or none at all, is the expected outcome
+                let filled = self.fak_sold_size(trade, units, sell_started, "No-bid dump").await;
+                crate::log_trading_event(&format!(
+                    "NO BID DUMP | Market: {} | Period: {} | Units: {:.6} | Purchase Price: ${:.6} | Trade ID: {}",
+                    trade.token_type.display_name(), trade.market_timestamp, filled.unwrap_or(units), trade.purchase_price, trade.trade_id));
+                self.settle_fak_exit(key, trade, units, filled, current_price, "No-bid dump").await;
+                true
+            }
+            NoBidPolicy::LowballLimit => {
+                let Some(price) = lowball_price else {
+                    return false;
+                };
+                let order = OrderRequest {
+                    token_id: trade.token_id.clone(),
+                    side: "SELL".to_string(),
+                    size: format_size(units, self.size_decimals(&trade.token_type), self.config().size_rounding),
+                    price: format!("{:.2}", price),
+                    order_type: "LIMIT".to_string(),
+                };
+                match self.place_limit_sell(&order).await {
+                    Ok(response) => {
+                        let order_id = response.order_id.unwrap_or_else(|| "N/A".to_string());
+                        crate::log_println!("🎣 LOWBALL LIMIT SELL: {} (period {}) has no bid - resting {} shares @ ${:.2} (bought ${:.6}, order {})",
+                            trade.token_type.display_name(), trade.market_timestamp, order.size, price, trade.purchase_price, order_id);
+                        crate::log_trading_event(&format!(
+                            "LOWBALL LIMIT SELL | Market: {} | Period: {} | Token: {} | Limit Price: ${:.2} | Size: {} | Order ID: {} | Trade ID: {}",
+                            trade.token_type.display_name(), trade.market_timestamp, &trade.token_id[..16], price, order.size,
+                            order_id, trade.trade_id));
+                        if let Some(t) = self.pending_trades.lock().await.get_mut(key) {
+                            t.limit_sell_orders_placed = true;
+                            t.sell_price = price;
+                            t.confirmed_balance = Some(units);
+                        }
+                        true
+                    }
+                    Err(e) => {
+                        warn!("Lowball limit sell failed for {}: {} - will retry on next check", trade.token_type.display_name(), e);
+                        false
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sell into strength: for every scale_out_schedule level the SELL price reaches for the first time, market-sell
+    /// that fraction of the shares still held. The trade keeps the rest and remembers which levels are done; a level
+    /// the FAK sell filled only partly is retried. Returns true if a sell was placed (the pending trade has been
+    /// updated or, if nothing is left, removed)
+    async fn try_scale_out(&self, key: &str, trade: &PendingTrade, current_bid: f64) -> bool {
+        let Some(schedule) = &self.config().scale_out_schedule else {
+            return false;
+        };
+        let is_hedge = key.contains("hedge") || key.contains("opposite");
+        if is_hedge || trade.no_sell || trade.claim_on_closure || !trade.buy_order_confirmed || trade.redemption_abandoned {
+            return false;
+        }
+        // At the target the regular sell takes the whole position
+        if current_bid <= 0.0 || current_bid >= trade.sell_price {
+            return false;
+        }
+        let reached: Vec<(f64, f64)> = schedule.iter()
+            .skip(trade.scale_out_levels_done)
+            .take_while(|(trigger, _)| current_bid >= *trigger)
+            .copied()
+            .collect();
+        if reached.is_empty() {
+            return false;
+        }
+        
+        let held = trade.confirmed_balance.unwrap_or(trade.units);
+        let kept = reached.iter().fold(held, |remaining, (_, fraction)| remaining * (1.0 - fraction));
+        let units = round_size(held - kept, self.size_decimals(&trade.token_type), self.config().size_rounding);
+        let levels = reached.iter().map(|(trigger, _)| format!("${:.2}", trigger)).collect::<Vec<_>>().join(", ");
+        if units <= 0.0 {
+            debug!("Scale-out level(s) {} for {} round to 0 shares - marking done", levels, trade.token_type.display_name());
+            if let Some(t) = self.pending_trades.lock().await.get_mut(key) {
+                t.scale_out_levels_done += reached.len();
+            }
+            return false;
+        }
+        
+        crate::log_println!("📈 SCALE OUT: {} (period {}) SELL price ${:.6} reached level(s) {} - selling {:.6} of {:.6} shares (target ${:.6})",
+            trade.token_type.display_name(), trade.market_timestamp, current_bid, levels, units, held, trade.sell_price);
+        let sell_started = chrono::Utc::now().timestamp() - LOST_ORDER_CLOCK_SKEW_SECS;
+        if let Err(e) = self.execute_sell(key, trade, units, current_bid, Some("FAK"), false).await {
+            warn!("Scale-out sell failed for {}: {} - will retry on next check", trade.token_type.display_name(), e);
+            return false;
+        }
+        // An unknown fill counts as complete - selling the level again could sell past the schedule
+        let sold = self.fak_sold_size(trade, units, sell_started, "Scale-out").await.unwrap_or(units);
+        // A level is done once the shares it and the levels before it call for have sold; the rest retry next check
+        let mut kept = held;
+        let levels_done = reached.iter()
+            .take_while(|(_, fraction)| {
+                kept *= 1.0 - fraction;
+                round_size(held - kept, self.size_decimals(&trade.token_type), self.config().size_rounding) <= sold + 0.000001
+            })
+            .count();
+        if sold < units - 0.000001 {
+            crate::log_println!("   ⚠️  Scale-out sold {:.6} of {:.6} shares - {} of {} level(s) done, the rest retry on the next check",
+                sold, units, levels_done, reached.len());
+        }
+        
+        let remaining = (held - sold).max(0.0);
+        let proceeds = current_bid * sold;
+        let scale_out_event = format!(
+            "SCALE OUT | Market: {} | Period: {} | Level(s): {} | Price: ${:.6} | Units: {:.6} | Proceeds: ${:.6} | PnL: ${:.6} | Remaining: {:.6} | Trade ID: {}",
+            trade.token_type.display_name(),
+            trade.market_timestamp,
+            levels,
+            current_bid,
+            sold,
+            proceeds,
+            proceeds - trade.purchase_price * sold,
+            remaining,
+            trade.trade_id
+        );
+        crate::log_trading_event(&scale_out_event);
+        
+        let mut pending = self.pending_trades.lock().await;
+        if remaining <= 0.000001 {
+            pending.remove(key);
+            drop(pending);
+            if let Some(ref detector) = self.detector {
+                detector.mark_cycle_completed(trade.token_type.clone()).await;
+            }
+        } else if let Some(t) = pending.get_mut(key) {
+            if units - sold > 0.000001 {
+                // execute_sell booked every share at the bid, so the unsold ones carry the bid as their cost
+                t.purchase_price = (trade.purchase_price * (held - units).max(0.0) + current_bid * (units - sold)) / remaining;
+            }
+            t.units = (t.units - sold).max(0.0);
+            t.confirmed_balance = t.confirmed_balance.map(|balance| (balance - sold).max(0.0));
+            t.investment_amount = t.purchase_price * t.units;
+            t.scale_out_levels_done += levels_done;
+        }
+        true
+    }
+
     /// Execute sell order with configurable order type
-    /// order_type: None or "FAK" for Fill-and-Kill (allows partial fills), "FOK" for Fill-or-Kill
-    /// Default: FAK (allows partial fills, better for limited liquidity situations)
+    /// order_type: "FAK" for Fill-and-Kill (allows partial fills), "FOK" for Fill-or-Kill
+    /// None uses the configured sell_order_tif (default FAK - better for limited liquidity situations)
     /// is_stop_loss: true if this is a stop-loss sell, false if it's a profit sell
     async fn execute_sell(
         &self,
@@ -2678,7 +5392,7 @@ impl Trader {
         order_type: Option<&str>,
         is_stop_loss: bool,
     ) -> Result<()> {
-        let order_type_str = order_type.unwrap_or("FAK");
+        let order_type_str = order_type.unwrap_or(self.config().sell_order_tif.as_str());
         let order_type_display = match order_type_str {
             "FAK" => "FAK (Fill-and-Kill - allows partial fills)",
             "FOK" => "FOK (Fill-or-Kill)",
@@ -2692,6 +5406,8 @@ impl Trader {
             *total += profit;
             let total_profit = *total;
             drop(total);
+            self.record_settlement(&trade.token_type, profit).await;
+            self.record_period_settlement(trade, units_to_sell, trade.purchase_price * units_to_sell, sell_value, true).await;
             
             crate::log_println!("🎮 SIMULATION MODE - Order NOT placed on exchange");
             crate::log_println!("   ✅ SIMULATION: Sell order would execute:");
@@ -2736,12 +5452,15 @@ impl Trader {
             }
             
             crate::log_println!("\n   📤 Placing SELL order...");
-            match self.api.place_market_order(
+            self.dump_orderbook(&trade.token_id, "BEFORE SELL", &trade.trade_id).await;
+            let sell_result = self.api.place_market_order(
                 &trade.token_id,
-                units_to_sell,  // Number of shares/units for SELL market orders
+                round_size(units_to_sell, self.size_decimals(&trade.token_type), self.config().size_rounding),  // Number of shares/units for SELL market orders
                 "SELL",
                 Some(order_type_str),
-            ).await {
+            ).await;
+            self.dump_orderbook(&trade.token_id, "AFTER SELL", &trade.trade_id).await;
+            match sell_result {
                 Ok(response) => {
                     crate::log_println!("   ✅ ORDER PLACED SUCCESSFULLY");
                     crate::log_println!("      Order ID: {:?}", response.order_id);
@@ -2757,6 +5476,8 @@ impl Trader {
                     *total += pnl;
                     let total_profit = *total;
                     drop(total);
+                    self.record_settlement(&trade.token_type, pnl).await;
+                    self.record_period_settlement(trade, units_to_sell, trade.purchase_price * units_to_sell, sell_value, true).await;
                     
                     // Log structured sell order to history.toml (profit or stop-loss)
                     let market_name = trade.token_type.display_name();
@@ -2768,7 +5489,7 @@ impl Trader {
                     let sell_type = if is_stop_loss { "STOP-LOSS" } else { "PROFIT" };
                     
                     let sell_event = format!(
-                        "SELL ORDER ({}) | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Revenue: ${:.6} | {}: ${:.6} | Order ID: {} | Status: SUCCESS",
+                        "SELL ORDER ({}) | Market: {} | Period: {} | Price: ${:.6} | Units: {:.6} | Revenue: ${:.6} | {}: ${:.6} | Order ID: {} | Status: SUCCESS | Trade ID: {}",
                         sell_type,
                         market_name,
                         trade.market_timestamp,
@@ -2777,7 +5498,8 @@ impl Trader {
                         sell_value,
                         if pnl >= 0.0 { "Profit" } else { "Loss" },
                         pnl,
-                        order_id_str
+                        order_id_str,
+                        trade.trade_id
                     );
                     crate::log_trading_event(&sell_event);
                     
@@ -2860,29 +5582,32 @@ impl Trader {
                     
                     // Analyze error type
                     crate::log_println!("\n🔍 Error Analysis:");
-                    let is_allowance_error = error_str.contains("allowance") || 
-                                           (error_str.contains("not enough") && error_str.contains("allowance"));
-                    let is_balance_error = error_str.contains("balance") && !error_str.contains("allowance");
-                    let is_fill_error = error_str.contains("couldn't be fully filled") || error_str.contains("FOK");
-                    let is_no_orders_error = error_str.contains("No opposing orders") || error_str.contains("no orders found");
-                    
-                    if is_allowance_error {
+                    let api_error = ApiError::classify_error(&e);
+                    if matches!(api_error, ApiError::InsufficientAllowance(_)) {
                         crate::log_println!("   Error Type: ALLOWANCE ERROR");
                         crate::log_println!("   Root Cause: Exchange contract is not approved to spend your tokens");
                         crate::log_println!("   Solution: Set setApprovalForAll on-chain:");
                         crate::log_println!("      cargo run --bin test_allowance -- --approve-only");
-                    } else if is_balance_error {
+                    } else if matches!(api_error, ApiError::InsufficientBalance(_)) {
                         crate::log_println!("   Error Type: BALANCE ERROR");
                         crate::log_println!("   Root Cause: You don't have enough tokens in your portfolio");
                         crate::log_println!("   Solution: Check your Polymarket portfolio - tokens may have been sold/redeemed");
-                    } else if is_fill_error {
+                    } else if matches!(api_error, ApiError::FillKill(_)) {
                         crate::log_println!("   Error Type: FILL ERROR");
                         crate::log_println!("   Root Cause: Order couldn't be fully filled (FOK/FAK order)");
                         crate::log_println!("   Solution: This is normal for market orders - partial fills may occur");
-                    } else if is_no_orders_error {
+                    } else if matches!(api_error, ApiError::NoLiquidity(_)) {
                         crate::log_println!("   Error Type: NO ORDERS ERROR");
                         crate::log_println!("   Root Cause: No opposing orders in the order book");
                         crate::log_println!("   Solution: Wait for market liquidity or try again later");
+                    } else if matches!(api_error, ApiError::Auth(_)) {
+                        crate::log_println!("   Error Type: AUTH ERROR");
+                        crate::log_println!("   Root Cause: The CLOB rejected the API credentials or the order signature");
+                        crate::log_println!("   Solution: Check polymarket.api_key / api_secret / api_passphrase, private_key and signature_type");
+                    } else if matches!(api_error, ApiError::RateLimited(_) | ApiError::Network(_) | ApiError::Http(..) | ApiError::CircuitOpen(_)) {
+                        crate::log_println!("   Error Type: {}", api_error.summary().to_uppercase());
+                        crate::log_println!("   Root Cause: The request did not get a normal response from the CLOB");
+                        crate::log_println!("   Solution: Transient - the sell is retried on the next check");
                     } else {
                         crate::log_println!("   Error Type: UNKNOWN");
                         crate::log_println!("   Root Cause: Unknown error - see full error message above");
@@ -2903,9 +5628,19 @@ impl Trader {
         Ok(())
     }
 
-    /// Check and settle trades when markets close
+    /// Check and settle trades when markets close, then write the summaries of markets that are done
     /// For momentum strategy: If token wasn't sold, it will be worth $1 if Up won, $0 if Down won
     pub async fn check_market_closure(&self) -> Result<()> {
+        // Never run two closure checks at once - both would try to settle/redeem the same trades
+        let _closure_guard = self.closure_check_lock.lock().await;
+        let result = self.settle_closed_markets().await;
+        self.write_period_summaries().await;
+        result
+    }
+
+    /// Resolve (simulation) or redeem (production) the positions of markets that have closed
+    async fn settle_closed_markets(&self) -> Result<()> {
+        
         // In simulation mode, check simulation tracker positions for market closure
         if self.simulation_mode {
             if let Some(tracker) = &self.simulation_tracker {
@@ -2923,8 +5658,8 @@ impl Trader {
                 
                 // Check each position for market closure
                 for position in positions {
-                    // Market closes at period_timestamp + 900 seconds
-                    let market_end_timestamp = position.period_timestamp + 900;
+                    // Market closes one period after period_timestamp
+                    let market_end_timestamp = position.period_timestamp + crate::period::seconds();
                     let seconds_until_close = market_end_timestamp.saturating_sub(current_timestamp);
                     
                     if current_timestamp < market_end_timestamp - 30 {
@@ -2974,8 +5709,24 @@ impl Trader {
                             }
                         };
                         
+                        // Positions this resolution settles, for the period summary (re-read: an earlier iteration
+                        // may already have resolved this market)
+                        let resolving: Vec<_> = tracker.get_all_positions().await.into_iter()
+                            .filter(|p| p.condition_id == condition_id && !p.sold)
+                            .collect();
+                        
                         // Resolve all positions for this market
                         let (spent, earned, pnl) = tracker.resolve_market_positions(&condition_id, market_resolved_up).await;
+                        if spent > 0.0 {
+                            self.record_settlement(&token_type, pnl).await;
+                            self.record_period_outcome(&condition_id, position.period_timestamp, &token_type, market_resolved_up).await;
+                            let mut summaries = self.period_summaries.lock().await;
+                            for resolved in &resolving {
+                                let value = if resolved.token_type.is_up() == market_resolved_up { resolved.units } else { 0.0 };
+                                period_summary_entry(&mut summaries, &condition_id, resolved.period_timestamp, &resolved.token_type)
+                                    .add_settlement(&resolved.token_id, resolved.units, resolved.investment_amount, value, false);
+                            }
+                        }
                         
                         // Get total spending and earnings
                         let (total_spent, total_earned, total_realized_pnl) = tracker.get_total_spending_and_earnings().await;
@@ -3042,20 +5793,30 @@ impl Trader {
             debug!("🔍 Checking market closure for {} unsold trade(s) (checking every 10 seconds)", unsold_count);
         }
         
+        // One redeemPositions call per market (and wallet) covers every position in it: trades sharing a batch key
+        // reuse the outcome of the first call this pass instead of redeeming one at a time
+        // Key: ApiClient::redemption_batch_key, value: Err(message) if that call failed
+        let mut batch_redemptions: HashMap<String, std::result::Result<(), String>> = HashMap::new();
+        
         for (key, trade) in pending_trades {
             // Skip if already sold
             if trade.sold {
                 continue;
             }
             
-            // Market closes at market_timestamp + 900 seconds
-            let market_end_timestamp = trade.market_timestamp + 900;
+            // Market closes one period after market_timestamp
+            let market_end_timestamp = trade.market_timestamp + crate::period::seconds();
             let seconds_until_close = market_end_timestamp.saturating_sub(current_timestamp);
             
             if current_timestamp < market_end_timestamp - 30 {
+                // Losing position in the final window - dump it if the bid still beats a $0 resolution
+                if self.try_emergency_exit(&key, &trade, seconds_until_close).await {
+                    continue;
+                }
+                
                 // Market hasn't closed yet - log periodically (every 30 seconds) to show we're monitoring
                 if seconds_until_close % 30 == 0 || seconds_until_close < 60 {
-                    crate::log_println!("⏳ Monitoring trade for market closure: {} token (period: {}), market closes in {}s", 
+                    crate::log_monitor_println!("⏳ Monitoring trade for market closure: {} token (period: {}), market closes in {}s", 
                         trade.token_type.display_name(), trade.market_timestamp, seconds_until_close);
                 }
                 continue; // Market hasn't closed yet
@@ -3144,10 +5905,11 @@ impl Trader {
                 // Log MARKET ENDED event to history.toml
                 let market_name = trade.token_type.display_name();
                 let market_end_event = format!(
-                    "MARKET ENDED | Market: {} | Period: {} | Condition: {}",
+                    "MARKET ENDED | Market: {} | Period: {} | Condition: {} | Trade ID: {}",
                     market_name,
                     trade.market_timestamp,
-                    trade.condition_id
+                    trade.condition_id,
+                    trade.trade_id
                 );
                 crate::log_trading_event(&market_end_event);
                 
@@ -3164,16 +5926,22 @@ impl Trader {
                 
                 // Log structured market result to history.toml
                 let result_event = format!(
-                    "MARKET RESULT | Market: {} | Period: {} | Outcome: {} | Token Value: ${:.6} | Cost: ${:.6} | Value: ${:.6} | Profit: ${:.6}",
+                    "MARKET RESULT | Market: {} | Period: {} | Outcome: {} | Token Value: ${:.6} | Cost: ${:.6} | Value: ${:.6} | Profit: ${:.6} | Trade ID: {}",
                     market_name,
                     trade.market_timestamp,
                     if token_winner { "WON" } else { "LOST" },
                     token_value,
                     total_cost,
                     total_value,
-                    profit
+                    profit,
+                    trade.trade_id
                 );
                 crate::log_trading_event(&result_event);
+                crate::notifier::notify(crate::notifier::NotifyEvent::MarketResolved, format!(
+                    "🏁 {} {} | Period {} | Cost ${:.2} | Value ${:.2} | PnL ${:.2}",
+                    market_name, if token_winner { "WON" } else { "LOST" }, trade.market_timestamp, total_cost, total_value, profit));
+                self.record_period_outcome(&trade.condition_id, trade.market_timestamp, &trade.token_type,
+                    token_winner == trade.token_type.is_up()).await;
                 
                 // Automatically redeem ALL unsold tokens after market resolution
                 // This replaces manual redemption - the bot will redeem all positions after market closes
@@ -3183,21 +5951,56 @@ impl Trader {
                 let mut redemption_successful = false;
                 
                 if should_redeem {
+                    // Dust and losing positions: redeeming them would burn more gas than they pay out
+                    if let Some(min_value) = self.redemption.min_value_to_redeem.filter(|&min| total_value < min) {
+                        crate::log_println!("   💸 Skipping redemption - resolved value ${:.6} is below redemption.min_value_to_redeem ${:.2}",
+                            total_value, min_value);
+                        crate::log_trading_event(&format!(
+                            "SKIPPED REDEMPTION (uneconomic) | Market: {} | Period: {} | Outcome: {} | Units: {:.6} | Value: ${:.6} | Min Value: ${:.2} | Trade ID: {}",
+                            market_name,
+                            trade.market_timestamp,
+                            if token_winner { "WON" } else { "LOST" },
+                            trade.units,
+                            total_value,
+                            min_value,
+                            trade.trade_id
+                        ));
+                        
+                        // Close it like a redeemed trade so it neither blocks new positions nor gets retried
+                        let mut pending = self.pending_trades.lock().await;
+                        pending.remove(&key);
+                        drop(pending);
+                        
+                        *self.total_profit.lock().await += profit;
+                        self.record_settlement(&trade.token_type, profit).await;
+                        self.record_period_settlement(&trade, trade.units, total_cost, total_value, false).await;
+                        crate::log_println!("   ✅ Trade closed without redemption (tokens left in the wallet)");
+                        continue;
+                    }
+                    
+                    let batch_key = self.api.redemption_batch_key(&trade.condition_id, &trade.token_id).await;
+                    let batched = batch_redemptions.get(&batch_key).cloned();
+                    
                     // CRITICAL: Check actual token balance before attempting redemption
                     // If balance is 0, tokens were already redeemed (manually or by bot) - mark as sold and skip
-                    crate::log_println!("   🔍 Checking token balance before redemption...");
-                    let current_balance = match self.api.check_balance_allowance(&trade.token_id).await {
-                        Ok((balance, _)) => {
-                            // Conditional tokens use 1e6 as base unit (like USDC)
-                            // Convert from smallest unit to actual shares
-                            let balance_decimal = balance / rust_decimal::Decimal::from(1_000_000u64);
-                            let balance_f64 = f64::try_from(balance_decimal).unwrap_or(0.0);
-                            crate::log_println!("   📊 Current token balance: {:.6} shares", balance_f64);
-                            balance_f64
-                        }
-                        Err(e) => {
-                            crate::log_println!("   ⚠️  Failed to check balance: {} - will attempt redemption anyway", e);
-                            trade.units // Use stored units as fallback
+                    // (not after this market's batch redemption went through - that emptied the balance)
+                    let current_balance = if batched == Some(Ok(())) {
+                        trade.units
+                    } else {
+                        crate::log_println!("   🔍 Checking token balance before redemption...");
+                        match self.api.check_balance_allowance(&trade.token_id).await {
+                            Ok((balance, _)) => {
+                                // Conditional tokens use 1e6 as base unit (like USDC)
+                                // Convert from smallest unit to actual shares
+                                let balance_decimal = balance / rust_decimal::Decimal::from(1_000_000u64);
+                                let balance_f64 = f64::try_from(balance_decimal).unwrap_or(0.0);
+                                crate::log_println!("   📊 Current token balance: {:.6} shares", balance_f64);
+                                balance_f64
+                            }
+                            Err(e) => {
+                                crate::log_println!("   ⚠️  Failed to check balance: {} - will attempt redemption anyway", e);
+                                trade.units // Use stored units as fallback
+                            }
                         }
                     };
                     
@@ -3209,9 +6012,10 @@ impl Trader {
                         // Log structured redemption status (already redeemed) to history.toml
                         let market_name = trade.token_type.display_name();
                         let redeem_event = format!(
-                            "REDEMPTION STATUS | Market: {} | Period: {} | Status: ALREADY_REDEEMED",
+                            "REDEMPTION STATUS | Market: {} | Period: {} | Status: ALREADY_REDEEMED | Trade ID: {}",
                             market_name,
-                            trade.market_timestamp
+                            trade.market_timestamp,
+                            trade.trade_id
                         );
                         crate::log_trading_event(&redeem_event);
                         
@@ -3227,6 +6031,8 @@ impl Trader {
                         let mut total = self.total_profit.lock().await;
                         *total += profit;
                         drop(total);
+                        self.record_settlement(&trade.token_type, profit).await;
+                        self.record_period_settlement(&trade, trade.units, total_cost, total_value, false).await;
                         
                         crate::log_println!("💰 Market Closed - Trade Already Redeemed");
                         crate::log_println!("   Token Type: {}", trade.token_type.display_name());
@@ -3257,17 +6063,31 @@ impl Trader {
                     
                     // Redeem tokens - pass trade data directly to avoid lookup issues
                     // Keep retrying until successful (similar to stop-loss retry logic)
-                    match self.redeem_token_by_id_with_trade(&trade_mut).await {
+                    let redeem_result = match &batched {
+                        Some(Ok(())) => {
+                            crate::log_println!("   📦 Covered by this market's batch redemption - no separate call needed");
+                            Ok(())
+                        }
+                        Some(Err(message)) => Err(anyhow::anyhow!("{} (batch redemption of this market)", message)),
+                        None => {
+                            let result = self.redeem_token_by_id_with_trade(&trade_mut).await;
+                            batch_redemptions.insert(batch_key, result.as_ref().map(|_| ()).map_err(|e| format!("{:#}", e)));
+                            result
+                        }
+                    };
+                    match redeem_result {
                         Ok(_) => {
                             crate::log_println!("   ✅ Tokens redeemed successfully (attempt {})", trade_mut.redemption_attempts);
                             
                             // Log structured redemption success to history.toml
                             let market_name = trade.token_type.display_name();
                             let redeem_event = format!(
-                                "REDEMPTION SUCCESS | Market: {} | Period: {} | Attempt: {} | Status: SUCCESS",
+                                "REDEMPTION SUCCESS | Market: {} | Period: {} | Attempt: {} | Status: {} | Trade ID: {}",
                                 market_name,
                                 trade.market_timestamp,
-                                trade_mut.redemption_attempts
+                                trade_mut.redemption_attempts,
+                                if batched.is_some() { "BATCHED" } else { "SUCCESS" },
+                                trade.trade_id
                             );
                             crate::log_trading_event(&redeem_event);
                             
@@ -3282,17 +6102,23 @@ impl Trader {
                                 crate::log_println!("   ⚠️  Maximum redemption attempts ({}) reached", max_redemption_attempts);
                                 crate::log_println!("   📋 Marking trade as abandoned - will not block new positions");
                                 trade_mut.redemption_abandoned = true;
+                                record_abandoned_trade(&trade_mut);
                                 
                                 // Log structured redemption failure to history.toml
                                 let market_name = trade.token_type.display_name();
                                 let redeem_event = format!(
-                                    "REDEMPTION FAILED | Market: {} | Period: {} | Attempts: {} | Status: ABANDONED | Error: {}",
+                                    "REDEMPTION FAILED | Market: {} | Period: {} | Attempts: {} | Status: ABANDONED | Error: {} | Trade ID: {}",
                                     market_name,
                                     trade.market_timestamp,
                                     trade_mut.redemption_attempts,
-                                    e.to_string().chars().take(100).collect::<String>() // Truncate long errors
+                                    e.to_string().chars().take(100).collect::<String>(), // Truncate long errors
+                                    trade.trade_id
                                 );
                                 crate::log_trading_event(&redeem_event);
+                                crate::notifier::notify(crate::notifier::NotifyEvent::RedemptionFailed, format!(
+                                    "❌ REDEMPTION FAILED {} | Period {} | {} attempts - abandoned: {}",
+                                    market_name, trade.market_timestamp, trade_mut.redemption_attempts,
+                                    e.to_string().chars().take(100).collect::<String>()));
                                 
                                 // Update trade in HashMap
                                 let mut pending = self.pending_trades.lock().await;
@@ -3301,7 +6127,7 @@ impl Trader {
                 }
                                 drop(pending);
                                 
-                                crate::log_println!("   ✅ Trade abandoned - you can manually redeem later if needed");
+                                crate::log_println!("   ✅ Trade abandoned - run with --recover-abandoned to retry later");
                                 crate::log_println!("   💡 New positions in new markets will NOT be blocked by this trade");
                             } else {
                                 warn!("⚠️  Token redemption failed: {} - will retry on next check (attempt {}/{})", 
@@ -3310,11 +6136,12 @@ impl Trader {
                                 // Log structured redemption retry to history.toml
                                 let market_name = trade.token_type.display_name();
                                 let redeem_event = format!(
-                                    "REDEMPTION RETRY | Market: {} | Period: {} | Attempt: {}/{} | Status: RETRYING",
+                                    "REDEMPTION RETRY | Market: {} | Period: {} | Attempt: {}/{} | Status: RETRYING | Trade ID: {}",
                                     market_name,
                                     trade.market_timestamp,
                                     trade_mut.redemption_attempts,
-                                    max_redemption_attempts
+                                    max_redemption_attempts,
+                                    trade.trade_id
                                 );
                                 crate::log_trading_event(&redeem_event);
                                 
@@ -3354,6 +6181,8 @@ impl Trader {
                 // Only log settlement and remove trade if redemption was successful
                 // If redemption failed, the trade remains for retry
                 if redemption_successful {
+                    self.record_settlement(&trade.token_type, profit).await;
+                    self.record_period_settlement(&trade, trade.units, total_cost, total_value, false).await;
                 crate::log_println!("💰 Market Closed - Momentum Trade Settled");
                     crate::log_println!("   Token Type: {}", trade.token_type.display_name());
                     crate::log_println!("   Outcome: {} token {}", trade.token_type.display_name(), if token_winner { "won" } else { "lost" });
@@ -3378,31 +6207,95 @@ impl Trader {
     }
 
     async fn check_market_result(&self, condition_id: &str, token_id: &str) -> Result<(bool, bool)> {
+        // Simulation resolves through its price feed (synthetic markets don't exist on the API)
+        if let Some(tracker) = &self.simulation_tracker {
+            return tracker.price_feed().get_market_result(condition_id, token_id).await;
+        }
+        
         let market = self.api.get_market(condition_id).await?;
         
-        let is_closed = market.closed;
+        // Never settle before a winner is set - a closed market without one is still awaiting resolution,
+        // and settling it then would book every position as a loss
+        let resolved = market.tokens.iter().any(|t| t.winner);
+        let settled = is_settled(market.closed, resolved, self.resolution.early_redeem_on_resolved);
+        if resolved != market.closed {
+            debug!("Market {} is {} - {}", condition_id,
+                if resolved { "resolved but not closed" } else { "closed but not resolved" },
+                if settled { "settling" } else { "waiting" });
+        }
         let is_winner = market.tokens.iter()
             .any(|t| t.token_id == token_id && t.winner);
         
-        Ok((is_closed, is_winner))
+        Ok((settled, is_winner))
     }
 
-    /// Redeem tokens using trade data directly (avoids lookup issues)
-    async fn redeem_token_by_id_with_trade(&self, trade: &PendingTrade) -> Result<()> {
-        // Determine outcome string based on token type
-        // For Up/Down markets: Up = "Up", Down = "Down"
-        let outcome = match trade.token_type {
-            crate::detector::TokenType::BtcUp | crate::detector::TokenType::EthUp | crate::detector::TokenType::SolanaUp | crate::detector::TokenType::XrpUp => "Up",
-            crate::detector::TokenType::BtcDown | crate::detector::TokenType::EthDown | crate::detector::TokenType::SolanaDown | crate::detector::TokenType::XrpDown => "Down",
-        };
-        
-        crate::log_println!("═══════════════════════════════════════════════════════════");
-        crate::log_println!("🔄 ATTEMPTING TOKEN REDEMPTION");
-        crate::log_println!("═══════════════════════════════════════════════════════════");
-        crate::log_println!("📊 Redemption Details:");
-        crate::log_println!("   Token Type: {}", trade.token_type.display_name());
-        crate::log_println!("   Token ID: {}...", &trade.token_id[..16]);
-        crate::log_println!("   Condition ID: {}...", &trade.condition_id[..16]);
+    /// Condition ids of ended markets (more than one period past market_timestamp) that still have open, non-abandoned trades
+    pub async fn markets_awaiting_resolution(&self) -> Vec<String> {
+        let current_timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let pending = self.pending_trades.lock().await;
+        let mut condition_ids: Vec<String> = pending.values()
+            .filter(|t| !t.sold && !t.redemption_abandoned && t.market_timestamp + crate::period::seconds() <= current_timestamp)
+            .map(|t| t.condition_id.clone())
+            .collect();
+        condition_ids.sort();
+        condition_ids.dedup();
+        condition_ids
+    }
+
+    /// Watch the gamma API's UMA resolution status of ended markets and run the closure check as soon as
+    /// one resolves, instead of waiting for the next market_closure_check_interval_seconds tick.
+    /// The periodic closure check stays in place as the fallback; check_market_result still gates settlement
+    /// on the CLOB market having a winner (and being closed, unless resolution.early_redeem_on_resolved),
+    /// so an early trigger just retries on the next tick
+    pub async fn run_resolution_watcher(&self, poll_interval: std::time::Duration) {
+        let mut triggered: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            let awaiting = self.markets_awaiting_resolution().await;
+            triggered.retain(|condition_id| awaiting.contains(condition_id));
+            
+            let mut newly_resolved = Vec::new();
+            for condition_id in awaiting.iter().filter(|c| !triggered.contains(*c)) {
+                match self.api.get_uma_resolution_status(condition_id).await {
+                    Ok(Some(status)) if status.eq_ignore_ascii_case("resolved") => newly_resolved.push(condition_id.clone()),
+                    Ok(_) => {}
+                    Err(e) => debug!("Resolution status check failed for {}: {}", condition_id, e),
+                }
+            }
+            if newly_resolved.is_empty() {
+                continue;
+            }
+            
+            for condition_id in &newly_resolved {
+                crate::log_println!("⚡ Market {}... resolved (UMA) - checking closure now", &condition_id[..condition_id.len().min(16)]);
+            }
+            triggered.extend(newly_resolved);
+            if let Err(e) = self.check_market_closure().await {
+                warn!("Error checking market closure after resolution: {}", e);
+            }
+        }
+    }
+
+    /// Redeem tokens using trade data directly (avoids lookup issues)
+    async fn redeem_token_by_id_with_trade(&self, trade: &PendingTrade) -> Result<()> {
+        // Determine outcome string based on token type
+        // For Up/Down markets: Up = "Up", Down = "Down"
+        let outcome = match trade.token_type {
+            crate::detector::TokenType::BtcUp | crate::detector::TokenType::EthUp | crate::detector::TokenType::SolanaUp | crate::detector::TokenType::XrpUp => "Up",
+            crate::detector::TokenType::BtcDown | crate::detector::TokenType::EthDown | crate::detector::TokenType::SolanaDown | crate::detector::TokenType::XrpDown => "Down",
+        };
+        
+        crate::log_println!("═══════════════════════════════════════════════════════════");
+        crate::log_println!("🔄 ATTEMPTING TOKEN REDEMPTION");
+        crate::log_println!("═══════════════════════════════════════════════════════════");
+        crate::log_println!("📊 Redemption Details:");
+        crate::log_println!("   Token Type: {}", trade.token_type.display_name());
+        crate::log_println!("   Token ID: {}...", &trade.token_id[..16]);
+        crate::log_println!("   Condition ID: {}...", &trade.condition_id[..16]);
         crate::log_println!("   Outcome: {}", outcome);
         crate::log_println!("   Units to redeem: {:.6}", trade.units);
         crate::log_println!("   Purchase price: ${:.6}", trade.purchase_price);
@@ -3426,6 +6319,57 @@ impl Trader {
         }
     }
     
+    /// Retry redemption for trades abandoned in previous runs (`--recover-abandoned`)
+    /// Re-checks each token's balance, redeems those still held and clears successful entries from the file
+    pub async fn recover_abandoned_trades(api: &PolymarketApi) -> Result<AbandonedRecovery> {
+        use rust_decimal::Decimal;
+
+        let trades = load_abandoned_trades()?;
+        let mut report = AbandonedRecovery::default();
+        let mut remaining = Vec::new();
+
+        crate::log_println!("🔄 Recovering {} abandoned trade(s) from {}", trades.len(), ABANDONED_TRADES_FILE);
+        for trade in trades {
+            let balance = match api.check_balance_only(&trade.token_id).await {
+                Ok(balance) => {
+                    let balance_decimal = balance / Decimal::from(1_000_000u64);
+                    f64::try_from(balance_decimal).unwrap_or(0.0)
+                }
+                Err(e) => {
+                    crate::log_println!("   ❌ {} (period {}): balance check failed: {}", trade.market, trade.market_timestamp, e);
+                    report.still_failing += 1;
+                    remaining.push(trade);
+                    continue;
+                }
+            };
+
+            if balance < 0.000001 {
+                crate::log_println!("   ✅ {} (period {}): no balance left - already settled", trade.market, trade.market_timestamp);
+                report.already_settled += 1;
+                continue;
+            }
+
+            match api.redeem_tokens(&trade.condition_id, &trade.token_id, &trade.outcome).await {
+                Ok(_) => {
+                    crate::log_println!("   ✅ {} (period {}): redeemed {:.6} shares", trade.market, trade.market_timestamp, balance);
+                    crate::log_trading_event(&format!(
+                        "REDEMPTION RECOVERED | Market: {} | Period: {} | Shares: {:.6} | Status: SUCCESS | Trade ID: {}",
+                        trade.market, trade.market_timestamp, balance, trade.trade_id
+                    ));
+                    report.recovered += 1;
+                }
+                Err(e) => {
+                    crate::log_println!("   ❌ {} (period {}): redemption still failing: {}", trade.market, trade.market_timestamp, e);
+                    report.still_failing += 1;
+                    remaining.push(trade);
+                }
+            }
+        }
+
+        save_abandoned_trades(&remaining)?;
+        Ok(report)
+    }
+
     /// Legacy method - kept for compatibility but uses trade lookup
     async fn redeem_token_by_id(&self, token_id: &str, _units: f64) -> Result<()> {
         // Get the condition ID and outcome from the token
@@ -3442,13 +6386,72 @@ impl Trader {
     }
 
     /// Reset for new period
+    /// Orders still resting on the old period's tokens (unfilled limit buys, profit sells) are cancelled before
+    /// its trades are dropped, so nothing is left on the book that the bot no longer tracks
     pub async fn reset_period(&self, old_period: u64) {
+        if old_period != 0 {
+            self.completed_periods.lock().await.insert(old_period);
+        }
+        let keep_open_positions = self.max_periods_reached().await;
+
+        let (dropped, kept): (Vec<PendingTrade>, Vec<PendingTrade>) = self.pending_trades.lock().await.values()
+            .filter(|trade| trade.market_timestamp == old_period)
+            .cloned()
+            .partition(|trade| !(keep_open_positions && Self::is_open_position(trade)));
+        let token_ids: std::collections::BTreeSet<String> = dropped.into_iter()
+            .map(|trade| trade.token_id)
+            .filter(|token_id| !kept.iter().any(|trade| &trade.token_id == token_id))
+            .collect();
+        for token_id in token_ids {
+            if self.simulation_mode {
+                if let Some(tracker) = &self.simulation_tracker {
+                    tracker.cancel_limit_order(&token_id, "BUY").await;
+                    tracker.cancel_limit_order(&token_id, "SELL").await;
+                }
+            } else {
+                let (cancelled, failed) = self.cancel_open_orders(&token_id, "Period rollover", "ROLLOVER CANCEL").await;
+                if !cancelled.is_empty() || failed > 0 {
+                    crate::log_println!("🔄 Period {} rollover: cancelled {} stale order(s) on {}{}", old_period,
+                        cancelled.len(), &token_id[..16],
+                        if failed > 0 { format!(" ({} failed - check the book manually)", failed) } else { String::new() });
+                }
+            }
+        }
+
         let mut pending = self.pending_trades.lock().await;
         // Remove trades from old period
-        pending.retain(|_, trade| trade.market_timestamp != old_period);
+        // On the final period (max_periods reached) keep open positions so they can settle/redeem before exit
+        pending.retain(|_, trade| {
+            trade.market_timestamp != old_period
+                || (keep_open_positions && Self::is_open_position(trade))
+        });
+        self.stop_loss_peaks.lock().unwrap().retain(|trade_id, _| pending.values().any(|trade| &trade.trade_id == trade_id));
         drop(pending);
     }
 
+    /// Whether the configured max_periods limit has been reached (no new entries after this)
+    pub async fn max_periods_reached(&self) -> bool {
+        match self.config().max_periods {
+            Some(max) => self.completed_periods.lock().await.len() as u64 >= max,
+            None => false,
+        }
+    }
+
+    /// Number of periods that have ended since startup
+    pub async fn completed_period_count(&self) -> u64 {
+        self.completed_periods.lock().await.len() as u64
+    }
+
+    /// Whether any filled position is still waiting to be sold or redeemed
+    pub async fn has_open_positions(&self) -> bool {
+        let pending = self.pending_trades.lock().await;
+        pending.values().any(Self::is_open_position)
+    }
+
+    fn is_open_position(trade: &PendingTrade) -> bool {
+        trade.buy_order_confirmed && !trade.sold && !trade.redemption_abandoned
+    }
+
     /// Print summary of all trades (for testing/verification)
     pub async fn print_trade_summary(&self) {
         // In simulation mode, print simulation position summary
@@ -3468,25 +6471,12 @@ impl Trader {
                 }
                 
                 // Get current prices for all positions
-                let mut current_prices: HashMap<String, TokenPrice> = HashMap::new();
-                for token_id in &token_ids {
-                    if !current_prices.contains_key(token_id) {
-                        if let Ok(orderbook) = self.api.get_orderbook(token_id).await {
-                            let bid = orderbook.bids.first().map(|e| e.price);
-                            let ask = orderbook.asks.first().map(|e| e.price);
-                            let token_price = TokenPrice {
-                                token_id: token_id.clone(),
-                                bid,
-                                ask,
-                            };
-                            current_prices.insert(token_id.clone(), token_price);
-                        }
-                    }
-                }
+                let current_prices = tracker.fetch_prices(&token_ids).await;
                 
                 let summary = tracker.get_position_summary(&current_prices).await;
                 tracker.log_position_summary(&current_prices).await;
                 crate::log_to_history(&summary);
+                crate::log_to_history(&format!("{}\n", self.rolling_pnl_summary().await));
             }
             return;
         }
@@ -3505,6 +6495,18 @@ impl Trader {
             (*tc, *tp, pc, list)
         };
 
+        // Mark every filled position once; the same prices feed the per-trade, total and account PnL lines
+        let mut marks: HashMap<String, Option<f64>> = HashMap::new();
+        let mut unrealized: HashMap<String, Option<f64>> = HashMap::new();
+        for (key, trade) in &pending_list {
+            if trade.buy_order_confirmed {
+                let mark = self.mark_price(&trade.token_id, &mut marks).await;
+                unrealized.insert(key.clone(), mark.map(|bid| (bid - trade.purchase_price) * trade.units));
+            }
+        }
+        let total_unrealized: f64 = unrealized.values().flatten().sum();
+        let unmarked = unrealized.values().filter(|u| u.is_none()).count();
+
         let ts = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
         let p = format!("[{}] ", ts);
 
@@ -3514,7 +6516,16 @@ impl Trader {
         out.push_str(&format!("{}📊 TRADE SUMMARY\n", p));
         out.push_str(&format!("{}═══════════════════════════════════════════════════════════\n", p));
         out.push_str(&format!("{}Total Trades Executed: {}\n", p, n));
-        out.push_str(&format!("{}Total Profit: ${:.6}\n", p, profit));
+        out.push_str(&format!("{}Total Profit (trading PnL): ${:.6}\n", p, profit));
+        if unmarked == 0 {
+            out.push_str(&format!("{}Unrealized PnL (at best bid): ${:.6}\n", p, total_unrealized));
+        } else {
+            out.push_str(&format!("{}Unrealized PnL (at best bid): ${:.6} ({} position(s) without a bid not included)\n", p, total_unrealized, unmarked));
+        }
+        if let Some(account) = self.account_pnl_summary(&mut marks).await {
+            out.push_str(&format!("{}{}\n", p, account));
+        }
+        out.push_str(&format!("{}{}\n", p, self.rolling_pnl_summary().await));
         out.push_str(&format!("{}Pending Trades: {}\n", p, pending_count));
         out.push_str(&format!("{} \n", p));
 
@@ -3529,6 +6540,15 @@ impl Trader {
                 out.push_str(&format!("{}   Units: {:.6}\n", p, trade.units));
                 out.push_str(&format!("{}   Purchase Price: ${:.6}\n", p, trade.purchase_price));
                 out.push_str(&format!("{}   Target Sell Price: ${:.6}\n", p, trade.sell_price));
+                match unrealized.get(key) {
+                    Some(Some(pnl)) => {
+                        let bid = marks.get(&trade.token_id).copied().flatten().unwrap_or(0.0);
+                        out.push_str(&format!("{}   Current Bid: ${:.6}\n", p, bid));
+                        out.push_str(&format!("{}   Unrealized PnL: ${:.6}\n", p, pnl));
+                    }
+                    Some(None) => out.push_str(&format!("{}   Unrealized PnL: unavailable (no bid)\n", p)),
+                    None => out.push_str(&format!("{}   Unrealized PnL: n/a (buy not filled)\n", p)),
+                }
                 out.push_str(&format!("{}   Market Timestamp: {}\n", p, trade.market_timestamp));
                 out.push_str(&format!("{}   Status: PENDING\n", p));
                 out.push_str(&format!("{}   Investment: ${:.6}\n", p, trade.investment_amount));
@@ -3542,6 +6562,410 @@ impl Trader {
     }
 }
 
+/// Sizing and prices of a hedge's limit sells, fixed when its background sell task is spawned
+pub(crate) struct HedgeSellPlan {
+    size_decimals: u32,
+    size_rounding: RoundingMode,
+    ladder: Vec<(f64, f64)>, // hedge_sell_ladder rungs as (price, fraction)
+}
+
+/// `Trader::pending_trades`: the trade map behind an async lock, written to its file (if any) after a lock that
+/// handed out mutable access is released. Writes happen on a background task, off the lock, and a burst of
+/// changes is coalesced into one write of the latest state
+#[derive(Default)]
+pub(crate) struct PendingTradeStore {
+    trades: Arc<Mutex<HashMap<String, PendingTrade>>>,
+    path: Option<std::path::PathBuf>, // None = in memory only
+    dirty: Arc<tokio::sync::Notify>, // Wakes the writer task after a change
+    writing: Arc<Mutex<()>>, // One write of the file at a time (writer task or `flush`)
+}
+
+impl PendingTradeStore {
+    /// Store persisted at `path`, starting from the trades already saved there
+    fn open(path: &str) -> Result<Self> {
+        let path = std::path::PathBuf::from(path);
+        let trades = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| anyhow::anyhow!("{} is not a valid pending trades file: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(anyhow::anyhow!("Failed to read {}: {}", path.display(), e)),
+        };
+        let store = Self { trades: Arc::new(Mutex::new(trades)), path: Some(path), ..Default::default() };
+        store.spawn_writer();
+        Ok(store)
+    }
+
+    async fn lock(&self) -> PendingTradesGuard<'_> {
+        let dirty = self.path.as_ref().map(|_| self.dirty.as_ref());
+        PendingTradesGuard { trades: self.trades.lock().await, dirty, changed: false }
+    }
+
+    /// Task writing the latest trades each time a change is signalled; ends with the store
+    fn spawn_writer(&self) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let (trades, dirty, writing) = (Arc::downgrade(&self.trades), self.dirty.clone(), self.writing.clone());
+        tokio::spawn(async move {
+            loop {
+                dirty.notified().await;
+                let Some(trades) = trades.upgrade() else {
+                    return;
+                };
+                Self::write(&path, &trades, &writing).await;
+            }
+        });
+    }
+
+    /// Write the current trades now rather than on the writer task, e.g. right before exiting
+    pub(crate) async fn flush(&self) {
+        if let Some(path) = &self.path {
+            Self::write(path, &self.trades, &self.writing).await;
+        }
+    }
+
+    async fn write(path: &std::path::Path, trades: &Mutex<HashMap<String, PendingTrade>>, writing: &Mutex<()>) {
+        let _writing = writing.lock().await;
+        let snapshot = trades.lock().await.clone();
+        let path = path.to_path_buf();
+        if let Err(e) = tokio::task::spawn_blocking(move || Self::save(&path, &snapshot)).await {
+            warn!("Pending trades writer failed: {}", e);
+        }
+    }
+
+    /// Write `trades` via a temporary file so a crash mid-write leaves the previous version intact (best effort)
+    fn save(path: &std::path::Path, trades: &HashMap<String, PendingTrade>) {
+        let tmp = path.with_extension("json.tmp");
+        let result = serde_json::to_string_pretty(trades)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| Ok(std::fs::write(&tmp, content)?))
+            .and_then(|_| Ok(std::fs::rename(&tmp, path)?));
+        if let Err(e) = result {
+            warn!("Failed to persist pending trades to {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Lock on the pending trade map; signals the writer task on drop if it was borrowed mutably
+pub(crate) struct PendingTradesGuard<'a> {
+    trades: tokio::sync::MutexGuard<'a, HashMap<String, PendingTrade>>,
+    dirty: Option<&'a tokio::sync::Notify>, // None = in memory only
+    changed: bool,
+}
+
+impl std::ops::Deref for PendingTradesGuard<'_> {
+    type Target = HashMap<String, PendingTrade>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.trades
+    }
+}
+
+impl std::ops::DerefMut for PendingTradesGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.changed = true;
+        &mut self.trades
+    }
+}
+
+impl Drop for PendingTradesGuard<'_> {
+    fn drop(&mut self) {
+        if let (true, Some(dirty)) = (self.changed, self.dirty) {
+            dirty.notify_one();
+        }
+    }
+}
+
+/// `Trader::risk`: the pre-trade risk budget - per-asset trade sizes (trading.trade_amount_by_asset), the open
+/// exposure cap (trading.max_total_notional) and the daily loss limit (trading.daily_loss_limit)
+/// Limits come from the config passed to each call, so a config reload applies from the next check on
+#[derive(Default)]
+pub(crate) struct RiskManager {
+    daily: std::sync::Mutex<DailyLoss>,
+}
+
+/// Realized PnL of one UTC day
+#[derive(Default)]
+struct DailyLoss {
+    day: Option<chrono::NaiveDate>,
+    realized: f64,
+    halted: bool, // daily_loss_limit was hit on `day` (reported once)
+}
+
+/// Why the risk budget refused a buy
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RiskBlock {
+    Exposure { entry: f64, open: f64, max: f64 },
+    DailyLoss { realized: f64, limit: f64 },
+}
+
+impl std::fmt::Display for RiskBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RiskBlock::Exposure { entry, open, max } => write!(f,
+                "${:.2} would exceed max_total_notional ${:.2} (open ${:.2}, {:.1}% utilized)",
+                entry, max, open, open / max * 100.0),
+            RiskBlock::DailyLoss { realized, limit } => write!(f,
+                "daily loss limit reached (realized today ${:.2}, limit -${:.2}) - buys resume at 00:00 UTC",
+                realized, limit),
+        }
+    }
+}
+
+impl RiskManager {
+    /// USD per buy for `token_type`'s asset when trading.trade_amount_by_asset has an entry for it
+    fn asset_trade_amount(config: &TradingConfig, token_type: &TokenType) -> Option<f64> {
+        config.trade_amount_by_asset.as_ref()
+            .and_then(|amounts| amounts.get(token_type.asset_key()).copied())
+    }
+
+    /// Today's entry, restarted when the UTC day changes
+    fn today(&self, now: chrono::DateTime<chrono::Utc>) -> std::sync::MutexGuard<'_, DailyLoss> {
+        let mut daily = self.daily.lock().unwrap();
+        if daily.day != Some(now.date_naive()) {
+            *daily = DailyLoss { day: Some(now.date_naive()), ..DailyLoss::default() };
+        }
+        daily
+    }
+
+    /// Add a settlement to today's realized PnL
+    /// Returns today's realized PnL the first time it reaches -daily_loss_limit, None otherwise
+    fn record_settlement(&self, config: &TradingConfig, pnl: f64, now: chrono::DateTime<chrono::Utc>) -> Option<f64> {
+        let mut daily = self.today(now);
+        daily.realized += pnl;
+        let limit = config.daily_loss_limit?;
+        if daily.halted || daily.realized > -limit {
+            return None;
+        }
+        daily.halted = true;
+        Some(daily.realized)
+    }
+
+    /// Check a buy adding `entry_notional` of exposure on top of `open_notional` against the budget
+    fn check_entry(&self, config: &TradingConfig, open_notional: f64, entry_notional: f64, now: chrono::DateTime<chrono::Utc>) -> std::result::Result<(), RiskBlock> {
+        if let Some(limit) = config.daily_loss_limit {
+            let realized = self.today(now).realized;
+            if realized <= -limit {
+                return Err(RiskBlock::DailyLoss { realized, limit });
+            }
+        }
+        if let Some(max) = config.max_total_notional {
+            if open_notional + entry_notional > max {
+                return Err(RiskBlock::Exposure { entry: entry_notional, open: open_notional, max });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn load_abandoned_trades() -> Result<Vec<AbandonedTrade>> {
+    let path = std::path::Path::new(ABANDONED_TRADES_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn save_abandoned_trades(trades: &[AbandonedTrade]) -> Result<()> {
+    let content = serde_json::to_string_pretty(trades)?;
+    std::fs::write(ABANDONED_TRADES_FILE, content)?;
+    Ok(())
+}
+
+/// Persist an abandoned trade so it can be retried with `--recover-abandoned` (best effort)
+fn record_abandoned_trade(trade: &PendingTrade) {
+    let outcome = match trade.token_type {
+        TokenType::BtcUp | TokenType::EthUp | TokenType::SolanaUp | TokenType::XrpUp => "Up",
+        TokenType::BtcDown | TokenType::EthDown | TokenType::SolanaDown | TokenType::XrpDown => "Down",
+    };
+    let result = load_abandoned_trades().and_then(|mut trades| {
+        trades.retain(|t| !(t.token_id == trade.token_id && t.market_timestamp == trade.market_timestamp));
+        trades.push(AbandonedTrade {
+            trade_id: trade.trade_id.clone(),
+            token_id: trade.token_id.clone(),
+            condition_id: trade.condition_id.clone(),
+            market: trade.token_type.display_name().to_string(),
+            outcome: outcome.to_string(),
+            units: trade.units,
+            purchase_price: trade.purchase_price,
+            market_timestamp: trade.market_timestamp,
+            redemption_attempts: trade.redemption_attempts,
+        });
+        save_abandoned_trades(&trades)
+    });
+    if let Err(e) = result {
+        warn!("Failed to record abandoned trade in {}: {}", ABANDONED_TRADES_FILE, e);
+    }
+}
+
+/// Round an order size to `decimals` places using `rounding` (trading.size_rounding)
+/// Goes through Decimal so large, low-priced share counts keep every whole share and the requested precision
+fn round_size(units: f64, decimals: u32, rounding: RoundingMode) -> f64 {
+    use rust_decimal::prelude::FromPrimitive;
+    rust_decimal::Decimal::from_f64(units)
+        .map(|d| d.round_dp_with_strategy(decimals, rounding.strategy()))
+        .and_then(|d| f64::try_from(d).ok())
+        .unwrap_or(units)
+}
+
+/// Whether an open position has been held for `hedge_after` without exit orders and should move to the standard-hedge flow
+/// Limit-order, no-sell and existing hedge trades have their own exit handling and are never promoted
+fn standard_hedge_due(key: &str, trade: &PendingTrade, hedge_after: std::time::Duration, now: std::time::Instant) -> bool {
+    if trade.sold || !trade.buy_order_confirmed || trade.limit_sell_orders_placed || trade.no_sell {
+        return false;
+    }
+    if key.contains("_limit") || key.contains("_individual_hedge") || key.contains("_standard_hedge") || key.contains("_dual_limit_hedge") {
+        return false;
+    }
+    now.saturating_duration_since(trade.timestamp) >= hedge_after
+}
+
+/// Re-key every due trade (see `standard_hedge_due`) as `<key>_standard_hedge` and mark its sell orders as placed
+/// Returns the promoted trades with their new keys, in key order
+fn claim_standard_hedges(
+    pending: &mut HashMap<String, PendingTrade>,
+    hedge_after: std::time::Duration,
+    now: std::time::Instant,
+) -> Vec<(String, PendingTrade)> {
+    let mut due: Vec<String> = pending.iter()
+        .filter(|(key, trade)| standard_hedge_due(key, trade, hedge_after, now))
+        .map(|(key, _)| key.clone())
+        .collect();
+    due.sort();
+
+    let mut claimed = Vec::new();
+    for key in due {
+        let hedge_key = format!("{}_standard_hedge", key);
+        if pending.contains_key(&hedge_key) {
+            continue;
+        }
+        if let Some(mut trade) = pending.remove(&key) {
+            trade.limit_sell_orders_placed = true;
+            pending.insert(hedge_key.clone(), trade.clone());
+            claimed.push((hedge_key, trade));
+        }
+    }
+    claimed
+}
+
+/// Compounded trade size: base + realized_pnl * fraction, clamped to [MIN_ORDER_AMOUNT, max]
+fn compound_trade_amount(base: f64, realized_pnl: f64, fraction: f64, max: Option<f64>) -> f64 {
+    let amount = (base + realized_pnl * fraction).max(MIN_ORDER_AMOUNT);
+    match max {
+        Some(max) => amount.min(max.max(MIN_ORDER_AMOUNT)),
+        None => amount,
+    }
+}
+
+/// Whether an ended market can be settled: a winner must be known, and unless `early_redeem_on_resolved`
+/// the market must also be flagged closed
+fn is_settled(closed: bool, resolved: bool, early_redeem_on_resolved: bool) -> bool {
+    resolved && (closed || early_redeem_on_resolved)
+}
+
+/// Why `token_id` is not the `token_type` outcome of `market`, or None if it is
+fn token_mismatch(market: &MarketDetails, token_id: &str, token_type: &TokenType) -> Option<String> {
+    let Some(token) = market.tokens.iter().find(|t| t.token_id == token_id) else {
+        return Some(format!("it is not a token of market {}", market.condition_id));
+    };
+    // Same outcome naming as discovery: "Up"/"1" and "Down"/"0"
+    let outcome = token.outcome.to_uppercase();
+    let is_up = if outcome.contains("UP") || outcome == "1" {
+        true
+    } else if outcome.contains("DOWN") || outcome == "0" {
+        false
+    } else {
+        return Some(format!("its outcome \"{}\" is neither Up nor Down", token.outcome));
+    };
+    if is_up != token_type.is_up() {
+        return Some(format!("its outcome is \"{}\"", token.outcome));
+    }
+    None
+}
+
+/// Order size string with exactly `decimals` places (see `round_size`)
+fn format_size(units: f64, decimals: u32, rounding: RoundingMode) -> String {
+    format!("{:.*}", decimals as usize, round_size(units, decimals, rounding))
+}
+
+/// Whether a failed order may still have reached the exchange (timeout / transport error)
+/// Explicit rejections (FOK not filled, insufficient balance, ...) are definitive and never ambiguous
+fn is_ambiguous_order_error(e: &anyhow::Error) -> bool {
+    matches!(ApiError::classify_error(e), ApiError::Network(_))
+}
+
+/// Whether an open order is the one `order` would have created at `placed_at` (same side, price and size,
+/// created no earlier than `placed_at` allowing for clock skew)
+fn is_lost_order(open: &OpenOrder, order: &OrderRequest, placed_at: i64) -> bool {
+    let (Ok(price), Ok(size)) = (order.price.parse::<f64>(), order.size.parse::<f64>()) else {
+        return false;
+    };
+    open.token_id == order.token_id
+        && open.side.eq_ignore_ascii_case(&order.side)
+        && (open.price - price).abs() < 1e-9
+        && (open.original_size - size).abs() < 1e-6
+        && open.created_at >= placed_at - LOST_ORDER_CLOCK_SKEW_SECS
+}
+
+/// Submit a buy without risking a duplicate
+/// Guarantee: a buy is only re-submitted after the CLOB's trade history confirms the previous attempt did not match.
+/// `matched` returns the shares our BUY trades in the token matched since the first attempt (CLOB /data/trades -
+/// trades are listed as soon as they match, unlike the token balance, which only moves once they settle).
+/// - Success or an explicit rejection is returned as-is (no retry)
+/// - On a timeout/transport error the trades are looked up after `settle_delay`:
+///   matched -> the order went through; a recovered response is returned instead of buying again
+///   nothing matched -> the attempt did not fill; retry while attempts remain
+///   unknown (lookup failed) -> the error is returned without re-submitting
+async fn place_buy_idempotent<P, PFut, M, MFut>(
+    mut place: P,
+    mut matched: M,
+    max_attempts: u32,
+    settle_delay: tokio::time::Duration,
+) -> Result<OrderResponse>
+where
+    P: FnMut() -> PFut,
+    PFut: std::future::Future<Output = Result<OrderResponse>>,
+    M: FnMut() -> MFut,
+    MFut: std::future::Future<Output = Result<f64>>,
+{
+    let mut attempt = 1;
+    loop {
+        let e = match place().await {
+            Ok(response) => return Ok(response),
+            Err(e) => e,
+        };
+        if !is_ambiguous_order_error(&e) {
+            return Err(e);
+        }
+        
+        tokio::time::sleep(settle_delay).await;
+        let shares = match matched().await {
+            Ok(shares) => shares,
+            Err(lookup_err) => {
+                warn!("Buy attempt {} failed ambiguously and the trade lookup failed ({}) - not re-submitting", attempt, lookup_err);
+                return Err(e);
+            }
+        };
+        
+        if shares > 0.000001 {
+            crate::log_println!("   ✅ Buy attempt {} errored ({}) but {:.6} shares matched - order went through, not re-submitting",
+                attempt, e, shares);
+            return Ok(OrderResponse {
+                order_id: None,
+                status: "MATCHED (recovered after timeout)".to_string(),
+                message: Some(format!("Original error: {}", e)),
+            });
+        }
+        
+        if attempt >= max_attempts {
+            return Err(e);
+        }
+        crate::log_println!("   🔁 Buy attempt {} failed ({}) and no trade matched - re-submitting", attempt, e);
+        attempt += 1;
+    }
+}
+
 // Helper trait for Decimal to f64 conversion
 trait ToF64 {
     fn to_f64(&self) -> Option<f64>;
@@ -3553,4 +6977,1103 @@ impl ToF64 for rust_decimal::Decimal {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn filled() -> OrderResponse {
+        OrderResponse {
+            order_id: Some("0xorder".to_string()),
+            status: "MATCHED".to_string(),
+            message: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn timeout_that_filled_is_not_bought_twice() {
+        // First attempt times out on our side but matches on the exchange (its trade is listed before the balance settles)
+        let placed = Arc::new(AtomicU32::new(0));
+        let place = || {
+            let placed = placed.clone();
+            async move {
+                placed.fetch_add(1, Ordering::SeqCst);
+                Err::<OrderResponse, _>(anyhow::anyhow!("Buy order timed out after 15s"))
+            }
+        };
+        let matched = || async { Ok(10.0) };
+
+        let response = place_buy_idempotent(place, matched, 3, tokio::time::Duration::ZERO).await.unwrap();
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+        assert!(response.status.contains("recovered"));
+    }
+
+    #[tokio::test]
+    async fn timeout_that_did_not_fill_is_resubmitted_once() {
+        let placed = Arc::new(AtomicU32::new(0));
+        let place = || {
+            let placed = placed.clone();
+            async move {
+                if placed.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Err(anyhow::anyhow!("error sending request: operation timed out"))
+                } else {
+                    Ok(filled())
+                }
+            }
+        };
+        let matched = || async { Ok(0.0) };
+
+        let response = place_buy_idempotent(place, matched, 3, tokio::time::Duration::ZERO).await.unwrap();
+        assert_eq!(placed.load(Ordering::SeqCst), 2);
+        assert_eq!(response.status, "MATCHED");
+    }
+
+    #[tokio::test]
+    async fn rejection_or_unknown_trades_are_never_resubmitted() {
+        let placed = Arc::new(AtomicU32::new(0));
+        let place = || {
+            let placed = placed.clone();
+            async move {
+                placed.fetch_add(1, Ordering::SeqCst);
+                Err::<OrderResponse, _>(anyhow::anyhow!("order couldn't be fully filled. FOK orders are fully filled or killed"))
+            }
+        };
+        assert!(place_buy_idempotent(place, || async { Ok(0.0) }, 3, tokio::time::Duration::ZERO).await.is_err());
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+
+        let placed = Arc::new(AtomicU32::new(0));
+        let place = || {
+            let placed = placed.clone();
+            async move {
+                placed.fetch_add(1, Ordering::SeqCst);
+                Err::<OrderResponse, _>(anyhow::anyhow!("Buy order timed out after 15s"))
+            }
+        };
+        let matched = || async { Err::<f64, _>(anyhow::anyhow!("trades endpoint unavailable")) };
+        assert!(place_buy_idempotent(place, matched, 3, tokio::time::Duration::ZERO).await.is_err());
+        assert_eq!(placed.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn low_price_high_quantity_size_is_not_truncated() {
+        // $25 at $0.011 = 2272.7272... shares - must keep every whole share and round to the nearest lot
+        let units = 25.0 / 0.011;
+        assert_eq!(format_size(units, 2, RoundingMode::Round), "2272.73");
+        assert_eq!(format_size(units, 0, RoundingMode::Round), "2273");
+        assert!(format_size(units, 2, RoundingMode::Round).parse::<f64>().unwrap() >= units - 0.005);
+
+        // Large share counts are never shortened or switched to exponent notation
+        assert_eq!(format_size(123_456.789, 2, RoundingMode::Round), "123456.79");
+        assert_eq!(format_size(1_000_000.0, 4, RoundingMode::Round), "1000000.0000");
+
+        // Finer precision is kept when the asset allows it
+        assert_eq!(format_size(10.123456, 4, RoundingMode::Round), "10.1235");
+        assert!((round_size(10.125, 2, RoundingMode::Round) - 10.13).abs() < 1e-9);
+    }
+
+    #[test]
+    fn floor_size_is_sellable_just_above_boundary() {
+        // Balance sits just above the 10.12 lot boundary - rounding to nearest would ask to sell more than held
+        let balance = 10.126;
+        assert_eq!(format_size(balance, 2, RoundingMode::Round), "10.13");
+        assert!(format_size(balance, 2, RoundingMode::Round).parse::<f64>().unwrap() > balance);
+
+        // Floor (the default) always yields a size the wallet can actually fill
+        assert_eq!(RoundingMode::default(), RoundingMode::Floor);
+        assert_eq!(format_size(balance, 2, RoundingMode::Floor), "10.12");
+        assert!(round_size(balance, 2, RoundingMode::Floor) <= balance);
+        assert_eq!(format_size(10.120001, 2, RoundingMode::Floor), "10.12");
+
+        // Ceil rounds any remainder up
+        assert_eq!(format_size(10.120001, 2, RoundingMode::Ceil), "10.13");
+        assert_eq!(format_size(10.12, 2, RoundingMode::Ceil), "10.12");
+    }
+
+    fn open_position(timestamp: std::time::Instant) -> PendingTrade {
+        PendingTrade {
+            trade_id: "dummy_trade_id".to_string(),
+            token_id: "dummy_token_id".to_string(),
+            condition_id: "dummy_condition_id".to_string(),
+            token_type: TokenType::BtcUp,
+            order_id: None,
+            investment_amount: 5.0,
+            units: 10.0,
+            purchase_price: 0.5,
+            sell_price: 0.99,
+            timestamp,
+            market_timestamp: 0,
+            sold: false,
+            confirmed_balance: Some(10.0),
+            buy_order_confirmed: true,
+            limit_sell_orders_placed: false,
+            no_sell: false,
+            claim_on_closure: false,
+            sell_attempts: 0,
+            redemption_attempts: 0,
+            redemption_abandoned: false,
+            scale_out_levels_done: 0,
+        }
+    }
+
+    #[test]
+    fn position_is_promoted_to_standard_hedge_once_after_hedge_minutes() {
+        let opened = std::time::Instant::now();
+        let hedge_after = std::time::Duration::from_secs(10 * 60);
+        let mut pending = HashMap::new();
+        pending.insert("0_dummy_token_id".to_string(), open_position(opened));
+        // Dual-limit fills are held to closure and never promoted
+        let mut held = open_position(opened);
+        held.no_sell = true;
+        pending.insert("0_dummy_held_token_id_limit".to_string(), held);
+
+        // 9 minutes in - not yet due
+        let at = |minutes: u64| opened + std::time::Duration::from_secs(minutes * 60);
+        assert!(claim_standard_hedges(&mut pending, hedge_after, at(9)).is_empty());
+        assert!(pending.contains_key("0_dummy_token_id"));
+
+        // 10 minutes in - moved to the standard-hedge flow with its sell orders claimed
+        let claimed = claim_standard_hedges(&mut pending, hedge_after, at(10));
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].0, "0_dummy_token_id_standard_hedge");
+        assert!(!pending.contains_key("0_dummy_token_id"));
+        assert!(pending["0_dummy_token_id_standard_hedge"].limit_sell_orders_placed);
+
+        // Later checks never place the ladder again
+        assert!(claim_standard_hedges(&mut pending, hedge_after, at(11)).is_empty());
+        assert!(claim_standard_hedges(&mut pending, hedge_after, at(14)).is_empty());
+        assert_eq!(pending.len(), 2);
+    }
+
+    #[test]
+    fn period_summary_rolls_up_several_trades_in_one_market() {
+        let mut summaries = HashMap::new();
+        // First trade: half scaled out at 0.7, the rest resolves as a winner
+        let summary = period_summary_entry(&mut summaries, "dummy_condition_id", 0, &TokenType::BtcUp);
+        summary.add_settlement("dummy_trade_1", 5.0, 2.5, 3.5, true);
+        summary.add_settlement("dummy_trade_1", 5.0, 2.5, 5.0, false);
+        // Re-entry in the same period, also held to resolution
+        period_summary_entry(&mut summaries, "dummy_condition_id", 0, &TokenType::BtcUp)
+            .add_settlement("dummy_trade_2", 10.0, 8.0, 10.0, false);
+
+        assert_eq!(summaries.len(), 1);
+        let summary = &summaries["dummy_condition_id"];
+        assert_eq!(summary.asset, "btc");
+        assert_eq!(summary.entries, 2);
+        assert_eq!(summary.exits, 1);
+        assert!((summary.cost - 13.0).abs() < 1e-9);
+        assert!((summary.proceeds - 18.5).abs() < 1e-9);
+        assert!((summary.net_pnl - 5.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn risk_budget_sizes_per_asset_and_halts_for_the_day_after_the_loss_limit() {
+        let mut config = crate::config::Config::default().trading;
+        config.fixed_trade_amount = 2.0;
+        config.trade_amount_by_asset = Some(HashMap::from([("btc".to_string(), 5.0)]));
+        config.max_total_notional = Some(20.0);
+        config.daily_loss_limit = Some(3.0);
+        assert_eq!(RiskManager::asset_trade_amount(&config, &TokenType::BtcDown), Some(5.0));
+        assert_eq!(RiskManager::asset_trade_amount(&config, &TokenType::SolanaUp), None);
+
+        let risk = RiskManager::default();
+        let day = |d: u32, h: u32| chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2026, 3, d, h, 0, 0).unwrap();
+        assert_eq!(risk.check_entry(&config, 10.0, 5.0, day(1, 9)), Ok(()));
+        assert_eq!(risk.check_entry(&config, 16.0, 5.0, day(1, 9)),
+            Err(RiskBlock::Exposure { entry: 5.0, open: 16.0, max: 20.0 }));
+
+        // Losses add up over the day; the breach is reported once and blocks every buy until the UTC day ends
+        assert_eq!(risk.record_settlement(&config, -2.0, day(1, 10)), None);
+        assert_eq!(risk.record_settlement(&config, 0.5, day(1, 11)), None);
+        assert_eq!(risk.record_settlement(&config, -1.5, day(1, 12)), Some(-3.0));
+        assert_eq!(risk.record_settlement(&config, -1.0, day(1, 13)), None);
+        assert_eq!(risk.check_entry(&config, 0.0, 1.0, day(1, 23)),
+            Err(RiskBlock::DailyLoss { realized: -4.0, limit: 3.0 }));
+        assert_eq!(risk.check_entry(&config, 0.0, 1.0, day(2, 0)), Ok(()));
+    }
+
+    mod check_pending_trades {
+        use super::*;
+        use crate::api_client::mock::MockApi;
+        use crate::price_feed::SyntheticPriceFeed;
+
+        const PERIOD: u64 = 1_700_000_100;
+        const CONDITION: &str = "dummy_condition_id_check_pending";
+        const UP: &str = "dummy_up_token_id_check_pending";
+        const DOWN: &str = "dummy_down_token_id_check_pending";
+
+        fn mock_trader(configure: impl FnOnce(&mut TradingConfig)) -> (Arc<MockApi>, Trader) {
+            let mut config = crate::config::Config::default().trading;
+            config.sell_price = 0.99;
+            config.stop_loss_price = None;
+            configure(&mut config);
+            let mock = Arc::new(MockApi::default());
+            mock.add_market(CONDITION, UP, DOWN);
+            mock.set_usdc_balance(1000.0);
+            let trader = Trader::new_with_price_feed(
+                mock.clone(), config, false, None, Arc::new(SyntheticPriceFeed::new(1, 0.01)),
+            ).unwrap();
+            (mock, trader)
+        }
+
+        /// Detector entry for `token_id` (UP or DOWN of the mock market) at `bid_price`
+        fn opportunity(token_id: &str, bid_price: f64) -> BuyOpportunity {
+            BuyOpportunity {
+                condition_id: CONDITION.to_string(),
+                token_id: token_id.to_string(),
+                token_type: if token_id == DOWN { TokenType::BtcDown } else { TokenType::BtcUp },
+                bid_price,
+                period_timestamp: PERIOD,
+                time_remaining_seconds: 300,
+                time_elapsed_seconds: 600,
+                use_market_order: false,
+                investment_amount_override: None,
+                sell_price_override: None,
+                is_individual_hedge: false,
+                is_standard_hedge: false,
+                dual_limit_shares: None,
+            }
+        }
+
+        /// Live, unmatched order for 10 shares
+        fn open_order(order_id: &str, token_id: &str, side: &str, price: f64) -> OpenOrder {
+            OpenOrder {
+                order_id: order_id.to_string(),
+                token_id: token_id.to_string(),
+                side: side.to_string(),
+                price,
+                original_size: 10.0,
+                size_matched: 0.0,
+                status: "LIVE".to_string(),
+                created_at: chrono::Utc::now().timestamp(),
+            }
+        }
+
+        /// Our market sell of `size` shares of `token_id`, as get_trades reports it
+        fn sell_fill(token_id: &str, size: f64) -> OwnTrade {
+            OwnTrade {
+                trade_id: "dummy_trade".to_string(),
+                token_id: token_id.to_string(),
+                side: "SELL".to_string(),
+                size,
+                price: 0.40,
+                status: "MATCHED".to_string(),
+                match_time: chrono::Utc::now().timestamp(),
+            }
+        }
+
+        /// Filled market buy with its profit-target sell resting on the book
+        fn filled_market_buy(purchase_price: f64, units: f64) -> PendingTrade {
+            let mut trade = open_position(std::time::Instant::now());
+            trade.token_id = UP.to_string();
+            trade.condition_id = CONDITION.to_string();
+            trade.market_timestamp = PERIOD;
+            trade.purchase_price = purchase_price;
+            trade.units = units;
+            trade.confirmed_balance = Some(units);
+            trade.limit_sell_orders_placed = true;
+            trade
+        }
+
+        #[tokio::test]
+        async fn limit_buy_fill_places_one_profit_sell() {
+            let (mock, trader) = mock_trader(|_| {});
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.59, 0.61);
+            let opportunity = opportunity(UP, 0.60);
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            let orders = mock.orders();
+            assert_eq!(orders.len(), 1);
+            assert_eq!((orders[0].side.as_str(), orders[0].price.as_str(), orders[0].size.as_str()), ("BUY", "0.60", "10.00"));
+
+            // Resting - no balance yet, nothing to do
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.orders().len(), 1);
+
+            // Balance arrives: fill recorded and exactly one profit-target sell placed, even across later checks
+            mock.set_balance(UP, 10.0);
+            trader.check_pending_trades().await.unwrap();
+            trader.check_pending_trades().await.unwrap();
+            let orders = mock.orders();
+            assert_eq!(orders.len(), 2);
+            assert_eq!(orders[1].side, "SELL");
+            assert_eq!(orders[1].token_id, UP);
+            assert!((orders[1].price.parse::<f64>().unwrap() - 0.99).abs() < 1e-9);
+
+            let pending = trader.pending_trades.lock().await;
+            let trade = &pending[&format!("{}_{}_limit", PERIOD, UP)];
+            assert!(trade.buy_order_confirmed);
+            assert!((trade.units - 10.0).abs() < 1e-9);
+            assert!(!trade.sold);
+        }
+
+        #[tokio::test]
+        async fn buy_fills_are_reported_to_the_strategy() {
+            #[derive(Default)]
+            struct RecordingStrategy {
+                fills: std::sync::Mutex<Vec<crate::strategy::StrategyFill>>,
+            }
+            #[async_trait::async_trait]
+            impl crate::strategy::Strategy for RecordingStrategy {
+                fn name(&self) -> &str {
+                    "recording"
+                }
+                async fn evaluate(&self, _: &crate::monitor::MarketSnapshot, _: &crate::strategy::StrategyState) -> Vec<BuyOpportunity> {
+                    Vec::new()
+                }
+                async fn on_fill(&self, fill: &crate::strategy::StrategyFill) {
+                    self.fills.lock().unwrap().push(fill.clone());
+                }
+            }
+
+            let strategy = Arc::new(RecordingStrategy::default());
+            let (mock, trader) = mock_trader(|_| {});
+            let trader = trader.with_strategy(strategy.clone());
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.59, 0.61);
+            let opportunity = opportunity(UP, 0.60);
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            trader.check_pending_trades().await.unwrap();
+            assert!(strategy.fills.lock().unwrap().is_empty());
+
+            mock.set_balance(UP, 10.0);
+            trader.check_pending_trades().await.unwrap();
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(*strategy.fills.lock().unwrap(), vec![crate::strategy::StrategyFill {
+                token_id: UP.to_string(),
+                token_type: TokenType::BtcUp,
+                condition_id: CONDITION.to_string(),
+                period_timestamp: PERIOD,
+                price: 0.60,
+                units: 10.0,
+            }]);
+
+            // The same fill seen again (e.g. the user channel after balance polling) is not handled twice
+            let key = format!("{}_{}_limit", PERIOD, UP);
+            let trade = trader.pending_trades.lock().await[&key].clone();
+            trader.handle_limit_buy_fill(&key, &trade, 0.0, 10.0).await;
+            assert_eq!(strategy.fills.lock().unwrap().len(), 1);
+            assert_eq!(mock.orders().len(), 2);
+        }
+
+        #[tokio::test]
+        async fn limit_buy_follows_order_status_through_partial_fill_and_cancel() {
+            let (mock, trader) = mock_trader(|_| {});
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.59, 0.61);
+            let opportunity = opportunity(UP, 0.60);
+            let key = format!("{}_{}_limit", PERIOD, UP);
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            let order_id = trader.pending_trades.lock().await[&key].order_id.clone().unwrap();
+
+            // Partially filled and still resting: the balance moved, but the buy isn't done yet
+            mock.set_balance(UP, 4.0);
+            mock.set_order_status(OpenOrder { size_matched: 4.0, status: "LIVE".to_string(), ..open_order(&order_id, UP, "BUY", 0.60) });
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.orders().len(), 1);
+            assert!(!trader.pending_trades.lock().await[&key].buy_order_confirmed);
+
+            // Fully matched: confirmed at the matched size with one profit-target sell for all of it
+            mock.set_order_status(OpenOrder { size_matched: 10.0, status: "MATCHED".to_string(), ..open_order(&order_id, UP, "BUY", 0.60) });
+            trader.check_pending_trades().await.unwrap();
+            let orders = mock.orders();
+            assert_eq!(orders.len(), 2);
+            assert_eq!((orders[1].side.as_str(), orders[1].size.as_str()), ("SELL", "10"));
+            let trade = trader.pending_trades.lock().await[&key].clone();
+            assert!(trade.buy_order_confirmed);
+            assert!((trade.units - 10.0).abs() < 1e-9);
+
+            // A buy cancelled before anything matched is dropped
+            trader.pending_trades.lock().await.clear();
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            let order_id = trader.pending_trades.lock().await[&key].order_id.clone().unwrap();
+            mock.set_order_status(OpenOrder { status: "CANCELED".to_string(), ..open_order(&order_id, UP, "BUY", 0.60) });
+            trader.check_pending_trades().await.unwrap();
+            assert!(trader.pending_trades.lock().await.is_empty());
+        }
+
+        #[tokio::test]
+        async fn control_cancel_keeps_what_a_limit_buy_matched() {
+            let (mock, trader) = mock_trader(|_| {});
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.59, 0.61);
+            let opportunity = opportunity(UP, 0.60);
+            let key = format!("{}_{}_limit", PERIOD, UP);
+
+            // 4 of 10 shares matched before the cancel: confirmed at 4 with a profit-target sell for them
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            let order_id = trader.pending_trades.lock().await[&key].order_id.clone().unwrap();
+            mock.set_order_status(OpenOrder { size_matched: 4.0, status: "CANCELED".to_string(), ..open_order(&order_id, UP, "BUY", 0.60) });
+            trader.cancel_order(&order_id).await.unwrap();
+            assert_eq!(mock.cancels(), vec![order_id]);
+            let trade = trader.pending_trades.lock().await[&key].clone();
+            assert!(trade.buy_order_confirmed);
+            assert!((trade.units - 4.0).abs() < 1e-9);
+            let orders = mock.orders();
+            assert_eq!((orders[1].side.as_str(), orders[1].size.as_str()), ("SELL", "4"));
+
+            // Nothing matched: dropped
+            trader.pending_trades.lock().await.clear();
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            let order_id = trader.pending_trades.lock().await[&key].order_id.clone().unwrap();
+            mock.set_order_status(OpenOrder { status: "CANCELED".to_string(), ..open_order(&order_id, UP, "BUY", 0.60) });
+            trader.cancel_order(&order_id).await.unwrap();
+            assert!(trader.pending_trades.lock().await.is_empty());
+        }
+
+        #[tokio::test]
+        async fn emergency_exit_keeps_tracking_what_a_partial_fak_sell_left() {
+            let (mock, trader) = mock_trader(|config| {
+                config.emergency_exit_seconds_before_close = Some(60);
+                config.emergency_exit_min_price = Some(0.05);
+            });
+            mock.set_price(UP, 0.40, 0.42);
+            mock.set_balance(UP, 10.0);
+            let key = format!("{}_{}", PERIOD, UP);
+            let mut trade = filled_market_buy(0.60, 10.0);
+            trade.limit_sell_orders_placed = false;
+            trader.pending_trades.lock().await.insert(key.clone(), trade.clone());
+            // Only 4 of the 10 shares found a buyer
+            mock.set_trades(vec![sell_fill(UP, 4.0)]);
+
+            assert!(trader.try_emergency_exit(&key, &trade, 30).await);
+            assert_eq!(mock.market_orders(), vec![(UP.to_string(), 10.0, "SELL".to_string())]);
+            let remaining = trader.pending_trades.lock().await[&key].clone();
+            assert!((remaining.units - 6.0).abs() < 1e-9);
+            assert_eq!(remaining.confirmed_balance, Some(6.0));
+            assert!((remaining.purchase_price - 0.40).abs() < 1e-9);
+        }
+
+        #[tokio::test]
+        async fn max_hold_exit_keeps_tracking_what_a_partial_fak_sell_left() {
+            let (mock, trader) = mock_trader(|config| config.max_hold_secs = Some(0));
+            mock.set_price(UP, 0.55, 0.57);
+            mock.set_balance(UP, 10.0);
+            let key = format!("{}_{}", PERIOD, UP);
+            let trade = filled_market_buy(0.60, 10.0);
+            trader.pending_trades.lock().await.insert(key.clone(), trade.clone());
+            mock.set_trades(vec![sell_fill(UP, 7.0)]);
+
+            assert!(trader.try_max_hold_exit(&key, &trade, 0.55).await);
+            assert_eq!(mock.market_orders(), vec![(UP.to_string(), 10.0, "SELL".to_string())]);
+            let remaining = trader.pending_trades.lock().await[&key].clone();
+            assert!((remaining.units - 3.0).abs() < 1e-9);
+            assert_eq!(remaining.confirmed_balance, Some(3.0));
+
+            // The rest fills on the next try and the trade is done
+            mock.set_trades(vec![sell_fill(UP, 3.0)]);
+            assert!(trader.try_max_hold_exit(&key, &remaining, 0.55).await);
+            assert!(!trader.pending_trades.lock().await.contains_key(&key));
+        }
+
+        #[tokio::test]
+        async fn force_sell_reports_and_keeps_tracking_a_partial_fill() {
+            let (mock, trader) = mock_trader(|_| {});
+            mock.set_price(UP, 0.55, 0.57);
+            mock.set_balance(UP, 10.0);
+            let key = format!("{}_{}", PERIOD, UP);
+            trader.pending_trades.lock().await.insert(key.clone(), filled_market_buy(0.60, 10.0));
+            mock.set_trades(vec![sell_fill(UP, 6.0)]);
+
+            let sold = trader.force_sell(UP).await.unwrap();
+            assert!((sold - 6.0).abs() < 1e-9);
+            let remaining = trader.pending_trades.lock().await[&key].clone();
+            assert!((remaining.units - 4.0).abs() < 1e-9);
+            assert_eq!(remaining.confirmed_balance, Some(4.0));
+        }
+
+        #[tokio::test]
+        async fn scale_out_level_that_fills_partly_is_retried() {
+            let (mock, trader) = mock_trader(|config| config.scale_out_schedule = Some(vec![(0.70, 0.5)]));
+            mock.set_price(UP, 0.70, 0.72);
+            mock.set_balance(UP, 10.0);
+            let key = format!("{}_{}", PERIOD, UP);
+            let mut trade = filled_market_buy(0.60, 10.0);
+            trade.sell_price = 0.90;
+            trader.pending_trades.lock().await.insert(key.clone(), trade.clone());
+            mock.set_trades(vec![sell_fill(UP, 2.0)]);
+
+            // 2 of the level's 5 shares sold: 8 still held, the level isn't done
+            assert!(trader.try_scale_out(&key, &trade, 0.70).await);
+            let trade = trader.pending_trades.lock().await[&key].clone();
+            assert!((trade.units - 8.0).abs() < 1e-9);
+            assert_eq!(trade.confirmed_balance, Some(8.0));
+            assert_eq!(trade.scale_out_levels_done, 0);
+
+            // Retried on half of what is left, which fills
+            mock.set_trades(vec![sell_fill(UP, 4.0)]);
+            assert!(trader.try_scale_out(&key, &trade, 0.70).await);
+            assert_eq!(mock.market_orders().last(), Some(&(UP.to_string(), 4.0, "SELL".to_string())));
+            let trade = trader.pending_trades.lock().await[&key].clone();
+            assert!((trade.units - 4.0).abs() < 1e-9);
+            assert_eq!(trade.scale_out_levels_done, 1);
+        }
+
+        #[tokio::test]
+        async fn maker_buy_joins_the_bid_and_goes_to_market_after_its_timeout() {
+            // Improvement never lets the buy reach the ask
+            assert_eq!(maker_buy_price(0.60, Some(0.62), 0.05), Some(0.61));
+            assert_eq!(maker_buy_price(0.0, Some(0.02), 0.0), None);
+
+            let (mock, trader) = mock_trader(|config| {
+                config.entry_order_type = EntryOrderType::Maker;
+                config.maker_timeout_secs = Some(0);
+            });
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.60, 0.62);
+            let opportunity = BuyOpportunity { dual_limit_shares: Some(10.0), ..opportunity(UP, 0.62) };
+            trader.enter(&opportunity).await.unwrap();
+            let orders = mock.orders();
+            assert_eq!(orders.len(), 1);
+            assert_eq!((orders[0].side.as_str(), orders[0].price.as_str(), orders[0].size.as_str()), ("BUY", "0.60", "10.00"));
+            assert!(mock.market_orders().is_empty());
+
+            // Timed out with 4 of 10 shares matched: cancelled, the 4 confirmed, the other 6 bought at the ask
+            let key = format!("{}_{}_limit", PERIOD, UP);
+            let order_id = trader.pending_trades.lock().await[&key].order_id.clone().unwrap();
+            mock.set_order_status(OpenOrder { size_matched: 4.0, ..open_order(&order_id, UP, "BUY", 0.60) });
+            mock.set_balance(UP, 10.0);
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.cancels(), vec![order_id]);
+            let market_orders = mock.market_orders();
+            assert_eq!(market_orders.len(), 1);
+            assert_eq!((market_orders[0].0.as_str(), market_orders[0].2.as_str()), (UP, "BUY"));
+            assert!((market_orders[0].1 - 6.0 * 0.62).abs() < 1e-9);
+            let pending = trader.pending_trades.lock().await;
+            assert!(pending[&key].buy_order_confirmed);
+            assert!((pending[&key].units - 4.0).abs() < 1e-9);
+            assert!(pending.contains_key(&format!("{}_{}", PERIOD, UP)));
+        }
+
+        #[tokio::test]
+        async fn unfilled_maker_buy_falls_back_to_its_own_size_unless_paused() {
+            let (mock, trader) = mock_trader(|config| {
+                config.entry_order_type = EntryOrderType::Maker;
+                config.maker_timeout_secs = Some(0);
+            });
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.60, 0.62);
+            let opportunity = BuyOpportunity { dual_limit_shares: Some(10.0), ..opportunity(UP, 0.62) };
+            let key = format!("{}_{}_limit", PERIOD, UP);
+
+            // Nothing matched: the market buy is for the maker buy's 10 shares, not the configured amount
+            trader.enter(&opportunity).await.unwrap();
+            let order_id = trader.pending_trades.lock().await[&key].order_id.clone().unwrap();
+            mock.set_order_status(open_order(&order_id, UP, "BUY", 0.60));
+            trader.check_pending_trades().await.unwrap();
+            let market_orders = mock.market_orders();
+            assert_eq!(market_orders.len(), 1);
+            assert!((market_orders[0].1 - 10.0 * 0.62).abs() < 1e-9);
+            assert!(!trader.pending_trades.lock().await.contains_key(&key));
+
+            // Paused while resting: cancelled without a market buy
+            trader.pending_trades.lock().await.clear();
+            trader.enter(&opportunity).await.unwrap();
+            let order_id = trader.pending_trades.lock().await[&key].order_id.clone().unwrap();
+            mock.set_order_status(open_order(&order_id, UP, "BUY", 0.60));
+            trader.set_paused(true);
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.cancels().len(), 2);
+            assert_eq!(mock.market_orders().len(), 1);
+            assert!(!trader.pending_trades.lock().await.contains_key(&key));
+        }
+
+        #[tokio::test]
+        async fn only_an_entry_that_placed_an_order_starts_the_buy_gap() {
+            let (mock, trader) = mock_trader(|config| {
+                config.entry_order_type = EntryOrderType::Limit;
+                config.min_seconds_between_buys = Some(60);
+            });
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.59, 0.61);
+            mock.set_price(DOWN, 0.39, 0.41);
+
+            // Skipped for insufficient funds: no order, so the next entry isn't throttled
+            mock.set_usdc_balance(0.0);
+            trader.enter(&opportunity(UP, 0.60)).await.unwrap();
+            assert!(mock.orders().is_empty());
+            mock.set_usdc_balance(1000.0);
+            trader.enter(&opportunity(UP, 0.60)).await.unwrap();
+            assert_eq!(mock.orders().len(), 1);
+            trader.enter(&opportunity(DOWN, 0.40)).await.unwrap();
+            assert_eq!(mock.orders().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn limit_buy_with_lost_response_is_adopted_from_open_orders() {
+            let (mock, trader) = mock_trader(|config| config.lost_order_lookup_secs = Some(1));
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.59, 0.61);
+            mock.fail_orders_with("error sending request: operation timed out");
+            // Only the order matching our price and size is ours
+            mock.set_open_orders(vec![open_order("dummy_other_order", UP, "BUY", 0.55), open_order("dummy_lost_order", UP, "BUY", 0.60)]);
+            let opportunity = opportunity(UP, 0.60);
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            assert_eq!(mock.orders().len(), 1);
+            let pending = trader.pending_trades.lock().await;
+            let trade = &pending[&format!("{}_{}_limit", PERIOD, UP)];
+            assert_eq!(trade.order_id.as_deref(), Some("dummy_lost_order"));
+            assert!(!trade.buy_order_confirmed);
+            drop(pending);
+
+            // Nothing on the book and a definitive rejection are both plain failures
+            mock.set_open_orders(Vec::new());
+            trader.pending_trades.lock().await.clear();
+            assert!(trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.is_err());
+            mock.fail_orders_with("not enough balance / allowance");
+            assert!(trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.is_err());
+            assert!(trader.pending_trades.lock().await.is_empty());
+        }
+
+        #[tokio::test]
+        async fn buys_are_skipped_while_usdc_balance_cannot_cover_them() {
+            let (mock, trader) = mock_trader(|_| {});
+            mock.set_balance(UP, 0.0);
+            mock.set_price(UP, 0.59, 0.61);
+            mock.set_usdc_balance(5.0);
+            let opportunity = opportunity(UP, 0.60);
+            // $6 needed, $5 available - skipped without an order or an error, twice
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            assert!(mock.orders().is_empty());
+            assert!(*trader.insufficient_funds.lock().await);
+
+            mock.set_usdc_balance(6.0);
+            trader.execute_limit_buy(&opportunity, true, Some(10.0)).await.unwrap();
+            assert_eq!(mock.orders().len(), 1);
+            assert!(!*trader.insufficient_funds.lock().await);
+        }
+
+        #[tokio::test]
+        async fn stop_loss_sells_and_places_opposite_limit_buy() {
+            let (mock, trader) = mock_trader(|config| config.stop_loss_price = Some(0.80));
+            let key = format!("{}_{}", PERIOD, UP);
+            trader.pending_trades.lock().await.insert(key.clone(), filled_market_buy(0.90, 10.0));
+            mock.set_balance(UP, 10.0);
+            mock.set_balance(DOWN, 0.0);
+
+            // Above the stop - held
+            mock.set_price(UP, 0.85, 0.87);
+            trader.check_pending_trades().await.unwrap();
+            assert!(mock.market_orders().is_empty());
+            assert!(!trader.pending_trades.lock().await[&key].sold);
+
+            // At/below the stop - market sell of the whole balance, then a hedge buy of the opposite side at 1 - stop
+            mock.set_price(UP, 0.75, 0.77);
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.market_orders(), vec![(UP.to_string(), 10.0, "SELL".to_string())]);
+            let orders = mock.orders();
+            assert_eq!(orders.len(), 1);
+            assert_eq!((orders[0].token_id.as_str(), orders[0].side.as_str(), orders[0].price.as_str(), orders[0].size.as_str()),
+                (DOWN, "BUY", "0.20", "10.00"));
+
+            let pending = trader.pending_trades.lock().await;
+            assert!(pending[&key].sold);
+            let hedge = &pending[&format!("{}_opposite_limit_{}", PERIOD, DOWN)];
+            assert!(!hedge.buy_order_confirmed);
+            assert_eq!(hedge.token_type, TokenType::BtcDown);
+            drop(pending);
+            assert!((*trader.total_profit.lock().await - (0.75 - 0.90) * 10.0).abs() < 1e-9);
+        }
+
+        #[tokio::test]
+        async fn stop_loss_skips_opposite_order_already_resting() {
+            let (mock, trader) = mock_trader(|config| config.stop_loss_price = Some(0.80));
+            let key = format!("{}_{}", PERIOD, UP);
+            trader.pending_trades.lock().await.insert(key.clone(), filled_market_buy(0.90, 10.0));
+            mock.set_balance(UP, 10.0);
+            mock.set_balance(DOWN, 0.0);
+            // The hedge bid from an earlier cycle is still on the book
+            mock.set_open_orders(vec![open_order("dummy_resting_hedge", DOWN, "BUY", 0.20)]);
+
+            mock.set_price(UP, 0.75, 0.77);
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.market_orders(), vec![(UP.to_string(), 10.0, "SELL".to_string())]);
+            assert!(mock.orders().is_empty());
+            let pending = trader.pending_trades.lock().await;
+            assert!(pending[&key].sold);
+            assert!(!pending.contains_key(&format!("{}_opposite_limit_{}", PERIOD, DOWN)));
+        }
+
+        #[tokio::test]
+        async fn limit_sell_that_does_not_rest_is_replaced_then_fails() {
+            let (mock, trader) = mock_trader(|config| {
+                config.verify_resting = true;
+                config.verify_resting_delay_ms = Some(0);
+                config.verify_resting_retries = Some(1);
+            });
+            mock.set_balance(UP, 10.0);
+            let order = OrderRequest {
+                token_id: UP.to_string(),
+                side: "SELL".to_string(),
+                size: "10.00".to_string(),
+                price: "0.99".to_string(),
+                order_type: "LIMIT".to_string(),
+            };
+
+            // Accepted but never on the book and the tokens are still held - one re-placement, then an error
+            assert!(trader.place_limit_sell(&order).await.is_err());
+            assert_eq!(mock.orders().len(), 2);
+
+            // The next order (dummy_order_2) rests
+            mock.set_open_orders(vec![open_order("dummy_order_2", UP, "SELL", 0.99)]);
+            let response = trader.place_limit_sell(&order).await.unwrap();
+            assert_eq!(response.order_id.as_deref(), Some("dummy_order_2"));
+            assert_eq!(mock.orders().len(), 3);
+
+            // Gone from the book because it filled - verified without a re-placement
+            mock.set_open_orders(Vec::new());
+            mock.set_balance(UP, 0.0);
+            trader.place_limit_sell(&order).await.unwrap();
+            assert_eq!(mock.orders().len(), 4);
+        }
+
+        #[tokio::test]
+        async fn pegged_limit_sell_follows_the_book_but_not_below_cost() {
+            let (mock, trader) = mock_trader(|_| {});
+            let trader = trader.with_sell_config(crate::config::SellConfig { peg_to_book: true, ..Default::default() });
+            let trades = vec![("dummy_key".to_string(), filled_market_buy(0.60, 10.0))];
+
+            // Target sell at 0.99 sits far behind other sellers at 0.80 - moved one tick in front of them
+            mock.set_price(UP, 0.70, 0.80);
+            mock.set_open_orders(vec![open_order("dummy_sell_order", UP, "SELL", 0.99)]);
+            trader.repeg_limit_sells(&trades).await;
+            assert_eq!(mock.orders().len(), 1);
+            assert_eq!(mock.orders()[0].price, "0.79");
+            assert_eq!(mock.orders()[0].size, "10.00");
+
+            // The book moved by less than the threshold - left alone
+            mock.set_price(UP, 0.70, 0.81);
+            mock.set_open_orders(vec![open_order("dummy_sell_order", UP, "SELL", 0.79)]);
+            trader.repeg_limit_sells(&trades).await;
+            assert_eq!(mock.orders().len(), 1);
+
+            // Sellers dropped below the purchase price - pegged at cost, never lower
+            mock.set_price(UP, 0.40, 0.50);
+            trader.repeg_limit_sells(&trades).await;
+            assert_eq!(mock.orders().len(), 2);
+            assert_eq!(mock.orders()[1].price, "0.60");
+        }
+
+        #[tokio::test]
+        async fn no_bid_policy_rests_a_lowball_sell_once() {
+            let (mock, trader) = mock_trader(|_| {});
+            let trader = trader.with_sell_config(crate::config::SellConfig {
+                no_bid_policy: NoBidPolicy::LowballLimit,
+                lowball_price: Some(0.05),
+                ..Default::default()
+            });
+            let key = format!("{}_{}", PERIOD, UP);
+            trader.pending_trades.lock().await.insert(key.clone(), filled_market_buy(0.60, 10.0));
+            mock.set_balance(UP, 10.0);
+            mock.set_open_orders(vec![open_order("dummy_sell_order", UP, "SELL", 0.99)]);
+
+            // Bids present - the profit target keeps resting
+            mock.set_price(UP, 0.50, 0.52);
+            trader.check_pending_trades().await.unwrap();
+            assert!(mock.orders().is_empty());
+
+            // Nobody bids - the target is replaced by a sell at the lowball price
+            mock.set_price(UP, 0.0, 0.08);
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.orders().len(), 1);
+            assert_eq!(mock.orders()[0].side, "SELL");
+            assert_eq!(mock.orders()[0].price, "0.05");
+            assert!(mock.market_orders().is_empty());
+            assert_eq!(trader.pending_trades.lock().await[&key].sell_price, 0.05);
 
+            // Already resting at the lowball price - not placed again
+            mock.set_open_orders(vec![open_order("dummy_sell_order", UP, "SELL", 0.05)]);
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.orders().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn no_bid_dump_keeps_tracking_what_did_not_fill() {
+            let (mock, trader) = mock_trader(|_| {});
+            let trader = trader.with_sell_config(crate::config::SellConfig {
+                no_bid_policy: NoBidPolicy::DumpAtAnyPrice,
+                ..Default::default()
+            });
+            let key = format!("{}_{}", PERIOD, UP);
+            trader.pending_trades.lock().await.insert(key.clone(), filled_market_buy(0.60, 10.0));
+            mock.set_balance(UP, 10.0);
+            mock.set_price(UP, 0.0, 0.08);
+
+            // Nothing filled: the whole position is still tracked
+            trader.check_pending_trades().await.unwrap();
+            assert_eq!(mock.market_orders(), vec![(UP.to_string(), 10.0, "SELL".to_string())]);
+            assert!((trader.pending_trades.lock().await[&key].units - 10.0).abs() < 1e-9);
+
+            // A buyer takes 6: the other 4 stay tracked
+            mock.set_trades(vec![sell_fill(UP, 6.0)]);
+            trader.check_pending_trades().await.unwrap();
+            let trade = trader.pending_trades.lock().await[&key].clone();
+            assert!((trade.units - 4.0).abs() < 1e-9);
+            assert_eq!(trade.confirmed_balance, Some(4.0));
+        }
+
+        #[tokio::test]
+        async fn export_positions_rolls_up_trades_per_token() {
+            let (mock, trader) = mock_trader(|_| {});
+            mock.set_price(UP, 0.70, 0.72);
+            let mut sold = filled_market_buy(0.50, 5.0);
+            sold.sold = true;
+            {
+                let mut pending = trader.pending_trades.lock().await;
+                pending.insert("dummy_first".to_string(), filled_market_buy(0.60, 10.0));
+                pending.insert("dummy_second".to_string(), filled_market_buy(0.66, 5.0));
+                pending.insert("dummy_sold".to_string(), sold);
+            }
+
+            let records = trader.export_positions().await;
+            assert_eq!(records.len(), 1);
+            let record = &records[0];
+            assert_eq!(record.token_id, UP);
+            assert_eq!(record.size, 15.0);
+            assert!((record.avg_price.unwrap() - 0.62).abs() < 1e-9);
+            assert_eq!(record.cur_price, Some(0.70));
+            assert!((record.cash_pnl.unwrap() - 1.2).abs() < 1e-9);
+
+            let json = serde_json::to_value(record).unwrap();
+            assert_eq!(json["asset"], UP);
+            assert_eq!(json["conditionId"], CONDITION);
+            assert!(json.get("avgPrice").is_some());
+        }
+
+        #[tokio::test]
+        async fn asymmetric_exit_sells_winners_and_holds_losers() {
+            let (mock, trader) = mock_trader(|config| {
+                config.asymmetric_exit = true;
+                config.winner_confidence_price = Some(0.95);
+                config.stop_loss_price = Some(0.50);
+            });
+            let winner = format!("{}_{}", PERIOD, UP);
+            let loser = format!("{}_{}", PERIOD, DOWN);
+            let mut down = filled_market_buy(0.60, 10.0);
+            down.token_id = DOWN.to_string();
+            down.token_type = TokenType::BtcDown;
+            {
+                let mut pending = trader.pending_trades.lock().await;
+                pending.insert(winner.clone(), filled_market_buy(0.60, 10.0));
+                pending.insert(loser.clone(), down);
+            }
+            mock.set_balance(UP, 10.0);
+            mock.set_balance(DOWN, 10.0);
+            mock.set_price(UP, 0.96, 0.97);
+            mock.set_price(DOWN, 0.03, 0.04);
+            mock.set_trades(vec![sell_fill(UP, 10.0)]);
+
+            trader.check_pending_trades().await.unwrap();
+            // Winner sold at market, loser kept (below the stop-loss, but not sold) and marked to be claimed
+            assert_eq!(mock.market_orders(), vec![(UP.to_string(), 10.0, "SELL".to_string())]);
+            let pending = trader.pending_trades.lock().await;
+            assert!(!pending.contains_key(&winner));
+            assert!(pending[&loser].claim_on_closure);
+            assert!(!pending[&loser].sold);
+            drop(pending);
+
+            // A winner sell that fills only partly keeps the rest tracked
+            trader.pending_trades.lock().await.insert(winner.clone(), filled_market_buy(0.60, 10.0));
+            mock.set_trades(vec![sell_fill(UP, 2.5)]);
+            let trade = trader.pending_trades.lock().await[&winner].clone();
+            assert!(trader.try_asymmetric_exit(&winner, &trade, 0.96).await);
+            let remaining = trader.pending_trades.lock().await[&winner].clone();
+            assert!((remaining.units - 7.5).abs() < 1e-9);
+            assert_eq!(remaining.confirmed_balance, Some(7.5));
+        }
+
+        #[tokio::test]
+        async fn resting_sell_fill_is_detected_when_balance_drops_to_zero() {
+            let (mock, trader) = mock_trader(|_| {});
+            let key = format!("{}_{}", PERIOD, UP);
+            trader.pending_trades.lock().await.insert(key.clone(), filled_market_buy(0.60, 10.0));
+            mock.set_balance(UP, 10.0);
+            mock.set_price(UP, 0.70, 0.72);
+
+            trader.check_pending_trades().await.unwrap();
+            assert!(!trader.pending_trades.lock().await[&key].sold);
+
+            // The resting sell filled: balance gone -> marked sold, no new orders
+            mock.set_balance(UP, 0.0);
+            trader.check_pending_trades().await.unwrap();
+            let pending = trader.pending_trades.lock().await;
+            assert!(pending[&key].sold);
+            assert_eq!(pending[&key].confirmed_balance, Some(0.0));
+            assert!(mock.orders().is_empty());
+            assert!(mock.market_orders().is_empty());
+        }
+
+        #[tokio::test]
+        async fn hedge_sells_follow_the_configured_ladder() {
+            let (mock, trader) = mock_trader(|config| config.hedge_sell_ladder = Some(vec![(0.93, 0.5), (0.96, 0.3), (0.99, 0.2)]));
+            let key = format!("{}_{}", PERIOD, UP);
+            let mut trade = filled_market_buy(0.85, 10.0);
+            trade.limit_sell_orders_placed = false;
+            trader.pending_trades.lock().await.insert(key.clone(), trade.clone());
+
+            let plan = HedgeSellPlan { size_decimals: 2, size_rounding: RoundingMode::Floor, ladder: trader.hedge_sell_ladder() };
+            Trader::place_hedge_sell_orders_with_retry(mock.as_ref(), &trader.pending_trades, &key, &trade, 10.0, true, &plan).await;
+            let orders: Vec<(String, String, String)> = mock.orders().into_iter().map(|o| (o.side, o.price, o.size)).collect();
+            assert_eq!(orders, vec![
+                ("SELL".to_string(), "0.93".to_string(), "5.00".to_string()),
+                ("SELL".to_string(), "0.96".to_string(), "3.00".to_string()),
+                ("SELL".to_string(), "0.99".to_string(), "2.00".to_string()),
+            ]);
+            assert!(trader.pending_trades.lock().await[&key].limit_sell_orders_placed);
+
+            // Unset: the full position at $0.93 and at $0.98
+            let (_, trader) = mock_trader(|_| {});
+            assert_eq!(trader.hedge_sell_ladder(), vec![(0.93, 1.0), (0.98, 1.0)]);
+        }
+
+        #[tokio::test]
+        async fn shutdown_cancels_resting_orders_of_unsettled_trades() {
+            let (mock, trader) = mock_trader(|_| {});
+            let mut sold = filled_market_buy(0.60, 10.0);
+            sold.token_id = DOWN.to_string();
+            sold.sold = true;
+            {
+                let mut pending = trader.pending_trades.lock().await;
+                pending.insert("dummy_open".to_string(), filled_market_buy(0.60, 10.0));
+                pending.insert("dummy_sold".to_string(), sold);
+            }
+            mock.set_open_orders(vec![open_order("dummy_up_sell", UP, "SELL", 0.99), open_order("dummy_down_sell", DOWN, "SELL", 0.99)]);
+
+            let report = trader.shutdown().await;
+            assert_eq!((report.cancelled, report.failed), (1, 0));
+            assert_eq!(mock.cancels(), vec!["dummy_up_sell".to_string()]);
+            // The cancelled sell is placed again on the next start
+            assert!(!trader.pending_trades.lock().await["dummy_open"].limit_sell_orders_placed);
+        }
+
+        #[test]
+        fn trailing_and_time_based_stops_follow_the_position() {
+            let (_, trader) = mock_trader(|config| config.stop_loss = Some(StopLoss::Trailing { percent: 10.0 }));
+            let trade = filled_market_buy(0.60, 10.0);
+            // Trails the highest price since entry and never moves down
+            assert!((trader.stop_loss_for(&trade, 0.60).unwrap() - 0.54).abs() < 1e-9);
+            assert!((trader.stop_loss_for(&trade, 0.80).unwrap() - 0.72).abs() < 1e-9);
+            assert!((trader.stop_loss_for(&trade, 0.70).unwrap() - 0.72).abs() < 1e-9);
+
+            let (_, trader) = mock_trader(|config| config.stop_loss = Some(StopLoss::TimeBased { price: 0.50, after_minutes: 5 }));
+            assert_eq!(trader.stop_loss_for(&trade, 0.40), None);
+            let mut held = trade.clone();
+            held.timestamp = std::time::Instant::now() - std::time::Duration::from_secs(6 * 60);
+            assert_eq!(trader.stop_loss_for(&held, 0.40), Some(0.50));
+            // stop_loss_price alone is a fixed stop
+            let (_, trader) = mock_trader(|config| config.stop_loss_price = Some(0.45));
+            assert_eq!(trader.stop_loss_for(&trade, 0.40), Some(0.45));
+        }
+
+        #[tokio::test]
+        async fn period_rollover_cancels_orders_left_on_the_old_periods_tokens() {
+            let (mock, trader) = mock_trader(|_| {});
+            let mut unfilled = open_position(std::time::Instant::now());
+            unfilled.token_id = DOWN.to_string();
+            unfilled.market_timestamp = PERIOD;
+            unfilled.buy_order_confirmed = false;
+            let mut next_period = filled_market_buy(0.60, 10.0);
+            next_period.market_timestamp = PERIOD + crate::period::seconds();
+            next_period.token_id = "dummy_next_period_token_id".to_string();
+            {
+                let mut pending = trader.pending_trades.lock().await;
+                pending.insert(format!("{}_{}_limit", PERIOD, DOWN), unfilled);
+                pending.insert("dummy_next".to_string(), next_period);
+            }
+            mock.set_open_orders(vec![
+                open_order("dummy_stale_buy", DOWN, "BUY", 0.45),
+                open_order("dummy_next_buy", "dummy_next_period_token_id", "BUY", 0.45),
+            ]);
+
+            trader.reset_period(PERIOD).await;
+            assert_eq!(mock.cancels(), vec!["dummy_stale_buy".to_string()]);
+            let pending = trader.pending_trades.lock().await;
+            assert_eq!(pending.keys().cloned().collect::<Vec<_>>(), vec!["dummy_next".to_string()]);
+        }
+
+        #[tokio::test]
+        async fn resolved_positions_below_min_value_to_redeem_are_closed_without_redeeming() {
+            let (mock, trader) = mock_trader(|_| {});
+            let trader = trader.with_redemption(crate::config::RedemptionConfig { min_value_to_redeem: Some(0.50) });
+            let loser = format!("{}_{}", PERIOD, UP);
+            let dust = format!("{}_{}", PERIOD, DOWN);
+            let mut down = filled_market_buy(0.60, 0.40);
+            down.token_id = DOWN.to_string();
+            down.token_type = TokenType::BtcDown;
+            {
+                let mut pending = trader.pending_trades.lock().await;
+                pending.insert(loser.clone(), filled_market_buy(0.60, 10.0));
+                pending.insert(dust.clone(), down);
+            }
+            mock.set_balance(UP, 10.0);
+            mock.set_balance(DOWN, 0.40);
+            mock.resolve_market(CONDITION, false);
+
+            // Up lost ($0) and the winning Down leg is worth $0.40 - both under $0.50, so neither is redeemed
+            trader.settle_closed_markets().await.unwrap();
+            assert!(trader.pending_trades.lock().await.is_empty());
+            assert!(mock.redemptions().is_empty());
+            assert!(!trader.has_active_position(PERIOD, TokenType::BtcUp).await);
+            assert!((*trader.total_profit.lock().await - (-6.0 + 0.40 - 0.24)).abs() < 1e-9);
+        }
+
+        #[tokio::test]
+        async fn positions_in_one_resolved_market_are_redeemed_with_a_single_call() {
+            let (mock, trader) = mock_trader(|_| {});
+            let mut down = filled_market_buy(0.40, 5.0);
+            down.token_id = DOWN.to_string();
+            down.token_type = TokenType::BtcDown;
+            {
+                let mut pending = trader.pending_trades.lock().await;
+                pending.insert(format!("{}_{}", PERIOD, UP), filled_market_buy(0.60, 10.0));
+                pending.insert(format!("{}_{}", PERIOD, DOWN), down);
+            }
+            mock.set_balance(UP, 10.0);
+            mock.set_balance(DOWN, 5.0);
+            mock.resolve_market(CONDITION, true);
+
+            trader.settle_closed_markets().await.unwrap();
+            assert_eq!(mock.redemptions().len(), 1);
+            assert!(trader.pending_trades.lock().await.is_empty());
+            assert!((*trader.total_profit.lock().await - (4.0 - 2.0)).abs() < 1e-9);
+        }
+
+        #[tokio::test]
+        async fn persisted_trades_are_restored_and_unfilled_buys_survive_the_startup_sync() {
+            let path = std::env::temp_dir().join(format!("dummy_pending_trades_test_{}.json", std::process::id()));
+            let _ = std::fs::remove_file(&path);
+            let path = path.to_str().unwrap();
+            let filled = format!("{}_{}", PERIOD, UP);
+            let resting = format!("{}_{}_limit", PERIOD, DOWN);
+            {
+                let (_, trader) = mock_trader(|_| {});
+                let trader = trader.with_trade_persistence(path).unwrap();
+                let mut buy = filled_market_buy(0.40, 5.0);
+                buy.token_id = DOWN.to_string();
+                buy.token_type = TokenType::BtcDown;
+                buy.buy_order_confirmed = false;
+                buy.order_id = Some("dummy_resting_buy".to_string());
+                let mut pending = trader.pending_trades.lock().await;
+                pending.insert(filled.clone(), filled_market_buy(0.60, 10.0));
+                pending.insert(resting.clone(), buy);
+                drop(pending);
+                trader.pending_trades.flush().await;
+            }
+
+            // Restart: both trades come back; the sync keeps the unfilled buy and picks up the filled one's balance
+            let (mock, trader) = mock_trader(|_| {});
+            let trader = trader.with_trade_persistence(path).unwrap();
+            mock.set_balance(UP, 9.5);
+            mock.set_balance(DOWN, 0.0);
+            trader.sync_trades_with_portfolio().await.unwrap();
+            let pending = trader.pending_trades.lock().await;
+            assert_eq!(pending.len(), 2);
+            assert_eq!(pending[&filled].units, 9.5);
+            assert!(pending[&filled].limit_sell_orders_placed);
+            assert_eq!(pending[&resting].order_id.as_deref(), Some("dummy_resting_buy"));
+            assert!(!pending[&resting].buy_order_confirmed);
+            drop(pending);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! Trading from several wallets (`polymarket.wallets`).
+//!
+//! `WalletPool` is an `ApiClient` over one client per wallet. Each new position is bought from the wallet the
+//! rotation picks, skipping wallets whose USDC balance can't cover the buy; the pool remembers which wallet holds which token and which wallet placed which order, so
+//! sells, cancels, balance checks and redemptions of that position go to the same wallet. After a restart a
+//! token's wallet is found again by asking each wallet for its balance. Market data is read through the primary
+//! wallet's client.
+
+use crate::api::PolymarketApi;
+use crate::api_client::ApiClient;
+use crate::config::{Config, WalletRotation};
+use crate::models::*;
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Label of the wallet configured by the top-level polymarket credentials
+pub const PRIMARY_WALLET: &str = "primary";
+
+/// One trading wallet of a `WalletPool`
+pub struct Wallet {
+    pub label: String,
+    pub api: Arc<dyn ApiClient>,
+    pub weight: u32,
+}
+
+pub struct WalletPool {
+    wallets: Vec<Wallet>, // Primary first
+    rotation: WalletRotation,
+    credit: Mutex<Vec<i64>>, // Smooth weighted round-robin state, one entry per wallet
+    token_wallet: Mutex<HashMap<String, usize>>, // Key: token_id
+    order_wallet: Mutex<HashMap<String, usize>>, // Key: order_id
+}
+
+/// API clients for the additional wallets in `polymarket.wallets` as (label, client, weight), set up with the
+/// same URLs, network and chain settings as the primary client
+pub fn wallet_apis(config: &Config) -> Result<Vec<(String, Arc<PolymarketApi>, u32)>> {
+    config.polymarket.wallets.iter()
+        .map(|wallet| {
+            let api = PolymarketApi::new(
+                config.polymarket.gamma_api_url.clone(),
+                config.polymarket.clob_api_url.clone(),
+                wallet.api_key.clone(),
+                wallet.api_secret.clone(),
+                wallet.api_passphrase.clone(),
+                wallet.private_key.clone(),
+                wallet.proxy_wallet_address.clone(),
+                wallet.signature_type,
+            ).with_network(&config.network)?.with_chain(&config.chain)?;
+            Ok((wallet.label.clone(), Arc::new(api), wallet.weight.unwrap_or(1)))
+        })
+        .collect()
+}
+
+impl WalletPool {
+    pub fn new(wallets: Vec<Wallet>, rotation: WalletRotation) -> Self {
+        Self {
+            credit: Mutex::new(vec![0; wallets.len()]),
+            wallets,
+            rotation,
+            token_wallet: Mutex::new(HashMap::new()),
+            order_wallet: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Labels of the wallets, primary first
+    pub fn labels(&self) -> Vec<String> {
+        self.wallets.iter().map(|w| w.label.clone()).collect()
+    }
+
+    /// Next wallet for a new position (smooth weighted round-robin - round_robin gives every wallet weight 1)
+    fn next_wallet(&self) -> usize {
+        let weight = |wallet: &Wallet| match self.rotation {
+            WalletRotation::RoundRobin => 1,
+            WalletRotation::Weighted => i64::from(wallet.weight.max(1)),
+        };
+        let total: i64 = self.wallets.iter().map(weight).sum();
+        let mut credit = self.credit.lock().unwrap();
+        for (credit, wallet) in credit.iter_mut().zip(&self.wallets) {
+            *credit += weight(wallet);
+        }
+        // Highest credit wins, the earlier wallet on a tie
+        let chosen = (0..credit.len()).rev().max_by_key(|&i| credit[i]).unwrap_or(0);
+        credit[chosen] -= total;
+        chosen
+    }
+
+    /// Wallet a buy of `token_id` for `amount` dollars goes to: the one already holding it, otherwise the next in
+    /// rotation whose USDC balance covers the amount. A wallet whose balance can't be read is used only if none
+    /// covers it (the exchange decides); Err when every balance was read and none is enough
+    async fn buy_wallet(&self, token_id: &str, amount: f64) -> Result<usize> {
+        if let Some(&index) = self.token_wallet.lock().unwrap().get(token_id) {
+            return Ok(index);
+        }
+        let mut unknown = None;
+        let mut chosen = None;
+        for _ in 0..self.wallets.len() {
+            let index = self.next_wallet();
+            match self.wallets[index].api.get_usdc_balance().await {
+                Ok(balance) if balance + 1e-9 >= amount => {
+                    chosen = Some(index);
+                    break;
+                }
+                Ok(balance) => eprintln!("👛 Skipping wallet \"{}\" for a ${:.2} buy - USDC balance ${:.2}",
+                    self.wallets[index].label, amount, balance),
+                Err(e) => {
+                    log::warn!("USDC balance of wallet \"{}\" unavailable: {}", self.wallets[index].label, e);
+                    unknown = unknown.or(Some(index));
+                }
+            }
+        }
+        let index = chosen.or(unknown)
+            .ok_or_else(|| anyhow::anyhow!("no wallet's USDC balance covers a ${:.2} buy", amount))?;
+        self.token_wallet.lock().unwrap().insert(token_id.to_string(), index);
+        eprintln!("👛 Buying {} from wallet \"{}\"", &token_id[..16.min(token_id.len())], self.wallets[index].label);
+        Ok(index)
+    }
+
+    /// Wallet holding `token_id` and its balance there; a token the pool hasn't seen is looked up by balance
+    /// (primary with its balance if no wallet holds any)
+    async fn holding_wallet(&self, token_id: &str) -> Result<(usize, Option<Decimal>)> {
+        if let Some(&index) = self.token_wallet.lock().unwrap().get(token_id) {
+            return Ok((index, None));
+        }
+        let mut primary_balance = Decimal::ZERO;
+        for (index, wallet) in self.wallets.iter().enumerate() {
+            let balance = wallet.api.check_balance_only(token_id).await?;
+            if balance > Decimal::ZERO {
+                self.token_wallet.lock().unwrap().insert(token_id.to_string(), index);
+                return Ok((index, Some(balance)));
+            }
+            if index == 0 {
+                primary_balance = balance;
+            }
+        }
+        Ok((0, Some(primary_balance)))
+    }
+
+    async fn token_api(&self, token_id: &str) -> Result<&Arc<dyn ApiClient>> {
+        let (index, _) = self.holding_wallet(token_id).await?;
+        Ok(&self.wallets[index].api)
+    }
+
+    fn primary(&self) -> &Arc<dyn ApiClient> {
+        &self.wallets[0].api
+    }
+
+    fn record_order(&self, index: usize, response: &OrderResponse) {
+        if let Some(order_id) = &response.order_id {
+            self.order_wallet.lock().unwrap().insert(order_id.clone(), index);
+        }
+    }
+
+    /// Wallets to try for an order id: the one that placed it, or all of them (orders placed before a restart)
+    fn order_wallets(&self, order_id: &str) -> Vec<usize> {
+        match self.order_wallet.lock().unwrap().get(order_id) {
+            Some(&index) => vec![index],
+            None => (0..self.wallets.len()).collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiClient for WalletPool {
+    async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook> {
+        self.primary().get_orderbook(token_id).await
+    }
+
+    async fn get_best_price(&self, token_id: &str) -> Result<Option<TokenPrice>> {
+        self.primary().get_best_price(token_id).await
+    }
+
+    async fn get_price(&self, token_id: &str, side: &str) -> Result<Decimal> {
+        self.primary().get_price(token_id, side).await
+    }
+
+    async fn get_market(&self, condition_id: &str) -> Result<MarketDetails> {
+        self.primary().get_market(condition_id).await
+    }
+
+    async fn get_uma_resolution_status(&self, condition_id: &str) -> Result<Option<String>> {
+        self.primary().get_uma_resolution_status(condition_id).await
+    }
+
+    async fn check_balance_only(&self, token_id: &str) -> Result<Decimal> {
+        match self.holding_wallet(token_id).await? {
+            (_, Some(balance)) => Ok(balance),
+            (index, None) => self.wallets[index].api.check_balance_only(token_id).await,
+        }
+    }
+
+    async fn check_balance_allowance(&self, token_id: &str) -> Result<(Decimal, Decimal)> {
+        self.token_api(token_id).await?.check_balance_allowance(token_id).await
+    }
+
+    /// Total over all wallets
+    async fn get_usdc_balance(&self) -> Result<f64> {
+        Ok(self.wallet_usdc_balances().await?.iter().map(|(_, balance)| balance).sum())
+    }
+
+    /// The holding wallet's balance for a token it already holds, otherwise the largest single-wallet balance
+    /// (buys aren't split across wallets)
+    async fn buy_usdc_balance(&self, token_id: &str) -> Result<f64> {
+        let holding = self.token_wallet.lock().unwrap().get(token_id).copied();
+        if let Some(index) = holding {
+            return self.wallets[index].api.get_usdc_balance().await;
+        }
+        Ok(self.wallet_usdc_balances().await?.iter().map(|(_, balance)| *balance).fold(0.0, f64::max))
+    }
+
+    async fn wallet_usdc_balances(&self) -> Result<Vec<(String, f64)>> {
+        let mut balances = Vec::with_capacity(self.wallets.len());
+        for wallet in &self.wallets {
+            balances.push((wallet.label.clone(), wallet.api.get_usdc_balance().await?));
+        }
+        Ok(balances)
+    }
+
+    /// True only if every wallet is approved
+    async fn check_is_approved_for_all(&self) -> Result<bool> {
+        for wallet in &self.wallets {
+            if !wallet.api.check_is_approved_for_all().await? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    async fn update_balance_allowance_for_sell(&self, token_id: &str) -> Result<()> {
+        self.token_api(token_id).await?.update_balance_allowance_for_sell(token_id).await
+    }
+
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResponse> {
+        let index = if order.side == "BUY" {
+            let amount = order.size.parse::<f64>().unwrap_or(0.0) * order.price.parse::<f64>().unwrap_or(0.0);
+            self.buy_wallet(&order.token_id, amount).await?
+        } else {
+            self.holding_wallet(&order.token_id).await?.0
+        };
+        let response = self.wallets[index].api.place_order(order).await?;
+        self.record_order(index, &response);
+        Ok(response)
+    }
+
+    async fn place_market_order(&self, token_id: &str, amount: f64, side: &str, order_type: Option<&str>) -> Result<OrderResponse> {
+        let index = if side == "BUY" {
+            self.buy_wallet(token_id, amount).await?
+        } else {
+            self.holding_wallet(token_id).await?.0
+        };
+        let response = self.wallets[index].api.place_market_order(token_id, amount, side, order_type).await?;
+        self.record_order(index, &response);
+        Ok(response)
+    }
+
+    async fn get_open_orders(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        self.token_api(token_id).await?.get_open_orders(token_id).await
+    }
+
+    async fn get_trades(&self, token_id: &str, after: i64) -> Result<Vec<OwnTrade>> {
+        self.token_api(token_id).await?.get_trades(token_id, after).await
+    }
+
+    async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+        let mut last_error = None;
+        for index in self.order_wallets(order_id) {
+            match self.wallets[index].api.get_order(order_id).await {
+                Ok(order) => return Ok(order),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No wallet knows order {}", order_id)))
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let mut last_error = None;
+        for index in self.order_wallets(order_id) {
+            match self.wallets[index].api.cancel_order(order_id).await {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No wallet knows order {}", order_id)))
+    }
+
+    async fn redeem_tokens(&self, condition_id: &str, token_id: &str, outcome: &str) -> Result<RedeemResponse> {
+        self.token_api(token_id).await?.redeem_tokens(condition_id, token_id, outcome).await
+    }
+
+    /// Each wallet redeems its own positions, so a market is batched per holding wallet
+    async fn redemption_batch_key(&self, condition_id: &str, token_id: &str) -> String {
+        match self.holding_wallet(token_id).await {
+            Ok((index, _)) => format!("{}:{}", condition_id, self.wallets[index].label),
+            Err(_) => format!("{}:{}", condition_id, token_id), // Unknown wallet - don't batch
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_client::mock::MockApi;
+
+    const TOKEN_A: &str = "dummy_wallet_pool_token_a";
+    const TOKEN_B: &str = "dummy_wallet_pool_token_b";
+    const TOKEN_C: &str = "dummy_wallet_pool_token_c";
+    const TOKEN_D: &str = "dummy_wallet_pool_token_d";
+    const TOKEN_E: &str = "dummy_wallet_pool_token_e";
+    const TOKEN_F: &str = "dummy_wallet_pool_token_f";
+
+    fn pool(rotation: WalletRotation, weights: [u32; 2]) -> (Arc<MockApi>, Arc<MockApi>, WalletPool) {
+        let (primary, second) = (Arc::new(MockApi::default()), Arc::new(MockApi::default()));
+        let pool = WalletPool::new(vec![
+            Wallet { label: PRIMARY_WALLET.to_string(), api: primary.clone(), weight: weights[0] },
+            Wallet { label: "second".to_string(), api: second.clone(), weight: weights[1] },
+        ], rotation);
+        (primary, second, pool)
+    }
+
+    #[test]
+    fn weighted_rotation_interleaves_in_proportion_to_weights() {
+        let (_, _, round_robin) = pool(WalletRotation::RoundRobin, [3, 1]);
+        assert_eq!((0..4).map(|_| round_robin.next_wallet()).collect::<Vec<_>>(), vec![0, 1, 0, 1]);
+        let (_, _, weighted) = pool(WalletRotation::Weighted, [2, 1]);
+        assert_eq!((0..6).map(|_| weighted.next_wallet()).collect::<Vec<_>>(), vec![0, 1, 0, 0, 1, 0]);
+    }
+
+    #[tokio::test]
+    async fn positions_stay_with_the_wallet_that_bought_them() {
+        let (primary, second, pool) = pool(WalletRotation::RoundRobin, [1, 1]);
+        primary.set_usdc_balance(10.0);
+        second.set_usdc_balance(10.0);
+        pool.place_market_order(TOKEN_A, 5.0, "BUY", Some("FOK")).await.unwrap();
+        pool.place_market_order(TOKEN_B, 5.0, "BUY", Some("FOK")).await.unwrap();
+        assert_eq!(primary.market_orders().len(), 1);
+        assert_eq!(second.market_orders().len(), 1);
+
+        // The sell of B goes to the wallet that bought it, not the next in rotation
+        second.set_balance(TOKEN_B, 8.0);
+        pool.place_market_order(TOKEN_B, 8.0, "SELL", Some("FAK")).await.unwrap();
+        assert_eq!(second.market_orders().len(), 2);
+        assert_eq!(primary.market_orders().len(), 1);
+
+        // A token bought before a restart is found by its balance
+        second.set_balance(TOKEN_C, 3.0);
+        assert_eq!(pool.check_balance_only(TOKEN_C).await.unwrap(), Decimal::from(3_000_000));
+        pool.place_market_order(TOKEN_C, 3.0, "SELL", Some("FAK")).await.unwrap();
+        assert_eq!(second.market_orders().len(), 3);
+
+        primary.set_usdc_balance(10.0);
+        second.set_usdc_balance(4.5);
+        assert_eq!(pool.wallet_usdc_balances().await.unwrap(),
+            vec![(PRIMARY_WALLET.to_string(), 10.0), ("second".to_string(), 4.5)]);
+        assert!((pool.get_usdc_balance().await.unwrap() - 14.5).abs() < 1e-9);
+        assert!((pool.buy_usdc_balance(TOKEN_B).await.unwrap() - 4.5).abs() < 1e-9);
+
+        // A wallet that can't cover the buy is skipped in the rotation; a buy no wallet covers isn't placed
+        pool.place_market_order(TOKEN_D, 5.0, "BUY", Some("FOK")).await.unwrap();
+        pool.place_market_order(TOKEN_E, 5.0, "BUY", Some("FOK")).await.unwrap();
+        assert_eq!(primary.market_orders().len(), 3);
+        assert_eq!(second.market_orders().len(), 3);
+        assert!(pool.place_market_order(TOKEN_F, 50.0, "BUY", Some("FOK")).await.is_err());
+    }
+}